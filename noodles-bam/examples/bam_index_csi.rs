@@ -0,0 +1,76 @@
+//! Builds and writes a coordinate-sorted index (CSI) from a BAM file.
+//!
+//! The input BAM must be coordinate-sorted, i.e., `SO:coordinate`. The min shift defaults to 14,
+//! and the depth, to 5.
+//!
+//! This writes the output to stdout rather than `<src>.csi`.
+//!
+//! The output is similar to the output of `samtools index -c -m <min-shift> <src>`.
+
+use std::{env, fs::File, io};
+
+use noodles_bam as bam;
+use noodles_csi::{self as csi, index::reference_sequence::bin::Chunk};
+use noodles_sam::{self as sam, alignment::Record};
+
+fn is_coordinate_sorted(header: &sam::Header) -> bool {
+    use sam::header::record::value::map::header::SortOrder;
+
+    if let Some(hdr) = header.header() {
+        if let Some(sort_order) = hdr.sort_order() {
+            return sort_order == SortOrder::Coordinate;
+        }
+    }
+
+    false
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = env::args().skip(1);
+
+    let src = args.next().expect("missing src");
+    let min_shift = args.next().map(|s| s.parse()).transpose()?.unwrap_or(14);
+    let depth = args.next().map(|s| s.parse()).transpose()?.unwrap_or(5);
+
+    let mut reader = File::open(src).map(bam::Reader::new)?;
+    let header: sam::Header = reader.read_header()?.parse()?;
+    reader.read_reference_sequences()?;
+
+    if !is_coordinate_sorted(&header) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "the input BAM must be coordinate-sorted to be indexed",
+        )
+        .into());
+    }
+
+    let mut record = Record::default();
+
+    let mut indexer = bam::Indexer::default()
+        .set_min_shift(min_shift)
+        .set_depth(depth);
+    let mut start_position = reader.virtual_position();
+
+    loop {
+        match reader.read_record(&mut record) {
+            Ok(0) => break,
+            Ok(_) => {}
+            Err(e) => return Err(e.into()),
+        }
+
+        let end_position = reader.virtual_position();
+        let chunk = Chunk::new(start_position, end_position);
+
+        indexer.add_record(&record, chunk)?;
+
+        start_position = end_position;
+    }
+
+    let index = indexer.build(header.reference_sequences().len());
+
+    let stdout = io::stdout().lock();
+    let mut writer = csi::Writer::new(stdout);
+    writer.write_index(&index)?;
+
+    Ok(())
+}
@@ -1,3 +1,7 @@
+mod builder;
+
+pub use self::builder::Builder;
+
 use std::ffi::CString;
 
 use noodles_bgzf as bgzf;
@@ -5,15 +9,17 @@ use noodles_sam::{
     self as sam,
     alignment::Record,
     header::record::value::{map::ReferenceSequence, Map},
+    record::quality_scores::transform::Transforms,
 };
 use tokio::io::{self, AsyncWrite, AsyncWriteExt};
 
-use crate::writer::record::encode_record;
+use crate::writer::{encode_transformed_record, PROGRAM_NAME};
 
 /// An async BAM writer.
 pub struct Writer<W> {
     inner: W,
     buf: Vec<u8>,
+    quality_scores_transforms: Transforms,
 }
 
 impl<W> Writer<W>
@@ -98,7 +104,15 @@ where
     /// # }
     /// ```
     pub async fn write_header(&mut self, header: &sam::Header) -> io::Result<()> {
-        write_header(&mut self.inner, header).await
+        if self.quality_scores_transforms.is_empty() {
+            write_header(&mut self.inner, header).await
+        } else {
+            let mut header = header.clone();
+            self.quality_scores_transforms
+                .add_program(&mut header, PROGRAM_NAME, env!("CARGO_PKG_VERSION"))
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+            write_header(&mut self.inner, &header).await
+        }
     }
 
     /// Writes the binary reference sequences after the SAM header.
@@ -155,7 +169,12 @@ where
     /// ```
     pub async fn write_record(&mut self, header: &sam::Header, record: &Record) -> io::Result<()> {
         self.buf.clear();
-        encode_record(&mut self.buf, header, record)?;
+        encode_transformed_record(
+            &mut self.buf,
+            header,
+            record,
+            &self.quality_scores_transforms,
+        )?;
 
         let block_size = u32::try_from(self.buf.len())
             .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
@@ -199,6 +218,19 @@ impl<W> Writer<bgzf::AsyncWriter<W>>
 where
     W: AsyncWrite + Unpin,
 {
+    /// Creates an async BAM writer builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// let builder = bam::AsyncWriter::builder(Vec::new());
+    /// let writer = builder.build();
+    /// ```
+    pub fn builder(inner: W) -> Builder<W> {
+        Builder::new(inner)
+    }
+
     /// Creates an async BAM writer with a default compression level.
     ///
     /// # Examples
@@ -217,6 +249,7 @@ impl<W> From<W> for Writer<W> {
         Self {
             inner,
             buf: Vec::new(),
+            quality_scores_transforms: Transforms::default(),
         }
     }
 }
@@ -300,4 +333,55 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_write_record_with_quality_scores_transforms(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        use sam::record::{
+            quality_scores::{transform::Bins, Transform},
+            Flags,
+        };
+
+        use crate::AsyncReader;
+
+        let mut writer = Writer::builder(Vec::new())
+            .set_quality_scores_transform(Transform::Bin(Bins::illumina_8_level()))
+            .add_quality_scores_transform(Flags::SECONDARY, Transform::Drop)
+            .build();
+
+        let header = sam::Header::default();
+        writer.write_header(&header).await?;
+        writer
+            .write_reference_sequences(header.reference_sequences())
+            .await?;
+
+        for flags in [Flags::UNMAPPED, Flags::UNMAPPED | Flags::SECONDARY] {
+            let record = Record::builder()
+                .set_flags(flags)
+                .set_sequence("ACGT".parse()?)
+                .set_quality_scores("+5?I".parse()?)
+                .build();
+
+            writer.write_record(&header, &record).await?;
+        }
+
+        writer.shutdown().await?;
+
+        let data = writer.into_inner().into_inner();
+        let mut reader = AsyncReader::new(data.as_slice());
+        let actual_header: sam::Header = reader.read_header().await?.parse()?;
+        reader.read_reference_sequences().await?;
+
+        assert!(actual_header.programs().contains_key(PROGRAM_NAME));
+
+        let mut record = Record::default();
+
+        reader.read_record(&mut record).await?;
+        assert_eq!(record.quality_scores().to_string(), "07BI");
+
+        reader.read_record(&mut record).await?;
+        assert!(record.quality_scores().is_empty());
+
+        Ok(())
+    }
 }
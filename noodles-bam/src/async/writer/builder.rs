@@ -0,0 +1,79 @@
+use noodles_bgzf as bgzf;
+use noodles_sam::record::{
+    quality_scores::{transform::Transforms, Transform},
+    Flags,
+};
+use tokio::io::AsyncWrite;
+
+use super::Writer;
+
+/// An async BAM writer builder.
+#[derive(Debug)]
+pub struct Builder<W> {
+    inner: W,
+    quality_scores_transforms: Transforms,
+}
+
+impl<W> Builder<W>
+where
+    W: AsyncWrite + Unpin,
+{
+    pub(crate) fn new(inner: W) -> Self {
+        Self {
+            inner,
+            quality_scores_transforms: Transforms::default(),
+        }
+    }
+
+    /// Sets the quality scores transform.
+    ///
+    /// This is the same as [`crate::writer::Builder::set_quality_scores_transform`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// use noodles_sam::record::quality_scores::{transform::Bins, Transform};
+    ///
+    /// let builder = bam::AsyncWriter::builder(Vec::new())
+    ///     .set_quality_scores_transform(Transform::Bin(Bins::illumina_8_level()));
+    /// ```
+    pub fn set_quality_scores_transform(mut self, transform: Transform) -> Self {
+        self.quality_scores_transforms.set_default(transform);
+        self
+    }
+
+    /// Adds a quality scores transform for records with any of the given flags set.
+    ///
+    /// This is the same as [`crate::writer::Builder::add_quality_scores_transform`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// use noodles_sam::record::{quality_scores::Transform, Flags};
+    ///
+    /// let builder = bam::AsyncWriter::builder(Vec::new())
+    ///     .add_quality_scores_transform(Flags::UNMAPPED | Flags::SECONDARY, Transform::Drop);
+    /// ```
+    pub fn add_quality_scores_transform(mut self, flags: Flags, transform: Transform) -> Self {
+        self.quality_scores_transforms.push(flags, transform);
+        self
+    }
+
+    /// Builds an async BAM writer.
+    ///
+    /// The given stream is wrapped in a BGZF encoder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// let writer = bam::AsyncWriter::builder(Vec::new()).build();
+    /// ```
+    pub fn build(self) -> Writer<bgzf::AsyncWriter<W>> {
+        let mut writer = Writer::from(bgzf::AsyncWriter::new(self.inner));
+        writer.quality_scores_transforms = self.quality_scores_transforms;
+        writer
+    }
+}
@@ -1,9 +1,11 @@
 use std::{io, mem};
 
+use noodles_core::Position;
 use noodles_csi::index::reference_sequence::bin::Chunk;
 use noodles_sam::alignment::Record;
 
 use super::{reference_sequence, Index};
+use crate::indexer::{alignment_context, AlignmentContext};
 
 /// A BAM index builder.
 #[derive(Default)]
@@ -12,6 +14,7 @@ pub struct Builder {
     reference_sequence_builder: reference_sequence::Builder,
     reference_sequence_builders: Vec<reference_sequence::Builder>,
     unplaced_unmapped_record_count: u64,
+    last_position: Option<(usize, Position)>,
 }
 
 impl Builder {
@@ -20,6 +23,9 @@ impl Builder {
     /// The record must have an associated chunk denoting its start and end
     /// position in the file.
     ///
+    /// Records must be coordinate-sorted. This returns an error if the reference sequence ID and
+    /// alignment start of the record are less than those of the previous placed record.
+    ///
     /// # Examples
     ///
     /// ```
@@ -36,29 +42,43 @@ impl Builder {
     ///     bgzf::VirtualPosition::from(377),
     /// );
     ///
-    /// builder.add_record(&record, chunk);
+    /// builder.add_record(&record, chunk)?;
+    /// # Ok::<_, std::io::Error>(())
     /// ```
     pub fn add_record(&mut self, record: &Record, chunk: Chunk) -> io::Result<()> {
-        let (reference_sequence_id, start, end) = match (
-            record.reference_sequence_id(),
-            record.alignment_start(),
-            record.alignment_end(),
-        ) {
-            (Some(reference_sequence_id), Some(start), Some(end)) => {
-                (reference_sequence_id, start, end)
-            }
-            _ => {
+        self.add_alignment_context(alignment_context(record), chunk)
+    }
+
+    pub(crate) fn add_alignment_context(
+        &mut self,
+        alignment_context: Option<AlignmentContext>,
+        chunk: Chunk,
+    ) -> io::Result<()> {
+        let (reference_sequence_id, start, end, flags) = match alignment_context {
+            Some(context) => context,
+            None => {
                 self.unplaced_unmapped_record_count += 1;
                 return Ok(());
             }
         };
 
+        let position = (reference_sequence_id, start);
+
+        if matches!(self.last_position, Some(last_position) if position < last_position) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid record order: records must be coordinate-sorted",
+            ));
+        }
+
+        self.last_position = Some(position);
+
         if reference_sequence_id != self.current_reference_sequence_id {
             self.add_reference_sequences_builders_until(reference_sequence_id);
         }
 
         self.reference_sequence_builder
-            .add_record(start, end, record.flags(), chunk)
+            .add_record(start, end, flags, chunk)
     }
 
     fn add_reference_sequences_builders_until(&mut self, reference_sequence_id: usize) {
@@ -108,7 +128,6 @@ impl Builder {
 #[cfg(test)]
 mod tests {
     use noodles_bgzf as bgzf;
-    use noodles_core::Position;
     use noodles_csi::BinningIndex;
     use noodles_sam::record::Flags;
 
@@ -147,4 +166,44 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_add_record_with_unsorted_records() -> Result<(), Box<dyn std::error::Error>> {
+        fn build_record(
+            reference_sequence_id: usize,
+            alignment_start: usize,
+        ) -> Result<Record, Box<dyn std::error::Error>> {
+            Ok(Record::builder()
+                .set_flags(Flags::empty())
+                .set_reference_sequence_id(reference_sequence_id)
+                .set_alignment_start(Position::try_from(alignment_start)?)
+                .set_cigar("4M".parse()?)
+                .build())
+        }
+
+        let chunk = Chunk::new(
+            bgzf::VirtualPosition::from(55),
+            bgzf::VirtualPosition::from(89),
+        );
+
+        let mut builder = Builder::default();
+        builder.add_record(&build_record(0, 8)?, chunk)?;
+        builder.add_record(&build_record(0, 8)?, chunk)?;
+        builder.add_record(&build_record(1, 2)?, chunk)?;
+
+        // Unplaced, unmapped records do not participate in the ordering.
+        builder.add_record(&Record::default(), chunk)?;
+
+        assert!(matches!(
+            builder.add_record(&build_record(1, 1)?, chunk),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        assert!(matches!(
+            builder.add_record(&build_record(0, 13)?, chunk),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
 }
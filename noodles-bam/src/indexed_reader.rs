@@ -8,6 +8,7 @@ use std::io::{self, Read, Seek};
 
 use noodles_bgzf as bgzf;
 use noodles_core::Region;
+use noodles_csi as csi;
 use noodles_sam::{alignment::Record, header::ReferenceSequences};
 
 use crate::reader::UnmappedRecords;
@@ -18,10 +19,31 @@ use super::{
     Reader,
 };
 
+/// An index associated with a BAM.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Index {
+    /// A BAM index (BAI).
+    Bai(bai::Index),
+    /// A coordinate-sorted index (CSI).
+    Csi(csi::Index),
+}
+
+impl From<bai::Index> for Index {
+    fn from(index: bai::Index) -> Self {
+        Self::Bai(index)
+    }
+}
+
+impl From<csi::Index> for Index {
+    fn from(index: csi::Index) -> Self {
+        Self::Csi(index)
+    }
+}
+
 /// An indexed BAM reader.
 pub struct IndexedReader<R> {
     inner: Reader<R>,
-    index: bai::Index,
+    index: Index,
 }
 
 impl<R> IndexedReader<R>
@@ -43,6 +65,11 @@ where
         self.inner.into_inner()
     }
 
+    /// Returns the associated index.
+    pub fn index(&self) -> &Index {
+        &self.index
+    }
+
     /// Reads the raw SAM header.
     pub fn read_header(&mut self) -> io::Result<String> {
         self.inner.read_header()
@@ -79,10 +106,15 @@ where
     R: Read,
 {
     /// Creates an indexed BAM reader.
-    pub fn new(inner: R, index: bai::Index) -> Self {
+    ///
+    /// The index can either be a BAM index (BAI) or a coordinate-sorted index (CSI).
+    pub fn new<I>(inner: R, index: I) -> Self
+    where
+        I: Into<Index>,
+    {
         Self {
             inner: Reader::new(inner),
-            index,
+            index: index.into(),
         }
     }
 }
@@ -97,11 +129,17 @@ where
         reference_sequences: &ReferenceSequences,
         region: &Region,
     ) -> io::Result<Query<'_, R>> {
-        self.inner.query(reference_sequences, &self.index, region)
+        match &self.index {
+            Index::Bai(index) => self.inner.query(reference_sequences, index, region),
+            Index::Csi(index) => self.inner.query(reference_sequences, index, region),
+        }
     }
 
     /// Returns an iterator of unmapped records after querying for the unmapped region.
     pub fn query_unmapped(&mut self) -> io::Result<UnmappedRecords<'_, R>> {
-        self.inner.query_unmapped(&self.index)
+        match &self.index {
+            Index::Bai(index) => self.inner.query_unmapped(index),
+            Index::Csi(index) => self.inner.query_unmapped(index),
+        }
     }
 }
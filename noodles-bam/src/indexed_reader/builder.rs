@@ -6,24 +6,33 @@ use std::{
 };
 
 use noodles_bgzf as bgzf;
+use noodles_csi as csi;
 
-use super::IndexedReader;
+use super::{Index, IndexedReader};
 use crate::bai;
 
 /// An indexed BAM reader builder.
 #[derive(Default)]
 pub struct Builder {
-    index: Option<bai::Index>,
+    index: Option<Index>,
 }
 
 impl Builder {
     /// Sets an index.
-    pub fn set_index(mut self, index: bai::Index) -> Self {
-        self.index = Some(index);
+    ///
+    /// This can either be a BAM index (BAI) or a coordinate-sorted index (CSI).
+    pub fn set_index<I>(mut self, index: I) -> Self
+    where
+        I: Into<Index>,
+    {
+        self.index = Some(index.into());
         self
     }
 
     /// Builds an indexed BAM reader from a path.
+    ///
+    /// If no index is set, this attempts to read an associated index, first checking for a BAM
+    /// index (`<src>.bai`) and then a coordinate-sorted index (`<src>.csi`).
     pub fn build_from_path<P>(self, src: P) -> io::Result<IndexedReader<bgzf::Reader<File>>>
     where
         P: AsRef<Path>,
@@ -32,10 +41,7 @@ impl Builder {
 
         let index = match self.index {
             Some(index) => index,
-            None => {
-                let index_src = build_index_src(src);
-                bai::read(index_src)?
-            }
+            None => read_associated_index(src)?,
         };
 
         let file = File::open(src)?;
@@ -44,12 +50,28 @@ impl Builder {
     }
 }
 
-fn build_index_src<P>(src: P) -> PathBuf
+fn read_associated_index<P>(src: P) -> io::Result<Index>
 where
     P: AsRef<Path>,
 {
-    const EXT: &str = "bai";
-    push_ext(src.as_ref().into(), EXT)
+    let src = src.as_ref();
+
+    let bai_src = push_ext(src.into(), "bai");
+
+    if bai_src.exists() {
+        return bai::read(bai_src).map(Index::from);
+    }
+
+    let csi_src = push_ext(src.into(), "csi");
+
+    if csi_src.exists() {
+        return csi::read(csi_src).map(Index::from);
+    }
+
+    Err(io::Error::new(
+        io::ErrorKind::NotFound,
+        "could not find associated index (.bai or .csi)",
+    ))
 }
 
 fn push_ext<S>(path: PathBuf, ext: S) -> PathBuf
@@ -67,7 +89,10 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_build_index_src() {
-        assert_eq!(build_index_src("ref.fa"), PathBuf::from("ref.fa.bai"));
+    fn test_push_ext() {
+        assert_eq!(
+            push_ext(PathBuf::from("sample.bam"), "bai"),
+            PathBuf::from("sample.bam.bai")
+        );
     }
 }
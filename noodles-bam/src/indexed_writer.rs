@@ -0,0 +1,420 @@
+//! Indexed BAM writer.
+
+mod builder;
+
+pub use self::builder::{Builder, IndexFormat};
+
+use std::{
+    collections::VecDeque,
+    io::{self, Write},
+    path::PathBuf,
+};
+
+use noodles_bgzf as bgzf;
+use noodles_csi::{self as csi, index::reference_sequence::bin::Chunk};
+use noodles_sam::{self as sam, alignment::Record, header::ReferenceSequences};
+
+use super::{
+    bai,
+    indexed_reader::Index,
+    indexer::{alignment_context, AlignmentContext},
+    Writer,
+};
+
+// A record that is written but not yet indexed, as the compressed positions of its chunk are not
+// yet known.
+struct PendingRecord {
+    alignment_context: Option<AlignmentContext>,
+    start_position: bgzf::writer::BlockPosition,
+    resolved_start_position: Option<bgzf::VirtualPosition>,
+    end_position: bgzf::writer::BlockPosition,
+}
+
+enum Indexer {
+    Bai(bai::index::Builder),
+    Csi(crate::Indexer),
+}
+
+impl Indexer {
+    fn add_alignment_context(
+        &mut self,
+        alignment_context: Option<AlignmentContext>,
+        chunk: Chunk,
+    ) -> io::Result<()> {
+        match self {
+            Self::Bai(builder) => builder.add_alignment_context(alignment_context, chunk),
+            Self::Csi(indexer) => indexer.add_alignment_context(alignment_context, chunk),
+        }
+    }
+
+    fn build(self, reference_sequence_count: usize) -> Index {
+        match self {
+            Self::Bai(builder) => Index::Bai(builder.build(reference_sequence_count)),
+            Self::Csi(indexer) => Index::Csi(indexer.build(reference_sequence_count)),
+        }
+    }
+}
+
+/// An indexed BAM writer.
+///
+/// This builds an index of the records as they are written. Records must be coordinate-sorted.
+///
+/// When the BGZF stream is compressed by multiple workers, a record is indexed once the blocks
+/// before its end are written, so writing never waits on pending blocks to be compressed.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_bam::{self as bam, indexed_reader::Index};
+/// use noodles_sam::{self as sam, alignment::Record};
+///
+/// let mut writer = bam::indexed_writer::Builder::default().build_from_writer(Vec::new());
+///
+/// let header = sam::Header::default();
+/// writer.write_header(&header)?;
+/// writer.write_reference_sequences(header.reference_sequences())?;
+///
+/// let record = Record::default();
+/// writer.write_record(&header, &record)?;
+///
+/// let index = writer.finish()?;
+/// assert!(matches!(index, Index::Bai(_)));
+/// # Ok::<_, io::Error>(())
+/// ```
+pub struct IndexedWriter<W>
+where
+    W: Write,
+{
+    inner: Writer<bgzf::Writer<W>>,
+    indexer: Indexer,
+    pending_records: VecDeque<PendingRecord>,
+    reference_sequence_count: usize,
+    index_dst: Option<PathBuf>,
+}
+
+impl<W> IndexedWriter<W>
+where
+    W: Write,
+{
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &bgzf::Writer<W> {
+        self.inner.get_ref()
+    }
+
+    /// Writes a SAM header.
+    pub fn write_header(&mut self, header: &sam::Header) -> io::Result<()> {
+        self.inner.write_header(header)
+    }
+
+    /// Writes SAM reference sequences.
+    ///
+    /// The number of reference sequences written determines the number of reference sequences in
+    /// the index.
+    pub fn write_reference_sequences(
+        &mut self,
+        reference_sequences: &ReferenceSequences,
+    ) -> io::Result<()> {
+        self.inner.write_reference_sequences(reference_sequences)?;
+        self.reference_sequence_count = reference_sequences.len();
+        Ok(())
+    }
+
+    /// Writes a BAM record and adds it to the index.
+    ///
+    /// This returns an error if the record is out of coordinate order. When the BGZF stream is
+    /// compressed by multiple workers, the error may instead be returned by a subsequent write
+    /// or [`Self::finish`].
+    pub fn write_record(&mut self, header: &sam::Header, record: &Record) -> io::Result<()> {
+        let start_position = self.inner.get_mut().block_position();
+        self.inner.write_record(header, record)?;
+        let end_position = self.inner.get_mut().block_position();
+
+        self.pending_records.push_back(PendingRecord {
+            alignment_context: alignment_context(record),
+            start_position,
+            resolved_start_position: None,
+            end_position,
+        });
+
+        self.index_pending_records()
+    }
+
+    // Adds pending records to the index, in order, whose chunks can be resolved.
+    fn index_pending_records(&mut self) -> io::Result<()> {
+        let writer = self.inner.get_mut();
+
+        while let Some(record) = self.pending_records.front_mut() {
+            if record.resolved_start_position.is_none() {
+                record.resolved_start_position =
+                    writer.resolve_block_position(record.start_position);
+            }
+
+            let start_position = match record.resolved_start_position {
+                Some(position) => position,
+                None => break,
+            };
+
+            let end_position = match writer.resolve_block_position(record.end_position) {
+                Some(position) => position,
+                None => break,
+            };
+
+            let alignment_context = record.alignment_context;
+            self.pending_records.pop_front();
+
+            let chunk = Chunk::new(start_position, end_position);
+            self.indexer
+                .add_alignment_context(alignment_context, chunk)?;
+        }
+
+        Ok(())
+    }
+
+    /// Finishes the output stream and returns the index.
+    ///
+    /// If the writer was built from a path, the index is also written next to it, i.e., to
+    /// `<dst>.bai` or `<dst>.csi`.
+    pub fn finish(mut self) -> io::Result<Index> {
+        self.inner.try_finish()?;
+        self.index_pending_records()?;
+
+        let index = self.indexer.build(self.reference_sequence_count);
+
+        if let Some(dst) = self.index_dst {
+            match &index {
+                Index::Bai(index) => bai::write(dst, index)?,
+                Index::Csi(index) => csi::write(dst, index)?,
+            }
+        }
+
+        Ok(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_core::Position;
+    use noodles_csi::BinningIndex;
+    use noodles_sam::header::record::value::{map::ReferenceSequence, Map};
+
+    use super::*;
+    use crate::IndexedReader;
+
+    fn build_header() -> Result<sam::Header, Box<dyn std::error::Error>> {
+        Ok(sam::Header::builder()
+            .add_reference_sequence(Map::<ReferenceSequence>::new("sq0".parse()?, 8)?)
+            .add_reference_sequence(Map::<ReferenceSequence>::new("sq1".parse()?, 13)?)
+            .build())
+    }
+
+    fn build_record(
+        reference_sequence_id: usize,
+        alignment_start: usize,
+    ) -> Result<Record, Box<dyn std::error::Error>> {
+        Ok(Record::builder()
+            .set_flags(sam::record::Flags::empty())
+            .set_reference_sequence_id(reference_sequence_id)
+            .set_alignment_start(Position::try_from(alignment_start)?)
+            .set_cigar("4M".parse()?)
+            .set_sequence("ACGT".parse()?)
+            .build())
+    }
+
+    #[test]
+    fn test_finish() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header()?;
+
+        for index_format in [
+            IndexFormat::Bai,
+            IndexFormat::Csi {
+                min_shift: 14,
+                depth: 5,
+            },
+        ] {
+            let mut buf = Vec::new();
+
+            let mut writer = Builder::default()
+                .set_index_format(index_format)
+                .build_from_writer(&mut buf);
+
+            writer.write_header(&header)?;
+            writer.write_reference_sequences(header.reference_sequences())?;
+            writer.write_record(&header, &build_record(0, 1)?)?;
+            writer.write_record(&header, &build_record(1, 5)?)?;
+            writer.write_record(&header, &Record::default())?;
+
+            let index = writer.finish()?;
+
+            let unplaced_unmapped_record_count = match &index {
+                Index::Bai(index) => index.unplaced_unmapped_record_count(),
+                Index::Csi(index) => index.unplaced_unmapped_record_count(),
+            };
+            assert_eq!(unplaced_unmapped_record_count, Some(1));
+
+            let mut reader = IndexedReader::new(io::Cursor::new(&buf), index);
+            reader.read_header()?;
+            reader.read_reference_sequences()?;
+
+            let region = "sq1".parse()?;
+            let records: Vec<_> = reader
+                .query(header.reference_sequences(), &region)?
+                .collect::<io::Result<_>>()?;
+
+            assert_eq!(records.len(), 1);
+            assert_eq!(records[0].reference_sequence_id(), Some(1));
+
+            let unmapped_records: Vec<_> = reader.query_unmapped()?.collect::<io::Result<_>>()?;
+            assert_eq!(unmapped_records.len(), 1);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_finish_with_multiple_workers() -> Result<(), Box<dyn std::error::Error>> {
+        use std::num::NonZeroUsize;
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(Map::<ReferenceSequence>::new("sq0".parse()?, 1 << 20)?)
+            .build();
+
+        let records = (1..=1 << 14)
+            .map(|alignment_start| build_record(0, alignment_start))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for index_format in [
+            IndexFormat::Bai,
+            IndexFormat::Csi {
+                min_shift: 14,
+                depth: 5,
+            },
+        ] {
+            let mut indexes = Vec::new();
+
+            for worker_count in [1, 4] {
+                let mut writer = Builder::default()
+                    .set_index_format(index_format)
+                    .set_worker_count(NonZeroUsize::try_from(worker_count)?)
+                    .build_from_writer(Vec::new());
+
+                writer.write_header(&header)?;
+                writer.write_reference_sequences(header.reference_sequences())?;
+
+                for record in &records {
+                    writer.write_record(&header, record)?;
+                }
+
+                indexes.push(writer.finish()?);
+            }
+
+            match (&indexes[0], &indexes[1]) {
+                (Index::Bai(expected), Index::Bai(actual)) => {
+                    assert_eq!(sorted_bai_bins(actual), sorted_bai_bins(expected));
+                }
+                (Index::Csi(expected), Index::Csi(actual)) => {
+                    assert_eq!(sorted_csi_bins(actual), sorted_csi_bins(expected));
+                }
+                _ => panic!("index format mismatch"),
+            }
+        }
+
+        Ok(())
+    }
+
+    // Bins are built in an unspecified order.
+    fn sorted_bai_bins(index: &bai::Index) -> Vec<Vec<bai::index::reference_sequence::Bin>> {
+        index
+            .reference_sequences()
+            .iter()
+            .map(|reference_sequence| {
+                let mut bins = reference_sequence.bins().to_vec();
+                bins.sort_by_key(|bin| bin.id());
+                bins
+            })
+            .collect()
+    }
+
+    fn sorted_csi_bins(index: &csi::Index) -> Vec<Vec<csi::index::reference_sequence::Bin>> {
+        index
+            .reference_sequences()
+            .iter()
+            .map(|reference_sequence| {
+                let mut bins = reference_sequence.bins().to_vec();
+                bins.sort_by_key(|bin| bin.id());
+                bins
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_write_record_with_unsorted_records() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header()?;
+
+        for index_format in [
+            IndexFormat::Bai,
+            IndexFormat::Csi {
+                min_shift: 14,
+                depth: 5,
+            },
+        ] {
+            let mut writer = Builder::default()
+                .set_index_format(index_format)
+                .build_from_writer(Vec::new());
+
+            writer.write_header(&header)?;
+            writer.write_reference_sequences(header.reference_sequences())?;
+            writer.write_record(&header, &build_record(1, 8)?)?;
+
+            assert!(matches!(
+                writer.write_record(&header, &build_record(0, 13)?),
+                Err(e) if e.kind() == io::ErrorKind::InvalidInput
+            ));
+
+            assert!(matches!(
+                writer.write_record(&header, &build_record(1, 5)?),
+                Err(e) if e.kind() == io::ErrorKind::InvalidInput
+            ));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_record_with_quality_scores_transforms() -> Result<(), Box<dyn std::error::Error>>
+    {
+        use sam::record::{quality_scores::Transform, Flags};
+
+        let header = build_header()?;
+
+        let mut buf = Vec::new();
+
+        let mut writer = Builder::default()
+            .add_quality_scores_transform(Flags::SECONDARY, Transform::Drop)
+            .build_from_writer(&mut buf);
+
+        writer.write_header(&header)?;
+        writer.write_reference_sequences(header.reference_sequences())?;
+
+        let record = Record::builder()
+            .set_flags(Flags::UNMAPPED | Flags::SECONDARY)
+            .set_sequence("ACGT".parse()?)
+            .set_quality_scores("+5?I".parse()?)
+            .build();
+
+        writer.write_record(&header, &record)?;
+        writer.finish()?;
+
+        let mut reader = crate::Reader::new(buf.as_slice());
+        let actual_header: sam::Header = reader.read_header()?.parse()?;
+        reader.read_reference_sequences()?;
+
+        assert!(actual_header.programs().contains_key("noodles-bam"));
+
+        let mut record = Record::default();
+        reader.read_record(&mut record)?;
+        assert!(record.quality_scores().is_empty());
+
+        Ok(())
+    }
+}
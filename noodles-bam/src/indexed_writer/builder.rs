@@ -0,0 +1,234 @@
+use std::{
+    collections::VecDeque,
+    ffi::{OsStr, OsString},
+    fs::File,
+    io::{self, Write},
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+};
+
+use noodles_bgzf::{self as bgzf, writer::CompressionLevel};
+use noodles_sam::record::{
+    quality_scores::{transform::Transforms, Transform},
+    Flags,
+};
+
+use super::{IndexedWriter, Indexer};
+use crate::{bai, Writer};
+
+/// The format of an index built by an [`IndexedWriter`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum IndexFormat {
+    /// A BAM index (BAI).
+    #[default]
+    Bai,
+    /// A coordinate-sorted index (CSI) with the given min shift and depth.
+    Csi {
+        /// The number of bits for the minimum interval.
+        min_shift: u8,
+        /// The max depth of the binning index.
+        depth: u8,
+    },
+}
+
+impl IndexFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            Self::Bai => "bai",
+            Self::Csi { .. } => "csi",
+        }
+    }
+}
+
+/// An indexed BAM writer builder.
+#[derive(Debug, Default)]
+pub struct Builder {
+    index_format: IndexFormat,
+    compression_level: Option<CompressionLevel>,
+    worker_count: Option<NonZeroUsize>,
+    quality_scores_transforms: Transforms,
+}
+
+impl Builder {
+    /// Sets the format of the index to build.
+    ///
+    /// By default, this builds a BAM index (BAI).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::indexed_writer::{self, IndexFormat};
+    ///
+    /// let builder = indexed_writer::Builder::default().set_index_format(IndexFormat::Csi {
+    ///     min_shift: 14,
+    ///     depth: 5,
+    /// });
+    /// ```
+    pub fn set_index_format(mut self, index_format: IndexFormat) -> Self {
+        self.index_format = index_format;
+        self
+    }
+
+    /// Sets the compression level of the BGZF stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::indexed_writer;
+    /// use noodles_bgzf::writer::CompressionLevel;
+    ///
+    /// let builder = indexed_writer::Builder::default()
+    ///     .set_compression_level(CompressionLevel::best());
+    /// ```
+    pub fn set_compression_level(mut self, compression_level: CompressionLevel) -> Self {
+        self.compression_level = Some(compression_level);
+        self
+    }
+
+    /// Sets the worker count of the BGZF stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_bam::indexed_writer;
+    ///
+    /// let worker_count = NonZeroUsize::try_from(4)?;
+    /// let builder = indexed_writer::Builder::default().set_worker_count(worker_count);
+    /// # Ok::<_, std::num::TryFromIntError>(())
+    /// ```
+    pub fn set_worker_count(mut self, worker_count: NonZeroUsize) -> Self {
+        self.worker_count = Some(worker_count);
+        self
+    }
+
+    /// Sets the quality scores transform.
+    ///
+    /// This is the same as [`crate::writer::Builder::set_quality_scores_transform`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::indexed_writer;
+    /// use noodles_sam::record::quality_scores::{transform::Bins, Transform};
+    ///
+    /// let builder = indexed_writer::Builder::default()
+    ///     .set_quality_scores_transform(Transform::Bin(Bins::illumina_8_level()));
+    /// ```
+    pub fn set_quality_scores_transform(mut self, transform: Transform) -> Self {
+        self.quality_scores_transforms.set_default(transform);
+        self
+    }
+
+    /// Adds a quality scores transform for records with any of the given flags set.
+    ///
+    /// This is the same as [`crate::writer::Builder::add_quality_scores_transform`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::indexed_writer;
+    /// use noodles_sam::record::{quality_scores::Transform, Flags};
+    ///
+    /// let builder = indexed_writer::Builder::default()
+    ///     .add_quality_scores_transform(Flags::UNMAPPED | Flags::SECONDARY, Transform::Drop);
+    /// ```
+    pub fn add_quality_scores_transform(mut self, flags: Flags, transform: Transform) -> Self {
+        self.quality_scores_transforms.push(flags, transform);
+        self
+    }
+
+    /// Builds an indexed BAM writer from a path.
+    ///
+    /// When the writer is finished, the index is written to `<dst>.bai` or `<dst>.csi`, depending
+    /// on the index format.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use noodles_bam::indexed_writer;
+    /// let writer = indexed_writer::Builder::default().build_from_path("out.bam")?;
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn build_from_path<P>(self, dst: P) -> io::Result<IndexedWriter<File>>
+    where
+        P: AsRef<Path>,
+    {
+        let dst = dst.as_ref();
+        let index_dst = push_ext(dst.into(), self.index_format.extension());
+
+        let file = File::create(dst)?;
+        let mut writer = self.build_from_writer(file);
+        writer.index_dst = Some(index_dst);
+
+        Ok(writer)
+    }
+
+    /// Builds an indexed BAM writer from a writer.
+    ///
+    /// The index is only returned by [`IndexedWriter::finish`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::indexed_writer;
+    /// let writer = indexed_writer::Builder::default().build_from_writer(Vec::new());
+    /// ```
+    pub fn build_from_writer<W>(self, writer: W) -> IndexedWriter<W>
+    where
+        W: Write,
+    {
+        let mut builder = bgzf::Writer::builder(writer);
+
+        if let Some(compression_level) = self.compression_level {
+            builder = builder.set_compression_level(compression_level);
+        }
+
+        if let Some(worker_count) = self.worker_count {
+            builder = builder.set_worker_count(worker_count);
+        }
+
+        let indexer = match self.index_format {
+            IndexFormat::Bai => Indexer::Bai(bai::Index::builder()),
+            IndexFormat::Csi { min_shift, depth } => Indexer::Csi(
+                crate::Indexer::default()
+                    .set_min_shift(min_shift)
+                    .set_depth(depth),
+            ),
+        };
+
+        let mut inner = Writer::from(builder.build());
+        inner.quality_scores_transforms = self.quality_scores_transforms;
+
+        IndexedWriter {
+            inner,
+            indexer,
+            pending_records: VecDeque::new(),
+            reference_sequence_count: 0,
+            index_dst: None,
+        }
+    }
+}
+
+fn push_ext<S>(path: PathBuf, ext: S) -> PathBuf
+where
+    S: AsRef<OsStr>,
+{
+    let mut s = OsString::from(path);
+    s.push(".");
+    s.push(ext);
+    PathBuf::from(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_ext() {
+        assert_eq!(
+            push_ext(PathBuf::from("out.bam"), "bai"),
+            PathBuf::from("out.bam.bai")
+        );
+    }
+}
@@ -0,0 +1,174 @@
+//! BAM coordinate-sorted index (CSI) indexer.
+
+use std::io;
+
+use noodles_core::Position;
+use noodles_csi::{self as csi, index::reference_sequence::bin::Chunk};
+use noodles_sam::{alignment::Record, record::Flags};
+
+// The reference sequence ID, start and end positions, and flags of a placed record.
+pub(crate) type AlignmentContext = (usize, Position, Position, Flags);
+
+pub(crate) fn alignment_context(record: &Record) -> Option<AlignmentContext> {
+    match (
+        record.reference_sequence_id(),
+        record.alignment_start(),
+        record.alignment_end(),
+    ) {
+        (Some(id), Some(start), Some(end)) => Some((id, start, end, record.flags())),
+        _ => None,
+    }
+}
+
+/// A BAM coordinate-sorted index (CSI) indexer.
+///
+/// This creates a CSI from coordinate-sorted BAM records and their chunks. Unlike a BAM index
+/// (BAI), the min shift and depth are configurable, which allows indexing reference sequences
+/// longer than 2^29 - 1 bases.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_bam as bam;
+///
+/// let indexer = bam::Indexer::default().set_min_shift(14).set_depth(6);
+/// let index = indexer.build(0);
+///
+/// assert_eq!(index.min_shift(), 14);
+/// assert_eq!(index.depth(), 6);
+/// ```
+#[derive(Debug, Default)]
+pub struct Indexer {
+    inner: csi::index::Indexer,
+}
+
+impl Indexer {
+    /// Sets a min shift.
+    ///
+    /// This is the number of bits for the minimum interval. By default, this is 14.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// let indexer = bam::Indexer::default().set_min_shift(13);
+    /// ```
+    pub fn set_min_shift(mut self, min_shift: u8) -> Self {
+        self.inner = self.inner.set_min_shift(min_shift);
+        self
+    }
+
+    /// Sets a max depth.
+    ///
+    /// By default, this is 5.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// let indexer = bam::Indexer::default().set_depth(6);
+    /// ```
+    pub fn set_depth(mut self, depth: u8) -> Self {
+        self.inner = self.inner.set_depth(depth);
+        self
+    }
+
+    /// Adds a record.
+    ///
+    /// The chunk is the start and end virtual positions of the record in the BAM.
+    ///
+    /// This returns an error if the record is out of coordinate order or if its end position
+    /// exceeds the maximum position supported by the min shift and depth.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_csi::index::reference_sequence::bin::Chunk;
+    /// use noodles_sam::alignment::Record;
+    ///
+    /// let mut indexer = bam::Indexer::default();
+    ///
+    /// let record = Record::default();
+    /// let chunk = Chunk::new(
+    ///     bgzf::VirtualPosition::from(144),
+    ///     bgzf::VirtualPosition::from(233),
+    /// );
+    ///
+    /// indexer.add_record(&record, chunk)?;
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn add_record(&mut self, record: &Record, chunk: Chunk) -> io::Result<()> {
+        self.add_alignment_context(alignment_context(record), chunk)
+    }
+
+    pub(crate) fn add_alignment_context(
+        &mut self,
+        alignment_context: Option<AlignmentContext>,
+        chunk: Chunk,
+    ) -> io::Result<()> {
+        let alignment_context =
+            alignment_context.map(|(id, start, end, flags)| (id, start, end, !flags.is_unmapped()));
+
+        self.inner.add_record(alignment_context, chunk)
+    }
+
+    /// Builds a coordinate-sorted index (CSI).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// use noodles_csi::BinningIndex;
+    ///
+    /// let index = bam::Indexer::default().build(2);
+    /// assert_eq!(index.reference_sequences().len(), 2);
+    /// ```
+    pub fn build(self, reference_sequence_count: usize) -> csi::Index {
+        self.inner.build(reference_sequence_count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_bgzf as bgzf;
+    use noodles_core::Position;
+    use noodles_csi::BinningIndex;
+    use noodles_sam as sam;
+
+    use super::*;
+
+    #[test]
+    fn test_build() -> Result<(), Box<dyn std::error::Error>> {
+        let chunk = |start, end| {
+            Chunk::new(
+                bgzf::VirtualPosition::from(start),
+                bgzf::VirtualPosition::from(end),
+            )
+        };
+
+        let record = Record::builder()
+            .set_flags(sam::record::Flags::empty())
+            .set_reference_sequence_id(1)
+            .set_alignment_start(Position::try_from(8)?)
+            .set_cigar("4M".parse()?)
+            .build();
+
+        let mut indexer = Indexer::default().set_depth(6);
+        indexer.add_record(&record, chunk(55, 89))?;
+        indexer.add_record(&Record::default(), chunk(89, 144))?;
+
+        let index = indexer.build(2);
+
+        assert_eq!(index.depth(), 6);
+        assert_eq!(index.reference_sequences().len(), 2);
+        assert_eq!(index.unplaced_unmapped_record_count(), Some(1));
+
+        let start = Position::try_from(8)?;
+        let end = Position::try_from(11)?;
+        assert_eq!(index.query(1, start..=end)?, [chunk(55, 89)]);
+
+        Ok(())
+    }
+}
@@ -26,7 +26,8 @@
 //!
 //! ## Query records
 //!
-//! Querying allows filtering records by region. It requires an associated BAM index (BAI).
+//! Querying allows filtering records by region. It requires an associated BAM index (BAI) or
+//! coordinate-sorted index (CSI).
 //!
 //! ```no_run
 //! # use std::fs::File;
@@ -54,12 +55,17 @@ mod r#async;
 
 pub mod bai;
 pub mod indexed_reader;
+pub mod indexed_writer;
+pub mod indexer;
 pub mod lazy;
 pub mod reader;
 pub mod record;
 pub mod writer;
 
-pub use self::{indexed_reader::IndexedReader, reader::Reader, writer::Writer};
+pub use self::{
+    indexed_reader::IndexedReader, indexed_writer::IndexedWriter, indexer::Indexer, reader::Reader,
+    writer::Writer,
+};
 
 #[cfg(feature = "async")]
 pub use self::r#async::{Reader as AsyncReader, Writer as AsyncWriter};
@@ -29,7 +29,7 @@ use noodles_sam::{
     },
 };
 
-use super::{bai, lazy, MAGIC_NUMBER};
+use super::{lazy, MAGIC_NUMBER};
 
 /// A BAM reader.
 ///
@@ -407,6 +407,9 @@ where
 
     /// Returns an iterator of unmapped records after querying for the unmapped region.
     ///
+    /// The index can be any binning index, e.g., a BAM index (BAI) or coordinate-sorted index
+    /// (CSI).
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -423,7 +426,10 @@ where
     /// }
     /// # Ok::<(), io::Error>(())
     /// ```
-    pub fn query_unmapped(&mut self, index: &bai::Index) -> io::Result<UnmappedRecords<'_, R>> {
+    pub fn query_unmapped<I>(&mut self, index: &I) -> io::Result<UnmappedRecords<'_, R>>
+    where
+        I: BinningIndex,
+    {
         if let Some(pos) = index.first_record_in_last_linear_bin_start_position() {
             self.seek(pos)?;
         } else {
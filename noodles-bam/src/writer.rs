@@ -1,7 +1,10 @@
 //! BAM writer.
 
+mod builder;
 pub mod record;
 
+pub use self::builder::Builder;
+
 use std::{
     ffi::CString,
     io::{self, Write},
@@ -16,10 +19,13 @@ use noodles_sam::{
         record::value::{map::ReferenceSequence, Map},
         ReferenceSequences,
     },
+    record::quality_scores::transform::Transforms,
 };
 
 use self::record::encode_record;
 
+pub(crate) const PROGRAM_NAME: &str = "noodles-bam";
+
 /// A BAM writer.
 ///
 /// # Examples
@@ -42,6 +48,7 @@ use self::record::encode_record;
 pub struct Writer<W> {
     inner: W,
     buf: Vec<u8>,
+    pub(crate) quality_scores_transforms: Transforms,
 }
 
 impl<W> Writer<W>
@@ -103,7 +110,15 @@ where
     /// # Ok::<(), io::Error>(())
     /// ```
     pub fn write_header(&mut self, header: &sam::Header) -> io::Result<()> {
-        write_header(&mut self.inner, header)
+        if self.quality_scores_transforms.is_empty() {
+            write_header(&mut self.inner, header)
+        } else {
+            let mut header = header.clone();
+            self.quality_scores_transforms
+                .add_program(&mut header, PROGRAM_NAME, env!("CARGO_PKG_VERSION"))
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+            write_header(&mut self.inner, &header)
+        }
     }
 
     /// Writes SAM reference sequences.
@@ -156,7 +171,12 @@ where
     /// ```
     pub fn write_record(&mut self, header: &sam::Header, record: &Record) -> io::Result<()> {
         self.buf.clear();
-        encode_record(&mut self.buf, header, record)?;
+        encode_transformed_record(
+            &mut self.buf,
+            header,
+            record,
+            &self.quality_scores_transforms,
+        )?;
 
         let block_size = u32::try_from(self.buf.len())
             .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
@@ -210,6 +230,7 @@ impl<W> From<W> for Writer<W> {
         Self {
             inner,
             buf: Vec::new(),
+            quality_scores_transforms: Transforms::default(),
         }
     }
 }
@@ -292,6 +313,23 @@ where
     Ok(())
 }
 
+// Encodes a record after applying the quality scores transform selected by its flags, if any.
+pub(crate) fn encode_transformed_record(
+    buf: &mut Vec<u8>,
+    header: &sam::Header,
+    record: &Record,
+    transforms: &Transforms,
+) -> io::Result<()> {
+    match transforms.get(record.flags()) {
+        Some(transform) => {
+            let mut record = record.clone();
+            transform.apply(record.quality_scores_mut());
+            encode_record(buf, header, &record)
+        }
+        None => encode_record(buf, header, record),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use noodles_sam::{record::Data, AlignmentWriter};
@@ -538,4 +576,57 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_write_record_with_quality_scores_transforms() -> Result<(), Box<dyn std::error::Error>>
+    {
+        use sam::record::{
+            quality_scores::{transform::Bins, Transform},
+            Flags,
+        };
+
+        let mut writer = Builder::default()
+            .set_quality_scores_transform(Transform::Bin(Bins::illumina_8_level()))
+            .add_quality_scores_transform(Flags::SECONDARY, Transform::Drop)
+            .build_from_writer(Vec::new());
+
+        let header = sam::Header::default();
+        writer.write_header(&header)?;
+        writer.write_reference_sequences(header.reference_sequences())?;
+
+        for flags in [Flags::UNMAPPED, Flags::UNMAPPED | Flags::SECONDARY] {
+            let record = Record::builder()
+                .set_flags(flags)
+                .set_sequence("ACGT".parse()?)
+                .set_quality_scores("+5?I".parse()?)
+                .build();
+
+            writer.write_record(&header, &record)?;
+        }
+
+        writer.try_finish()?;
+
+        let mut reader = Reader::new(writer.get_ref().get_ref().as_slice());
+        let actual_header: sam::Header = reader.read_header()?.parse()?;
+        reader.read_reference_sequences()?;
+
+        let program = actual_header
+            .programs()
+            .get(PROGRAM_NAME)
+            .ok_or("missing program")?;
+        assert_eq!(
+            program.description(),
+            Some("quality scores transformed: 0x100=drop;*=bin(2:6,10:15,20:22,25:27,30:33,35:37,40:40)")
+        );
+
+        let mut record = Record::default();
+
+        reader.read_record(&mut record)?;
+        assert_eq!(record.quality_scores().to_string(), "07BI");
+
+        reader.read_record(&mut record)?;
+        assert!(record.quality_scores().is_empty());
+
+        Ok(())
+    }
 }
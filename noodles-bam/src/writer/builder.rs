@@ -0,0 +1,79 @@
+use std::io::Write;
+
+use noodles_bgzf as bgzf;
+use noodles_sam::record::{
+    quality_scores::{transform::Transforms, Transform},
+    Flags,
+};
+
+use super::Writer;
+
+/// A BAM writer builder.
+#[derive(Debug, Default)]
+pub struct Builder {
+    quality_scores_transforms: Transforms,
+}
+
+impl Builder {
+    /// Sets the quality scores transform.
+    ///
+    /// The transform is applied to the quality scores of each record before it is encoded, e.g.,
+    /// to bin quality scores. It is used for records that do not match a transform added with
+    /// [`Self::add_quality_scores_transform`].
+    ///
+    /// When any transform is set, a program (`@PG`) is added to the written SAM header.
+    ///
+    /// By default, quality scores are unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// use noodles_sam::record::quality_scores::{transform::Bins, Transform};
+    ///
+    /// let builder = bam::writer::Builder::default()
+    ///     .set_quality_scores_transform(Transform::Bin(Bins::illumina_8_level()));
+    /// ```
+    pub fn set_quality_scores_transform(mut self, transform: Transform) -> Self {
+        self.quality_scores_transforms.set_default(transform);
+        self
+    }
+
+    /// Adds a quality scores transform for records with any of the given flags set.
+    ///
+    /// Transforms added this way take precedence over the one set by
+    /// [`Self::set_quality_scores_transform`] and are matched in the order they are added.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// use noodles_sam::record::{quality_scores::Transform, Flags};
+    ///
+    /// let builder = bam::writer::Builder::default()
+    ///     .add_quality_scores_transform(Flags::UNMAPPED | Flags::SECONDARY, Transform::Drop);
+    /// ```
+    pub fn add_quality_scores_transform(mut self, flags: Flags, transform: Transform) -> Self {
+        self.quality_scores_transforms.push(flags, transform);
+        self
+    }
+
+    /// Builds a BAM writer from a writer.
+    ///
+    /// The given stream is wrapped in a BGZF encoder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// let writer = bam::writer::Builder::default().build_from_writer(Vec::new());
+    /// ```
+    pub fn build_from_writer<W>(self, writer: W) -> Writer<bgzf::Writer<W>>
+    where
+        W: Write,
+    {
+        let mut writer = Writer::new(writer);
+        writer.quality_scores_transforms = self.quality_scores_transforms;
+        writer
+    }
+}
@@ -0,0 +1,95 @@
+//! Indexed BCF reader.
+
+mod builder;
+
+pub use self::builder::Builder;
+
+use std::io::{self, Read, Seek};
+
+use noodles_bgzf as bgzf;
+use noodles_core::Region;
+use noodles_csi as csi;
+
+use super::{
+    header::string_maps::ContigStringMap,
+    reader::{Query, Records},
+    Reader, Record,
+};
+
+/// An indexed BCF reader.
+pub struct IndexedReader<R> {
+    inner: Reader<R>,
+    index: csi::Index,
+}
+
+impl<R> IndexedReader<R>
+where
+    R: Read,
+{
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        self.inner.get_ref()
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        self.inner.get_mut()
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner.into_inner()
+    }
+
+    /// Returns the associated index.
+    pub fn index(&self) -> &csi::Index {
+        &self.index
+    }
+
+    /// Reads the BCF file format.
+    pub fn read_file_format(&mut self) -> io::Result<(u8, u8)> {
+        self.inner.read_file_format()
+    }
+
+    /// Reads the raw VCF header.
+    pub fn read_header(&mut self) -> io::Result<String> {
+        self.inner.read_header()
+    }
+
+    /// Reads a single record.
+    pub fn read_record(&mut self, record: &mut Record) -> io::Result<usize> {
+        self.inner.read_record(record)
+    }
+
+    /// Returns an iterator over records starting from the current stream position.
+    pub fn records(&mut self) -> Records<'_, R> {
+        self.inner.records()
+    }
+}
+
+impl<R> IndexedReader<bgzf::Reader<R>>
+where
+    R: Read,
+{
+    /// Creates an indexed BCF reader.
+    pub fn new(inner: R, index: csi::Index) -> Self {
+        Self {
+            inner: Reader::new(inner),
+            index,
+        }
+    }
+}
+
+impl<R> IndexedReader<bgzf::Reader<R>>
+where
+    R: Read + Seek,
+{
+    /// Returns an iterator over records that intersect the given region.
+    pub fn query(
+        &mut self,
+        contig_string_map: &ContigStringMap,
+        region: &Region,
+    ) -> io::Result<Query<'_, R>> {
+        self.inner.query(contig_string_map, &self.index, region)
+    }
+}
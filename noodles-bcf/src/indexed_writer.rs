@@ -0,0 +1,371 @@
+//! Indexed BCF writer.
+
+mod builder;
+
+pub use self::builder::Builder;
+
+use std::{
+    collections::VecDeque,
+    io::{self, Write},
+    path::PathBuf,
+};
+
+use noodles_bgzf as bgzf;
+use noodles_core::Position;
+use noodles_csi::{self as csi, index::reference_sequence::bin::Chunk};
+use noodles_vcf as vcf;
+
+use super::{header::StringMaps, Record, Writer};
+
+// A record that is written but not yet indexed, as the compressed positions of its chunk are not
+// yet known.
+struct PendingRecord {
+    alignment_context: (usize, Position, Position),
+    start_position: bgzf::writer::BlockPosition,
+    resolved_start_position: Option<bgzf::VirtualPosition>,
+    end_position: bgzf::writer::BlockPosition,
+}
+
+/// An indexed BCF writer.
+///
+/// This builds a coordinate-sorted index (CSI) of the records as they are written. Records must
+/// be coordinate-sorted.
+///
+/// When the BGZF stream is compressed by multiple workers, a record is indexed once the blocks
+/// before its end are written, so writing never waits on pending blocks to be compressed.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_bcf::{self as bcf, header::StringMaps};
+/// use noodles_csi::BinningIndex;
+/// use noodles_vcf::{
+///     self as vcf,
+///     header::record::value::{map::Contig, Map},
+///     record::Position,
+/// };
+///
+/// let mut writer = bcf::indexed_writer::Builder::default().build_from_writer(Vec::new());
+///
+/// let header = vcf::Header::builder()
+///     .add_contig(Map::<Contig>::new("sq0".parse()?))
+///     .build();
+///
+/// writer.write_file_format()?;
+/// writer.write_header(&header)?;
+///
+/// let string_maps = StringMaps::from(&header);
+///
+/// let record = vcf::Record::builder()
+///     .set_chromosome("sq0".parse()?)
+///     .set_position(Position::from(8))
+///     .set_reference_bases("A".parse()?)
+///     .build()?;
+///
+/// writer.write_vcf_record(&header, &string_maps, &record)?;
+///
+/// let index = writer.finish()?;
+/// assert_eq!(index.reference_sequences().len(), 1);
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub struct IndexedWriter<W>
+where
+    W: Write,
+{
+    inner: Writer<bgzf::Writer<W>>,
+    indexer: csi::index::Indexer,
+    pending_records: VecDeque<PendingRecord>,
+    reference_sequence_count: usize,
+    index_dst: Option<PathBuf>,
+}
+
+impl<W> IndexedWriter<W>
+where
+    W: Write,
+{
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &bgzf::Writer<W> {
+        self.inner.get_ref()
+    }
+
+    /// Writes a BCF file format.
+    pub fn write_file_format(&mut self) -> io::Result<()> {
+        self.inner.write_file_format()
+    }
+
+    /// Writes a VCF header.
+    ///
+    /// The number of contigs in the header determines the number of reference sequences in the
+    /// index.
+    pub fn write_header(&mut self, header: &vcf::Header) -> io::Result<()> {
+        self.inner.write_header(header)?;
+        self.reference_sequence_count = header.contigs().len();
+        Ok(())
+    }
+
+    /// Writes a record and adds it to the index.
+    ///
+    /// This returns an error if the record is out of coordinate order. When the BGZF stream is
+    /// compressed by multiple workers, the error may instead be returned by a subsequent write or
+    /// [`Self::finish`].
+    pub fn write_record(&mut self, record: &Record) -> io::Result<()> {
+        let start_position = self.inner.get_mut().block_position();
+        self.inner.write_record(record)?;
+        let end_position = self.inner.get_mut().block_position();
+
+        self.add_record(
+            record.chromosome_id(),
+            record.position(),
+            record.end()?,
+            start_position,
+            end_position,
+        )
+    }
+
+    /// Writes a VCF record and adds it to the index.
+    ///
+    /// This returns an error if the record is out of coordinate order. When the BGZF stream is
+    /// compressed by multiple workers, the error may instead be returned by a subsequent write or
+    /// [`Self::finish`].
+    pub fn write_vcf_record(
+        &mut self,
+        header: &vcf::Header,
+        string_maps: &StringMaps,
+        record: &vcf::Record,
+    ) -> io::Result<()> {
+        let start_position = self.inner.get_mut().block_position();
+        self.inner.write_vcf_record(header, string_maps, record)?;
+        let end_position = self.inner.get_mut().block_position();
+
+        // The chromosome is guaranteed to be in the string map, as it was written above.
+        let chromosome_id = match record.chromosome() {
+            vcf::record::Chromosome::Name(name) => string_maps.contigs().get_index_of(name),
+            vcf::record::Chromosome::Symbol(_) => None,
+        }
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "invalid chromosome"))?;
+
+        let end = record
+            .end()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        self.add_record(
+            chromosome_id,
+            record.position(),
+            end,
+            start_position,
+            end_position,
+        )
+    }
+
+    fn add_record(
+        &mut self,
+        chromosome_id: usize,
+        start: vcf::record::Position,
+        end: vcf::record::Position,
+        start_position: bgzf::writer::BlockPosition,
+        end_position: bgzf::writer::BlockPosition,
+    ) -> io::Result<()> {
+        // Telomeric positions (0) are indexed at the first position.
+        let start = Position::new(usize::from(start)).unwrap_or(Position::MIN);
+        let end = Position::new(usize::from(end)).unwrap_or(Position::MIN);
+
+        self.pending_records.push_back(PendingRecord {
+            alignment_context: (chromosome_id, start, end),
+            start_position,
+            resolved_start_position: None,
+            end_position,
+        });
+
+        self.index_pending_records()
+    }
+
+    // Adds pending records to the index, in order, whose chunks can be resolved.
+    fn index_pending_records(&mut self) -> io::Result<()> {
+        let writer = self.inner.get_mut();
+
+        while let Some(record) = self.pending_records.front_mut() {
+            if record.resolved_start_position.is_none() {
+                record.resolved_start_position =
+                    writer.resolve_block_position(record.start_position);
+            }
+
+            let start_position = match record.resolved_start_position {
+                Some(position) => position,
+                None => break,
+            };
+
+            let end_position = match writer.resolve_block_position(record.end_position) {
+                Some(position) => position,
+                None => break,
+            };
+
+            let (chromosome_id, start, end) = record.alignment_context;
+            self.pending_records.pop_front();
+
+            let chunk = Chunk::new(start_position, end_position);
+            self.indexer
+                .add_record(Some((chromosome_id, start, end, true)), chunk)?;
+        }
+
+        Ok(())
+    }
+
+    /// Finishes the output stream and returns the index.
+    ///
+    /// If the writer was built from a path, the index is also written next to it, i.e., to
+    /// `<dst>.csi`.
+    pub fn finish(mut self) -> io::Result<csi::Index> {
+        self.inner.try_finish()?;
+        self.index_pending_records()?;
+
+        let index = self.indexer.build(self.reference_sequence_count);
+
+        if let Some(dst) = self.index_dst {
+            csi::write(dst, &index)?;
+        }
+
+        Ok(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use noodles_vcf::header::record::value::{map::Contig, Map};
+
+    use super::*;
+    use crate::Reader;
+
+    #[test]
+    fn test_finish() -> Result<(), Box<dyn std::error::Error>> {
+        let header = vcf::Header::builder()
+            .add_contig(Map::<Contig>::new("sq0".parse()?))
+            .add_contig(Map::<Contig>::new("sq1".parse()?))
+            .build();
+
+        let string_maps = StringMaps::from(&header);
+
+        let mut buf = Vec::new();
+        let mut writer = Builder::default().build_from_writer(&mut buf);
+
+        writer.write_file_format()?;
+        writer.write_header(&header)?;
+
+        for (chromosome, position) in [("sq0", 8), ("sq1", 13), ("sq1", 21)] {
+            let record = vcf::Record::builder()
+                .set_chromosome(chromosome.parse()?)
+                .set_position(vcf::record::Position::from(position))
+                .set_reference_bases("A".parse()?)
+                .build()?;
+
+            writer.write_vcf_record(&header, &string_maps, &record)?;
+        }
+
+        let index = writer.finish()?;
+
+        let mut reader = Reader::new(Cursor::new(&buf));
+        reader.read_file_format()?;
+        reader.read_header()?;
+
+        let region = "sq1:14-21".parse()?;
+        let records: Vec<_> = reader
+            .query(string_maps.contigs(), &index, &region)?
+            .collect::<io::Result<_>>()?;
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(usize::from(records[0].position()), 21);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_finish_with_multiple_workers() -> Result<(), Box<dyn std::error::Error>> {
+        use std::num::NonZeroUsize;
+
+        let header = vcf::Header::builder()
+            .add_contig(Map::<Contig>::new("sq0".parse()?))
+            .build();
+
+        let string_maps = StringMaps::from(&header);
+
+        let records = (1..=1 << 15)
+            .map(|position| {
+                vcf::Record::builder()
+                    .set_chromosome("sq0".parse()?)
+                    .set_position(vcf::record::Position::from(position))
+                    .set_reference_bases("A".parse()?)
+                    .build()
+                    .map_err(Box::<dyn std::error::Error>::from)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut indexes = Vec::new();
+
+        for worker_count in [1, 4] {
+            let mut writer = Builder::default()
+                .set_worker_count(NonZeroUsize::try_from(worker_count)?)
+                .build_from_writer(Vec::new());
+
+            writer.write_file_format()?;
+            writer.write_header(&header)?;
+
+            for record in &records {
+                writer.write_vcf_record(&header, &string_maps, record)?;
+            }
+
+            indexes.push(writer.finish()?);
+        }
+
+        assert_eq!(sorted_bins(&indexes[1]), sorted_bins(&indexes[0]));
+
+        Ok(())
+    }
+
+    // Bins are built in an unspecified order.
+    fn sorted_bins(index: &csi::Index) -> Vec<Vec<csi::index::reference_sequence::Bin>> {
+        use csi::BinningIndex;
+
+        index
+            .reference_sequences()
+            .iter()
+            .map(|reference_sequence| {
+                let mut bins = reference_sequence.bins().to_vec();
+                bins.sort_by_key(|bin| bin.id());
+                bins
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_write_vcf_record_with_unsorted_records() -> Result<(), Box<dyn std::error::Error>> {
+        let header = vcf::Header::builder()
+            .add_contig(Map::<Contig>::new("sq0".parse()?))
+            .add_contig(Map::<Contig>::new("sq1".parse()?))
+            .build();
+
+        let string_maps = StringMaps::from(&header);
+
+        let mut writer = Builder::default().build_from_writer(Vec::new());
+        writer.write_file_format()?;
+        writer.write_header(&header)?;
+
+        let build_record = |chromosome: &str| {
+            vcf::Record::builder()
+                .set_chromosome(chromosome.parse().unwrap())
+                .set_position(vcf::record::Position::from(1))
+                .set_reference_bases("A".parse().unwrap())
+                .build()
+        };
+
+        writer.write_vcf_record(&header, &string_maps, &build_record("sq1")?)?;
+
+        assert!(matches!(
+            writer.write_vcf_record(&header, &string_maps, &build_record("sq0")?),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+}
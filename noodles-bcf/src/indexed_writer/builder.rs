@@ -0,0 +1,189 @@
+use std::{
+    collections::VecDeque,
+    ffi::{OsStr, OsString},
+    fs::File,
+    io::{self, Write},
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+};
+
+use noodles_bgzf::{self as bgzf, writer::CompressionLevel};
+use noodles_csi as csi;
+
+use super::IndexedWriter;
+use crate::Writer;
+
+/// An indexed BCF writer builder.
+#[derive(Debug)]
+pub struct Builder {
+    min_shift: u8,
+    depth: u8,
+    compression_level: Option<CompressionLevel>,
+    worker_count: Option<NonZeroUsize>,
+}
+
+impl Builder {
+    /// Sets the min shift of the index.
+    ///
+    /// By default, this is 14.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf::indexed_writer;
+    /// let builder = indexed_writer::Builder::default().set_min_shift(13);
+    /// ```
+    pub fn set_min_shift(mut self, min_shift: u8) -> Self {
+        self.min_shift = min_shift;
+        self
+    }
+
+    /// Sets the max depth of the index.
+    ///
+    /// By default, this is 5.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf::indexed_writer;
+    /// let builder = indexed_writer::Builder::default().set_depth(6);
+    /// ```
+    pub fn set_depth(mut self, depth: u8) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    /// Sets the compression level of the BGZF stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf::indexed_writer;
+    /// use noodles_bgzf::writer::CompressionLevel;
+    ///
+    /// let builder = indexed_writer::Builder::default()
+    ///     .set_compression_level(CompressionLevel::best());
+    /// ```
+    pub fn set_compression_level(mut self, compression_level: CompressionLevel) -> Self {
+        self.compression_level = Some(compression_level);
+        self
+    }
+
+    /// Sets the worker count of the BGZF stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_bcf::indexed_writer;
+    ///
+    /// let worker_count = NonZeroUsize::try_from(4)?;
+    /// let builder = indexed_writer::Builder::default().set_worker_count(worker_count);
+    /// # Ok::<_, std::num::TryFromIntError>(())
+    /// ```
+    pub fn set_worker_count(mut self, worker_count: NonZeroUsize) -> Self {
+        self.worker_count = Some(worker_count);
+        self
+    }
+
+    /// Builds an indexed BCF writer from a path.
+    ///
+    /// When the writer is finished, the index is written to `<dst>.csi`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use noodles_bcf::indexed_writer;
+    /// let writer = indexed_writer::Builder::default().build_from_path("out.bcf")?;
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn build_from_path<P>(self, dst: P) -> io::Result<IndexedWriter<File>>
+    where
+        P: AsRef<Path>,
+    {
+        let dst = dst.as_ref();
+        let index_dst = build_index_dst(dst);
+
+        let file = File::create(dst)?;
+        let mut writer = self.build_from_writer(file);
+        writer.index_dst = Some(index_dst);
+
+        Ok(writer)
+    }
+
+    /// Builds an indexed BCF writer from a writer.
+    ///
+    /// The index is only returned by [`IndexedWriter::finish`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf::indexed_writer;
+    /// let writer = indexed_writer::Builder::default().build_from_writer(Vec::new());
+    /// ```
+    pub fn build_from_writer<W>(self, writer: W) -> IndexedWriter<W>
+    where
+        W: Write,
+    {
+        let mut builder = bgzf::Writer::builder(writer);
+
+        if let Some(compression_level) = self.compression_level {
+            builder = builder.set_compression_level(compression_level);
+        }
+
+        if let Some(worker_count) = self.worker_count {
+            builder = builder.set_worker_count(worker_count);
+        }
+
+        let indexer = csi::Index::indexer()
+            .set_min_shift(self.min_shift)
+            .set_depth(self.depth);
+
+        IndexedWriter {
+            inner: Writer::from(builder.build()),
+            indexer,
+            pending_records: VecDeque::new(),
+            reference_sequence_count: 0,
+            index_dst: None,
+        }
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            min_shift: 14,
+            depth: 5,
+            compression_level: None,
+            worker_count: None,
+        }
+    }
+}
+
+fn build_index_dst<P>(dst: P) -> PathBuf
+where
+    P: AsRef<Path>,
+{
+    const EXT: &str = "csi";
+    push_ext(dst.as_ref().into(), EXT)
+}
+
+fn push_ext<S>(path: PathBuf, ext: S) -> PathBuf
+where
+    S: AsRef<OsStr>,
+{
+    let mut s = OsString::from(path);
+    s.push(".");
+    s.push(ext);
+    PathBuf::from(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_index_dst() {
+        assert_eq!(build_index_dst("out.bcf"), PathBuf::from("out.bcf.csi"));
+    }
+}
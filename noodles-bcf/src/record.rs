@@ -2,9 +2,9 @@
 
 mod convert;
 mod filters;
-mod genotypes;
+pub mod genotypes;
 mod info;
-pub(crate) mod value;
+pub mod value;
 
 pub(crate) use self::value::Value;
 pub use self::{filters::Filters, genotypes::Genotypes, info::Info};
@@ -1,8 +1,18 @@
+//! BCF record genotypes.
+
+pub mod column;
+
+pub use self::column::Column;
+
 use std::io;
 
 use noodles_vcf as vcf;
 
-use crate::header::string_maps::StringStringMap;
+use crate::{
+    header::string_maps::StringStringMap,
+    reader::{string_map::read_string_map_index, value::read_type},
+    record::value::Type,
+};
 
 /// BCF record genotypes.
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
@@ -13,6 +23,28 @@ pub struct Genotypes {
 }
 
 impl Genotypes {
+    /// Creates genotypes by wrapping the given buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf::record::Genotypes;
+    ///
+    /// let data = vec![
+    ///     0x11, 0x01, 0x21, 0x02, 0x04, // GT=0/1
+    ///     0x11, 0x02, 0x11, 0x08, // DP=8
+    /// ];
+    ///
+    /// let genotypes = Genotypes::new(data, 2, 1);
+    /// ```
+    pub fn new(buf: Vec<u8>, format_count: usize, sample_count: usize) -> Self {
+        Self {
+            buf,
+            format_count,
+            sample_count,
+        }
+    }
+
     /// Converts BCF record genotypes to VCF record genotypes.
     ///
     /// # Examples
@@ -55,6 +87,79 @@ impl Genotypes {
         Ok(genotypes)
     }
 
+    /// Returns the column of the genotype field with the given key.
+    ///
+    /// The column borrows the raw genotypes buffer, i.e., no VCF record genotypes are built.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bcf::{
+    ///     header::StringMaps,
+    ///     record::{genotypes::Column, value::Int8, Genotypes},
+    /// };
+    /// use noodles_vcf::{
+    ///     self as vcf,
+    ///     header::{format::Key, record::value::{map, Map}},
+    /// };
+    ///
+    /// let header = vcf::Header::builder()
+    ///     .add_format(Map::<map::Format>::from(Key::Genotype))
+    ///     .add_format(Map::<map::Format>::from(Key::ReadDepth))
+    ///     .add_sample_name("sample0")
+    ///     .add_sample_name("sample1")
+    ///     .build();
+    ///
+    /// let string_maps = StringMaps::from(&header);
+    ///
+    /// let data = vec![
+    ///     0x11, 0x01, 0x21, 0x02, 0x04, 0x04, 0x05, // GT=0/1, GT=1|1
+    ///     0x11, 0x02, 0x11, 0x08, 0x80, // DP=8, DP=.
+    /// ];
+    ///
+    /// let genotypes = Genotypes::new(data, 2, 2);
+    ///
+    /// match genotypes.get(string_maps.strings(), &Key::ReadDepth).transpose()? {
+    ///     Some(Column::Int8(values)) => {
+    ///         let depths: Vec<_> = values.iter().filter_map(|vector| vector.get(0)).collect();
+    ///         assert_eq!(depths, [Int8::Value(8), Int8::Missing]);
+    ///     }
+    ///     _ => panic!("invalid column"),
+    /// }
+    ///
+    /// if let Some(column) = genotypes.get(string_maps.strings(), &Key::Genotype).transpose()? {
+    ///     let genotypes = column.genotypes()?;
+    ///     let genotype = genotypes.get(0).unwrap();
+    ///     assert_eq!(genotype.alleles().count(), 2);
+    /// }
+    ///
+    /// assert!(genotypes.get(string_maps.strings(), &Key::ConditionalGenotypeQuality).is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn get(
+        &self,
+        string_string_map: &StringStringMap,
+        key: &vcf::header::format::Key,
+    ) -> Option<io::Result<Column<'_>>> {
+        let i = string_string_map.get_index_of(key.as_ref())?;
+
+        let mut src = &self.buf[..];
+
+        for _ in 0..self.format_count() {
+            match read_column(&mut src, self.len()) {
+                Ok((j, column)) => {
+                    if j == i {
+                        return Some(Ok(column));
+                    }
+                }
+                Err(e) => return Some(Err(e)),
+            }
+        }
+
+        None
+    }
+
     /// Returns the number of samples.
     ///
     /// # Examples
@@ -121,6 +226,56 @@ impl Genotypes {
     }
 }
 
+fn read_column<'a>(src: &mut &'a [u8], sample_count: usize) -> io::Result<(usize, Column<'a>)> {
+    use self::column::Values;
+
+    fn split_values<'a>(
+        src: &mut &'a [u8],
+        values_per_sample: usize,
+        width: usize,
+        sample_count: usize,
+    ) -> io::Result<&'a [u8]> {
+        let len = values_per_sample
+            .checked_mul(width)
+            .and_then(|n| n.checked_mul(sample_count))
+            .ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidData, "invalid genotype field length")
+            })?;
+
+        if src.len() < len {
+            return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
+        }
+
+        let (buf, rest) = src.split_at(len);
+        *src = rest;
+
+        Ok(buf)
+    }
+
+    let i = read_string_map_index(src)?;
+
+    let column = match read_type(src)? {
+        Some(Type::Int8(len)) => split_values(src, len, 1, sample_count)
+            .map(|buf| Column::Int8(Values::new(buf, len, sample_count)))?,
+        Some(Type::Int16(len)) => split_values(src, len, 2, sample_count)
+            .map(|buf| Column::Int16(Values::new(buf, len, sample_count)))?,
+        Some(Type::Int32(len)) => split_values(src, len, 4, sample_count)
+            .map(|buf| Column::Int32(Values::new(buf, len, sample_count)))?,
+        Some(Type::Float(len)) => split_values(src, len, 4, sample_count)
+            .map(|buf| Column::Float(Values::new(buf, len, sample_count)))?,
+        Some(Type::String(len)) => split_values(src, len, 1, sample_count)
+            .map(|buf| Column::String(Values::new(buf, len, sample_count)))?,
+        None => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "missing genotype field type",
+            ))
+        }
+    };
+
+    Ok((i, column))
+}
+
 impl AsRef<[u8]> for Genotypes {
     fn as_ref(&self) -> &[u8] {
         &self.buf
@@ -132,3 +287,84 @@ impl AsMut<Vec<u8>> for Genotypes {
         &mut self.buf
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use vcf::header::{
+        format::Key,
+        record::value::{map::Format, Map},
+    };
+
+    use super::*;
+    use crate::{header::StringMaps, record::value::Int16};
+
+    #[test]
+    fn test_get() -> io::Result<()> {
+        let header = vcf::Header::builder()
+            .add_format(Map::<Format>::from(Key::Genotype))
+            .add_format(Map::<Format>::from(Key::ReadDepths))
+            .add_format(Map::<Format>::from(Key::Filter))
+            .add_sample_name("sample0")
+            .add_sample_name("sample1")
+            .build();
+
+        let string_maps = StringMaps::from(&header);
+
+        let data = vec![
+            0x11, 0x01, 0x21, 0x02, 0x04, 0x04, 0x81, // GT=0/1, GT=1
+            0x11, 0x02, 0x22, 0x05, 0x00, 0x00, 0x01, 0x0d, 0x00, 0x01,
+            0x80, // AD=5,256, AD=13
+            0x11, 0x03, 0x27, b'q', b'0', b'P', 0x00, // FT=q0, FT=P
+        ];
+
+        let genotypes = Genotypes::new(data, 3, 2);
+
+        match genotypes.get(string_maps.strings(), &Key::ReadDepths) {
+            Some(Ok(Column::Int16(values))) => {
+                assert_eq!(values.values_per_sample(), 2);
+
+                let actual: Vec<Vec<_>> = values
+                    .iter()
+                    .map(|vector| vector.iter().collect())
+                    .collect();
+                let expected = [
+                    vec![Int16::Value(5), Int16::Value(256)],
+                    vec![Int16::Value(13), Int16::EndOfVector],
+                ];
+                assert_eq!(actual, expected);
+            }
+            _ => panic!("invalid column"),
+        }
+
+        match genotypes.get(string_maps.strings(), &Key::Filter) {
+            Some(Ok(Column::String(values))) => {
+                let actual: Vec<_> = values.iter().map(|vector| vector.as_bytes()).collect();
+                assert_eq!(actual, [&b"q0"[..], &b"P"[..]]);
+            }
+            _ => panic!("invalid column"),
+        }
+
+        let column = genotypes
+            .get(string_maps.strings(), &Key::Genotype)
+            .transpose()?
+            .expect("missing GT column");
+        let allele_counts: Vec<_> = column
+            .genotypes()?
+            .iter()
+            .map(|genotype| genotype.alleles().count())
+            .collect();
+        assert_eq!(allele_counts, [2, 1]);
+
+        assert!(genotypes
+            .get(string_maps.strings(), &Key::GenotypeCopyNumber)
+            .is_none());
+
+        let genotypes = Genotypes::new(vec![0x11, 0x01, 0x21, 0x02], 1, 2);
+        assert!(matches!(
+            genotypes.get(string_maps.strings(), &Key::Genotype),
+            Some(Err(ref e)) if e.kind() == io::ErrorKind::UnexpectedEof
+        ));
+
+        Ok(())
+    }
+}
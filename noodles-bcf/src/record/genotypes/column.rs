@@ -0,0 +1,115 @@
+//! BCF record genotypes column.
+
+mod genotype;
+mod values;
+
+pub use self::{
+    genotype::{Genotype, GenotypeValues},
+    values::{Values, Vector},
+};
+
+use std::io;
+
+use crate::record::value::{Float, Int16, Int32, Int8};
+
+/// A BCF record genotypes column.
+///
+/// A column holds the values of a single genotype field (`FORMAT`) for all samples. It borrows
+/// the record genotypes buffer, and values are decoded on access.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Column<'a> {
+    /// 8-bit integer values.
+    Int8(Values<'a, Int8>),
+    /// 16-bit integer values.
+    Int16(Values<'a, Int16>),
+    /// 32-bit integer values.
+    Int32(Values<'a, Int32>),
+    /// 32-bit floating-point values.
+    Float(Values<'a, Float>),
+    /// Character values.
+    String(Values<'a, u8>),
+}
+
+impl<'a> Column<'a> {
+    /// Returns the number of samples.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf::record::genotypes::{column::Values, Column};
+    ///
+    /// let data = [0x05, 0x08]; // DP=5, DP=8
+    /// let column = Column::Int8(Values::new(&data, 1, 2));
+    ///
+    /// assert_eq!(column.len(), 2);
+    /// ```
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Int8(values) => values.len(),
+            Self::Int16(values) => values.len(),
+            Self::Int32(values) => values.len(),
+            Self::Float(values) => values.len(),
+            Self::String(values) => values.len(),
+        }
+    }
+
+    /// Returns whether there are any samples.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf::record::genotypes::{column::Values, Column};
+    /// let column = Column::Int8(Values::new(&[], 1, 0));
+    /// assert!(column.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the column as genotype (`GT`) values.
+    ///
+    /// This returns an error if the column does not hold integer values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bcf::record::genotypes::{column::Values, Column};
+    ///
+    /// let data = [
+    ///     0x02, 0x04, // 0/1
+    ///     0x04, 0x05, // 1|1
+    /// ];
+    /// let column = Column::Int8(Values::new(&data, 2, 2));
+    ///
+    /// let genotypes = column.genotypes()?;
+    /// assert_eq!(genotypes.len(), 2);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn genotypes(&self) -> io::Result<GenotypeValues<'a>> {
+        match self {
+            Self::Int8(values) => Ok(GenotypeValues::new(
+                values.as_bytes(),
+                1,
+                values.values_per_sample(),
+                values.len(),
+            )),
+            Self::Int16(values) => Ok(GenotypeValues::new(
+                values.as_bytes(),
+                2,
+                values.values_per_sample(),
+                values.len(),
+            )),
+            Self::Int32(values) => Ok(GenotypeValues::new(
+                values.as_bytes(),
+                4,
+                values.values_per_sample(),
+                values.len(),
+            )),
+            Self::Float(_) | Self::String(_) => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid genotype column type",
+            )),
+        }
+    }
+}
@@ -0,0 +1,264 @@
+use noodles_vcf::record::genotypes::genotype::field::value::genotype::{allele::Phasing, Allele};
+
+use crate::record::value::{Int16, Int32, Int8};
+
+/// The genotype (`GT`) values of a BCF record genotypes column.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct GenotypeValues<'a> {
+    buf: &'a [u8],
+    width: usize,
+    values_per_sample: usize,
+    sample_count: usize,
+}
+
+impl<'a> GenotypeValues<'a> {
+    pub(super) fn new(
+        buf: &'a [u8],
+        width: usize,
+        values_per_sample: usize,
+        sample_count: usize,
+    ) -> Self {
+        Self {
+            buf,
+            width,
+            values_per_sample,
+            sample_count,
+        }
+    }
+
+    /// Returns the number of samples.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bcf::record::genotypes::{column::Values, Column};
+    ///
+    /// let data = [0x02, 0x04]; // 0/1
+    /// let column = Column::Int8(Values::new(&data, 2, 1));
+    ///
+    /// let genotypes = column.genotypes()?;
+    /// assert_eq!(genotypes.len(), 1);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn len(&self) -> usize {
+        self.sample_count
+    }
+
+    /// Returns whether there are any samples.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bcf::record::genotypes::{column::Values, Column};
+    /// let column = Column::Int8(Values::new(&[], 2, 0));
+    /// assert!(column.genotypes()?.is_empty());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the genotype of the sample at the given index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bcf::record::genotypes::{column::Values, Column};
+    /// use noodles_vcf::record::genotypes::genotype::field::value::genotype::{
+    ///     allele::Phasing,
+    ///     Allele,
+    /// };
+    ///
+    /// let data = [
+    ///     0x02, 0x04, // 0/1
+    ///     0x04, 0x05, // 1|1
+    /// ];
+    /// let column = Column::Int8(Values::new(&data, 2, 2));
+    /// let genotypes = column.genotypes()?;
+    ///
+    /// let genotype = genotypes.get(1).unwrap();
+    /// let actual: Vec<_> = genotype.alleles().collect();
+    /// let expected = [
+    ///     Allele::new(Some(1), None),
+    ///     Allele::new(Some(1), Some(Phasing::Phased)),
+    /// ];
+    /// assert_eq!(actual, expected);
+    ///
+    /// assert!(genotypes.get(2).is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn get(&self, i: usize) -> Option<Genotype<'a>> {
+        if i >= self.sample_count {
+            return None;
+        }
+
+        let len = self.values_per_sample * self.width;
+        let start = i * len;
+        let end = start + len;
+
+        self.buf
+            .get(start..end)
+            .map(|buf| Genotype::new(buf, self.width))
+    }
+
+    /// Returns an iterator over the genotypes of all samples.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bcf::record::genotypes::{column::Values, Column};
+    ///
+    /// let data = [
+    ///     0x02, 0x04, // 0/1
+    ///     0x04, 0x81, // 1
+    /// ];
+    /// let column = Column::Int8(Values::new(&data, 2, 2));
+    /// let genotypes = column.genotypes()?;
+    ///
+    /// let allele_counts: Vec<_> = genotypes.iter().map(|genotype| genotype.alleles().count()).collect();
+    /// assert_eq!(allele_counts, [2, 1]);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = Genotype<'a>> + 'a {
+        let genotypes = *self;
+        (0..genotypes.len()).map_while(move |i| genotypes.get(i))
+    }
+}
+
+/// The genotype (`GT`) of a sample in a BCF record genotypes column.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Genotype<'a> {
+    buf: &'a [u8],
+    width: usize,
+}
+
+impl<'a> Genotype<'a> {
+    fn new(buf: &'a [u8], width: usize) -> Self {
+        Self { buf, width }
+    }
+
+    /// Returns an iterator over the alleles of the genotype.
+    ///
+    /// The first allele has no phasing. Decoding stops at the end of the vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bcf::record::genotypes::{column::Values, Column};
+    /// use noodles_vcf::record::genotypes::genotype::field::value::genotype::{
+    ///     allele::Phasing,
+    ///     Allele,
+    /// };
+    ///
+    /// let data = [0x00, 0x00, 0x05, 0x00, 0x01, 0x80]; // .|1
+    /// let column = Column::Int16(Values::new(&data, 3, 1));
+    /// let genotypes = column.genotypes()?;
+    ///
+    /// let genotype = genotypes.get(0).unwrap();
+    /// let actual: Vec<_> = genotype.alleles().collect();
+    /// let expected = [
+    ///     Allele::new(None, None),
+    ///     Allele::new(Some(1), Some(Phasing::Phased)),
+    /// ];
+    /// assert_eq!(actual, expected);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn alleles(&self) -> impl Iterator<Item = Allele> + 'a {
+        let decode: fn(&[u8]) -> Option<i32> = match self.width {
+            1 => decode_int8,
+            2 => decode_int16,
+            _ => decode_int32,
+        };
+
+        self.buf
+            .chunks_exact(self.width)
+            .map_while(decode)
+            .enumerate()
+            .map(|(i, value)| parse_allele(i, value))
+    }
+}
+
+// Returns `None` at the end of the vector. A missing value is read as a missing allele.
+fn decode_int8(src: &[u8]) -> Option<i32> {
+    match Int8::from(src[0] as i8) {
+        Int8::Value(n) | Int8::Reserved(n) => Some(i32::from(n)),
+        Int8::Missing => Some(0),
+        Int8::EndOfVector => None,
+    }
+}
+
+fn decode_int16(src: &[u8]) -> Option<i32> {
+    match Int16::from(i16::from_le_bytes([src[0], src[1]])) {
+        Int16::Value(n) | Int16::Reserved(n) => Some(i32::from(n)),
+        Int16::Missing => Some(0),
+        Int16::EndOfVector => None,
+    }
+}
+
+fn decode_int32(src: &[u8]) -> Option<i32> {
+    match Int32::from(i32::from_le_bytes([src[0], src[1], src[2], src[3]])) {
+        Int32::Value(n) | Int32::Reserved(n) => Some(n),
+        Int32::Missing => Some(0),
+        Int32::EndOfVector => None,
+    }
+}
+
+fn parse_allele(i: usize, value: i32) -> Allele {
+    let position = usize::try_from((value >> 1) - 1).ok();
+
+    let phasing = if i == 0 {
+        None
+    } else if value & 0x01 == 1 {
+        Some(Phasing::Phased)
+    } else {
+        Some(Phasing::Unphased)
+    };
+
+    Allele::new(position, phasing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alleles() {
+        fn t(buf: &[u8], width: usize, expected: &[Allele]) {
+            let genotype = Genotype::new(buf, width);
+            let actual: Vec<_> = genotype.alleles().collect();
+            assert_eq!(actual, expected);
+        }
+
+        t(
+            &[0x02, 0x04],
+            1,
+            &[
+                Allele::new(Some(0), None),
+                Allele::new(Some(1), Some(Phasing::Unphased)),
+            ],
+        );
+
+        t(
+            &[0x02, 0x05, 0x81],
+            1,
+            &[
+                Allele::new(Some(0), None),
+                Allele::new(Some(1), Some(Phasing::Phased)),
+            ],
+        );
+
+        t(&[0x80, 0x81], 1, &[Allele::new(None, None)]);
+        t(&[0x81, 0x81], 1, &[]);
+
+        t(
+            &[0x04, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x80],
+            4,
+            &[Allele::new(Some(1), None)],
+        );
+    }
+}
@@ -0,0 +1,308 @@
+use std::marker::PhantomData;
+
+use crate::record::value::{Float, Int16, Int32, Int8};
+
+mod private {
+    pub trait Decode: Copy {
+        const SIZE: usize;
+
+        fn decode(src: &[u8]) -> Self;
+    }
+}
+
+use self::private::Decode;
+
+impl Decode for Int8 {
+    const SIZE: usize = 1;
+
+    fn decode(src: &[u8]) -> Self {
+        Self::from(src[0] as i8)
+    }
+}
+
+impl Decode for Int16 {
+    const SIZE: usize = 2;
+
+    fn decode(src: &[u8]) -> Self {
+        Self::from(i16::from_le_bytes([src[0], src[1]]))
+    }
+}
+
+impl Decode for Int32 {
+    const SIZE: usize = 4;
+
+    fn decode(src: &[u8]) -> Self {
+        Self::from(i32::from_le_bytes([src[0], src[1], src[2], src[3]]))
+    }
+}
+
+impl Decode for Float {
+    const SIZE: usize = 4;
+
+    fn decode(src: &[u8]) -> Self {
+        Self::from(f32::from_le_bytes([src[0], src[1], src[2], src[3]]))
+    }
+}
+
+impl Decode for u8 {
+    const SIZE: usize = 1;
+
+    fn decode(src: &[u8]) -> Self {
+        src[0]
+    }
+}
+
+/// The values of a BCF record genotypes column.
+///
+/// Each sample has a vector of the same number of values. Shorter vectors are padded with
+/// end-of-vector values.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Values<'a, T> {
+    buf: &'a [u8],
+    values_per_sample: usize,
+    sample_count: usize,
+    _ty: PhantomData<T>,
+}
+
+impl<'a, T> Values<'a, T> {
+    /// Creates genotypes column values by wrapping the given buffer.
+    ///
+    /// The buffer is the raw little-endian values of all samples.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf::record::{genotypes::column::Values, value::Int8};
+    /// let data = [0x05, 0x08]; // DP=5, DP=8
+    /// let values: Values<'_, Int8> = Values::new(&data, 1, 2);
+    /// ```
+    pub fn new(buf: &'a [u8], values_per_sample: usize, sample_count: usize) -> Self {
+        Self {
+            buf,
+            values_per_sample,
+            sample_count,
+            _ty: PhantomData,
+        }
+    }
+
+    /// Returns the number of samples.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf::record::{genotypes::column::Values, value::Int8};
+    /// let data = [0x05, 0x08]; // DP=5, DP=8
+    /// let values: Values<'_, Int8> = Values::new(&data, 1, 2);
+    /// assert_eq!(values.len(), 2);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.sample_count
+    }
+
+    /// Returns whether there are any samples.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf::record::{genotypes::column::Values, value::Int8};
+    /// let values: Values<'_, Int8> = Values::new(&[], 1, 0);
+    /// assert!(values.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of values per sample, including end-of-vector padding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf::record::{genotypes::column::Values, value::Int8};
+    /// let data = [0x05, 0x08]; // AD=5,8
+    /// let values: Values<'_, Int8> = Values::new(&data, 2, 1);
+    /// assert_eq!(values.values_per_sample(), 2);
+    /// ```
+    pub fn values_per_sample(&self) -> usize {
+        self.values_per_sample
+    }
+
+    pub(super) fn as_bytes(&self) -> &'a [u8] {
+        self.buf
+    }
+}
+
+impl<'a, T> Values<'a, T>
+where
+    T: Decode,
+{
+    /// Returns the value vector of the sample at the given index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf::record::{genotypes::column::Values, value::Int8};
+    ///
+    /// let data = [0x05, 0x08]; // DP=5, DP=8
+    /// let values: Values<'_, Int8> = Values::new(&data, 1, 2);
+    ///
+    /// let vector = values.get(1).unwrap();
+    /// assert_eq!(vector.get(0), Some(Int8::Value(8)));
+    ///
+    /// assert!(values.get(2).is_none());
+    /// ```
+    pub fn get(&self, i: usize) -> Option<Vector<'a, T>> {
+        if i >= self.sample_count {
+            return None;
+        }
+
+        let len = self.values_per_sample * T::SIZE;
+        let start = i * len;
+        let end = start + len;
+
+        self.buf.get(start..end).map(Vector::new)
+    }
+
+    /// Returns an iterator over the value vectors of all samples.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf::record::{genotypes::column::Values, value::Int8};
+    ///
+    /// let data = [0x05, 0x80]; // DP=5, DP=.
+    /// let values: Values<'_, Int8> = Values::new(&data, 1, 2);
+    ///
+    /// let actual: Vec<_> = values.iter().flat_map(|vector| vector.iter()).collect();
+    /// assert_eq!(actual, [Int8::Value(5), Int8::Missing]);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = Vector<'a, T>> + 'a
+    where
+        T: 'a,
+    {
+        let values = *self;
+        (0..values.len()).map_while(move |i| values.get(i))
+    }
+}
+
+/// The value vector of a sample in a BCF record genotypes column.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Vector<'a, T> {
+    buf: &'a [u8],
+    _ty: PhantomData<T>,
+}
+
+impl<'a, T> Vector<'a, T>
+where
+    T: Decode,
+{
+    fn new(buf: &'a [u8]) -> Self {
+        Self {
+            buf,
+            _ty: PhantomData,
+        }
+    }
+
+    /// Returns the number of values, including end-of-vector padding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf::record::{genotypes::column::Values, value::Int8};
+    ///
+    /// let data = [0x05, 0x81]; // AD=5
+    /// let values: Values<'_, Int8> = Values::new(&data, 2, 1);
+    ///
+    /// let vector = values.get(0).unwrap();
+    /// assert_eq!(vector.len(), 2);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.buf.len() / T::SIZE
+    }
+
+    /// Returns whether there are any values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf::record::{genotypes::column::Values, value::Int8};
+    ///
+    /// let data = [0x05];
+    /// let values: Values<'_, Int8> = Values::new(&data, 1, 1);
+    ///
+    /// let vector = values.get(0).unwrap();
+    /// assert!(!vector.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the value at the given index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf::record::{genotypes::column::Values, value::Int8};
+    ///
+    /// let data = [0x05, 0x81]; // AD=5
+    /// let values: Values<'_, Int8> = Values::new(&data, 2, 1);
+    ///
+    /// let vector = values.get(0).unwrap();
+    /// assert_eq!(vector.get(0), Some(Int8::Value(5)));
+    /// assert_eq!(vector.get(1), Some(Int8::EndOfVector));
+    /// assert!(vector.get(2).is_none());
+    /// ```
+    pub fn get(&self, i: usize) -> Option<T> {
+        let start = i * T::SIZE;
+        let end = start + T::SIZE;
+        self.buf.get(start..end).map(T::decode)
+    }
+
+    /// Returns an iterator over all values, including missing and end-of-vector values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf::record::{genotypes::column::Values, value::Int16};
+    ///
+    /// let data = [0x05, 0x00, 0x00, 0x80, 0x01, 0x80]; // AD=5,.
+    /// let values: Values<'_, Int16> = Values::new(&data, 3, 1);
+    ///
+    /// let vector = values.get(0).unwrap();
+    /// let actual: Vec<_> = vector.iter().collect();
+    /// assert_eq!(actual, [Int16::Value(5), Int16::Missing, Int16::EndOfVector]);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = T> + 'a
+    where
+        T: 'a,
+    {
+        self.buf.chunks_exact(T::SIZE).map(T::decode)
+    }
+}
+
+impl<'a> Vector<'a, u8> {
+    /// Returns the characters of a string value with the trailing padding removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf::record::genotypes::column::Values;
+    ///
+    /// let data = *b"ndls\x00\x00";
+    /// let values: Values<'_, u8> = Values::new(&data, 6, 1);
+    ///
+    /// let vector = values.get(0).unwrap();
+    /// assert_eq!(vector.as_bytes(), b"ndls");
+    /// ```
+    pub fn as_bytes(&self) -> &'a [u8] {
+        const NUL: u8 = 0x00;
+
+        let end = self
+            .buf
+            .iter()
+            .rposition(|&b| b != NUL)
+            .map(|i| i + 1)
+            .unwrap_or(0);
+
+        &self.buf[..end]
+    }
+}
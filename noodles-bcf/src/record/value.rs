@@ -1,3 +1,5 @@
+//! BCF record values.
+
 mod float;
 mod int16;
 mod int32;
@@ -6,15 +8,25 @@ mod ty;
 
 pub use self::{float::Float, int16::Int16, int32::Int32, int8::Int8, ty::Type};
 
+/// A BCF record value.
 #[derive(Clone, Debug, PartialEq)]
 pub enum Value {
+    /// An 8-bit integer.
     Int8(Option<Int8>),
+    /// An array of 8-bit integers.
     Int8Array(Vec<i8>),
+    /// A 16-bit integer.
     Int16(Option<Int16>),
+    /// An array of 16-bit integers.
     Int16Array(Vec<i16>),
+    /// A 32-bit integer.
     Int32(Option<Int32>),
+    /// An array of 32-bit integers.
     Int32Array(Vec<i32>),
+    /// A 32-bit floating-point number.
     Float(Option<Float>),
+    /// An array of 32-bit floating-point numbers.
     FloatArray(Vec<f32>),
+    /// A string.
     String(Option<String>),
 }
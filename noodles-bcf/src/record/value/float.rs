@@ -1,9 +1,14 @@
-#[derive(Clone, Debug, PartialEq)]
+/// A BCF record 32-bit floating-point value.
+#[derive(Clone, Copy, Debug, PartialEq)]
 #[non_exhaustive]
 pub enum Float {
+    /// A value.
     Value(f32),
+    /// A missing value.
     Missing,
+    /// The end of a vector, i.e., padding after the last value.
     EndOfVector,
+    /// A reserved value.
     Reserved(f32),
 }
 
@@ -1,9 +1,14 @@
-#[derive(Clone, Debug, Eq, PartialEq)]
+/// A BCF record 8-bit integer value.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 #[non_exhaustive]
 pub enum Int8 {
+    /// A value.
     Value(i8),
+    /// A missing value.
     Missing,
+    /// The end of a vector, i.e., padding after the last value.
     EndOfVector,
+    /// A reserved value.
     Reserved(i8),
 }
 
@@ -49,6 +49,11 @@ where
             values.push(value);
         }
 
+        if key == &Key::Genotype {
+            write_genotype_genotype_field_values(writer, &values)?;
+            continue;
+        }
+
         let format = header.formats().get(key).ok_or_else(|| {
             io::Error::new(io::ErrorKind::InvalidData, "missing FORMAT header record")
         })?;
@@ -78,6 +83,145 @@ where
         .and_then(|i| write_string_map_index(writer, i))
 }
 
+fn write_genotype_genotype_field_values<W>(
+    writer: &mut W,
+    values: &[Option<&Value>],
+) -> io::Result<()>
+where
+    W: Write,
+{
+    let mut encoded_values = Vec::with_capacity(values.len());
+
+    for value in values {
+        let encoded_value = match value {
+            Some(Value::String(s)) => encode_genotype_genotype_field_value(s)?,
+            Some(v) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("type mismatch: expected String, got {:?}", v),
+                ))
+            }
+            None => vec![encode_allele(None, false)?],
+        };
+
+        encoded_values.push(encoded_value);
+    }
+
+    let max_len = encoded_values.iter().map(|v| v.len()).max().unwrap_or(1);
+    let max = encoded_values
+        .iter()
+        .flatten()
+        .copied()
+        .max()
+        .unwrap_or_default();
+
+    if max <= i32::from(Int8::MAX_VALUE) {
+        write_type(writer, Some(Type::Int8(max_len)))?;
+
+        write_padded_values(
+            &encoded_values,
+            max_len,
+            i32::from(i8::from(Int8::EndOfVector)),
+            |n| {
+                i8::try_from(n)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+                    .and_then(|m| writer.write_i8(m))
+            },
+        )
+    } else if max <= i32::from(Int16::MAX_VALUE) {
+        write_type(writer, Some(Type::Int16(max_len)))?;
+
+        write_padded_values(
+            &encoded_values,
+            max_len,
+            i32::from(i16::from(Int16::EndOfVector)),
+            |n| {
+                i16::try_from(n)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+                    .and_then(|m| writer.write_i16::<LittleEndian>(m))
+            },
+        )
+    } else {
+        write_type(writer, Some(Type::Int32(max_len)))?;
+
+        write_padded_values(
+            &encoded_values,
+            max_len,
+            i32::from(Int32::EndOfVector),
+            |n| writer.write_i32::<LittleEndian>(n),
+        )
+    }
+}
+
+fn write_padded_values<F>(
+    values: &[Vec<i32>],
+    max_len: usize,
+    end_of_vector: i32,
+    mut write_value: F,
+) -> io::Result<()>
+where
+    F: FnMut(i32) -> io::Result<()>,
+{
+    for vs in values {
+        for &n in vs {
+            write_value(n)?;
+        }
+
+        for _ in vs.len()..max_len {
+            write_value(end_of_vector)?;
+        }
+    }
+
+    Ok(())
+}
+
+// Each allele is encoded as `(i + 1) << 1 | is_phased`, where a missing allele has an index of -1.
+fn encode_genotype_genotype_field_value(s: &str) -> io::Result<Vec<i32>> {
+    const PHASED: char = '|';
+    const UNPHASED: char = '/';
+
+    let mut values = Vec::new();
+    let mut is_phased = false;
+    let mut start = 0;
+
+    for (i, c) in s.char_indices() {
+        if c == PHASED || c == UNPHASED {
+            values.push(encode_allele(parse_allele(&s[start..i])?, is_phased)?);
+            is_phased = c == PHASED;
+            start = i + c.len_utf8();
+        }
+    }
+
+    values.push(encode_allele(parse_allele(&s[start..])?, is_phased)?);
+
+    Ok(values)
+}
+
+fn parse_allele(s: &str) -> io::Result<Option<usize>> {
+    match s {
+        "." => Ok(None),
+        _ => s
+            .parse()
+            .map(Some)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e)),
+    }
+}
+
+fn encode_allele(allele: Option<usize>, is_phased: bool) -> io::Result<i32> {
+    let i = allele.map(|i| i + 1).unwrap_or(0);
+
+    i32::try_from(i)
+        .ok()
+        .and_then(|n| n.checked_mul(2))
+        .map(|n| n | i32::from(is_phased))
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid genotype allele index: {:?}", allele),
+            )
+        })
+}
+
 pub fn write_genotype_field_values<W>(
     writer: &mut W,
     format: &Map<Format>,
@@ -688,6 +832,64 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_write_genotype_genotype_field_values() -> io::Result<()> {
+        let mut buf = Vec::new();
+
+        let gt_0 = Value::String(String::from("0/1"));
+        let gt_1 = Value::String(String::from("1|1"));
+        let gt_2 = Value::String(String::from("./."));
+        let gt_3 = Value::String(String::from("0"));
+        let values = [Some(&gt_0), Some(&gt_1), Some(&gt_2), Some(&gt_3), None];
+
+        write_genotype_genotype_field_values(&mut buf, &values)?;
+
+        let expected = [
+            0x21, // Some(Type::Int8(2))
+            0x02, 0x04, // 0/1
+            0x04, 0x05, // 1|1
+            0x00, 0x00, // ./.
+            0x02, 0x81, // 0
+            0x00, 0x81, // .
+        ];
+
+        assert_eq!(buf, expected);
+
+        // Allele indices >= 63 do not fit in an Int8.
+        buf.clear();
+        let gt_0 = Value::String(String::from("0/63"));
+        let gt_1 = Value::String(String::from("1"));
+        write_genotype_genotype_field_values(&mut buf, &[Some(&gt_0), Some(&gt_1)])?;
+
+        let expected = [
+            0x22, // Some(Type::Int16(2))
+            0x02, 0x00, 0x80, 0x00, // 0/63
+            0x04, 0x00, 0x01, 0x80, // 1
+        ];
+
+        assert_eq!(buf, expected);
+
+        buf.clear();
+        let gt_0 = Value::String(String::from("0|16383"));
+        write_genotype_genotype_field_values(&mut buf, &[Some(&gt_0)])?;
+
+        let expected = [
+            0x23, // Some(Type::Int32(2))
+            0x02, 0x00, 0x00, 0x00, // 0
+            0x01, 0x80, 0x00, 0x00, // |16383
+        ];
+
+        assert_eq!(buf, expected);
+
+        let invalid = Value::Integer(0);
+        assert!(matches!(
+            write_genotype_genotype_field_values(&mut buf, &[Some(&invalid)]),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+
     #[test]
     fn test_write_genotype_field_values_with_integer_values(
     ) -> Result<(), Box<dyn std::error::Error>> {
@@ -119,6 +119,49 @@ where
         self.block.virtual_position()
     }
 
+    /// Reads the next BGZF frame without decompressing it.
+    ///
+    /// A frame is a whole compressed block, i.e., the gzip header, compressed data, and trailer.
+    /// The frame is read into the given buffer, which is resized to the frame size. Any unread
+    /// data in the current block is discarded.
+    ///
+    /// This returns the uncompressed size of the block (ISIZE) or `None` if the stream is at EOF.
+    /// An error is returned if the reader decompresses blocks on multiple workers, as frames are
+    /// read ahead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// writer.write_all(b"noodles")?;
+    /// let data = writer.finish()?;
+    ///
+    /// let mut reader = bgzf::Reader::new(&data[..]);
+    /// let mut buf = Vec::new();
+    ///
+    /// assert_eq!(reader.read_raw_frame(&mut buf)?, Some(7));
+    /// assert_eq!(reader.read_raw_frame(&mut buf)?, Some(0)); // EOF block
+    /// assert_eq!(reader.read_raw_frame(&mut buf)?, None);
+    /// assert_eq!(reader.position(), data.len() as u64);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn read_raw_frame(&mut self, buf: &mut Vec<u8>) -> io::Result<Option<usize>> {
+        let uncompressed_size = match self.inner.next_raw_frame(buf)? {
+            Some(n) => n,
+            None => return Ok(None),
+        };
+
+        self.position += buf.len() as u64;
+
+        self.block = Block::default();
+        self.block.set_position(self.position);
+
+        Ok(Some(uncompressed_size))
+    }
+
     fn read_block(&mut self) -> io::Result<()> {
         if let Some(mut block) = self.inner.next_block()? {
             block.set_position(self.position);
@@ -320,4 +363,58 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_read_raw_frame() -> Result<(), Box<dyn std::error::Error>> {
+        #[rustfmt::skip]
+        let data = [
+            // block 0 (b"noodles")
+            0x1f, 0x8b, 0x08, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0xff, 0x06, 0x00, 0x42, 0x43,
+            0x02, 0x00, 0x22, 0x00, 0xcb, 0xcb, 0xcf, 0x4f, 0xc9, 0x49, 0x2d, 0x06, 0x00, 0xa1,
+            0x58, 0x2a, 0x80, 0x07, 0x00, 0x00, 0x00,
+            // block 1 (b"bgzf")
+            0x1f, 0x8b, 0x08, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0xff, 0x06, 0x00, 0x42, 0x43,
+            0x02, 0x00, 0x1f, 0x00, 0x4b, 0x4a, 0xaf, 0x4a, 0x03, 0x00, 0x20, 0x68, 0xf2, 0x8c,
+            0x04, 0x00, 0x00, 0x00,
+            // EOF block
+            0x1f, 0x8b, 0x08, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0xff, 0x06, 0x00, 0x42, 0x43,
+            0x02, 0x00, 0x1b, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        ];
+
+        let mut reader = Reader::new(&data[..]);
+
+        // The rest of the current block is discarded.
+        let mut buf = [0; 3];
+        reader.read_exact(&mut buf)?;
+        assert_eq!(&buf, b"noo");
+
+        let mut frame = Vec::new();
+        assert_eq!(reader.read_raw_frame(&mut frame)?, Some(4));
+        assert_eq!(frame, &data[35..67]);
+        assert_eq!(
+            reader.virtual_position(),
+            VirtualPosition::try_from((67, 0))?
+        );
+
+        assert_eq!(reader.read_raw_frame(&mut frame)?, Some(0));
+        assert_eq!(frame, &data[67..]);
+
+        assert!(reader.read_raw_frame(&mut frame)?.is_none());
+
+        let mut reader = Reader::new(&data[..20]);
+        assert!(matches!(
+            reader.read_raw_frame(&mut frame),
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof
+        ));
+
+        let mut invalid_data = data;
+        invalid_data[12] = b'X';
+        let mut reader = Reader::new(&invalid_data[..]);
+        assert!(matches!(
+            reader.read_raw_frame(&mut frame),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
 }
@@ -44,6 +44,16 @@ where
             Self::Multi(reader) => reader.next_block(),
         }
     }
+
+    pub fn next_raw_frame(&mut self, buf: &mut Vec<u8>) -> io::Result<Option<usize>> {
+        match self {
+            Self::Single(reader) => reader.next_raw_frame(buf),
+            Self::Multi(_) => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "raw frames cannot be read with multiple workers",
+            )),
+        }
+    }
 }
 
 fn read_frame<R>(reader: &mut R) -> io::Result<Option<Vec<u8>>>
@@ -160,6 +170,16 @@ where
     Ok((crc32, r#isize))
 }
 
+// Validates the header of a frame and returns the uncompressed size of its block.
+fn parse_raw_frame(src: &[u8]) -> io::Result<usize> {
+    let (header, _, trailer) = split_frame(src);
+
+    parse_header(header)?;
+    let (_, r#isize) = parse_trailer(trailer)?;
+
+    Ok(r#isize)
+}
+
 pub(crate) fn parse_frame(src: &[u8]) -> io::Result<Block> {
     let (header, cdata, trailer) = split_frame(src);
 
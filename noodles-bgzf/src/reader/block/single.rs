@@ -39,4 +39,14 @@ where
             Ok(None)
         }
     }
+
+    pub fn next_raw_frame(&mut self, buf: &mut Vec<u8>) -> io::Result<Option<usize>> {
+        use super::{parse_raw_frame, read_frame_into};
+
+        if read_frame_into(&mut self.inner, buf)?.is_some() {
+            parse_raw_frame(buf).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
 }
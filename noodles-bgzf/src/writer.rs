@@ -1,18 +1,24 @@
 //! BGZF writer.
 
+mod block_position;
 mod builder;
 mod compression_level;
+mod deflater;
 
-pub use self::{builder::Builder, compression_level::CompressionLevel};
+pub use self::{
+    block_position::BlockPosition, builder::Builder, compression_level::CompressionLevel,
+};
 
 use std::{
     cmp,
+    collections::VecDeque,
     io::{self, Write},
 };
 
 use byteorder::{LittleEndian, WriteBytesExt};
 use flate2::Crc;
 
+use self::deflater::Deflater;
 use super::{gz, VirtualPosition, BGZF_HEADER_SIZE, BGZF_MAX_ISIZE};
 
 // The max DEFLATE overhead for 65536 bytes of data at compression level 0.
@@ -83,6 +89,14 @@ where
     position: u64,
     buf: Vec<u8>,
     compression_level: CompressionLevelImpl,
+    deflater: Option<Deflater>,
+    block_number: u64,
+    written_block_count: u64,
+    // The compressed offsets of the last written blocks that unresolved block positions may
+    // refer to.
+    block_offsets: VecDeque<u64>,
+    unresolved_block_position_count: usize,
+    first_unresolved_block_number: u64,
 }
 
 impl<W> Writer<W>
@@ -142,6 +156,10 @@ where
 
     /// Returns the current position of the stream.
     ///
+    /// This is the number of compressed bytes written to the underlying writer. When the writer
+    /// compresses blocks on worker threads, blocks that are still pending are not included, i.e.,
+    /// this is the compressed position of the first pending block.
+    ///
     /// # Examples
     ///
     /// ```
@@ -155,6 +173,11 @@ where
 
     /// Returns the current virtual position of the stream.
     ///
+    /// When the writer compresses blocks on worker threads, this waits for all pending blocks to
+    /// be compressed, as their compressed sizes are needed to determine the current compressed
+    /// position. Use [`Self::block_position`] to get a position without waiting, e.g., when
+    /// building an index.
+    ///
     /// # Panics
     ///
     /// This panics if the stream flushed >= 256 TiB of compressed data.
@@ -169,12 +192,129 @@ where
     pub fn virtual_position(&self) -> VirtualPosition {
         // SAFETY: The uncompressed buffer is guaranteed to be <= `MAX_UNCOMPRESSED_POSITION`.
         let uncompressed_position = self.buf.len() as u16;
-        VirtualPosition::try_from((self.position, uncompressed_position)).unwrap()
+
+        let compressed_position = match self.deflater.as_ref() {
+            Some(deflater) => self.position + deflater.pending_size(),
+            None => self.position,
+        };
+
+        VirtualPosition::try_from((compressed_position, uncompressed_position)).unwrap()
+    }
+
+    /// Returns the current block position of the stream.
+    ///
+    /// Unlike [`Self::virtual_position`], this never waits for pending blocks to be compressed.
+    /// The block position is resolved to a virtual position using
+    /// [`Self::resolve_block_position`] after the blocks before it are written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// writer.write_all(b"noodles")?;
+    ///
+    /// let block_position = writer.block_position();
+    /// assert_eq!(block_position.block_number(), 0);
+    /// assert_eq!(block_position.uncompressed_position(), 7);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn block_position(&mut self) -> BlockPosition {
+        if self.unresolved_block_position_count == 0 {
+            self.first_unresolved_block_number = self.block_number;
+        }
+
+        self.unresolved_block_position_count += 1;
+
+        // SAFETY: The uncompressed buffer is guaranteed to be <= `MAX_UNCOMPRESSED_POSITION`.
+        BlockPosition::new(self.block_number, self.buf.len() as u16)
+    }
+
+    /// Resolves a block position to a virtual position.
+    ///
+    /// This returns `None` if the blocks before the block position are not yet written.
+    ///
+    /// Block positions are expected to be resolved in the order they were returned by
+    /// [`Self::block_position`]. Resolving a block position releases the compressed offsets of
+    /// the blocks before it, after which earlier block positions can no longer be resolved.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the stream flushed >= 256 TiB of compressed data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// writer.write_all(b"noodles")?;
+    ///
+    /// let block_position = writer.block_position();
+    ///
+    /// assert_eq!(
+    ///     writer.resolve_block_position(block_position),
+    ///     Some(writer.virtual_position())
+    /// );
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn resolve_block_position(
+        &mut self,
+        block_position: BlockPosition,
+    ) -> Option<VirtualPosition> {
+        let block_number = block_position.block_number();
+
+        let compressed_position = if block_number == self.written_block_count {
+            self.position
+        } else if block_number < self.written_block_count {
+            let first_block_number = self.written_block_count - self.block_offsets.len() as u64;
+            let i = block_number.checked_sub(first_block_number)?;
+            self.block_offsets[i as usize]
+        } else {
+            return None;
+        };
+
+        self.unresolved_block_position_count =
+            self.unresolved_block_position_count.saturating_sub(1);
+
+        if self.unresolved_block_position_count == 0 {
+            self.block_offsets.clear();
+        } else {
+            let first_block_number = self.written_block_count - self.block_offsets.len() as u64;
+            let released_block_count = block_number.saturating_sub(first_block_number) as usize;
+            self.block_offsets.drain(..released_block_count);
+            self.first_unresolved_block_number = block_number;
+        }
+
+        let uncompressed_position = block_position.uncompressed_position();
+        Some(VirtualPosition::try_from((compressed_position, uncompressed_position)).unwrap())
+    }
+
+    // Records the compressed offset of the next block to be written, if an unresolved block
+    // position may refer to it.
+    fn add_written_block(&mut self) {
+        if self.unresolved_block_position_count > 0
+            && self.written_block_count >= self.first_unresolved_block_number
+        {
+            self.block_offsets.push_back(self.position);
+        }
+
+        self.written_block_count += 1;
     }
 
     fn flush_block(&mut self) -> io::Result<()> {
+        if self.deflater.is_some() {
+            return self.send_block();
+        }
+
         let (cdata, crc32, r#isize) = deflate_data(&self.buf, self.compression_level)?;
 
+        self.add_written_block();
+        self.block_number += 1;
+
         let inner = self.inner.as_mut().unwrap();
         let block_size = BGZF_HEADER_SIZE + cdata.len() + gz::TRAILER_SIZE;
 
@@ -189,6 +329,43 @@ where
         Ok(())
     }
 
+    fn send_block(&mut self) -> io::Result<()> {
+        let buf = std::mem::replace(&mut self.buf, Vec::with_capacity(MAX_BUF_SIZE));
+        self.block_number += 1;
+
+        let deflater = self.deflater.as_mut().unwrap();
+        deflater.send(buf);
+
+        if deflater.is_full() {
+            self.write_frame()?;
+        }
+
+        Ok(())
+    }
+
+    fn write_frame(&mut self) -> io::Result<bool> {
+        let deflater = self.deflater.as_mut().unwrap();
+
+        match deflater.recv() {
+            Some(result) => {
+                let frame = result?;
+                self.add_written_block();
+                self.inner.as_mut().unwrap().write_all(&frame)?;
+                self.position += frame.len() as u64;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    fn write_pending_frames(&mut self) -> io::Result<()> {
+        if self.deflater.is_some() {
+            while self.write_frame()? {}
+        }
+
+        Ok(())
+    }
+
     /// Attempts to finish the output stream by flushing any remaining buffers.
     ///
     /// This then appends the final BGZF EOF block.
@@ -208,6 +385,10 @@ where
     pub fn try_finish(&mut self) -> io::Result<()> {
         self.flush()?;
 
+        // The EOF block is the last block.
+        self.add_written_block();
+        self.block_number += 1;
+
         let inner = self.inner.as_mut().unwrap();
         let result = inner.write_all(BGZF_EOF);
 
@@ -260,18 +441,18 @@ where
         self.buf.extend_from_slice(&buf[..max_write_len]);
 
         if self.buf.len() >= MAX_BUF_SIZE {
-            self.flush()?;
+            self.flush_block()?;
         }
 
         Ok(max_write_len)
     }
 
     fn flush(&mut self) -> io::Result<()> {
-        if self.buf.is_empty() {
-            Ok(())
-        } else {
-            self.flush_block()
+        if !self.buf.is_empty() {
+            self.flush_block()?;
         }
+
+        self.write_pending_frames()
     }
 }
 
@@ -390,6 +571,85 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_write_with_multiple_workers() -> Result<(), Box<dyn std::error::Error>> {
+        use std::{io::Read, num::NonZeroUsize};
+
+        use crate::Reader;
+
+        let data: Vec<u8> = (0..4 * MAX_BUF_SIZE + 8).map(|i| (i % 251) as u8).collect();
+
+        let mut writer = Builder::new(Vec::new())
+            .set_worker_count(NonZeroUsize::try_from(2)?)
+            .build();
+
+        writer.write_all(&data)?;
+
+        let mut expected_writer = Writer::new(Vec::new());
+        expected_writer.write_all(&data)?;
+
+        assert_eq!(
+            writer.virtual_position(),
+            expected_writer.virtual_position()
+        );
+
+        let expected = expected_writer.finish()?;
+        let actual = writer.finish()?;
+        assert_eq!(actual, expected);
+
+        let mut reader = Reader::new(&actual[..]);
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+        assert_eq!(buf, data);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_resolve_block_position_with_multiple_workers() -> Result<(), Box<dyn std::error::Error>>
+    {
+        use std::num::NonZeroUsize;
+
+        let data: Vec<u8> = (0..4 * MAX_BUF_SIZE + 8).map(|i| (i % 251) as u8).collect();
+
+        let mut writer = Builder::new(Vec::new())
+            .set_worker_count(NonZeroUsize::try_from(2)?)
+            .build();
+
+        let mut expected_writer = Writer::new(Vec::new());
+
+        let mut block_positions = Vec::new();
+        let mut expected = Vec::new();
+
+        for chunk in data.chunks(MAX_BUF_SIZE / 3) {
+            block_positions.push(writer.block_position());
+            expected.push(expected_writer.virtual_position());
+
+            writer.write_all(chunk)?;
+            expected_writer.write_all(chunk)?;
+        }
+
+        block_positions.push(writer.block_position());
+        expected.push(expected_writer.virtual_position());
+
+        // The block before the last block is still pending.
+        let last_block_position = block_positions.last().copied().ok_or("missing position")?;
+        assert!(writer.resolve_block_position(last_block_position).is_none());
+
+        writer.try_finish()?;
+
+        let actual: Vec<_> = block_positions
+            .into_iter()
+            .map(|block_position| writer.resolve_block_position(block_position))
+            .collect::<Option<_>>()
+            .ok_or("unresolved block position")?;
+
+        assert_eq!(actual, expected);
+        assert!(writer.block_offsets.is_empty());
+
+        Ok(())
+    }
+
     #[test]
     fn test_write_header() {
         let mut writer = io::sink();
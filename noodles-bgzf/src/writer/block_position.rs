@@ -0,0 +1,53 @@
+/// A position in a BGZF stream relative to a block.
+///
+/// A block position is the sequence number of a block and the position in its uncompressed data.
+/// Unlike a [`crate::VirtualPosition`], it does not depend on the compressed sizes of preceding
+/// blocks, so it is known while blocks are still being compressed. It is resolved to a virtual
+/// position by [`super::Writer::resolve_block_position`] once the blocks before it are written.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub struct BlockPosition {
+    block_number: u64,
+    uncompressed_position: u16,
+}
+
+impl BlockPosition {
+    pub(crate) fn new(block_number: u64, uncompressed_position: u16) -> Self {
+        Self {
+            block_number,
+            uncompressed_position,
+        }
+    }
+
+    /// Returns the sequence number of the block.
+    ///
+    /// The first block written is block 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// assert_eq!(writer.block_position().block_number(), 0);
+    /// ```
+    pub fn block_number(&self) -> u64 {
+        self.block_number
+    }
+
+    /// Returns the position in the uncompressed data of the block.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// writer.write_all(b"noodles")?;
+    ///
+    /// assert_eq!(writer.block_position().uncompressed_position(), 7);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn uncompressed_position(&self) -> u16 {
+        self.uncompressed_position
+    }
+}
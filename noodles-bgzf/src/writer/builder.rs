@@ -1,12 +1,18 @@
-use std::io::Write;
+use std::{collections::VecDeque, io::Write, num::NonZeroUsize};
 
-use super::{CompressionLevel, Writer, MAX_BUF_SIZE};
+use super::{CompressionLevel, Deflater, Writer, MAX_BUF_SIZE};
+
+const DEFAULT_WORKER_COUNT: NonZeroUsize = match NonZeroUsize::new(1) {
+    Some(worker_count) => worker_count,
+    None => unreachable!(),
+};
 
 /// A BGZF writer builder.
 #[derive(Debug)]
 pub struct Builder<W> {
     inner: W,
     compression_level: Option<CompressionLevel>,
+    worker_count: NonZeroUsize,
 }
 
 impl<W> Builder<W>
@@ -17,6 +23,7 @@ where
         Self {
             inner,
             compression_level: None,
+            worker_count: DEFAULT_WORKER_COUNT,
         }
     }
 
@@ -37,7 +44,29 @@ where
         self
     }
 
-    /// Builds an async BGZF writer.
+    /// Sets the worker count.
+    ///
+    /// When the worker count is greater than 1, blocks are compressed in parallel on worker
+    /// threads and written in order. By default, the worker count is set to 1, i.e., blocks are
+    /// compressed on the calling thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    ///
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let worker_count = NonZeroUsize::try_from(4)?;
+    /// let builder = bgzf::Writer::builder(Vec::new()).set_worker_count(worker_count);
+    /// # Ok::<_, std::num::TryFromIntError>(())
+    /// ```
+    pub fn set_worker_count(mut self, worker_count: NonZeroUsize) -> Self {
+        self.worker_count = worker_count;
+        self
+    }
+
+    /// Builds a BGZF writer.
     ///
     /// # Examples
     ///
@@ -46,13 +75,25 @@ where
     /// let writer = bgzf::Writer::builder(Vec::new()).build();
     /// ```
     pub fn build(self) -> Writer<W> {
-        let compression_level = self.compression_level.unwrap_or_default();
+        let compression_level = self.compression_level.unwrap_or_default().into();
+
+        let deflater = if self.worker_count.get() == 1 {
+            None
+        } else {
+            Some(Deflater::new(self.worker_count, compression_level))
+        };
 
         Writer {
             inner: Some(self.inner),
             position: 0,
             buf: Vec::with_capacity(MAX_BUF_SIZE),
-            compression_level: compression_level.into(),
+            compression_level,
+            deflater,
+            block_number: 0,
+            written_block_count: 0,
+            block_offsets: VecDeque::new(),
+            unresolved_block_position_count: 0,
+            first_unresolved_block_number: 0,
         }
     }
 }
@@ -0,0 +1,213 @@
+use std::{
+    collections::VecDeque,
+    io,
+    num::NonZeroUsize,
+    sync::Mutex,
+    thread::{self, JoinHandle},
+};
+
+use crossbeam_channel::{Receiver, Sender};
+
+use super::CompressionLevelImpl;
+
+type BufferedTx = Sender<io::Result<Vec<u8>>>;
+type BufferedRx = Receiver<io::Result<Vec<u8>>>;
+type DeflaterTx = Sender<(Vec<u8>, BufferedTx)>;
+type DeflaterRx = Receiver<(Vec<u8>, BufferedTx)>;
+
+#[derive(Debug)]
+enum Frame {
+    Pending(BufferedRx),
+    Ready(io::Result<Vec<u8>>),
+}
+
+impl Frame {
+    fn wait(&mut self) -> &io::Result<Vec<u8>> {
+        if let Self::Pending(buffered_rx) = self {
+            let result = buffered_rx.recv().unwrap_or_else(|_| {
+                Err(io::Error::new(
+                    io::ErrorKind::BrokenPipe,
+                    "deflater disconnected",
+                ))
+            });
+
+            *self = Self::Ready(result);
+        }
+
+        match self {
+            Self::Ready(result) => result,
+            Self::Pending(_) => unreachable!(),
+        }
+    }
+
+    fn into_result(mut self) -> io::Result<Vec<u8>> {
+        self.wait();
+
+        match self {
+            Self::Ready(result) => result,
+            Self::Pending(_) => unreachable!(),
+        }
+    }
+}
+
+/// A pool of workers that compresses blocks in parallel.
+///
+/// Blocks are compressed to whole BGZF frames and are returned in the order they were sent.
+#[derive(Debug)]
+pub(crate) struct Deflater {
+    deflater_tx: Option<DeflaterTx>,
+    deflater_handles: Vec<JoinHandle<()>>,
+    // The queue is guarded so that the sizes of pending frames can be resolved through a shared
+    // reference, e.g., when calculating a virtual position.
+    queue: Mutex<VecDeque<Frame>>,
+    capacity: usize,
+}
+
+impl Deflater {
+    pub(crate) fn new(worker_count: NonZeroUsize, compression_level: CompressionLevelImpl) -> Self {
+        let worker_count = worker_count.get();
+
+        let (deflater_tx, deflater_rx) = crossbeam_channel::bounded(worker_count);
+        let deflater_handles = spawn_deflaters(worker_count, compression_level, deflater_rx);
+
+        Self {
+            deflater_tx: Some(deflater_tx),
+            deflater_handles,
+            queue: Mutex::new(VecDeque::with_capacity(worker_count)),
+            capacity: worker_count,
+        }
+    }
+
+    /// Returns whether the number of pending frames is at capacity.
+    pub(crate) fn is_full(&mut self) -> bool {
+        self.queue_mut().len() >= self.capacity
+    }
+
+    /// Queues uncompressed data to be compressed as a block.
+    pub(crate) fn send(&mut self, buf: Vec<u8>) {
+        let (buffered_tx, buffered_rx) = crossbeam_channel::bounded(1);
+
+        self.deflater_tx
+            .as_ref()
+            .unwrap()
+            .send((buf, buffered_tx))
+            .unwrap();
+
+        self.queue_mut().push_back(Frame::Pending(buffered_rx));
+    }
+
+    /// Removes the oldest frame, waiting for it to be compressed if necessary.
+    pub(crate) fn recv(&mut self) -> Option<io::Result<Vec<u8>>> {
+        self.queue_mut().pop_front().map(Frame::into_result)
+    }
+
+    /// Returns the total size of all queued frames, waiting for each to be compressed.
+    ///
+    /// Frames that failed to compress do not contribute to the total. Their errors are returned
+    /// when they are received.
+    pub(crate) fn pending_size(&self) -> u64 {
+        let mut queue = self.queue.lock().unwrap();
+
+        queue
+            .iter_mut()
+            .filter_map(|frame| frame.wait().as_ref().ok())
+            .map(|buf| buf.len() as u64)
+            .sum()
+    }
+
+    fn queue_mut(&mut self) -> &mut VecDeque<Frame> {
+        self.queue.get_mut().unwrap()
+    }
+
+    fn shutdown(&mut self) {
+        self.deflater_tx.take();
+
+        for handle in self.deflater_handles.drain(..) {
+            handle.join().unwrap();
+        }
+    }
+}
+
+impl Drop for Deflater {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+fn spawn_deflaters(
+    worker_count: usize,
+    compression_level: CompressionLevelImpl,
+    deflater_rx: DeflaterRx,
+) -> Vec<JoinHandle<()>> {
+    let mut handles = Vec::with_capacity(worker_count);
+
+    for _ in 0..worker_count {
+        let deflater_rx = deflater_rx.clone();
+
+        handles.push(thread::spawn(move || {
+            while let Ok((src, buffered_tx)) = deflater_rx.recv() {
+                let result = deflate_frame(&src, compression_level);
+
+                if buffered_tx.send(result).is_err() {
+                    continue;
+                }
+            }
+        }));
+    }
+
+    handles
+}
+
+fn deflate_frame(src: &[u8], compression_level: CompressionLevelImpl) -> io::Result<Vec<u8>> {
+    use crate::{gz, BGZF_HEADER_SIZE};
+
+    use super::{deflate_data, write_header, write_trailer};
+
+    let (cdata, crc32, r#isize) = deflate_data(src, compression_level)?;
+
+    let block_size = BGZF_HEADER_SIZE + cdata.len() + gz::TRAILER_SIZE;
+    let mut dst = Vec::with_capacity(block_size);
+
+    write_header(&mut dst, block_size)?;
+    dst.extend_from_slice(&cdata);
+    write_trailer(&mut dst, crc32, r#isize)?;
+
+    Ok(dst)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recv() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::reader::block::parse_frame;
+
+        let worker_count = NonZeroUsize::try_from(2)?;
+        let mut deflater = Deflater::new(worker_count, CompressionLevelImpl::default());
+
+        assert!(!deflater.is_full());
+
+        deflater.send(b"noodles".to_vec());
+        deflater.send(b"bgzf".to_vec());
+
+        assert!(deflater.is_full());
+
+        let pending_size = deflater.pending_size();
+
+        let buf = deflater.recv().transpose()?.ok_or("missing frame")?;
+        let block = parse_frame(&buf)?;
+        assert_eq!(block.data().as_ref(), b"noodles");
+        let mut actual_size = buf.len() as u64;
+
+        let buf = deflater.recv().transpose()?.ok_or("missing frame")?;
+        let block = parse_frame(&buf)?;
+        assert_eq!(block.data().as_ref(), b"bgzf");
+        actual_size += buf.len() as u64;
+
+        assert_eq!(pending_size, actual_size);
+        assert!(deflater.recv().is_none());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,221 @@
+//! Prints the layout of a CRAM file.
+//!
+//! For each data container, this prints the container header, the compression header, and each
+//! slice header and its blocks. It ends with the total block sizes per data series and tag, which
+//! shows what takes up the most space in the file.
+//!
+//! The output is similar to `cram_dump` in io_lib.
+
+use std::{collections::HashMap, env, fs::File, io};
+
+use noodles_cram::{
+    self as cram,
+    container::{block::ContentId, Block},
+    data_container::{
+        compression_header::data_series_encoding_map::STANDARD_DATA_SERIES, CompressionHeader,
+        Slice,
+    },
+};
+
+#[derive(Default)]
+struct Size {
+    compressed: usize,
+    uncompressed: usize,
+}
+
+fn main() -> io::Result<()> {
+    let src = env::args().nth(1).expect("missing src");
+
+    let mut reader = File::open(src).map(cram::Reader::new)?;
+
+    let file_definition = reader.read_file_definition()?;
+    let version = file_definition.version();
+    println!("version: {}.{}", version.major(), version.minor());
+
+    reader.read_file_header()?;
+
+    let mut totals: HashMap<String, Size> = HashMap::new();
+    let mut i = 0;
+
+    while let Some((header, container)) = reader.read_data_container_with_container_header()? {
+        println!("container {}", i);
+        println!("  length: {}", header.len());
+        println!(
+            "  reference sequence context: {:?}",
+            header.reference_sequence_context()
+        );
+        println!("  record count: {}", header.record_count());
+        println!("  record counter: {}", header.record_counter());
+        println!("  base count: {}", header.base_count());
+        println!("  block count: {}", header.block_count());
+        println!("  landmarks: {:?}", header.landmarks());
+
+        let compression_header = container.compression_header();
+        print_compression_header(compression_header);
+
+        let labels = build_block_labels(compression_header);
+
+        for (j, slice) in container.slices().iter().enumerate() {
+            println!("  slice {}", j);
+            print_slice(slice, &labels, &mut totals);
+        }
+
+        i += 1;
+    }
+
+    let mut totals: Vec<_> = totals.into_iter().collect();
+    totals.sort_by(|(a_label, a), (b_label, b)| {
+        b.compressed
+            .cmp(&a.compressed)
+            .then_with(|| a_label.cmp(b_label))
+    });
+
+    let total_compressed: usize = totals.iter().map(|(_, size)| size.compressed).sum();
+
+    println!("totals");
+
+    for (label, size) in totals {
+        let percent = if total_compressed > 0 {
+            100.0 * size.compressed as f64 / total_compressed as f64
+        } else {
+            0.0
+        };
+
+        println!(
+            "  {}\tcompressed: {}\tuncompressed: {}\t{:.1}%",
+            label, size.compressed, size.uncompressed, percent
+        );
+    }
+
+    Ok(())
+}
+
+fn print_compression_header(compression_header: &CompressionHeader) {
+    let preservation_map = compression_header.preservation_map();
+
+    println!("  preservation map");
+    println!("    RN: {}", preservation_map.read_names_included());
+    println!("    AP: {}", preservation_map.ap_data_series_delta());
+    println!("    RR: {}", preservation_map.is_reference_required());
+    println!("    SM: {:?}", preservation_map.substitution_matrix());
+    println!("    TD: {:?}", preservation_map.tag_ids_dictionary());
+
+    let data_series_encoding_map = compression_header.data_series_encoding_map();
+
+    println!("  data series encoding map (external block content IDs)");
+
+    for &data_series in STANDARD_DATA_SERIES {
+        let block_content_ids: Vec<_> = data_series_encoding_map
+            .block_content_ids(data_series)
+            .iter()
+            .map(|id| id.to_string())
+            .collect();
+
+        println!(
+            "    {}: [{}]",
+            data_series_name(data_series.into()),
+            block_content_ids.join(", ")
+        );
+    }
+
+    println!("  tag encoding map");
+
+    let mut tag_encodings: Vec<_> = compression_header.tag_encoding_map().iter().collect();
+    tag_encodings.sort_by_key(|(id, _)| i32::from(**id));
+
+    for (id, encoding) in tag_encodings {
+        println!("    {}: {:?}", tag_name(*id), encoding.get());
+    }
+}
+
+fn print_slice(
+    slice: &Slice,
+    labels: &HashMap<ContentId, String>,
+    totals: &mut HashMap<String, Size>,
+) {
+    let header = slice.header();
+
+    println!(
+        "    reference sequence context: {:?}",
+        header.reference_sequence_context()
+    );
+    println!("    record count: {}", header.record_count());
+    println!("    record counter: {}", header.record_counter());
+    println!("    block count: {}", header.block_count());
+
+    let core_data_block = slice.core_data_block();
+    print_block(core_data_block);
+    add_block_size(totals, String::from("core"), core_data_block);
+
+    for block in slice.external_blocks() {
+        print_block(block);
+
+        let label =
+            if header.embedded_reference_bases_block_content_id() == Some(block.content_id()) {
+                String::from("embedded reference")
+            } else {
+                labels
+                    .get(&block.content_id())
+                    .cloned()
+                    .unwrap_or_else(|| format!("unknown ({})", block.content_id()))
+            };
+
+        add_block_size(totals, label, block);
+    }
+}
+
+fn print_block(block: &Block) {
+    println!(
+        "    block: content type: {:?}, content ID: {}, compression method: {:?}, compressed size: {}, uncompressed size: {}",
+        block.content_type(),
+        block.content_id(),
+        block.compression_method(),
+        block.data().len(),
+        block.uncompressed_len()
+    );
+}
+
+fn add_block_size(totals: &mut HashMap<String, Size>, label: String, block: &Block) {
+    let size = totals.entry(label).or_default();
+    size.compressed += block.data().len();
+    size.uncompressed += block.uncompressed_len();
+}
+
+// Maps external block content IDs to the names of the data series and tags that use them.
+fn build_block_labels(compression_header: &CompressionHeader) -> HashMap<ContentId, String> {
+    let mut names: HashMap<ContentId, Vec<String>> = HashMap::new();
+
+    let data_series_encoding_map = compression_header.data_series_encoding_map();
+
+    for &data_series in STANDARD_DATA_SERIES {
+        for id in data_series_encoding_map.block_content_ids(data_series) {
+            names
+                .entry(id)
+                .or_default()
+                .push(data_series_name(data_series.into()));
+        }
+    }
+
+    for (tag_id, encoding) in compression_header.tag_encoding_map().iter() {
+        for id in encoding.get().block_content_ids() {
+            names.entry(id).or_default().push(tag_name(*tag_id));
+        }
+    }
+
+    names
+        .into_iter()
+        .map(|(id, names)| (id, names.join("+")))
+        .collect()
+}
+
+fn data_series_name(raw_data_series: [u8; 2]) -> String {
+    String::from_utf8_lossy(&raw_data_series).into()
+}
+
+// A tag ID is the tag and value type packed into 3 bytes.
+fn tag_name(id: ContentId) -> String {
+    let n = i32::from(id);
+    let raw_tag = [(n >> 16) as u8, (n >> 8) as u8];
+    let ty = n as u8;
+    format!("{}:{}", String::from_utf8_lossy(&raw_tag), char::from(ty))
+}
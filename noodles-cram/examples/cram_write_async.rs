@@ -0,0 +1,69 @@
+//! Creates a new CRAM file.
+//!
+//! This writes a file definition, a header container built from a SAM header, one mapped record,
+//! and one unmapped record to stdout.
+//!
+//! Verify the output by piping to `samtools view --no-PG --with-header`.
+
+use noodles_core::Position;
+use noodles_cram as cram;
+use noodles_fasta as fasta;
+use noodles_sam::{
+    self as sam,
+    alignment::Record,
+    header::record::value::{
+        map::{Program, ReferenceSequence},
+        Map,
+    },
+};
+use tokio::io;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    use fasta::record::{Definition, Sequence};
+
+    let reference_sequences = vec![
+        fasta::Record::new(
+            Definition::new("sq0", None),
+            Sequence::from(b"TTCACCCA".to_vec()),
+        ),
+        fasta::Record::new(
+            Definition::new("sq1", None),
+            Sequence::from(b"GATCTTACTTTTT".to_vec()),
+        ),
+    ];
+
+    let header = sam::Header::builder()
+        .set_header(Default::default())
+        .add_reference_sequence(Map::<ReferenceSequence>::new("sq0".parse()?, 8)?)
+        .add_reference_sequence(Map::<ReferenceSequence>::new("sq1".parse()?, 13)?)
+        .add_program(Map::<Program>::new("noodles-cram"))
+        .add_comment("an example CRAM written by noodles-cram")
+        .build();
+
+    let repository = fasta::Repository::new(reference_sequences);
+    let mut writer = cram::AsyncWriter::builder(io::stdout())
+        .set_reference_sequence_repository(repository)
+        .build();
+
+    writer.write_file_definition().await?;
+    writer.write_file_header(&header).await?;
+
+    let record = Record::builder()
+        .set_flags(sam::record::Flags::empty())
+        .set_reference_sequence_id(1)
+        .set_alignment_start(Position::MIN)
+        .set_cigar("4M".parse()?)
+        .set_sequence("GATC".parse()?)
+        .set_quality_scores("NDLS".parse()?)
+        .build();
+
+    writer.write_alignment_record(&header, &record).await?;
+
+    let record = Record::default();
+    writer.write_alignment_record(&header, &record).await?;
+
+    writer.shutdown(&header).await?;
+
+    Ok(())
+}
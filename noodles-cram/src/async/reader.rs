@@ -18,6 +18,7 @@ use crate::{file_definition::Version, DataContainer, FileDefinition, Record};
 pub struct Reader<R> {
     inner: R,
     buf: BytesMut,
+    version: Version,
 }
 
 impl<R> Reader<R>
@@ -37,6 +38,7 @@ where
         Self {
             inner,
             buf: BytesMut::new(),
+            version: Version::default(),
         }
     }
 
@@ -46,6 +48,9 @@ where
     ///
     /// The position of the stream is expected to be at the start.
     ///
+    /// The format version is used to read the subsequent containers. CRAM 2.1, 3.0, and 3.1 are
+    /// supported.
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -66,6 +71,8 @@ where
         let format = read_format(&mut self.inner).await?;
         let file_id = read_file_id(&mut self.inner).await?;
 
+        self.version = format;
+
         Ok(FileDefinition::new(format, file_id))
     }
 
@@ -96,7 +103,7 @@ where
     /// ```
     pub async fn read_file_header(&mut self) -> io::Result<String> {
         use self::header_container::read_header_container;
-        read_header_container(&mut self.inner, &mut self.buf).await
+        read_header_container(&mut self.inner, &mut self.buf, self.version).await
     }
 
     /// Reads a data container.
@@ -127,7 +134,7 @@ where
     pub async fn read_data_container(&mut self) -> io::Result<Option<DataContainer>> {
         use self::data_container::read_data_container;
 
-        read_data_container(&mut self.inner, &mut self.buf).await
+        read_data_container(&mut self.inner, &mut self.buf, self.version).await
     }
 
     /// Returns an (async) stream over records starting from the current (input) stream position.
@@ -2,13 +2,14 @@ use tokio::io::{self, AsyncRead, AsyncReadExt};
 
 use crate::{
     data_container::Header,
+    file_definition::Version,
     r#async::reader::{
         num::{read_itf8, read_ltf8},
         CrcReader,
     },
 };
 
-pub async fn read_header<R>(reader: &mut R) -> io::Result<Option<Header>>
+pub async fn read_header<R>(reader: &mut R, version: Version) -> io::Result<Option<Header>>
 where
     R: AsyncRead + Unpin,
 {
@@ -26,9 +27,7 @@ where
 
     let number_of_records = read_itf8(&mut crc_reader).await?;
 
-    let record_counter = read_ltf8(&mut crc_reader).await.and_then(|n| {
-        u64::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
-    })?;
+    let record_counter = read_record_counter(&mut crc_reader, version).await?;
 
     let bases = read_ltf8(&mut crc_reader).await.and_then(|n| {
         u64::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
@@ -40,27 +39,33 @@ where
 
     let landmarks = read_landmarks(&mut crc_reader).await?;
 
-    let actual_crc32 = crc_reader.crc().sum();
+    let crc32 = if version.has_crc32() {
+        let actual_crc32 = crc_reader.crc().sum();
 
-    let reader = crc_reader.into_inner();
-    let expected_crc32 = reader.read_u32_le().await?;
+        let reader = crc_reader.into_inner();
+        let expected_crc32 = reader.read_u32_le().await?;
 
-    if actual_crc32 != expected_crc32 {
-        return Err(io::Error::new(
-            io::ErrorKind::InvalidData,
-            format!(
-                "container header checksum mismatch: expected {:08x}, got {:08x}",
-                expected_crc32, actual_crc32
-            ),
-        ));
-    }
+        if actual_crc32 != expected_crc32 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "container header checksum mismatch: expected {:08x}, got {:08x}",
+                    expected_crc32, actual_crc32
+                ),
+            ));
+        }
+
+        Some(actual_crc32)
+    } else {
+        None
+    };
 
     if is_eof(
         length,
         reference_sequence_id,
         alignment_start,
         number_of_blocks,
-        actual_crc32,
+        crc32,
     ) {
         return Ok(None);
     }
@@ -81,6 +86,20 @@ where
     Ok(Some(header))
 }
 
+async fn read_record_counter<R>(reader: &mut R, version: Version) -> io::Result<u64>
+where
+    R: AsyncRead + Unpin,
+{
+    // The record counter is an ITF8 in CRAM 2.x and an LTF8 in CRAM 3.x.
+    let n = if version.major() < 3 {
+        read_itf8(reader).await.map(i64::from)?
+    } else {
+        read_ltf8(reader).await?
+    };
+
+    u64::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
 async fn read_landmarks<R>(reader: &mut R) -> io::Result<Vec<usize>>
 where
     R: AsyncRead + Unpin,
@@ -127,7 +146,7 @@ mod tests {
         ];
 
         let mut reader = &data[..];
-        let actual = read_header(&mut reader).await?;
+        let actual = read_header(&mut reader, Version::default()).await?;
 
         let expected = Header::builder()
             .set_length(144)
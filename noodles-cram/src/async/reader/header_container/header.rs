@@ -1,11 +1,14 @@
 use tokio::io::{self, AsyncRead, AsyncReadExt};
 
-use crate::r#async::reader::{
-    num::{read_itf8, read_ltf8},
-    CrcReader,
+use crate::{
+    file_definition::Version,
+    r#async::reader::{
+        num::{read_itf8, read_ltf8},
+        CrcReader,
+    },
 };
 
-pub async fn read_header<R>(reader: &mut R) -> io::Result<usize>
+pub async fn read_header<R>(reader: &mut R, version: Version) -> io::Result<usize>
 where
     R: AsyncRead + Unpin,
 {
@@ -40,6 +43,10 @@ where
 
     read_landmarks(&mut crc_reader).await?;
 
+    if !version.has_crc32() {
+        return Ok(length);
+    }
+
     let actual_crc32 = crc_reader.crc().sum();
 
     let reader = crc_reader.into_inner();
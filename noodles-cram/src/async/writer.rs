@@ -0,0 +1,463 @@
+mod builder;
+
+pub use self::builder::Builder;
+
+use std::{mem, sync::Arc};
+
+use noodles_fasta as fasta;
+use noodles_sam as sam;
+use tokio::{
+    io::{self, AsyncWrite, AsyncWriteExt},
+    task,
+};
+
+use crate::{
+    writer::{
+        add_missing_reference_sequence_checksums,
+        container::write_eof_container,
+        encode_data_container,
+        header_container::{validate_reference_sequences, write_header_container},
+        write_file_definition, Options, PROGRAM_NAME,
+    },
+    FileDefinition, Record,
+};
+
+/// An async CRAM writer.
+///
+/// Building and encoding containers is CPU-bound and is done on a blocking thread (see
+/// [`tokio::task::spawn_blocking`]).
+///
+/// A call to [`Self::shutdown`] must be made before the writer is dropped.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// #
+/// # #[tokio::main]
+/// # async fn main() -> io::Result<()> {
+/// use noodles_cram as cram;
+/// use noodles_sam as sam;
+///
+/// let mut writer = cram::AsyncWriter::new(Vec::new());
+/// writer.write_file_definition().await?;
+///
+/// let header = sam::Header::default();
+/// writer.write_file_header(&header).await?;
+///
+/// let record = cram::Record::default();
+/// writer.write_record(&header, record).await?;
+///
+/// writer.shutdown(&header).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Writer<W> {
+    inner: W,
+    reference_sequence_repository: fasta::Repository,
+    options: Options,
+    data_container_builder: crate::data_container::Builder,
+    record_counter: u64,
+    header: Option<Arc<sam::Header>>,
+}
+
+impl<W> Writer<W>
+where
+    W: AsyncWrite + Unpin,
+{
+    /// Creates an async CRAM writer builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let builder = cram::AsyncWriter::builder(Vec::new());
+    /// let writer = builder.build();
+    /// ```
+    pub fn builder(inner: W) -> Builder<W> {
+        Builder::new(inner)
+    }
+
+    /// Creates an async CRAM writer with default options.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let writer = cram::AsyncWriter::new(Vec::new());
+    /// ```
+    pub fn new(inner: W) -> Self {
+        Self::builder(inner).build()
+    }
+
+    /// Returns a reference to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let writer = cram::AsyncWriter::new(Vec::new());
+    /// assert!(writer.get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let mut writer = cram::AsyncWriter::new(Vec::new());
+    /// assert!(writer.get_mut().is_empty());
+    /// ```
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Returns the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let writer = cram::AsyncWriter::new(Vec::new());
+    /// assert!(writer.into_inner().is_empty());
+    /// ```
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Shuts down the output stream.
+    ///
+    /// This writes any pending containers and a final EOF container.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> io::Result<()> {
+    /// use noodles_cram as cram;
+    /// use noodles_sam as sam;
+    ///
+    /// let header = sam::Header::default();
+    /// let mut writer = cram::AsyncWriter::new(Vec::new());
+    /// writer.shutdown(&header).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn shutdown(&mut self, header: &sam::Header) -> io::Result<()> {
+        self.flush(header).await?;
+
+        let mut buf = Vec::new();
+        write_eof_container(&mut buf)?;
+        self.inner.write_all(&buf).await?;
+        self.inner.flush().await?;
+
+        self.inner.shutdown().await
+    }
+
+    /// Writes a CRAM file definition.
+    ///
+    /// The file ID is set as a blank value (`[0x00; 20]`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> io::Result<()> {
+    /// use noodles_cram as cram;
+    ///
+    /// let mut writer = cram::AsyncWriter::new(Vec::new());
+    /// writer.write_file_definition().await?;
+    ///
+    /// assert_eq!(writer.get_ref(), &[
+    ///     // magic number (CRAM)
+    ///     0x43, 0x52, 0x41, 0x4d,
+    ///     // format (major, minor)
+    ///     0x03, 0x00,
+    ///     // file ID
+    ///     0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ///     0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    /// ]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_file_definition(&mut self) -> io::Result<()> {
+        let file_definition = FileDefinition::new(self.options.version, Default::default());
+
+        let mut buf = Vec::new();
+        write_file_definition(&mut buf, &file_definition)?;
+
+        self.inner.write_all(&buf).await
+    }
+
+    /// Writes a CRAM file header container.
+    ///
+    /// The position of the stream is expected to be directly after the file definition.
+    ///
+    /// Entries in the reference sequence dictionary that are missing MD5 checksums (`M5`) will
+    /// automatically be calculated and added to the written record, unless an external reference
+    /// is not required (see [`crate::writer::Builder::require_reference_sequences`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> io::Result<()> {
+    /// use noodles_cram as cram;
+    /// use noodles_sam as sam;
+    ///
+    /// let mut writer = cram::AsyncWriter::new(Vec::new());
+    /// writer.write_file_definition().await?;
+    ///
+    /// let header = sam::Header::default();
+    /// writer.write_file_header(&header).await?;
+    ///
+    /// writer.shutdown(&header).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_file_header(&mut self, header: &sam::Header) -> io::Result<()> {
+        let reference_sequence_repository = self.reference_sequence_repository.clone();
+        let mut header = header.clone();
+
+        let uses_reference_sequences = self.options.uses_reference_sequences();
+        let quality_scores_transforms = self.options.quality_scores_transforms.clone();
+
+        let (buf, header) = task::spawn_blocking(move || {
+            if uses_reference_sequences {
+                add_missing_reference_sequence_checksums(
+                    &reference_sequence_repository,
+                    header.reference_sequences_mut(),
+                )?;
+
+                validate_reference_sequences(header.reference_sequences())?;
+            }
+
+            if !quality_scores_transforms.is_empty() {
+                quality_scores_transforms
+                    .add_program(&mut header, PROGRAM_NAME, env!("CARGO_PKG_VERSION"))
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+            }
+
+            let mut buf = Vec::new();
+            write_header_container(&mut buf, &header)?;
+
+            Ok::<_, io::Error>((buf, header))
+        })
+        .await??;
+
+        self.inner.write_all(&buf).await?;
+        self.header = Some(Arc::new(header));
+
+        Ok(())
+    }
+
+    /// Writes a CRAM record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> io::Result<()> {
+    /// use noodles_cram as cram;
+    /// use noodles_sam as sam;
+    ///
+    /// let mut writer = cram::AsyncWriter::new(Vec::new());
+    /// writer.write_file_definition().await?;
+    ///
+    /// let header = sam::Header::default();
+    /// writer.write_file_header(&header).await?;
+    ///
+    /// let record = cram::Record::default();
+    /// writer.write_record(&header, record).await?;
+    ///
+    /// writer.shutdown(&header).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_record(
+        &mut self,
+        header: &sam::Header,
+        mut record: Record,
+    ) -> io::Result<()> {
+        use crate::data_container::builder::AddRecordError;
+
+        record.apply_quality_scores_transforms(&self.options.quality_scores_transforms);
+
+        loop {
+            match self
+                .data_container_builder
+                .add_record(&self.options, record)
+            {
+                Ok(_) => {
+                    self.record_counter += 1;
+                    return Ok(());
+                }
+                Err(e) => match e {
+                    AddRecordError::ContainerFull(r) => {
+                        record = r;
+                        self.flush(header).await?;
+                    }
+                    AddRecordError::SliceFull(r) => {
+                        record = r;
+                    }
+                    _ => return Err(io::Error::from(io::ErrorKind::InvalidInput)),
+                },
+            }
+        }
+    }
+
+    /// Writes an alignment record.
+    ///
+    /// The record is converted to a CRAM record before it is written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> io::Result<()> {
+    /// use noodles_cram as cram;
+    /// use noodles_sam::{self as sam, alignment::Record};
+    ///
+    /// let mut writer = cram::AsyncWriter::new(Vec::new());
+    /// writer.write_file_definition().await?;
+    ///
+    /// let header = sam::Header::default();
+    /// writer.write_file_header(&header).await?;
+    ///
+    /// let record = Record::default();
+    /// writer.write_alignment_record(&header, &record).await?;
+    ///
+    /// writer.shutdown(&header).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_alignment_record(
+        &mut self,
+        header: &sam::Header,
+        record: &sam::alignment::Record,
+    ) -> io::Result<()> {
+        let r = Record::try_from_alignment_record(header, record)?;
+        self.write_record(header, r).await
+    }
+
+    async fn flush(&mut self, header: &sam::Header) -> io::Result<()> {
+        if self.data_container_builder.is_empty() {
+            return Ok(());
+        }
+
+        let next_data_container_builder =
+            self.data_container_builder.successor(self.record_counter);
+        let data_container_builder = mem::replace(
+            &mut self.data_container_builder,
+            next_data_container_builder,
+        );
+
+        let reference_sequence_repository = self.reference_sequence_repository.clone();
+        let options = self.options.clone();
+
+        let header = self
+            .header
+            .get_or_insert_with(|| Arc::new(header.clone()))
+            .clone();
+
+        let buf = task::spawn_blocking(move || {
+            encode_data_container(
+                data_container_builder,
+                &options,
+                &reference_sequence_repository,
+                &header,
+            )
+        })
+        .await??;
+
+        self.inner.write_all(&buf).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_write_record() -> Result<(), Box<dyn std::error::Error>> {
+        use fasta::record::{Definition, Sequence};
+        use noodles_core::Position;
+        use sam::header::record::value::{map::ReferenceSequence, Map};
+
+        let repository = fasta::Repository::new(vec![fasta::Record::new(
+            Definition::new("sq0", None),
+            Sequence::from(b"TTCACCCA".to_vec()),
+        )]);
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(Map::<ReferenceSequence>::new("sq0".parse()?, 8)?)
+            .build();
+
+        let records = [
+            Record::builder()
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(2)?)
+                .set_read_length(4)
+                .set_bases("TCAC".parse()?)
+                .build(),
+            Record::default(),
+        ];
+
+        let mut writer = Writer::builder(Vec::new())
+            .set_reference_sequence_repository(repository.clone())
+            .build();
+
+        writer.write_file_definition().await?;
+        writer.write_file_header(&header).await?;
+
+        for record in records.iter().cloned() {
+            writer.write_record(&header, record).await?;
+        }
+
+        writer.shutdown(&header).await?;
+
+        let mut reader = crate::Reader::new(&writer.get_ref()[..]);
+        reader.read_file_definition()?;
+
+        let actual_header: sam::Header = reader.read_file_header()?.parse()?;
+        let actual_reference_sequence = actual_header.reference_sequences().get("sq0");
+        assert!(actual_reference_sequence
+            .and_then(|rs| rs.md5_checksum())
+            .is_some());
+
+        let actual: Vec<_> = reader
+            .records(&repository, &header)
+            .collect::<Result<_, _>>()?;
+
+        assert_eq!(actual.len(), records.len());
+
+        for (actual_record, expected_record) in actual.iter().zip(records.iter()) {
+            assert_eq!(
+                actual_record.reference_sequence_id(),
+                expected_record.reference_sequence_id()
+            );
+            assert_eq!(
+                actual_record.alignment_start(),
+                expected_record.alignment_start()
+            );
+            assert_eq!(actual_record.bases(), expected_record.bases());
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,294 @@
+use std::num::NonZeroUsize;
+
+use noodles_fasta as fasta;
+use noodles_sam::record::{quality_scores::Transform, Flags};
+use tokio::io::AsyncWrite;
+
+use super::Writer;
+use crate::{
+    data_container::BlockContentEncoderMap,
+    writer::{finalize_options, CompressionProfile, Options},
+    DataContainer,
+};
+
+/// An async CRAM writer builder.
+#[derive(Debug)]
+pub struct Builder<W> {
+    inner: W,
+    reference_sequence_repository: fasta::Repository,
+    options: Options,
+    block_content_encoder_map: Option<BlockContentEncoderMap>,
+    records_per_slice: Option<usize>,
+    compression_profile: Option<CompressionProfile>,
+    adaptive_compression: bool,
+}
+
+impl<W> Builder<W>
+where
+    W: AsyncWrite + Unpin,
+{
+    pub(crate) fn new(inner: W) -> Self {
+        Self {
+            inner,
+            reference_sequence_repository: fasta::Repository::default(),
+            options: Options::default(),
+            block_content_encoder_map: None,
+            records_per_slice: None,
+            compression_profile: None,
+            adaptive_compression: false,
+        }
+    }
+
+    /// Sets the reference sequence repository.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// use noodles_fasta as fasta;
+    ///
+    /// let repository = fasta::Repository::default();
+    /// let builder = cram::AsyncWriter::builder(Vec::new())
+    ///     .set_reference_sequence_repository(repository);
+    /// ```
+    pub fn set_reference_sequence_repository(
+        mut self,
+        reference_sequence_repository: fasta::Repository,
+    ) -> Self {
+        self.reference_sequence_repository = reference_sequence_repository;
+        self
+    }
+
+    /// Sets whether to preserve read names.
+    ///
+    /// If `false`, read names are discarded.
+    ///
+    /// The default is `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let builder = cram::AsyncWriter::builder(Vec::new()).preserve_read_names(false);
+    /// ```
+    pub fn preserve_read_names(mut self, value: bool) -> Self {
+        self.options.preserve_read_names = value;
+        self
+    }
+
+    /// Sets whether to encode alignment start positions as deltas.
+    ///
+    /// If `false`, record alignment start positions are written with their actual values.
+    ///
+    /// The default is `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let builder = cram::AsyncWriter::builder(Vec::new())
+    ///     .encode_alignment_start_positions_as_deltas(false);
+    /// ```
+    pub fn encode_alignment_start_positions_as_deltas(mut self, value: bool) -> Self {
+        self.options.encode_alignment_start_positions_as_deltas = value;
+        self
+    }
+
+    /// Sets whether to embed reference sequences.
+    ///
+    /// If `true`, the region of the reference sequence covered by a single-reference slice is
+    /// stored in the slice itself, and the reference is no longer required (`RR = false`) to
+    /// decode its records. The reference sequences are still read from the reference sequence
+    /// repository. Records in slices without a single reference sequence store their bases as
+    /// written.
+    ///
+    /// The default is `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let builder = cram::AsyncWriter::builder(Vec::new()).embed_reference_sequences(true);
+    /// ```
+    pub fn embed_reference_sequences(mut self, value: bool) -> Self {
+        self.options.embed_reference_sequences = value;
+        self
+    }
+
+    /// Sets whether an external reference is required to decode records.
+    ///
+    /// If `false`, the reference sequence repository is not used, and records store their
+    /// bases explicitly rather than as differences to the reference sequence (`RR = false`).
+    /// Missing MD5 checksums (`M5`) in the reference sequence dictionary are also not
+    /// calculated. This has no effect when reference sequences are embedded (see
+    /// [`Self::embed_reference_sequences`]).
+    ///
+    /// The default is `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let builder = cram::AsyncWriter::builder(Vec::new()).require_reference_sequences(false);
+    /// ```
+    pub fn require_reference_sequences(mut self, value: bool) -> Self {
+        self.options.reference_required = value;
+        self
+    }
+
+    /// Sets the block content-encoder map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::{self as cram, data_container::BlockContentEncoderMap};
+    ///
+    /// let block_content_encoder_map = BlockContentEncoderMap::default();
+    /// let builder = cram::AsyncWriter::builder(Vec::new())
+    ///     .set_block_content_encoder_map(block_content_encoder_map);
+    /// ```
+    pub fn set_block_content_encoder_map(mut self, map: BlockContentEncoderMap) -> Self {
+        self.block_content_encoder_map = Some(map);
+        self
+    }
+
+    /// Sets the compression profile.
+    ///
+    /// See [`crate::writer::Builder::set_compression_profile`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::{self as cram, writer::CompressionProfile};
+    /// let builder = cram::AsyncWriter::builder(Vec::new())
+    ///     .set_compression_profile(CompressionProfile::Small);
+    /// ```
+    pub fn set_compression_profile(mut self, compression_profile: CompressionProfile) -> Self {
+        self.compression_profile = Some(compression_profile);
+        self
+    }
+
+    /// Sets the maximum number of records per slice.
+    ///
+    /// The default is 10240.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    ///
+    /// use noodles_cram as cram;
+    ///
+    /// let records_per_slice = NonZeroUsize::try_from(25000)?;
+    /// let builder = cram::AsyncWriter::builder(Vec::new())
+    ///     .set_records_per_slice(records_per_slice);
+    /// # Ok::<_, std::num::TryFromIntError>(())
+    /// ```
+    pub fn set_records_per_slice(mut self, records_per_slice: NonZeroUsize) -> Self {
+        self.records_per_slice = Some(records_per_slice.get());
+        self
+    }
+
+    /// Sets the maximum number of slices per container.
+    ///
+    /// The default is 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    ///
+    /// use noodles_cram as cram;
+    ///
+    /// let slices_per_container = NonZeroUsize::try_from(4)?;
+    /// let builder = cram::AsyncWriter::builder(Vec::new())
+    ///     .set_slices_per_container(slices_per_container);
+    /// # Ok::<_, std::num::TryFromIntError>(())
+    /// ```
+    pub fn set_slices_per_container(mut self, slices_per_container: NonZeroUsize) -> Self {
+        self.options.slices_per_container = slices_per_container.get();
+        self
+    }
+
+    /// Sets whether to select block content encoders adaptively.
+    ///
+    /// See [`crate::writer::Builder::adaptive_compression`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let builder = cram::AsyncWriter::builder(Vec::new()).adaptive_compression(true);
+    /// ```
+    pub fn adaptive_compression(mut self, value: bool) -> Self {
+        self.adaptive_compression = value;
+        self
+    }
+
+    /// Sets the quality scores transform.
+    ///
+    /// See [`crate::writer::Builder::set_quality_scores_transform`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// use noodles_sam::record::quality_scores::{transform::Bins, Transform};
+    ///
+    /// let builder = cram::AsyncWriter::builder(Vec::new())
+    ///     .set_quality_scores_transform(Transform::Bin(Bins::illumina_8_level()));
+    /// ```
+    pub fn set_quality_scores_transform(mut self, transform: Transform) -> Self {
+        self.options
+            .quality_scores_transforms
+            .set_default(transform);
+        self
+    }
+
+    /// Adds a quality scores transform for records with any of the given flags set.
+    ///
+    /// See [`crate::writer::Builder::add_quality_scores_transform`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// use noodles_sam::record::{quality_scores::Transform, Flags};
+    ///
+    /// let builder = cram::AsyncWriter::builder(Vec::new())
+    ///     .add_quality_scores_transform(Flags::UNMAPPED | Flags::SECONDARY, Transform::Drop);
+    /// ```
+    pub fn add_quality_scores_transform(mut self, flags: Flags, transform: Transform) -> Self {
+        self.options
+            .quality_scores_transforms
+            .push(flags, transform);
+        self
+    }
+
+    /// Builds an async CRAM writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let writer = cram::AsyncWriter::builder(Vec::new()).build();
+    /// ```
+    pub fn build(self) -> Writer<W> {
+        let options = finalize_options(
+            self.options,
+            self.compression_profile,
+            self.block_content_encoder_map,
+            self.records_per_slice,
+            self.adaptive_compression,
+        );
+
+        Writer {
+            inner: self.inner,
+            reference_sequence_repository: self.reference_sequence_repository,
+            options,
+            data_container_builder: DataContainer::builder(0),
+            record_counter: 0,
+            header: None,
+        }
+    }
+}
@@ -1,15 +1,24 @@
 //! CRAM block content codecs.
+//!
+//! The CRAM 3.1 codecs (rANS 4x8, rANS Nx16, the adaptive arithmetic coder, fqzcomp, and the name
+//! tokenizer) each expose `encode` and `decode` functions, which can be used to compress data
+//! outside of CRAM blocks. To build only the codecs, disable the default features and enable the
+//! `codecs` feature.
 
 pub mod aac;
+#[cfg(feature = "io")]
 pub(crate) mod bzip2;
-pub(crate) mod fqzcomp;
+pub mod fqzcomp;
+#[cfg(feature = "io")]
 pub(crate) mod gzip;
+#[cfg(feature = "io")]
 pub(crate) mod lzma;
-pub(crate) mod name_tokenizer;
+pub mod name_tokenizer;
 pub mod rans_4x8;
 pub mod rans_nx16;
 
 /// A CRAM block content encoder.
+#[cfg(feature = "io")]
 #[derive(Clone, Debug)]
 pub enum Encoder {
     /// gzip
@@ -29,3 +38,186 @@ pub enum Encoder {
     /// fqzcomp
     Fqzcomp,
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use super::*;
+
+    // A xorshift64 generator so that the round trip tests are deterministic.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next_u64(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, n: usize) -> usize {
+            (self.next_u64() % n as u64) as usize
+        }
+    }
+
+    // Builds inputs of various lengths and distributions: uniform, small alphabets, and long runs.
+    fn build_inputs() -> Vec<Vec<u8>> {
+        let mut rng = Rng(0x6e6f6f646c6573);
+        let mut inputs = vec![Vec::new(), vec![0], vec![0xff; 3]];
+
+        for &len in &[1, 2, 15, 64, 255, 256, 1000, 4097] {
+            for alphabet_size in [1, 2, 4, 37, 256] {
+                let src: Vec<_> = (0..len).map(|_| rng.below(alphabet_size) as u8).collect();
+                inputs.push(src);
+            }
+
+            let mut src = Vec::with_capacity(len);
+
+            while src.len() < len {
+                let sym = rng.below(4) as u8;
+                let run_len = 1 + rng.below(32);
+                src.extend((0..run_len).map(|_| sym));
+            }
+
+            src.truncate(len);
+            inputs.push(src);
+        }
+
+        inputs
+    }
+
+    #[test]
+    fn test_rans_4x8_round_trip() -> io::Result<()> {
+        for src in build_inputs() {
+            for order in [rans_4x8::Order::Zero, rans_4x8::Order::One] {
+                let compressed_data = rans_4x8::encode(order, &src)?;
+                let decompressed_data = rans_4x8::decode(&mut &compressed_data[..])?;
+                assert_eq!(decompressed_data, src, "order = {:?}", order);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rans_nx16_round_trip() -> io::Result<()> {
+        use rans_nx16::Flags;
+
+        let flags_list = [
+            Flags::empty(),
+            Flags::ORDER,
+            Flags::N32,
+            Flags::ORDER | Flags::N32,
+            Flags::STRIPE,
+            Flags::CAT,
+            Flags::RLE,
+            Flags::ORDER | Flags::RLE,
+            Flags::PACK,
+            Flags::ORDER | Flags::PACK,
+            Flags::PACK | Flags::RLE,
+            Flags::ORDER | Flags::N32 | Flags::PACK | Flags::RLE,
+            Flags::ORDER | Flags::NO_SIZE,
+        ];
+
+        for src in build_inputs() {
+            for &flags in &flags_list {
+                let compressed_data = rans_nx16::encode(flags, &src)?;
+                let decompressed_data = rans_nx16::decode(&mut &compressed_data[..], src.len())?;
+                assert_eq!(decompressed_data, src, "flags = {:?}", flags);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_aac_round_trip() -> io::Result<()> {
+        use aac::Flags;
+
+        let flags_list = [
+            Flags::empty(),
+            Flags::ORDER,
+            Flags::STRIPE,
+            Flags::CAT,
+            Flags::EXT,
+            Flags::RLE,
+            Flags::ORDER | Flags::RLE,
+            Flags::PACK,
+            Flags::ORDER | Flags::PACK,
+            Flags::ORDER | Flags::PACK | Flags::RLE,
+            Flags::ORDER | Flags::NO_SIZE,
+        ];
+
+        for src in build_inputs() {
+            for &flags in &flags_list {
+                let compressed_data = aac::encode(flags, &src)?;
+                let decompressed_data = aac::decode(&mut &compressed_data[..], src.len())?;
+                assert_eq!(decompressed_data, src, "flags = {:?}", flags);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fqzcomp_round_trip() -> io::Result<()> {
+        let mut rng = Rng(0x66717a636f6d70);
+
+        for &record_count in &[0, 1, 2, 17, 100] {
+            for &max_score in &[1, 8, 41, 93] {
+                // Records without quality scores have a length of 0.
+                let lens: Vec<_> = (0..record_count).map(|_| rng.below(150)).collect();
+                let src: Vec<_> = (0..lens.iter().sum())
+                    .map(|_| rng.below(max_score + 1) as u8)
+                    .collect();
+
+                let compressed_data = fqzcomp::encode(&lens, &src)?;
+                let decompressed_data = fqzcomp::decode(&mut &compressed_data[..])?;
+                assert_eq!(decompressed_data, src);
+            }
+        }
+
+        assert!(matches!(
+            fqzcomp::encode(&[2, 3], b"noodles"),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_name_tokenizer_round_trip() -> io::Result<()> {
+        let mut rng = Rng(0x6e616d65);
+
+        for &name_count in &[0, 1, 2, 17, 200] {
+            let mut src = Vec::new();
+
+            for i in 0..name_count {
+                let name = match rng.below(4) {
+                    0 => format!("SRR{}.{}", 1000 + rng.below(3), i),
+                    1 => format!(
+                        "I{}:{}:{}:{:05}",
+                        rng.below(8),
+                        rng.below(2),
+                        rng.below(120),
+                        rng.below(100000),
+                    ),
+                    2 => format!("read_{:04}", rng.below(10000)),
+                    _ => (0..1 + rng.below(20))
+                        .map(|_| char::from(b'A' + rng.below(26) as u8))
+                        .collect(),
+                };
+
+                src.extend(name.as_bytes());
+                src.push(0x00);
+            }
+
+            let compressed_data = name_tokenizer::encode(&src)?;
+            let decompressed_data = name_tokenizer::decode(&mut &compressed_data[..])?;
+            assert_eq!(decompressed_data, src);
+        }
+
+        Ok(())
+    }
+}
@@ -6,5 +6,6 @@ mod flags;
 mod model;
 mod range_coder;
 
-pub use self::flags::Flags;
-pub(crate) use self::{decode::decode, encode::encode, model::Model, range_coder::RangeCoder};
+pub use self::{decode::decode, encode::encode, flags::Flags};
+
+pub(crate) use self::{model::Model, range_coder::RangeCoder};
@@ -3,8 +3,23 @@ use std::io::{self, Read};
 use byteorder::ReadBytesExt;
 
 use super::{Flags, Model, RangeCoder};
-use crate::reader::num::read_uint7;
-
+use crate::num::read_uint7;
+
+/// Decodes adaptive arithmetic coded data.
+///
+/// `len` is the uncompressed length. It is only used when the data was encoded with
+/// [`Flags::NO_SIZE`]; otherwise, the length is read from the stream.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_cram::codecs::aac::{self, Flags};
+///
+/// let compressed_data = aac::encode(Flags::NO_SIZE, b"noodles")?;
+/// let data = aac::decode(&mut &compressed_data[..], 7)?;
+/// assert_eq!(data, b"noodles");
+/// # Ok::<_, std::io::Error>(())
+/// ```
 pub fn decode<R>(reader: &mut R, mut len: usize) -> io::Result<Vec<u8>>
 where
     R: Read,
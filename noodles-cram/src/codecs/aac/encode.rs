@@ -3,8 +3,25 @@ use std::io::{self, Write};
 use byteorder::WriteBytesExt;
 
 use super::{Flags, Model, RangeCoder};
-use crate::writer::num::write_uint7;
-
+use crate::num::write_uint7;
+
+/// Encodes data using the adaptive arithmetic coder.
+///
+/// The given flags select the transforms and model order to use. Flags that cannot be applied to
+/// the input are ignored, e.g., [`Flags::PACK`] with more than 16 unique symbols.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_cram::codecs::aac::{self, Flags};
+///
+/// let src = b"noodles";
+/// let compressed_data = aac::encode(Flags::ORDER | Flags::RLE, src)?;
+///
+/// let decompressed_data = aac::decode(&mut &compressed_data[..], src.len())?;
+/// assert_eq!(decompressed_data, src);
+/// # Ok::<_, std::io::Error>(())
+/// ```
 pub fn encode(mut flags: Flags, src: &[u8]) -> io::Result<Vec<u8>> {
     use crate::codecs::rans_nx16::encode::pack;
 
@@ -233,12 +250,11 @@ fn encode_order_1(src: &[u8], dst: &mut Vec<u8>) -> io::Result<()> {
 
     let mut range_coder = RangeCoder::default();
 
-    models[0].encode(dst, &mut range_coder, src[0])?;
+    let mut last = 0;
 
-    for window in src.windows(2) {
-        let sym_0 = usize::from(window[0]);
-        let sym_1 = window[1];
-        models[sym_0].encode(dst, &mut range_coder, sym_1)?;
+    for &sym in src {
+        models[last].encode(dst, &mut range_coder, sym)?;
+        last = usize::from(sym);
     }
 
     range_coder.range_encode_end(dst)?;
@@ -252,15 +268,13 @@ mod tests {
 
     #[test]
     fn test_encode_ext() -> io::Result<()> {
-        use crate::codecs::bzip2;
+        use bzip2::write::BzEncoder;
 
         let actual = encode(Flags::EXT, b"noodles")?;
 
-        let mut expected = vec![0x04, 0x07];
-
-        let compression_level = ::bzip2::Compression::default();
-        let data = bzip2::encode(compression_level, b"noodles")?;
-        expected.extend(data);
+        let mut encoder = BzEncoder::new(vec![0x04, 0x07], Default::default());
+        encoder.write_all(b"noodles")?;
+        let expected = encoder.finish()?;
 
         assert_eq!(actual, expected);
 
@@ -308,6 +322,20 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_encode_order_1_with_empty_input() -> io::Result<()> {
+        use crate::codecs::aac::decode;
+
+        // Packing a single symbol leaves no data to entropy encode.
+        let data = b"nnnn";
+        let compressed_data = encode(Flags::ORDER | Flags::PACK, data)?;
+
+        let mut reader = &compressed_data[..];
+        assert_eq!(decode(&mut reader, 0)?, data);
+
+        Ok(())
+    }
+
     #[test]
     fn test_encode_cat() -> io::Result<()> {
         let actual = encode(Flags::CAT, b"noodles")?;
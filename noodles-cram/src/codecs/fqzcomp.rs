@@ -1,9 +1,14 @@
+//! fqzcomp quality scores codec.
+
 mod decode;
 mod encode;
 mod parameter;
 mod parameters;
 
-pub use self::{decode::decode, encode::encode};
+pub use self::{
+    decode::decode,
+    encode::{encode, encode_with_parameters, Parameters},
+};
 
 use super::aac::Model;
 
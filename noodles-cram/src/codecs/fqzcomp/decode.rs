@@ -8,8 +8,22 @@ use super::{
     parameters::{self, fqz_decode_params, Parameters},
     Models,
 };
-use crate::{codecs::aac::RangeCoder, reader::num::read_uint7};
-
+use crate::{codecs::aac::RangeCoder, num::read_uint7};
+
+/// Decodes fqzcomp-encoded quality scores.
+///
+/// This returns the concatenated quality scores of all records.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_cram::codecs::fqzcomp;
+///
+/// let compressed_data = fqzcomp::encode(&[3], &[13, 21, 34])?;
+/// let data = fqzcomp::decode(&mut &compressed_data[..])?;
+/// assert_eq!(data, [13, 21, 34]);
+/// # Ok::<_, std::io::Error>(())
+/// ```
 pub fn decode<R>(reader: &mut R) -> io::Result<Vec<u8>>
 where
     R: Read,
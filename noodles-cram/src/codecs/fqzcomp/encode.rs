@@ -3,9 +3,233 @@ use std::io::{self, Write};
 use byteorder::{LittleEndian, WriteBytesExt};
 
 use super::{parameter, parameters, Models};
-use crate::{codecs::aac::RangeCoder, writer::num::write_uint7};
+use crate::{codecs::aac::RangeCoder, num::write_uint7};
+
+/// fqzcomp encoder parameters.
+///
+/// These set the layout of the quality scores model context, which is built from the previous
+/// quality scores (`q_*`) and the position in the record (`p_*`). Each `*_loc` is the bit offset
+/// of its part in the 16-bit context.
+///
+/// The default uses 9 bits of quality scores history at offset 7 and 7 bits of position at
+/// offset 0.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Parameters {
+    q_bits: u8,
+    q_shift: u8,
+    q_loc: u8,
+    p_bits: u8,
+    p_shift: u8,
+    p_loc: u8,
+}
+
+impl Parameters {
+    /// Sets the number of bits of quality scores history in the context.
+    ///
+    /// This must be ≤ 15.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::codecs::fqzcomp::Parameters;
+    /// let parameters = Parameters::default().set_q_bits(8);
+    /// ```
+    pub fn set_q_bits(mut self, q_bits: u8) -> Self {
+        self.q_bits = q_bits;
+        self
+    }
+
+    /// Sets the number of bits each previous quality score is shifted by in the history.
+    ///
+    /// This must be ≤ 15.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::codecs::fqzcomp::Parameters;
+    /// let parameters = Parameters::default().set_q_shift(4);
+    /// ```
+    pub fn set_q_shift(mut self, q_shift: u8) -> Self {
+        self.q_shift = q_shift;
+        self
+    }
+
+    /// Sets the bit offset of the quality scores history in the context.
+    ///
+    /// This must be ≤ 15.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::codecs::fqzcomp::Parameters;
+    /// let parameters = Parameters::default().set_q_loc(8);
+    /// ```
+    pub fn set_q_loc(mut self, q_loc: u8) -> Self {
+        self.q_loc = q_loc;
+        self
+    }
+
+    /// Sets the number of bits of position in the context.
+    ///
+    /// This must be ≤ 8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::codecs::fqzcomp::Parameters;
+    /// let parameters = Parameters::default().set_p_bits(4);
+    /// ```
+    pub fn set_p_bits(mut self, p_bits: u8) -> Self {
+        self.p_bits = p_bits;
+        self
+    }
+
+    /// Sets the number of bits the position is shifted right by, i.e., the position bucket size
+    /// as a power of 2.
+    ///
+    /// This must be ≤ 15.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::codecs::fqzcomp::Parameters;
+    /// let parameters = Parameters::default().set_p_shift(2);
+    /// ```
+    pub fn set_p_shift(mut self, p_shift: u8) -> Self {
+        self.p_shift = p_shift;
+        self
+    }
+
+    /// Sets the bit offset of the position in the context.
+    ///
+    /// This must be ≤ 15.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::codecs::fqzcomp::Parameters;
+    /// let parameters = Parameters::default().set_p_loc(4);
+    /// ```
+    pub fn set_p_loc(mut self, p_loc: u8) -> Self {
+        self.p_loc = p_loc;
+        self
+    }
+
+    fn validate(&self) -> io::Result<()> {
+        const MAX_NIBBLE: u8 = 0x0f;
 
+        let is_valid = self.q_bits <= MAX_NIBBLE
+            && self.q_shift <= MAX_NIBBLE
+            && self.q_loc <= MAX_NIBBLE
+            && self.p_bits <= 8
+            && self.p_shift <= MAX_NIBBLE
+            && self.p_loc <= MAX_NIBBLE;
+
+        if is_valid {
+            Ok(())
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid fqzcomp parameters",
+            ))
+        }
+    }
+}
+
+impl Default for Parameters {
+    fn default() -> Self {
+        Self {
+            q_bits: 9,
+            q_shift: 5,
+            q_loc: 7,
+            p_bits: 7,
+            p_shift: 0,
+            p_loc: 0,
+        }
+    }
+}
+
+/// Encodes quality scores using fqzcomp.
+///
+/// `src` is the concatenated quality scores of all records, and `lens` is the length of each
+/// record's quality scores. Records with a length of 0 are skipped. The model parameters are
+/// derived from the input; use [`encode_with_parameters`] to set them.
+///
+/// # Errors
+///
+/// This returns an error if the sum of `lens` is not the length of `src`.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_cram::codecs::fqzcomp;
+///
+/// let lens = [4, 3];
+/// let src = [8, 8, 13, 21, 34, 34, 21];
+/// let compressed_data = fqzcomp::encode(&lens, &src)?;
+///
+/// let decompressed_data = fqzcomp::decode(&mut &compressed_data[..])?;
+/// assert_eq!(decompressed_data, src);
+/// # Ok::<_, std::io::Error>(())
+/// ```
 pub fn encode(lens: &[usize], src: &[u8]) -> io::Result<Vec<u8>> {
+    // Long records use a coarser position context.
+    let p_shift = match lens.iter().find(|&&len| len > 0) {
+        Some(&len) if len > 128 => 1,
+        _ => 0,
+    };
+
+    let parameters = Parameters::default().set_p_shift(p_shift);
+
+    encode_with_parameters(lens, src, &parameters)
+}
+
+/// Encodes quality scores using fqzcomp with the given parameters.
+///
+/// This is the same as [`encode`] but with the given model parameters.
+///
+/// # Errors
+///
+/// This returns an error if the sum of `lens` is not the length of `src` or the parameters are
+/// invalid.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_cram::codecs::fqzcomp::{self, Parameters};
+///
+/// let lens = [4, 3];
+/// let src = [8, 8, 13, 21, 34, 34, 21];
+///
+/// let parameters = Parameters::default()
+///     .set_q_bits(8)
+///     .set_q_loc(8)
+///     .set_p_bits(4)
+///     .set_p_loc(4);
+///
+/// let compressed_data = fqzcomp::encode_with_parameters(&lens, &src, &parameters)?;
+///
+/// let decompressed_data = fqzcomp::decode(&mut &compressed_data[..])?;
+/// assert_eq!(decompressed_data, src);
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn encode_with_parameters(
+    lens: &[usize],
+    src: &[u8],
+    parameters: &Parameters,
+) -> io::Result<Vec<u8>> {
+    parameters.validate()?;
+
+    // Records without quality scores have no data in the stream.
+    let lens: Vec<_> = lens.iter().copied().filter(|&len| len > 0).collect();
+
+    if lens.iter().sum::<usize>() != src.len() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "record lengths do not match the input length",
+        ));
+    }
+
     let mut dst = Vec::new();
 
     let len =
@@ -18,7 +242,7 @@ pub fn encode(lens: &[usize], src: &[u8]) -> io::Result<Vec<u8>> {
         *f = i as u8;
     }
 
-    let parameters = build_parameters(lens, src);
+    let parameters = build_parameters(&lens, src, parameters);
     fqz_encode_params(&mut dst, &parameters)?;
 
     let mut range_coder = RangeCoder::default();
@@ -74,7 +298,7 @@ pub fn encode(lens: &[usize], src: &[u8]) -> io::Result<Vec<u8>> {
         last += (qlast & ((1 << param.q_bits) - 1)) << param.q_loc;
 
         if param.flags.contains(parameter::Flags::HAVE_PTAB) {
-            last += u32::from(param.p_tab[p.min(1023)] << param.p_loc);
+            last += u32::from(param.p_tab[p.min(1023)]) << param.p_loc;
         }
 
         if param.flags.contains(parameter::Flags::HAVE_DTAB) {
@@ -94,7 +318,7 @@ pub fn encode(lens: &[usize], src: &[u8]) -> io::Result<Vec<u8>> {
     Ok(dst)
 }
 
-struct Parameters {
+struct GlobalParameters {
     pub gflags: parameters::Flags,
     pub max_sel: u8,
     pub s_tab: Vec<u8>,
@@ -122,7 +346,7 @@ struct Parameter {
     pub p_tab: Vec<u8>,
 }
 
-fn build_parameters(lens: &[usize], src: &[u8]) -> Parameters {
+fn build_parameters(lens: &[usize], src: &[u8], parameters: &Parameters) -> GlobalParameters {
     let mut max_symbol = u8::MIN;
     let mut symbol_counts = [0; 256];
 
@@ -132,10 +356,14 @@ fn build_parameters(lens: &[usize], src: &[u8]) -> Parameters {
         max_symbol = max_symbol.max(b)
     }
 
-    let q_shift = 5;
-    let q_bits = if q_shift > 4 { 9 } else { 8 };
-    let p_bits = 7;
-    let p_shift = if lens[0] > 128 { 1 } else { 0 };
+    let Parameters {
+        q_bits,
+        q_shift,
+        q_loc,
+        p_bits,
+        p_shift,
+        p_loc,
+    } = *parameters;
 
     let q_tab: Vec<_> = (0..=u8::MAX).collect();
 
@@ -157,9 +385,9 @@ fn build_parameters(lens: &[usize], src: &[u8]) -> Parameters {
         max_sym: max_symbol,
         q_bits,
         q_shift,
-        q_loc: 7,
+        q_loc,
         s_loc: 15,
-        p_loc: 0,
+        p_loc,
         d_loc: 15,
         q_tab,
         p_tab,
@@ -172,7 +400,7 @@ fn build_parameters(lens: &[usize], src: &[u8]) -> Parameters {
         *s = i as u8;
     }
 
-    Parameters {
+    GlobalParameters {
         gflags: parameters::Flags::empty(),
         max_sel: 0,
         s_tab,
@@ -181,7 +409,7 @@ fn build_parameters(lens: &[usize], src: &[u8]) -> Parameters {
     }
 }
 
-fn fqz_encode_params<W>(writer: &mut W, parameters: &Parameters) -> io::Result<()>
+fn fqz_encode_params<W>(writer: &mut W, parameters: &GlobalParameters) -> io::Result<()>
 where
     W: Write,
 {
@@ -378,4 +606,54 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_encode_with_parameters() -> io::Result<()> {
+        use super::super::decode;
+
+        let data = [
+            vec![0, 0, 0, 1, 1, 2, 1, 1, 0, 0],
+            vec![0, 1, 2, 3, 3, 3, 3, 3, 3, 3],
+            vec![2, 1, 1, 0, 0],
+        ];
+
+        let lens: Vec<_> = data.iter().map(|scores| scores.len()).collect();
+        let src: Vec<_> = data.into_iter().flatten().collect();
+
+        let parameters = Parameters::default()
+            .set_q_bits(8)
+            .set_q_shift(4)
+            .set_q_loc(8)
+            .set_p_bits(4)
+            .set_p_shift(2)
+            .set_p_loc(4);
+
+        let compressed_data = encode_with_parameters(&lens, &src, &parameters)?;
+        assert_ne!(compressed_data, encode(&lens, &src)?);
+
+        let decompressed_data = decode(&mut &compressed_data[..])?;
+        assert_eq!(decompressed_data, src);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_with_invalid_parameters() {
+        let lens = [4];
+        let src = [8, 8, 13, 21];
+
+        for parameters in [
+            Parameters::default().set_q_bits(16),
+            Parameters::default().set_q_shift(16),
+            Parameters::default().set_q_loc(16),
+            Parameters::default().set_p_bits(9),
+            Parameters::default().set_p_shift(16),
+            Parameters::default().set_p_loc(16),
+        ] {
+            assert!(matches!(
+                encode_with_parameters(&lens, &src, &parameters),
+                Err(e) if e.kind() == io::ErrorKind::InvalidInput
+            ));
+        }
+    }
 }
@@ -5,9 +5,23 @@ use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use super::Type;
 use crate::{
     codecs::{aac, rans_nx16},
-    reader::num::read_uint7,
+    num::read_uint7,
 };
 
+/// Decodes name tokenizer-encoded read names.
+///
+/// This reads until the end of the given reader and returns a list of NUL-terminated names.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_cram::codecs::name_tokenizer;
+///
+/// let compressed_data = name_tokenizer::encode(b"r0\x00r1\x00")?;
+/// let data = name_tokenizer::decode(&mut &compressed_data[..])?;
+/// assert_eq!(data, b"r0\x00r1\x00");
+/// # Ok::<_, std::io::Error>(())
+/// ```
 pub fn decode<R>(reader: &mut R) -> io::Result<Vec<u8>>
 where
     R: Read,
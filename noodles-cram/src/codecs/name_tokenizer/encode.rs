@@ -7,22 +7,47 @@ use std::{
 use byteorder::{LittleEndian, WriteBytesExt};
 
 use super::Type;
-use crate::writer::num::write_uint7;
+use crate::num::write_uint7;
 
 const NUL: u8 = 0x00;
 
+/// Encodes read names using the name tokenizer.
+///
+/// `src` is a list of NUL-terminated names.
+///
+/// # Errors
+///
+/// This returns an error if a name is not valid UTF-8.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_cram::codecs::name_tokenizer;
+///
+/// let src = b"r0\x00r1\x00r2\x00";
+/// let compressed_data = name_tokenizer::encode(src)?;
+///
+/// let decompressed_data = name_tokenizer::decode(&mut &compressed_data[..])?;
+/// assert_eq!(decompressed_data, src);
+/// # Ok::<_, std::io::Error>(())
+/// ```
 pub fn encode(mut src: &[u8]) -> io::Result<Vec<u8>> {
     let mut dst = Vec::new();
 
+    let is_empty = src.is_empty();
+
     if let Some(buf) = src.strip_suffix(&[NUL]) {
         src = buf;
     }
 
-    let names: Vec<_> = src
-        .split(|&b| b == NUL)
-        .map(str::from_utf8)
-        .collect::<Result<_, _>>()
-        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let names: Vec<_> = if is_empty {
+        Vec::new()
+    } else {
+        src.split(|&b| b == NUL)
+            .map(str::from_utf8)
+            .collect::<Result<_, _>>()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?
+    };
 
     write_header(&mut dst, src.len(), names.len())?;
 
@@ -4,8 +4,7 @@ mod decode;
 mod encode;
 mod order;
 
-pub use self::order::Order;
-pub(crate) use self::{decode::decode, encode::encode};
+pub use self::{decode::decode, encode::encode, order::Order};
 
 #[cfg(test)]
 mod tests {
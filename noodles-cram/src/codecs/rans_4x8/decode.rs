@@ -7,11 +7,32 @@ use byteorder::{LittleEndian, ReadBytesExt};
 
 use super::Order;
 
+/// Decodes rANS 4x8-encoded data.
+///
+/// This reads a single rANS 4x8 stream from the given reader.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_cram::codecs::rans_4x8::{self, Order};
+///
+/// let compressed_data = rans_4x8::encode(Order::Zero, b"noodles")?;
+/// let data = rans_4x8::decode(&mut &compressed_data[..])?;
+/// assert_eq!(data, b"noodles");
+/// # Ok::<_, std::io::Error>(())
+/// ```
 pub fn decode<R>(reader: &mut R) -> io::Result<Vec<u8>>
 where
     R: Read,
 {
-    let (order, _, data_len) = read_header(reader)?;
+    let (order, compressed_len, data_len) = read_header(reader)?;
+
+    if data_len == 0 {
+        let compressed_len = u64::try_from(compressed_len)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        io::copy(&mut reader.take(compressed_len), &mut io::sink())?;
+        return Ok(Vec::new());
+    }
 
     let mut dst = vec![0; data_len];
 
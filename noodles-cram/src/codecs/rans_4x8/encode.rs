@@ -13,10 +13,33 @@ const BASE: usize = 256;
 // Lower bound `L`.
 const LOWER_BOUND: u32 = 0x800000;
 
+/// Encodes data using rANS 4x8.
+///
+/// Order-1 encoding falls back to order-0 when the input is too short to fill all 4 states.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_cram::codecs::rans_4x8::{self, Order};
+///
+/// let src = b"noodles";
+/// let compressed_data = rans_4x8::encode(Order::One, src)?;
+///
+/// let decompressed_data = rans_4x8::decode(&mut &compressed_data[..])?;
+/// assert_eq!(decompressed_data, src);
+/// # Ok::<_, std::io::Error>(())
+/// ```
 pub fn encode(order: Order, src: &[u8]) -> io::Result<Vec<u8>> {
+    if src.is_empty() {
+        let mut dst = Vec::new();
+        write_header(&mut dst, Order::Zero, 0, 0)?;
+        return Ok(dst);
+    }
+
+    // The order-1 encoder interleaves 4 states and needs at least one byte per state.
     match order {
-        Order::Zero => order_0::encode(src),
-        Order::One => order_1::encode(src),
+        Order::One if src.len() >= 4 => order_1::encode(src),
+        _ => order_0::encode(src),
     }
 }
 
@@ -157,6 +180,14 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_encode_with_order_1_and_short_input() -> io::Result<()> {
+        let data = b"nd";
+        let actual = encode(Order::One, data)?;
+        assert_eq!(actual, encode(Order::Zero, data)?);
+        Ok(())
+    }
+
     #[test]
     fn test_write_header() -> io::Result<()> {
         let mut writer = Vec::new();
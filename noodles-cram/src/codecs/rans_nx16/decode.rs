@@ -11,8 +11,23 @@ use std::{
 use byteorder::{LittleEndian, ReadBytesExt};
 
 use super::Flags;
-use crate::reader::num::read_uint7;
-
+use crate::num::read_uint7;
+
+/// Decodes rANS Nx16-encoded data.
+///
+/// `len` is the uncompressed length. It is only used when the data was encoded with
+/// [`Flags::NO_SIZE`]; otherwise, the length is read from the stream.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_cram::codecs::rans_nx16::{self, Flags};
+///
+/// let compressed_data = rans_nx16::encode(Flags::NO_SIZE, b"noodles")?;
+/// let data = rans_nx16::decode(&mut &compressed_data[..], 7)?;
+/// assert_eq!(data, b"noodles");
+/// # Ok::<_, std::io::Error>(())
+/// ```
 pub fn decode<R>(reader: &mut R, mut len: usize) -> io::Result<Vec<u8>>
 where
     R: Read,
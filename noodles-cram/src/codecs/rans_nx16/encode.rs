@@ -8,8 +8,25 @@ use std::io::{self, Write};
 use byteorder::WriteBytesExt;
 
 use super::Flags;
-use crate::writer::num::write_uint7;
-
+use crate::num::write_uint7;
+
+/// Encodes data using rANS Nx16.
+///
+/// The given flags select the transforms and entropy coder to use. Flags that cannot be applied
+/// to the input are ignored, e.g., [`Flags::PACK`] with more than 16 unique symbols.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_cram::codecs::rans_nx16::{self, Flags};
+///
+/// let src = b"noodles";
+/// let compressed_data = rans_nx16::encode(Flags::ORDER | Flags::PACK, src)?;
+///
+/// let decompressed_data = rans_nx16::decode(&mut &compressed_data[..], src.len())?;
+/// assert_eq!(decompressed_data, src);
+/// # Ok::<_, std::io::Error>(())
+/// ```
 pub fn encode(mut flags: Flags, src: &[u8]) -> io::Result<Vec<u8>> {
     let mut src = src.to_vec();
     let mut dst = Vec::new();
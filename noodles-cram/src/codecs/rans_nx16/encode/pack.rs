@@ -2,7 +2,7 @@ use std::io;
 
 use byteorder::WriteBytesExt;
 
-use crate::writer::num::write_uint7;
+use crate::num::write_uint7;
 
 pub fn encode(src: &[u8]) -> io::Result<(Vec<u8>, Vec<u8>)> {
     let mut frequencies = [0; 256];
@@ -17,11 +17,19 @@ pub fn encode(src: &[u8]) -> io::Result<(Vec<u8>, Vec<u8>)> {
 
     for (sym, &f) in frequencies.iter().enumerate() {
         if f > 0 {
-            lut[sym] = n;
+            // Only the first 16 symbols need to be mapped. Other inputs cannot be packed.
+            lut[sym] = n as u8;
             n += 1;
         }
     }
 
+    if n == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "no symbols to pack",
+        ));
+    }
+
     let buf = if n <= 1 {
         Vec::new()
     } else if n <= 2 {
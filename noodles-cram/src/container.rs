@@ -1,3 +1,5 @@
+//! CRAM container.
+
 pub mod block;
 
 pub use self::block::Block;
@@ -1,11 +1,13 @@
+//! CRAM container block.
+
 mod builder;
 mod compression_method;
 mod content_id;
 mod content_type;
 
+pub(crate) use self::builder::Builder;
 pub use self::{
-    builder::Builder, compression_method::CompressionMethod, content_id::ContentId,
-    content_type::ContentType,
+    compression_method::CompressionMethod, content_id::ContentId, content_type::ContentType,
 };
 
 use std::{io, mem};
@@ -17,6 +19,10 @@ use crate::{
     num::itf8,
 };
 
+/// A CRAM container block.
+///
+/// A block holds (typically compressed) data of a single content type. Slices, for example, are
+/// made up of a core data block and external data blocks.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Block {
     compression_method: CompressionMethod,
@@ -28,30 +34,40 @@ pub struct Block {
 
 #[allow(clippy::len_without_is_empty)]
 impl Block {
-    pub fn builder() -> Builder {
+    pub(crate) fn builder() -> Builder {
         Builder::default()
     }
 
+    /// Returns the compression method.
     pub fn compression_method(&self) -> CompressionMethod {
         self.compression_method
     }
 
+    /// Returns the content type.
     pub fn content_type(&self) -> ContentType {
         self.content_type
     }
 
+    /// Returns the content ID.
+    ///
+    /// For external data blocks, this identifies the data series or tag the data belongs to.
     pub fn content_id(&self) -> ContentId {
         self.content_id
     }
 
+    /// Returns the size of the uncompressed data.
     pub fn uncompressed_len(&self) -> usize {
         self.uncompressed_len
     }
 
+    /// Returns the (typically compressed) data.
+    ///
+    /// The compressed size of the block data is the length of this slice.
     pub fn data(&self) -> &[u8] {
         &self.data
     }
 
+    /// Decompresses and returns the block data.
     pub fn decompressed_data(&self) -> io::Result<Bytes> {
         use crate::codecs::{bzip2, gzip, lzma};
 
@@ -95,6 +111,7 @@ impl Block {
         }
     }
 
+    /// Returns the size of the serialized block, including its header and checksum.
     pub fn len(&self) -> usize {
         // method
         mem::size_of::<u8>()
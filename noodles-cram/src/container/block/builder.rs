@@ -5,7 +5,7 @@ use bytes::Bytes;
 use super::{Block, CompressionMethod, ContentId, ContentType};
 use crate::codecs::Encoder;
 
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 pub struct Builder {
     compression_method: CompressionMethod,
     content_type: Option<ContentType>,
@@ -86,6 +86,21 @@ impl Builder {
         Ok(self)
     }
 
+    /// Compresses the given data using the given encoder or sets it uncompressed if there is no
+    /// encoder.
+    pub fn set_data_with_encoder(
+        self,
+        data: Vec<u8>,
+        encoder: Option<&Encoder>,
+    ) -> io::Result<Self> {
+        match encoder {
+            Some(encoder) => self.compress_and_set_data(data, encoder.clone()),
+            None => Ok(self
+                .set_uncompressed_len(data.len())
+                .set_data(Bytes::from(data))),
+        }
+    }
+
     pub fn build(self) -> Block {
         Block {
             compression_method: self.compression_method,
@@ -14,6 +14,7 @@ pub struct BlockContentEncoderMap {
     core_data_encoder: Option<Encoder>,
     data_series_encoders: Vec<Option<Encoder>>,
     tag_values_encoders: HashMap<block::ContentId, Option<Encoder>>,
+    default_tag_values_encoder: Option<Encoder>,
 }
 
 impl BlockContentEncoderMap {
@@ -49,13 +50,20 @@ impl BlockContentEncoderMap {
         &self.tag_values_encoders
     }
 
-    pub(crate) fn get_tag_values_encoders(
+    pub(crate) fn default_tag_values_encoder(&self) -> Option<&Encoder> {
+        self.default_tag_values_encoder.as_ref()
+    }
+
+    // Returns the encoder of the tag values block with the given block content ID, falling back
+    // to the default tag values encoder.
+    pub(crate) fn get_tag_values_encoder(
         &self,
         block_content_id: block::ContentId,
-    ) -> Option<Option<&Encoder>> {
+    ) -> Option<&Encoder> {
         self.tag_values_encoders
             .get(&block_content_id)
             .map(|e| e.as_ref())
+            .unwrap_or_else(|| self.default_tag_values_encoder())
     }
 }
 
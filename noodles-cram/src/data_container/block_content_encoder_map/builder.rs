@@ -15,6 +15,7 @@ pub struct Builder {
     core_data_encoder: Option<Encoder>,
     data_series_encoders: Vec<Option<Encoder>>,
     tag_values_encoders: HashMap<block::ContentId, Option<Encoder>>,
+    default_tag_values_encoder: Option<Encoder>,
 }
 
 #[allow(dead_code)]
@@ -80,6 +81,22 @@ impl Builder {
         self
     }
 
+    /// Sets the default tag values encoder.
+    ///
+    /// This is used for tag values blocks that do not have an encoder set with
+    /// [`Self::set_tag_values_encoder`]. The default is gzip.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::data_container::BlockContentEncoderMap;
+    /// let builder = BlockContentEncoderMap::builder().set_default_tag_values_encoder(None);
+    /// ```
+    pub fn set_default_tag_values_encoder(mut self, encoder: Option<Encoder>) -> Self {
+        self.default_tag_values_encoder = encoder;
+        self
+    }
+
     /// Builds a block content-encoder map.
     ///
     /// # Examples
@@ -93,6 +110,7 @@ impl Builder {
             core_data_encoder: self.core_data_encoder,
             data_series_encoders: self.data_series_encoders,
             tag_values_encoders: self.tag_values_encoders,
+            default_tag_values_encoder: self.default_tag_values_encoder,
         }
     }
 }
@@ -112,6 +130,7 @@ impl Default for Builder {
                 STANDARD_DATA_SERIES.len()
             ],
             tag_values_encoders: HashMap::new(),
+            default_tag_values_encoder: Some(Encoder::Gzip(compression_level)),
         }
     }
 }
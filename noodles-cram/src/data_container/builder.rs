@@ -3,17 +3,32 @@ use std::{io, mem, num};
 use noodles_fasta as fasta;
 use noodles_sam as sam;
 
-use super::{slice, CompressionHeader, DataContainer, Slice};
+use super::{slice, CompressionHeader, DataContainer, ReferenceSequenceContext, Slice};
 use crate::{writer::Options, Record};
 
-const MAX_SLICE_COUNT: usize = 1;
-
 #[derive(Debug)]
 pub struct Builder {
     slice_builder: slice::Builder,
     slice_builders: Vec<slice::Builder>,
     record_counter: u64,
     base_count: u64,
+    previous_slice: Option<SliceSummary>,
+}
+
+// The record count and reference sequence context of the last slice of the previous container.
+#[derive(Clone, Copy, Debug)]
+struct SliceSummary {
+    record_count: usize,
+    reference_sequence_context: ReferenceSequenceContext,
+}
+
+impl From<&slice::Builder> for SliceSummary {
+    fn from(slice_builder: &slice::Builder) -> Self {
+        Self {
+            record_count: slice_builder.records().len(),
+            reference_sequence_context: slice_builder.reference_sequence_context(),
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -30,9 +45,17 @@ impl Builder {
             slice_builders: Vec::new(),
             record_counter,
             base_count: 0,
+            previous_slice: None,
         }
     }
 
+    /// Creates a builder for the data container that follows this one.
+    pub fn successor(&self, record_counter: u64) -> Self {
+        let mut builder = Self::new(record_counter);
+        builder.previous_slice = self.last_slice();
+        builder
+    }
+
     pub fn is_empty(&self) -> bool {
         self.slice_builder.is_empty() && self.slice_builders.is_empty()
     }
@@ -41,12 +64,14 @@ impl Builder {
         self.base_count
     }
 
-    pub fn add_record(&mut self, record: Record) -> Result<(), AddRecordError> {
-        if self.slice_builders.len() >= MAX_SLICE_COUNT {
+    pub fn add_record(&mut self, options: &Options, record: Record) -> Result<(), AddRecordError> {
+        if self.slice_builders.len() >= options.slices_per_container
+            || !self.accepts_reference_sequence(options, &record)
+        {
             return Err(AddRecordError::ContainerFull(record));
         }
 
-        match self.slice_builder.add_record(record) {
+        match self.slice_builder.add_record(options, record) {
             Ok(r) => {
                 self.base_count += u64::try_from(r.read_length())
                     .map_err(AddRecordError::InvalidRecordReadLength)?;
@@ -62,12 +87,97 @@ impl Builder {
         }
     }
 
+    fn last_slice(&self) -> Option<SliceSummary> {
+        if self.slice_builder.is_empty() {
+            self.slice_builders
+                .last()
+                .map(SliceSummary::from)
+                .or(self.previous_slice)
+        } else {
+            Some(SliceSummary::from(&self.slice_builder))
+        }
+    }
+
+    // Records are split into containers by reference sequence, and unplaced, unmapped records are
+    // only stored in unmapped-only containers. When records are spread over many small reference
+    // sequences, they are instead stored together in multi-reference slices.
+    fn accepts_reference_sequence(&self, options: &Options, record: &Record) -> bool {
+        let reference_sequence_context = if !self.slice_builder.is_empty() {
+            self.slice_builder.reference_sequence_context()
+        } else if let Some(slice_builder) = self.slice_builders.last() {
+            slice_builder.reference_sequence_context()
+        } else {
+            return true;
+        };
+
+        match (reference_sequence_context, record.reference_sequence_id()) {
+            (ReferenceSequenceContext::Some(context), Some(id)) => {
+                id == context.reference_sequence_id()
+                    || self.can_start_multi_reference_slice(options)
+            }
+            (ReferenceSequenceContext::Many, Some(_)) => true,
+            (ReferenceSequenceContext::None, None) => true,
+            _ => false,
+        }
+    }
+
+    // A multi-reference slice is started when both the current and previous slices are small,
+    // i.e., less than a quarter full.
+    fn can_start_multi_reference_slice(&self, options: &Options) -> bool {
+        let is_small = |record_count| record_count < options.records_per_slice / 4;
+
+        if options.embed_reference_sequences || !is_small(self.slice_builder.records().len()) {
+            return false;
+        }
+
+        let previous_slice = if self.slice_builders.is_empty() {
+            self.previous_slice
+        } else {
+            self.slice_builders.last().map(SliceSummary::from)
+        };
+
+        match previous_slice {
+            Some(slice) => {
+                slice.reference_sequence_context.is_many() || is_small(slice.record_count)
+            }
+            None => false,
+        }
+    }
+
     pub fn build(
-        mut self,
+        self,
         options: &Options,
         reference_sequence_repository: &fasta::Repository,
         header: &sam::Header,
     ) -> io::Result<DataContainer> {
+        let (options, compression_header, slice_builders, record_counter) =
+            self.into_parts(options);
+
+        let slices = slice_builders
+            .into_iter()
+            .map(|builder| {
+                builder.build(
+                    &options,
+                    reference_sequence_repository,
+                    header,
+                    &compression_header,
+                    record_counter,
+                )
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(DataContainer {
+            compression_header,
+            slices,
+        })
+    }
+
+    /// Builds the compression header and returns it with the options for, the builders of, and
+    /// the record counter of the slices.
+    pub fn into_parts(
+        mut self,
+        options: &Options,
+    ) -> (Options, CompressionHeader, Vec<slice::Builder>, u64) {
         if !self.slice_builder.is_empty() {
             self.slice_builders.push(self.slice_builder);
         }
@@ -84,25 +194,12 @@ impl Builder {
 
         let compression_header = build_compression_header(&options, &self.slice_builders);
 
-        let record_counter = self.record_counter;
-        let slices = self
-            .slice_builders
-            .into_iter()
-            .map(|builder| {
-                builder.build(
-                    &options.block_content_encoder_map,
-                    reference_sequence_repository,
-                    header,
-                    &compression_header,
-                    record_counter,
-                )
-            })
-            .collect::<Result<_, _>>()?;
-
-        Ok(DataContainer {
+        (
+            options,
             compression_header,
-            slices,
-        })
+            self.slice_builders,
+            self.record_counter,
+        )
     }
 }
 
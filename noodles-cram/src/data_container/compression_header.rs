@@ -2,16 +2,17 @@
 
 mod builder;
 pub mod data_series_encoding_map;
-pub(crate) mod encoding;
+pub mod encoding;
 pub mod preservation_map;
-mod tag_encoding_map;
+pub mod tag_encoding_map;
 
 pub(crate) use self::{
     builder::Builder,
-    data_series_encoding_map::DataSeriesEncodingMap,
-    encoding::Encoding,
-    preservation_map::{PreservationMap, SubstitutionMatrix, TagIdsDictionary},
-    tag_encoding_map::TagEncodingMap,
+    preservation_map::{SubstitutionMatrix, TagIdsDictionary},
+};
+pub use self::{
+    data_series_encoding_map::DataSeriesEncodingMap, encoding::Encoding,
+    preservation_map::PreservationMap, tag_encoding_map::TagEncodingMap,
 };
 
 /// A CRAM data container compression header.
@@ -42,15 +43,18 @@ impl CompressionHeader {
         }
     }
 
-    pub(crate) fn preservation_map(&self) -> &PreservationMap {
+    /// Returns the preservation map.
+    pub fn preservation_map(&self) -> &PreservationMap {
         &self.preservation_map
     }
 
-    pub(crate) fn data_series_encoding_map(&self) -> &DataSeriesEncodingMap {
+    /// Returns the data series encoding map.
+    pub fn data_series_encoding_map(&self) -> &DataSeriesEncodingMap {
         &self.data_series_encoding_map
     }
 
-    pub(crate) fn tag_encoding_map(&self) -> &TagEncodingMap {
+    /// Returns the tag encoding map.
+    pub fn tag_encoding_map(&self) -> &TagEncodingMap {
         &self.tag_encoding_map
     }
 }
@@ -4,7 +4,7 @@ mod builder;
 pub(crate) mod data_series;
 
 pub(crate) use self::builder::Builder;
-pub use self::data_series::DataSeries;
+pub use self::data_series::{DataSeries, STANDARD_DATA_SERIES};
 
 use super::{
     encoding::codec::{Byte, ByteArray, Integer},
@@ -14,7 +14,7 @@ use crate::container::block;
 
 /// A container compression header data series encoding map.
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub(crate) struct DataSeriesEncodingMap {
+pub struct DataSeriesEncodingMap {
     bam_bit_flags_encoding: Encoding<Integer>,
     cram_bit_flags_encoding: Encoding<Integer>,
     reference_id_encoding: Option<Encoding<Integer>>,
@@ -45,11 +45,13 @@ pub(crate) struct DataSeriesEncodingMap {
     quality_scores_encoding: Option<Encoding<Byte>>,
 }
 
+#[allow(clippy::len_without_is_empty)]
 impl DataSeriesEncodingMap {
     pub(crate) fn builder() -> Builder {
         Builder::default()
     }
 
+    /// Returns the number of data series with an encoding.
     pub fn len(&self) -> usize {
         // BAM bit flags, CRAM bit flags, read lengths, in-seq positions, read groups, tag IDs
         let mut n = 6;
@@ -148,117 +150,205 @@ impl DataSeriesEncodingMap {
         n
     }
 
+    /// Returns the BAM bit flags (`BF`) encoding.
     pub fn bam_bit_flags_encoding(&self) -> &Encoding<Integer> {
         &self.bam_bit_flags_encoding
     }
 
+    /// Returns the CRAM bit flags (`CF`) encoding.
     pub fn cram_bit_flags_encoding(&self) -> &Encoding<Integer> {
         &self.cram_bit_flags_encoding
     }
 
+    /// Returns the reference ID (`RI`) encoding.
     pub fn reference_id_encoding(&self) -> Option<&Encoding<Integer>> {
         self.reference_id_encoding.as_ref()
     }
 
+    /// Returns the read lengths (`RL`) encoding.
     pub fn read_lengths_encoding(&self) -> &Encoding<Integer> {
         &self.read_lengths_encoding
     }
 
+    /// Returns the in-seq positions (`AP`) encoding.
     pub fn in_seq_positions_encoding(&self) -> &Encoding<Integer> {
         &self.in_seq_positions_encoding
     }
 
+    /// Returns the read groups (`RG`) encoding.
     pub fn read_groups_encoding(&self) -> &Encoding<Integer> {
         &self.read_groups_encoding
     }
 
+    /// Returns the read names (`RN`) encoding.
     pub fn read_names_encoding(&self) -> Option<&Encoding<ByteArray>> {
         self.read_names_encoding.as_ref()
     }
 
+    /// Returns the next mate bit flags (`MF`) encoding.
     pub fn next_mate_bit_flags_encoding(&self) -> Option<&Encoding<Integer>> {
         self.next_mate_bit_flags_encoding.as_ref()
     }
 
+    /// Returns the next fragment reference sequence ID (`NS`) encoding.
     pub fn next_fragment_reference_sequence_id_encoding(&self) -> Option<&Encoding<Integer>> {
         self.next_fragment_reference_sequence_id_encoding.as_ref()
     }
 
+    /// Returns the next mate alignment start (`NP`) encoding.
     pub fn next_mate_alignment_start_encoding(&self) -> Option<&Encoding<Integer>> {
         self.next_mate_alignment_start_encoding.as_ref()
     }
 
+    /// Returns the template size (`TS`) encoding.
     pub fn template_size_encoding(&self) -> Option<&Encoding<Integer>> {
         self.template_size_encoding.as_ref()
     }
 
+    /// Returns the distance to next fragment (`NF`) encoding.
     pub fn distance_to_next_fragment_encoding(&self) -> Option<&Encoding<Integer>> {
         self.distance_to_next_fragment_encoding.as_ref()
     }
 
+    /// Returns the tag IDs (`TL`) encoding.
     pub fn tag_ids_encoding(&self) -> &Encoding<Integer> {
         &self.tag_ids_encoding
     }
 
+    /// Returns the number of read features (`FN`) encoding.
     pub fn number_of_read_features_encoding(&self) -> Option<&Encoding<Integer>> {
         self.number_of_read_features_encoding.as_ref()
     }
 
+    /// Returns the read features codes (`FC`) encoding.
     pub fn read_features_codes_encoding(&self) -> Option<&Encoding<Byte>> {
         self.read_features_codes_encoding.as_ref()
     }
 
+    /// Returns the in-read positions (`FP`) encoding.
     pub fn in_read_positions_encoding(&self) -> Option<&Encoding<Integer>> {
         self.in_read_positions_encoding.as_ref()
     }
 
+    /// Returns the deletion lengths (`DL`) encoding.
     pub fn deletion_lengths_encoding(&self) -> Option<&Encoding<Integer>> {
         self.deletion_lengths_encoding.as_ref()
     }
 
+    /// Returns the stretches of bases (`BB`) encoding.
     pub fn stretches_of_bases_encoding(&self) -> Option<&Encoding<ByteArray>> {
         self.stretches_of_bases_encoding.as_ref()
     }
 
+    /// Returns the stretches of quality scores (`QQ`) encoding.
     pub fn stretches_of_quality_scores_encoding(&self) -> Option<&Encoding<ByteArray>> {
         self.stretches_of_quality_scores_encoding.as_ref()
     }
 
+    /// Returns the base substitution codes (`BS`) encoding.
     pub fn base_substitution_codes_encoding(&self) -> Option<&Encoding<Byte>> {
         self.base_substitution_codes_encoding.as_ref()
     }
 
+    /// Returns the insertion (`IN`) encoding.
     pub fn insertion_encoding(&self) -> Option<&Encoding<ByteArray>> {
         self.insertion_encoding.as_ref()
     }
 
+    /// Returns the reference skip length (`RS`) encoding.
     pub fn reference_skip_length_encoding(&self) -> Option<&Encoding<Integer>> {
         self.reference_skip_length_encoding.as_ref()
     }
 
+    /// Returns the padding (`PD`) encoding.
     pub fn padding_encoding(&self) -> Option<&Encoding<Integer>> {
         self.padding_encoding.as_ref()
     }
 
+    /// Returns the hard clip (`HC`) encoding.
     pub fn hard_clip_encoding(&self) -> Option<&Encoding<Integer>> {
         self.hard_clip_encoding.as_ref()
     }
 
+    /// Returns the soft clip (`SC`) encoding.
     pub fn soft_clip_encoding(&self) -> Option<&Encoding<ByteArray>> {
         self.soft_clip_encoding.as_ref()
     }
 
+    /// Returns the mapping qualities (`MQ`) encoding.
     pub fn mapping_qualities_encoding(&self) -> Option<&Encoding<Integer>> {
         self.mapping_qualities_encoding.as_ref()
     }
 
+    /// Returns the bases (`BA`) encoding.
     pub fn bases_encoding(&self) -> Option<&Encoding<Byte>> {
         self.bases_encoding.as_ref()
     }
 
+    /// Returns the quality scores (`QS`) encoding.
     pub fn quality_scores_encoding(&self) -> Option<&Encoding<Byte>> {
         self.quality_scores_encoding.as_ref()
     }
+
+    /// Returns the content IDs of the external blocks read by the encoding of the given data
+    /// series.
+    ///
+    /// This is empty if the data series has no encoding or if it is only read from the core data
+    /// block.
+    pub fn block_content_ids(&self, data_series: DataSeries) -> Vec<block::ContentId> {
+        fn ids<C>(
+            encoding: Option<&Encoding<C>>,
+            f: fn(&C) -> Vec<block::ContentId>,
+        ) -> Vec<block::ContentId> {
+            encoding.map(|e| f(e.get())).unwrap_or_default()
+        }
+
+        let integer = Integer::block_content_ids;
+        let byte = Byte::block_content_ids;
+        let byte_array = ByteArray::block_content_ids;
+
+        match data_series {
+            DataSeries::BamBitFlags => ids(Some(self.bam_bit_flags_encoding()), integer),
+            DataSeries::CramBitFlags => ids(Some(self.cram_bit_flags_encoding()), integer),
+            DataSeries::ReferenceId => ids(self.reference_id_encoding(), integer),
+            DataSeries::ReadLengths => ids(Some(self.read_lengths_encoding()), integer),
+            DataSeries::InSeqPositions => ids(Some(self.in_seq_positions_encoding()), integer),
+            DataSeries::ReadGroups => ids(Some(self.read_groups_encoding()), integer),
+            DataSeries::ReadNames => ids(self.read_names_encoding(), byte_array),
+            DataSeries::NextMateBitFlags => ids(self.next_mate_bit_flags_encoding(), integer),
+            DataSeries::NextFragmentReferenceSequenceId => {
+                ids(self.next_fragment_reference_sequence_id_encoding(), integer)
+            }
+            DataSeries::NextMateAlignmentStart => {
+                ids(self.next_mate_alignment_start_encoding(), integer)
+            }
+            DataSeries::TemplateSize => ids(self.template_size_encoding(), integer),
+            DataSeries::DistanceToNextFragment => {
+                ids(self.distance_to_next_fragment_encoding(), integer)
+            }
+            DataSeries::TagIds => ids(Some(self.tag_ids_encoding()), integer),
+            DataSeries::NumberOfReadFeatures => {
+                ids(self.number_of_read_features_encoding(), integer)
+            }
+            DataSeries::ReadFeaturesCodes => ids(self.read_features_codes_encoding(), byte),
+            DataSeries::InReadPositions => ids(self.in_read_positions_encoding(), integer),
+            DataSeries::DeletionLengths => ids(self.deletion_lengths_encoding(), integer),
+            DataSeries::StretchesOfBases => ids(self.stretches_of_bases_encoding(), byte_array),
+            DataSeries::StretchesOfQualityScores => {
+                ids(self.stretches_of_quality_scores_encoding(), byte_array)
+            }
+            DataSeries::BaseSubstitutionCodes => ids(self.base_substitution_codes_encoding(), byte),
+            DataSeries::Insertion => ids(self.insertion_encoding(), byte_array),
+            DataSeries::ReferenceSkipLength => ids(self.reference_skip_length_encoding(), integer),
+            DataSeries::Padding => ids(self.padding_encoding(), integer),
+            DataSeries::HardClip => ids(self.hard_clip_encoding(), integer),
+            DataSeries::SoftClip => ids(self.soft_clip_encoding(), byte_array),
+            DataSeries::MappingQualities => ids(self.mapping_qualities_encoding(), integer),
+            DataSeries::Bases => ids(self.bases_encoding(), byte),
+            DataSeries::QualityScores => ids(self.quality_scores_encoding(), byte),
+            DataSeries::ReservedTc | DataSeries::ReservedTn => Vec::new(),
+        }
+    }
 }
 
 impl Default for DataSeriesEncodingMap {
@@ -385,4 +475,16 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_block_content_ids() {
+        let map = DataSeriesEncodingMap::default();
+
+        assert_eq!(
+            map.block_content_ids(DataSeries::QualityScores),
+            [block::ContentId::from(DataSeries::QualityScores)]
+        );
+
+        assert!(map.block_content_ids(DataSeries::ReservedTc).is_empty());
+    }
 }
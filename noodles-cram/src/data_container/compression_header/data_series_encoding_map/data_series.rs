@@ -2,6 +2,7 @@ use std::{error, fmt};
 
 use crate::container::block;
 
+/// The data series defined in CRAM 3.
 pub static STANDARD_DATA_SERIES: &[DataSeries; 28] = &[
     DataSeries::BamBitFlags,
     DataSeries::CramBitFlags,
@@ -1,16 +1,23 @@
+//! CRAM data container compression header encoding.
+
 pub mod codec;
 mod kind;
 
-pub use self::kind::Kind;
+pub(crate) use self::kind::Kind;
 
+/// A CRAM data container compression header encoding.
+///
+/// An encoding wraps a codec for a type of value: a byte ([`codec::Byte`]), an integer
+/// ([`codec::Integer`]), or a byte array ([`codec::ByteArray`]).
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Encoding<C>(C);
 
 impl<C> Encoding<C> {
-    pub fn new(codec: C) -> Self {
+    pub(crate) fn new(codec: C) -> Self {
         Self(codec)
     }
 
+    /// Returns the codec.
     pub fn get(&self) -> &C {
         &self.0
     }
@@ -1,3 +1,5 @@
+//! CRAM data container compression header encoding codecs.
+
 mod byte;
 mod byte_array;
 mod integer;
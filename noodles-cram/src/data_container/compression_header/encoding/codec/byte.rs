@@ -1,9 +1,20 @@
 use crate::container::block;
 
+/// A CRAM data container compression header byte codec.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Byte {
-    // block_content_id
+    /// Bytes are read from an external block (block content ID).
     External(block::ContentId),
-    // alphabet, bit_lens
+    /// Canonical Huffman coding in the core data block (alphabet, bit lengths).
     Huffman(Vec<i32>, Vec<u32>),
 }
+
+impl Byte {
+    /// Returns the content IDs of the external blocks read by this codec.
+    pub fn block_content_ids(&self) -> Vec<block::ContentId> {
+        match self {
+            Self::External(block_content_id) => vec![*block_content_id],
+            Self::Huffman(..) => Vec::new(),
+        }
+    }
+}
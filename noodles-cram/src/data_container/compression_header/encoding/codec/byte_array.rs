@@ -6,10 +6,57 @@ use crate::{
     },
 };
 
+/// A CRAM data container compression header byte array codec.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum ByteArray {
-    // len_encoding, value_encoding
+    /// A length followed by that many bytes (length encoding, value encoding).
     ByteArrayLen(Encoding<Integer>, Encoding<Byte>),
-    // stop_byte, block_content_id
+    /// Bytes read from an external block until a stop byte (stop byte, block content ID).
     ByteArrayStop(u8, block::ContentId),
 }
+
+impl ByteArray {
+    /// Returns the content IDs of the external blocks read by this codec.
+    pub fn block_content_ids(&self) -> Vec<block::ContentId> {
+        match self {
+            Self::ByteArrayLen(len_encoding, value_encoding) => {
+                let mut ids = len_encoding.get().block_content_ids();
+
+                for id in value_encoding.get().block_content_ids() {
+                    if !ids.contains(&id) {
+                        ids.push(id);
+                    }
+                }
+
+                ids
+            }
+            Self::ByteArrayStop(_, block_content_id) => vec![*block_content_id],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_block_content_ids() {
+        let codec = ByteArray::ByteArrayLen(
+            Encoding::new(Integer::External(block::ContentId::from(1))),
+            Encoding::new(Byte::External(block::ContentId::from(2))),
+        );
+        assert_eq!(
+            codec.block_content_ids(),
+            [block::ContentId::from(1), block::ContentId::from(2)]
+        );
+
+        let codec = ByteArray::ByteArrayLen(
+            Encoding::new(Integer::Beta(0, 8)),
+            Encoding::new(Byte::External(block::ContentId::from(2))),
+        );
+        assert_eq!(codec.block_content_ids(), [block::ContentId::from(2)]);
+
+        let codec = ByteArray::ByteArrayStop(0x00, block::ContentId::from(3));
+        assert_eq!(codec.block_content_ids(), [block::ContentId::from(3)]);
+    }
+}
@@ -1,19 +1,32 @@
 use crate::container::block;
 
+/// A CRAM data container compression header integer codec.
+///
+/// Other than [`Self::External`], integers are read from the core data block.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Integer {
-    // block_content_id
+    /// Integers are read from an external block as ITF8 values (block content ID).
     External(block::ContentId),
-    // offset, m
+    /// Golomb coding (offset, M).
     Golomb(i32, i32),
-    // alphabet, bit_lens
+    /// Canonical Huffman coding (alphabet, bit lengths).
     Huffman(Vec<i32>, Vec<u32>),
-    // offset, len
+    /// Beta coding, i.e., fixed-width binary (offset, bit length).
     Beta(i32, u32),
-    // offset, k
+    /// Subexponential coding (offset, k).
     Subexp(i32, i32),
-    // offset, log2_m
+    /// Golomb-Rice coding (offset, log2(M)).
     GolombRice(i32, i32),
-    // offset
+    /// Elias gamma coding (offset).
     Gamma(i32),
 }
+
+impl Integer {
+    /// Returns the content IDs of the external blocks read by this codec.
+    pub fn block_content_ids(&self) -> Vec<block::ContentId> {
+        match self {
+            Self::External(block_content_id) => vec![*block_content_id],
+            _ => Vec::new(),
+        }
+    }
+}
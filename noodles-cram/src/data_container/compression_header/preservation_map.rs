@@ -2,16 +2,17 @@
 
 mod builder;
 pub(crate) mod key;
-pub(crate) mod substitution_matrix;
+pub mod substitution_matrix;
 pub mod tag_ids_dictionary;
 
-pub(crate) use {
-    builder::Builder, key::Key, substitution_matrix::SubstitutionMatrix,
-    tag_ids_dictionary::TagIdsDictionary,
-};
+pub(crate) use {builder::Builder, key::Key};
+pub use {substitution_matrix::SubstitutionMatrix, tag_ids_dictionary::TagIdsDictionary};
 
+/// A CRAM data container compression header preservation map.
+///
+/// The preservation map has flags and dictionaries that apply to all records in the container.
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub(crate) struct PreservationMap {
+pub struct PreservationMap {
     read_names_included: bool,
     ap_data_series_delta: bool,
     is_reference_required: bool,
@@ -20,7 +21,7 @@ pub(crate) struct PreservationMap {
 }
 
 impl PreservationMap {
-    pub fn new(
+    pub(crate) fn new(
         read_names_included: bool,
         ap_data_series_delta: bool,
         is_reference_required: bool,
@@ -36,22 +37,27 @@ impl PreservationMap {
         }
     }
 
+    /// Returns whether read names are preserved for all records (`RN`).
     pub fn read_names_included(&self) -> bool {
         self.read_names_included
     }
 
+    /// Returns whether alignment starts are stored as deltas (`AP`).
     pub fn ap_data_series_delta(&self) -> bool {
         self.ap_data_series_delta
     }
 
+    /// Returns whether a reference sequence is required to restore the data (`RR`).
     pub fn is_reference_required(&self) -> bool {
         self.is_reference_required
     }
 
+    /// Returns the substitution matrix (`SM`).
     pub fn substitution_matrix(&self) -> &SubstitutionMatrix {
         &self.substitution_matrix
     }
 
+    /// Returns the tag IDs dictionary (`TD`).
     pub fn tag_ids_dictionary(&self) -> &TagIdsDictionary {
         &self.tag_ids_dictionary
     }
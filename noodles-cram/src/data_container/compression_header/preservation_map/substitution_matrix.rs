@@ -1,7 +1,9 @@
+//! CRAM data container compression header preservation map substitution matrix.
+
 mod builder;
 mod histogram;
 
-pub use self::builder::Builder;
+pub(crate) use self::builder::Builder;
 
 use std::{cmp, error, fmt};
 
@@ -10,21 +12,21 @@ use crate::record::feature::substitution::Base;
 
 type Substitutions = [[Base; 4]; 5];
 
+/// A CRAM data container compression header preservation map substitution matrix.
+///
+/// The substitution matrix maps a reference base and a 2-bit substitution code to a read base.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct SubstitutionMatrix {
     substitutions: Substitutions,
 }
 
 impl SubstitutionMatrix {
-    pub fn builder() -> Builder {
-        Builder::default()
-    }
-
+    /// Returns the read base for the given reference base and substitution code.
     pub fn get(&self, reference_base: Base, substitution_code: u8) -> Base {
         self.substitutions[reference_base as usize][substitution_code as usize]
     }
 
-    pub fn find_code(&self, reference_base: Base, read_base: Base) -> u8 {
+    pub(crate) fn find_code(&self, reference_base: Base, read_base: Base) -> u8 {
         for code in [0b00, 0b01, 0b10, 0b11] {
             if self.get(reference_base, code) == read_base {
                 return code;
@@ -77,6 +79,7 @@ impl From<Histogram> for SubstitutionMatrix {
     }
 }
 
+/// An error returned when raw substitution matrix bytes fail to convert.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct TryFromByteArrayError([u8; 5]);
 
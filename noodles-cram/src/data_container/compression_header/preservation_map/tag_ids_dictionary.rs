@@ -8,8 +8,11 @@ pub use self::key::Key;
 
 use std::ops::Deref;
 
+/// A CRAM data container compression header preservation map tag IDs dictionary.
+///
+/// Each entry is a list of tag keys used by one or more records.
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub(crate) struct TagIdsDictionary(Vec<Vec<Key>>);
+pub struct TagIdsDictionary(Vec<Vec<Key>>);
 
 impl Deref for TagIdsDictionary {
     type Target = [Vec<Key>];
@@ -1,12 +1,17 @@
+//! CRAM data container compression header tag encoding map.
+
 mod builder;
 
-pub use self::builder::Builder;
+pub(crate) use self::builder::Builder;
 
 use std::{collections::HashMap, ops::Deref};
 
 use super::{encoding::codec::ByteArray, Encoding};
 use crate::container::block;
 
+/// A CRAM data container compression header tag encoding map.
+///
+/// This maps tag IDs (as block content IDs) to the encodings of their values.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct TagEncodingMap(HashMap<block::ContentId, Encoding<ByteArray>>);
 
@@ -1,9 +1,14 @@
+//! CRAM data container header.
+
 mod builder;
 
-pub use self::builder::Builder;
+pub(crate) use self::builder::Builder;
 
 use super::ReferenceSequenceContext;
 
+/// A CRAM data container header.
+///
+/// The container header describes the size and position of the data container and its slices.
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct Header {
     length: usize,
@@ -17,34 +22,43 @@ pub struct Header {
 
 #[allow(clippy::len_without_is_empty)]
 impl Header {
-    pub fn builder() -> Builder {
+    pub(crate) fn builder() -> Builder {
         Builder::default()
     }
 
+    /// Returns the size of the container data (the compression header and slices) in bytes.
     pub fn len(&self) -> usize {
         self.length
     }
 
+    /// Returns the reference sequence context.
     pub fn reference_sequence_context(&self) -> ReferenceSequenceContext {
         self.reference_sequence_context
     }
 
+    /// Returns the number of records in the container.
     pub fn record_count(&self) -> i32 {
         self.record_count
     }
 
+    /// Returns the record counter, i.e., the 0-based index of the first record in the container.
     pub fn record_counter(&self) -> u64 {
         self.record_counter
     }
 
+    /// Returns the number of read bases in the container.
     pub fn base_count(&self) -> u64 {
         self.base_count
     }
 
+    /// Returns the number of blocks in the container.
     pub fn block_count(&self) -> usize {
         self.block_count
     }
 
+    /// Returns the landmarks.
+    ///
+    /// Each landmark is the position of a slice relative to the start of the container data.
     pub fn landmarks(&self) -> &[usize] {
         &self.landmarks
     }
@@ -1,7 +1,10 @@
+//! CRAM data container slice.
+
 pub(crate) mod builder;
-pub(crate) mod header;
+pub mod header;
 
-pub use self::{builder::Builder, header::Header};
+pub(crate) use self::builder::Builder;
+pub use self::header::Header;
 
 use std::io;
 
@@ -40,15 +43,18 @@ impl Slice {
         }
     }
 
-    pub(crate) fn header(&self) -> &Header {
+    /// Returns the slice header.
+    pub fn header(&self) -> &Header {
         &self.header
     }
 
-    pub(crate) fn core_data_block(&self) -> &Block {
+    /// Returns the core data block.
+    pub fn core_data_block(&self) -> &Block {
         &self.core_data_block
     }
 
-    pub(crate) fn external_blocks(&self) -> &[Block] {
+    /// Returns the external data blocks.
+    pub fn external_blocks(&self) -> &[Block] {
         &self.external_blocks
     }
 
@@ -161,12 +167,20 @@ impl Slice {
         // § 11 "Reference sequences" (2021-11-15): "All CRAM reader implementations are
         // expected to check for reference MD5 checksums and report any missing or
         // mismatching entries."
-        if compression_header
-            .preservation_map()
-            .is_reference_required()
+        if let ReferenceSequenceContext::Some(context) = self.header().reference_sequence_context()
         {
-            if let ReferenceSequenceContext::Some(context) =
-                self.header().reference_sequence_context()
+            let expected_md5 = self.header().reference_md5();
+
+            let actual_md5 = if let Some(sequence) = &embedded_reference_sequence {
+                // An embedded reference sequence is only checked when a checksum is given.
+                if expected_md5.iter().all(|&b| b == 0) {
+                    None
+                } else {
+                    Some(builder::calculate_normalized_sequence_digest(&sequence[..]))
+                }
+            } else if compression_header
+                .preservation_map()
+                .is_reference_required()
             {
                 let reference_sequence_name = header
                     .reference_sequences()
@@ -182,10 +196,14 @@ impl Slice {
                 let start = context.alignment_start();
                 let end = context.alignment_end();
 
-                let actual_md5 =
-                    builder::calculate_normalized_sequence_digest(&sequence[start..=end]);
-                let expected_md5 = self.header().reference_md5();
+                Some(builder::calculate_normalized_sequence_digest(
+                    &sequence[start..=end],
+                ))
+            } else {
+                None
+            };
 
+            if let Some(actual_md5) = actual_md5 {
                 if actual_md5 != expected_md5 {
                     return Err(io::Error::new(
                         io::ErrorKind::InvalidData,
@@ -206,7 +224,22 @@ impl Slice {
 
             let mut alignment_start = record.alignment_start.expect("invalid alignment start");
 
-            let reference_sequence = if compression_header
+            // An embedded reference sequence takes precedence over an external one, even if the
+            // reference is marked as required.
+            let reference_sequence = if let Some(ref sequence) = embedded_reference_sequence {
+                let offset = match self.header().reference_sequence_context() {
+                    ReferenceSequenceContext::Some(context) => {
+                        usize::from(context.alignment_start())
+                    }
+                    _ => panic!("invalid slice alignment start"),
+                };
+
+                let start = usize::from(alignment_start) - offset + 1;
+                alignment_start = Position::try_from(start)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+                Some(sequence.clone())
+            } else if compression_header
                 .preservation_map()
                 .is_reference_required()
             {
@@ -221,19 +254,6 @@ impl Slice {
                     .expect("invalid reference sequence name");
 
                 Some(sequence)
-            } else if let Some(ref sequence) = embedded_reference_sequence {
-                let offset = match self.header().reference_sequence_context() {
-                    ReferenceSequenceContext::Some(context) => {
-                        usize::from(context.alignment_start())
-                    }
-                    _ => panic!("invalid slice alignment start"),
-                };
-
-                let start = usize::from(alignment_start) - offset + 1;
-                alignment_start = Position::try_from(start)
-                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
-
-                Some(sequence.clone())
             } else {
                 None
             };
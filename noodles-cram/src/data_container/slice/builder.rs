@@ -1,7 +1,8 @@
-use std::{collections::HashMap, io};
+use std::{collections::HashMap, io, sync::Arc};
 
 use bytes::Bytes;
 use md5::{Digest, Md5};
+use noodles_core::Position;
 use noodles_fasta as fasta;
 use noodles_sam as sam;
 
@@ -10,16 +11,19 @@ use crate::{
     container::{block, Block},
     data_container::{
         compression_header::data_series_encoding_map::data_series::STANDARD_DATA_SERIES,
-        BlockContentEncoderMap, CompressionHeader, ReferenceSequenceContext,
+        CompressionHeader, ReferenceSequenceContext,
     },
-    record::Flags,
-    writer, BitWriter, Record,
+    record::{Feature, Features, Flags},
+    writer::{self, BlockContentEncoderSelector, Options},
+    BitWriter, Record,
 };
 
 use super::{Header, Slice};
 
 const CORE_DATA_BLOCK_CONTENT_ID: i32 = 0;
-const MAX_RECORD_COUNT: usize = 10240;
+
+// This is the block content ID after the last standard data series (`TM`).
+const EMBEDDED_REFERENCE_BASES_BLOCK_CONTENT_ID: i32 = 29;
 
 #[derive(Debug, Default)]
 pub struct Builder {
@@ -45,8 +49,12 @@ impl Builder {
         self.reference_sequence_context
     }
 
-    pub fn add_record(&mut self, record: Record) -> Result<&Record, AddRecordError> {
-        if self.records.len() >= MAX_RECORD_COUNT {
+    pub fn add_record(
+        &mut self,
+        options: &Options,
+        record: Record,
+    ) -> Result<&Record, AddRecordError> {
+        if self.records.len() >= options.records_per_slice {
             return Err(AddRecordError::SliceFull(record));
         }
 
@@ -75,82 +83,342 @@ impl Builder {
     }
 
     pub fn build(
-        mut self,
-        block_content_encoder_map: &BlockContentEncoderMap,
+        self,
+        options: &Options,
         reference_sequence_repostitory: &fasta::repository::Repository,
         header: &sam::Header,
         compression_header: &CompressionHeader,
         record_counter: u64,
     ) -> io::Result<Slice> {
-        let (core_data_block, external_blocks) = write_records(
-            block_content_encoder_map,
+        self.encode(
+            options,
+            reference_sequence_repostitory,
+            header,
+            compression_header,
+            record_counter,
+        )
+        .and_then(|encoded_slice| encoded_slice.build())
+    }
+
+    /// Encodes the records of the slice without compressing the resulting blocks.
+    pub fn encode(
+        mut self,
+        options: &Options,
+        reference_sequence_repostitory: &fasta::repository::Repository,
+        header: &sam::Header,
+        compression_header: &CompressionHeader,
+        record_counter: u64,
+    ) -> io::Result<EncodedSlice> {
+        let embedded_reference_sequence = match self.reference_sequence_context {
+            ReferenceSequenceContext::Some(context) if options.embed_reference_sequences => {
+                let reference_sequence = get_reference_sequence(
+                    reference_sequence_repostitory,
+                    header,
+                    context.reference_sequence_id(),
+                )?;
+
+                let (start, end) = (context.alignment_start(), context.alignment_end());
+                let sequence = get_reference_sequence_slice(&reference_sequence, start, end)?;
+
+                Some(sequence.to_vec())
+            }
+            _ => None,
+        };
+
+        if !compression_header
+            .preservation_map()
+            .is_reference_required()
+            && embedded_reference_sequence.is_none()
+        {
+            for record in &mut self.records {
+                set_explicit_bases(record);
+            }
+        }
+
+        let (core_data_block, mut external_blocks) = write_records(
+            options,
             compression_header,
             self.reference_sequence_context,
             &mut self.records,
         )?;
 
+        let mut embedded_reference_bases_block_content_id = None;
+
+        if let Some(sequence) = &embedded_reference_sequence {
+            let block_content_id =
+                block::ContentId::from(EMBEDDED_REFERENCE_BASES_BLOCK_CONTENT_ID);
+
+            let builder = Block::builder()
+                .set_content_type(block::ContentType::ExternalData)
+                .set_content_id(block_content_id);
+
+            external_blocks.push(UncompressedBlock {
+                builder,
+                block_content_id,
+                data: sequence.clone(),
+                compression: Compression::Encoder(Some(Encoder::Gzip(Default::default())), None),
+            });
+
+            embedded_reference_bases_block_content_id = Some(block_content_id);
+        }
+
         let mut block_content_ids = Vec::with_capacity(external_blocks.len() + 1);
-        block_content_ids.push(core_data_block.content_id());
+        block_content_ids.push(core_data_block.block_content_id);
 
         for block in &external_blocks {
-            block_content_ids.push(block.content_id());
+            block_content_ids.push(block.block_content_id);
         }
 
         let reference_md5 = match self.reference_sequence_context {
             ReferenceSequenceContext::Some(context) => {
-                let reference_sequence_name = header
-                    .reference_sequences()
-                    .get_index(context.reference_sequence_id())
-                    .map(|(_, rs)| rs.name())
-                    .expect("invalid reference sequence ID");
-
-                let reference_sequence = reference_sequence_repostitory
-                    .get(reference_sequence_name)
-                    .expect("missing reference sequence")
-                    .expect("invalid reference sequence");
-
-                let (start, end) = (context.alignment_start(), context.alignment_end());
-                let sequence = &reference_sequence[start..=end];
-
-                calculate_normalized_sequence_digest(sequence)
+                if let Some(sequence) = &embedded_reference_sequence {
+                    calculate_normalized_sequence_digest(sequence)
+                } else if compression_header
+                    .preservation_map()
+                    .is_reference_required()
+                {
+                    let reference_sequence = get_reference_sequence(
+                        reference_sequence_repostitory,
+                        header,
+                        context.reference_sequence_id(),
+                    )?;
+
+                    let (start, end) = (context.alignment_start(), context.alignment_end());
+                    let sequence = get_reference_sequence_slice(&reference_sequence, start, end)?;
+
+                    calculate_normalized_sequence_digest(sequence)
+                } else {
+                    [0; 16]
+                }
             }
             _ => [0; 16],
         };
 
-        let header = Header::builder()
+        let mut builder = Header::builder()
             .set_reference_sequence_context(self.reference_sequence_context)
             .set_record_count(self.records.len())
             .set_record_counter(record_counter)
             .set_block_count(block_content_ids.len())
             .set_block_content_ids(block_content_ids)
-            .set_reference_md5(reference_md5)
-            .build();
+            .set_reference_md5(reference_md5);
 
-        Ok(Slice::new(header, core_data_block, external_blocks))
+        if let Some(id) = embedded_reference_bases_block_content_id {
+            builder = builder.set_embedded_reference_bases_block_content_id(id);
+        }
+
+        let header = builder.build();
+
+        Ok(EncodedSlice {
+            header,
+            core_data_block,
+            external_blocks,
+        })
     }
 }
 
-fn write_records(
-    block_content_encoder_map: &BlockContentEncoderMap,
-    compression_header: &CompressionHeader,
-    reference_sequence_context: ReferenceSequenceContext,
-    records: &mut [Record],
-) -> io::Result<(Block, Vec<Block>)> {
-    use crate::codecs::fqzcomp;
+/// A slice with encoded records whose blocks are not yet compressed.
+#[derive(Debug)]
+pub struct EncodedSlice {
+    header: Header,
+    core_data_block: UncompressedBlock,
+    external_blocks: Vec<UncompressedBlock>,
+}
+
+impl EncodedSlice {
+    /// Compresses the blocks and builds the slice.
+    pub fn build(self) -> io::Result<Slice> {
+        let core_data_block = self.core_data_block.compress()?;
+
+        let external_blocks = self
+            .external_blocks
+            .into_iter()
+            .map(|block| block.compress())
+            .collect::<io::Result<_>>()?;
+
+        Ok(Slice::new(self.header, core_data_block, external_blocks))
+    }
+
+    /// Returns the slice header, the core data block, and the external blocks.
+    pub fn into_parts(self) -> (Header, UncompressedBlock, Vec<UncompressedBlock>) {
+        (self.header, self.core_data_block, self.external_blocks)
+    }
+}
+
+/// A block whose data is not yet compressed.
+#[derive(Debug)]
+pub struct UncompressedBlock {
+    builder: block::Builder,
+    block_content_id: block::ContentId,
+    data: Vec<u8>,
+    compression: Compression,
+}
+
+#[derive(Debug)]
+enum Compression {
+    // The encoder can be overridden by the block content encoder selector, if set.
+    Encoder(Option<Encoder>, Option<Arc<BlockContentEncoderSelector>>),
+    // fqzcomp requires the read lengths of the records in the slice.
+    Fqzcomp(Vec<usize>),
+}
 
-    fn set_block_data(
+impl UncompressedBlock {
+    fn new(
+        options: &Options,
         builder: block::Builder,
-        buf: Vec<u8>,
+        block_content_id: block::ContentId,
+        data: Vec<u8>,
         encoder: Option<&Encoder>,
-    ) -> io::Result<block::Builder> {
-        match encoder {
-            Some(encoder) => builder.compress_and_set_data(buf, encoder.clone()),
-            None => Ok(builder
-                .set_uncompressed_len(buf.len())
-                .set_data(Bytes::from(buf))),
+    ) -> Self {
+        Self {
+            builder,
+            block_content_id,
+            data,
+            compression: Compression::Encoder(
+                encoder.cloned(),
+                options.block_content_encoder_selector.clone(),
+            ),
         }
     }
 
+    /// Compresses the data and builds the block.
+    pub fn compress(self) -> io::Result<Block> {
+        use crate::codecs::fqzcomp;
+
+        match self.compression {
+            Compression::Encoder(encoder, Some(selector)) => selector.build_block(
+                self.builder,
+                self.block_content_id,
+                self.data,
+                encoder.as_ref(),
+            ),
+            Compression::Encoder(encoder, None) => self
+                .builder
+                .set_data_with_encoder(self.data, encoder.as_ref())
+                .map(|builder| builder.build()),
+            Compression::Fqzcomp(lens) => {
+                let data = fqzcomp::encode(&lens, &self.data)?;
+
+                Ok(self
+                    .builder
+                    .set_uncompressed_len(self.data.len())
+                    .set_compression_method(block::CompressionMethod::Fqzcomp)
+                    .set_data(Bytes::from(data))
+                    .build())
+            }
+        }
+    }
+}
+
+fn get_reference_sequence(
+    reference_sequence_repostitory: &fasta::repository::Repository,
+    header: &sam::Header,
+    reference_sequence_id: usize,
+) -> io::Result<fasta::record::Sequence> {
+    let reference_sequence_name = header
+        .reference_sequences()
+        .get_index(reference_sequence_id)
+        .map(|(_, rs)| rs.name())
+        .ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "invalid reference sequence ID")
+        })?;
+
+    reference_sequence_repostitory
+        .get(reference_sequence_name)
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("missing reference sequence: {}", reference_sequence_name),
+            )
+        })?
+}
+
+fn get_reference_sequence_slice(
+    reference_sequence: &fasta::record::Sequence,
+    start: Position,
+    end: Position,
+) -> io::Result<&[u8]> {
+    reference_sequence.get(start..=end).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "alignment span is out of reference sequence bounds",
+        )
+    })
+}
+
+// Replaces the bases of a mapped record that would otherwise be resolved from the reference
+// sequence, i.e., implicit matches and substitutions, with explicit bases (`BB`).
+fn set_explicit_bases(record: &mut Record) {
+    if record.bam_flags().is_unmapped() || record.bases().is_empty() {
+        return;
+    }
+
+    let bases = record.bases().as_ref();
+    let read_length = record.read_length();
+
+    let mut features = Vec::with_capacity(record.features().len());
+    let mut read_position = 1;
+
+    for feature in record.features().iter() {
+        let position = usize::from(feature.position());
+
+        if position > read_position {
+            match build_bases_feature(bases, read_position, position) {
+                Some(f) => features.push(f),
+                None => return,
+            }
+
+            read_position = position;
+        }
+
+        let len = match feature {
+            Feature::Substitution(..) => {
+                match build_bases_feature(bases, position, position + 1) {
+                    Some(f) => features.push(f),
+                    None => return,
+                }
+
+                read_position = position + 1;
+                continue;
+            }
+            Feature::Bases(_, bases) => bases.len(),
+            Feature::Insertion(_, bases) => bases.len(),
+            Feature::SoftClip(_, bases) => bases.len(),
+            Feature::ReadBase(..) | Feature::InsertBase(..) => 1,
+            _ => 0,
+        };
+
+        features.push(feature.clone());
+        read_position = read_position.max(position + len);
+    }
+
+    if read_position <= read_length {
+        match build_bases_feature(bases, read_position, read_length + 1) {
+            Some(f) => features.push(f),
+            None => return,
+        }
+    }
+
+    record.features = Features::from(features);
+}
+
+fn build_bases_feature(
+    bases: &[sam::record::sequence::Base],
+    start: usize,
+    end: usize,
+) -> Option<Feature> {
+    let position = Position::new(start)?;
+    let bases = bases.get(start - 1..end - 1)?;
+    Some(Feature::Bases(position, bases.to_vec()))
+}
+
+fn write_records(
+    options: &Options,
+    compression_header: &CompressionHeader,
+    reference_sequence_context: ReferenceSequenceContext,
+    records: &mut [Record],
+) -> io::Result<(UncompressedBlock, Vec<UncompressedBlock>)> {
+    let block_content_encoder_map = &options.block_content_encoder_map;
+
     let mut core_data_writer = BitWriter::new(Vec::new());
 
     let mut external_data_writers = HashMap::new();
@@ -182,54 +450,62 @@ fn write_records(
         record_writer.write_record(record)?;
     }
 
-    let core_data_block = core_data_writer.finish().and_then(|buf| {
-        let mut builder = Block::builder()
-            .set_content_type(block::ContentType::CoreData)
-            .set_content_id(block::ContentId::from(CORE_DATA_BLOCK_CONTENT_ID));
-
-        builder = set_block_data(builder, buf, block_content_encoder_map.core_data_encoder())?;
+    let core_data_block = core_data_writer.finish().map(|buf| {
+        let block_content_id = block::ContentId::from(CORE_DATA_BLOCK_CONTENT_ID);
 
-        Ok(builder.build())
+        let builder = Block::builder()
+            .set_content_type(block::ContentType::CoreData)
+            .set_content_id(block_content_id);
+
+        UncompressedBlock::new(
+            options,
+            builder,
+            block_content_id,
+            buf,
+            block_content_encoder_map.core_data_encoder(),
+        )
     })?;
 
-    let external_blocks: Vec<_> = external_data_writers
+    let external_blocks = external_data_writers
         .into_iter()
         .filter(|(_, buf)| !buf.is_empty())
         .map(|(block_content_id, buf)| {
-            let mut builder = Block::builder()
+            let builder = Block::builder()
                 .set_content_type(block::ContentType::ExternalData)
                 .set_content_id(block_content_id);
 
-            builder = if let Some(encoder) =
+            if let Some(encoder) =
                 block_content_encoder_map.get_data_series_encoder(block_content_id)
             {
                 match encoder {
                     Some(Encoder::Fqzcomp) => {
                         if all_quality_scores_stored_as_arrays {
-                            let lens: Vec<_> = records.iter().map(|r| r.read_length()).collect();
-                            let data = fqzcomp::encode(&lens, &buf)?;
-
-                            builder
-                                .set_uncompressed_len(buf.len())
-                                .set_compression_method(block::CompressionMethod::Fqzcomp)
-                                .set_data(Bytes::from(data))
+                            let lens = records.iter().map(|r| r.read_length()).collect();
+
+                            UncompressedBlock {
+                                builder,
+                                block_content_id,
+                                data: buf,
+                                compression: Compression::Fqzcomp(lens),
+                            }
                         } else {
-                            set_block_data(builder, buf, Some(&Encoder::Gzip(Default::default())))?
+                            UncompressedBlock::new(
+                                options,
+                                builder,
+                                block_content_id,
+                                buf,
+                                Some(&Encoder::Gzip(Default::default())),
+                            )
                         }
                     }
-                    _ => set_block_data(builder, buf, encoder)?,
+                    _ => UncompressedBlock::new(options, builder, block_content_id, buf, encoder),
                 }
-            } else if let Some(encoder) =
-                block_content_encoder_map.get_tag_values_encoders(block_content_id)
-            {
-                set_block_data(builder, buf, encoder)?
             } else {
-                set_block_data(builder, buf, Some(&Encoder::Gzip(Default::default())))?
-            };
-
-            Ok(builder.build())
+                let encoder = block_content_encoder_map.get_tag_values_encoder(block_content_id);
+                UncompressedBlock::new(options, builder, block_content_id, buf, encoder)
+            }
         })
-        .collect::<Result<_, io::Error>>()?;
+        .collect();
 
     Ok((core_data_block, external_blocks))
 }
@@ -327,4 +603,40 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_set_explicit_bases() -> Result<(), Box<dyn std::error::Error>> {
+        use sam::record::sequence::Base;
+
+        use crate::record::feature::substitution;
+
+        let features = Features::from(vec![
+            Feature::SoftClip(Position::try_from(1)?, vec![Base::A]),
+            Feature::Substitution(Position::try_from(3)?, substitution::Value::Code(0)),
+            Feature::Deletion(Position::try_from(5)?, 2),
+        ]);
+
+        let mut record = Record::builder()
+            .set_bam_flags(sam::record::Flags::empty())
+            .set_read_length(6)
+            .set_alignment_start(Position::MIN)
+            .set_bases("ACGTAC".parse()?)
+            .set_features(features)
+            .build();
+
+        set_explicit_bases(&mut record);
+
+        let expected = Features::from(vec![
+            Feature::SoftClip(Position::try_from(1)?, vec![Base::A]),
+            Feature::Bases(Position::try_from(2)?, vec![Base::C]),
+            Feature::Bases(Position::try_from(3)?, vec![Base::G]),
+            Feature::Bases(Position::try_from(4)?, vec![Base::T]),
+            Feature::Deletion(Position::try_from(5)?, 2),
+            Feature::Bases(Position::try_from(5)?, vec![Base::A, Base::C]),
+        ]);
+
+        assert_eq!(record.features(), &expected);
+
+        Ok(())
+    }
 }
@@ -45,6 +45,13 @@ impl Version {
     pub fn minor(&self) -> u8 {
         self.minor
     }
+
+    /// Returns whether container headers and blocks end with a CRC32 checksum.
+    ///
+    /// Checksums were added in CRAM 3.0.
+    pub(crate) fn has_crc32(&self) -> bool {
+        self.major >= 3
+    }
 }
 
 impl Default for Version {
@@ -61,4 +68,11 @@ mod tests {
     fn test_default() {
         assert_eq!(Version::default(), Version::new(3, 0));
     }
+
+    #[test]
+    fn test_has_crc32() {
+        assert!(!Version::new(2, 1).has_crc32());
+        assert!(Version::new(3, 0).has_crc32());
+        assert!(Version::new(3, 1).has_crc32());
+    }
 }
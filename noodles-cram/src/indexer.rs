@@ -1,4 +1,10 @@
-use std::{cmp, collections::HashMap, fs::File, io, path::Path};
+use std::{
+    cmp,
+    collections::HashMap,
+    fs::File,
+    io::{self, Read, Seek},
+    path::Path,
+};
 
 use noodles_core::Position;
 
@@ -23,6 +29,13 @@ where
     P: AsRef<Path>,
 {
     let mut reader = File::open(src).map(Reader::new)?;
+    build_index(&mut reader)
+}
+
+pub(crate) fn build_index<R>(reader: &mut Reader<R>) -> io::Result<crai::Index>
+where
+    R: Read + Seek,
+{
     reader.read_file_definition()?;
     reader.read_file_header()?;
 
@@ -90,21 +103,12 @@ fn push_index_records(
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 struct SliceReferenceSequenceAlignmentRangeInclusive {
     start: Option<Position>,
     end: Option<Position>,
 }
 
-impl Default for SliceReferenceSequenceAlignmentRangeInclusive {
-    fn default() -> Self {
-        Self {
-            start: Position::new(usize::MAX),
-            end: None,
-        }
-    }
-}
-
 fn push_index_records_for_multi_reference_slice(
     index: &mut crai::Index,
     compression_header: &CompressionHeader,
@@ -125,8 +129,13 @@ fn push_index_records_for_multi_reference_slice(
             .entry(reference_sequence_id)
             .or_default();
 
-        let alignment_start = record.alignment_start();
-        range.start = cmp::min(range.start, alignment_start);
+        if let Some(alignment_start) = record.alignment_start() {
+            range.start = Some(
+                range
+                    .start
+                    .map_or(alignment_start, |start| cmp::min(start, alignment_start)),
+            );
+        }
 
         let alignment_end = record.alignment_end();
         range.end = cmp::max(range.end, alignment_end);
@@ -137,17 +146,16 @@ fn push_index_records_for_multi_reference_slice(
     sorted_reference_sequence_ids.sort_unstable();
 
     for reference_sequence_id in sorted_reference_sequence_ids {
-        let (alignment_start, alignment_span) = if reference_sequence_id.is_some() {
-            let range = &reference_sequence_ids[&reference_sequence_id];
+        let range = &reference_sequence_ids[&reference_sequence_id];
 
-            if let (Some(start), Some(end)) = (range.start, range.end) {
+        // Placed, unmapped records may not have an alignment end.
+        let (alignment_start, alignment_span) = match (range.start, range.end) {
+            (Some(start), Some(end)) if reference_sequence_id.is_some() => {
                 let span = usize::from(end) - usize::from(start) + 1;
                 (Some(start), span)
-            } else {
-                todo!("unhandled interval: {:?}", range);
             }
-        } else {
-            (None, 0)
+            (Some(start), None) if reference_sequence_id.is_some() => (Some(start), 1),
+            _ => (None, 0),
         };
 
         let record = crai::Record::new(
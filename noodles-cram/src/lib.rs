@@ -1,32 +1,55 @@
 #![warn(missing_docs)]
 
 //! **noodles-cram** handles the reading and writing of the CRAM format.
+//!
+//! The default `io` feature enables the CRAM reader, writer, and data structures. To only use the
+//! block content codecs ([`codecs`]), disable the default features and enable `codecs`.
 
 #[cfg(feature = "async")]
 mod r#async;
 
+#[cfg(feature = "io")]
 mod bit_reader;
+#[cfg(feature = "io")]
 mod bit_writer;
+#[cfg(feature = "codecs")]
 pub mod codecs;
-pub(crate) mod container;
+#[cfg(feature = "io")]
+pub mod container;
+#[cfg(feature = "io")]
 pub mod crai;
+#[cfg(feature = "io")]
 pub mod data_container;
+#[cfg(feature = "io")]
 pub mod file_definition;
+#[cfg(feature = "io")]
 mod huffman;
+#[cfg(feature = "io")]
+pub mod indexed_reader;
+#[cfg(feature = "io")]
 mod indexer;
+#[cfg(feature = "codecs")]
 mod num;
+#[cfg(feature = "io")]
 pub mod reader;
+#[cfg(feature = "io")]
 pub mod record;
+#[cfg(feature = "io")]
+mod worker_pool;
+#[cfg(feature = "io")]
 pub mod writer;
 
+#[cfg(feature = "io")]
 pub use self::{
-    data_container::DataContainer, file_definition::FileDefinition, indexer::index, reader::Reader,
-    record::Record, writer::Writer,
+    data_container::DataContainer, file_definition::FileDefinition, indexed_reader::IndexedReader,
+    indexer::index, reader::Reader, record::Record, writer::Writer,
 };
 
 #[cfg(feature = "async")]
-pub use self::r#async::Reader as AsyncReader;
+pub use self::r#async::{Reader as AsyncReader, Writer as AsyncWriter};
 
+#[cfg(feature = "io")]
 pub(crate) use self::{bit_reader::BitReader, bit_writer::BitWriter};
 
+#[cfg(feature = "io")]
 static MAGIC_NUMBER: &[u8] = b"CRAM";
@@ -1 +1,15 @@
+#[cfg(feature = "io")]
 pub mod itf8;
+mod read;
+mod write;
+
+#[cfg(feature = "io")]
+pub use self::{
+    read::{get_itf8, get_ltf8, read_ltf8},
+    write::write_ltf8,
+};
+
+pub use self::{
+    read::{read_itf8, read_uint7},
+    write::{write_itf8, write_uint7},
+};
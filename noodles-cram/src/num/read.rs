@@ -0,0 +1,12 @@
+mod itf8;
+#[cfg(feature = "io")]
+mod ltf8;
+mod vlq;
+
+#[cfg(feature = "io")]
+pub use self::{
+    itf8::get_itf8,
+    ltf8::{get_ltf8, read_ltf8},
+};
+
+pub use self::{itf8::read_itf8, vlq::read_uint7};
@@ -1,8 +1,10 @@
 use std::io::{self, Read};
 
 use byteorder::ReadBytesExt;
+#[cfg(feature = "io")]
 use bytes::Buf;
 
+#[cfg(feature = "io")]
 pub fn get_itf8<B>(src: &mut B) -> io::Result<i32>
 where
     B: Buf,
@@ -89,6 +91,7 @@ where
 mod tests {
     use super::*;
 
+    #[cfg(feature = "io")]
     #[test]
     fn test_get_itf8() -> io::Result<()> {
         fn t(mut data: &[u8], expected: i32) -> io::Result<()> {
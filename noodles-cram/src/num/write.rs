@@ -0,0 +1,9 @@
+mod itf8;
+#[cfg(feature = "io")]
+mod ltf8;
+mod vlq;
+
+#[cfg(feature = "io")]
+pub use self::ltf8::write_ltf8;
+
+pub use self::{itf8::write_itf8, vlq::write_uint7};
@@ -1,16 +1,21 @@
 //! CRAM reader and record iterator.
 
+mod builder;
 pub(crate) mod container;
 pub(crate) mod data_container;
 pub(crate) mod header_container;
-pub(crate) mod num;
 mod query;
 pub(crate) mod record;
 mod records;
 
-pub use self::records::Records;
+pub use self::{builder::Builder, records::Records};
 
-use std::io::{self, Read, Seek, SeekFrom};
+pub(crate) use crate::num;
+
+use std::{
+    io::{self, Read, Seek, SeekFrom},
+    num::NonZeroUsize,
+};
 
 use bytes::BytesMut;
 use noodles_core::Region;
@@ -18,7 +23,7 @@ use noodles_fasta as fasta;
 use noodles_sam as sam;
 
 pub use self::query::Query;
-use super::{crai, file_definition::Version, FileDefinition, MAGIC_NUMBER};
+use super::{crai, file_definition::Version, FileDefinition, Record, MAGIC_NUMBER};
 use crate::data_container::DataContainer;
 
 /// A CRAM reader.
@@ -50,6 +55,8 @@ use crate::data_container::DataContainer;
 pub struct Reader<R> {
     inner: R,
     buf: BytesMut,
+    version: Version,
+    worker_count: NonZeroUsize,
 }
 
 impl<R> Reader<R>
@@ -67,10 +74,7 @@ where
     /// # Ok::<(), io::Error>(())
     /// ```
     pub fn new(reader: R) -> Self {
-        Self {
-            inner: reader,
-            buf: BytesMut::new(),
-        }
+        Builder::default().build_from_reader(reader)
     }
 
     /// Returns a reference to the underlying reader.
@@ -121,6 +125,9 @@ where
     ///
     /// The position of the stream is expected to be at the start.
     ///
+    /// The format version is used to read the subsequent containers. CRAM 2.1, 3.0, and 3.1 are
+    /// supported.
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -136,6 +143,8 @@ where
         let format = read_format(&mut self.inner)?;
         let file_id = read_file_id(&mut self.inner)?;
 
+        self.version = format;
+
         Ok(FileDefinition::new(format, file_id))
     }
 
@@ -161,14 +170,34 @@ where
     /// ```
     pub fn read_file_header(&mut self) -> io::Result<String> {
         use self::header_container::read_header_container;
-        read_header_container(&mut self.inner, &mut self.buf)
+        read_header_container(&mut self.inner, &mut self.buf, self.version)
     }
 
-    pub(crate) fn read_data_container_with_container_header(
+    /// Reads a data container and its header.
+    ///
+    /// This is the same as [`Self::read_data_container`] but also returns the container header,
+    /// which is useful to inspect the layout of a CRAM file.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_cram as cram;
+    ///
+    /// let mut reader = File::open("sample.cram").map(cram::Reader::new)?;
+    /// reader.read_file_definition()?;
+    /// reader.read_file_header()?;
+    ///
+    /// while let Some((header, container)) = reader.read_data_container_with_container_header()? {
+    ///     println!("records: {}, slices: {}", header.record_count(), container.slices().len());
+    /// }
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn read_data_container_with_container_header(
         &mut self,
     ) -> io::Result<Option<(crate::data_container::Header, DataContainer)>> {
         use self::data_container::read_data_container_with_container_header;
-        read_data_container_with_container_header(&mut self.inner, &mut self.buf)
+        read_data_container_with_container_header(&mut self.inner, &mut self.buf, self.version)
     }
 
     /// Reads a data container.
@@ -194,7 +223,7 @@ where
     pub fn read_data_container(&mut self) -> io::Result<Option<DataContainer>> {
         use self::data_container::read_data_container;
 
-        read_data_container(&mut self.inner, &mut self.buf)
+        read_data_container(&mut self.inner, &mut self.buf, self.version)
     }
 
     /// Returns a iterator over records starting from the current stream position.
@@ -320,6 +349,59 @@ where
             region.interval(),
         ))
     }
+
+    /// Returns an iterator of unmapped records after querying for the unmapped region.
+    ///
+    /// Unplaced, unmapped records are stored in containers that are indexed without a reference
+    /// sequence ID. If the index has no such containers, the iterator is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_cram::{self as cram, crai};
+    /// use noodles_fasta as fasta;
+    ///
+    /// let mut reader = File::open("sample.cram").map(cram::Reader::new)?;
+    /// reader.read_file_definition()?;
+    ///
+    /// let repository = fasta::Repository::default();
+    /// let header = reader.read_file_header()?.parse()?;
+    /// let index = crai::read("sample.cram.crai")?;
+    /// let query = reader.query_unmapped(&repository, &header, &index)?;
+    ///
+    /// for result in query {
+    ///     let record = result?;
+    ///     // ...
+    /// }
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn query_unmapped<'a>(
+        &'a mut self,
+        reference_sequence_repository: &'a fasta::Repository,
+        header: &'a sam::Header,
+        index: &crai::Index,
+    ) -> io::Result<impl Iterator<Item = io::Result<Record>> + 'a> {
+        let records = match index
+            .iter()
+            .find(|record| record.reference_sequence_id().is_none())
+        {
+            Some(record) => {
+                self.seek(SeekFrom::Start(record.offset()))?;
+                Some(self.records(reference_sequence_repository, header))
+            }
+            None => None,
+        };
+
+        Ok(records.into_iter().flatten().filter(|result| {
+            result
+                .as_ref()
+                .map(|record| {
+                    record.flags().is_unmapped() && record.reference_sequence_id().is_none()
+                })
+                .unwrap_or(true)
+        }))
+    }
 }
 
 impl<R> sam::AlignmentReader<R> for Reader<R>
@@ -406,6 +488,114 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_records_with_cram_2_1() -> Result<(), Box<dyn std::error::Error>> {
+        #[rustfmt::skip]
+        let data = [
+            0x43, 0x52, 0x41, 0x4d, // magic number = b"CRAM"
+            0x02, 0x01, // format version = (2, 1)
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // file ID
+            // header container
+            0x11, 0x00, 0x00, 0x00, // length = 17 bytes
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, // container header fields
+            0x00, 0x00, 0x00, 0x0c, 0x0c, // file header block header
+            0x08, 0x00, 0x00, 0x00, 0x40, 0x48, 0x44, 0x09, 0x56, 0x4e, 0x3a,
+            0x31, // b"@HD\tVN:1"
+            // data container
+            0x6e, 0x01, 0x00, 0x00, // length = 366 bytes
+            0xff, 0xff, 0xff, 0xff, 0x0f, // reference sequence ID = None (-1)
+            0x00, // starting position on the reference = 0
+            0x00, // alignment span = 0
+            0x02, // number of records = 2
+            0xf1, 0x00, 0x00, 0x00, 0x00, // record counter = 268435456 (ITF8)
+            0x08, // bases = 8
+            0x10, // number of blocks = 16
+            0x01, // landmark count = 1
+            0x80, 0xb7, // landmarks[0] = 183
+            0x00, 0x01, 0x00, 0x80, 0xb0, 0x80, 0xb0, // compression header block header
+            0x15, 0x05, 0x52, 0x4e, 0x01, 0x41, 0x50, 0x01, 0x52, 0x52, 0x00, 0x53, 0x4d, 0x1b,
+            0x1b, 0x1b, 0x1b, 0x1b, 0x54, 0x44, 0x01, 0x00, 0x80, 0x96, 0x1c, 0x42, 0x46, 0x01,
+            0x01, 0x01, 0x43, 0x46, 0x01, 0x01, 0x02, 0x52, 0x49, 0x01, 0x01, 0x03, 0x52, 0x4c,
+            0x01, 0x01, 0x04, 0x41, 0x50, 0x01, 0x01, 0x05, 0x52, 0x47, 0x01, 0x01, 0x06, 0x52,
+            0x4e, 0x05, 0x02, 0x00, 0x07, 0x4d, 0x46, 0x01, 0x01, 0x08, 0x4e, 0x53, 0x01, 0x01,
+            0x09, 0x4e, 0x50, 0x01, 0x01, 0x0a, 0x54, 0x53, 0x01, 0x01, 0x0b, 0x4e, 0x46, 0x01,
+            0x01, 0x0c, 0x54, 0x4c, 0x01, 0x01, 0x0d, 0x46, 0x4e, 0x01, 0x01, 0x0e, 0x46, 0x43,
+            0x01, 0x01, 0x0f, 0x46, 0x50, 0x01, 0x01, 0x10, 0x44, 0x4c, 0x01, 0x01, 0x11, 0x42,
+            0x42, 0x05, 0x02, 0x00, 0x12, 0x51, 0x51, 0x04, 0x06, 0x01, 0x01, 0x13, 0x01, 0x01,
+            0x13, 0x42, 0x53, 0x01, 0x01, 0x14, 0x49, 0x4e, 0x05, 0x02, 0x00, 0x15, 0x52, 0x53,
+            0x01, 0x01, 0x16, 0x50, 0x44, 0x01, 0x01, 0x17, 0x48, 0x43, 0x01, 0x01, 0x18, 0x53,
+            0x43, 0x05, 0x02, 0x00, 0x19, 0x4d, 0x51, 0x01, 0x01, 0x1a, 0x42, 0x41, 0x01, 0x01,
+            0x1b, 0x51, 0x53, 0x01, 0x01, 0x1c, 0x01, 0x00,
+            0x00, 0x02, 0x00, 0x32, 0x32, // slice header block header
+            0xff, 0xff, 0xff, 0xff, 0x0f, 0x00, 0x00, 0x02, 0xf1, 0x00, 0x00, 0x00, 0x00, 0x0e,
+            0x0e, 0x00, 0x02, 0x0b, 0x0d, 0x04, 0x0a, 0x1b, 0x01, 0x05, 0x07, 0x09, 0x1c, 0x06,
+            0x08, 0xff, 0xff, 0xff, 0xff, 0x0f, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x05, 0x00, 0x00, 0x00, // core data block header
+            0x00, 0x04, 0x02, 0x02, 0x02, // external data (block content ID = 2) block header
+            0x03, 0x03,
+            0x00, 0x04, 0x0b, 0x02, 0x02, // external data (block content ID = 11) block header
+            0x00, 0x00,
+            0x00, 0x04, 0x0d, 0x02, 0x02, // external data (block content ID = 13) block header
+            0x00, 0x00,
+            0x00, 0x04, 0x04, 0x02, 0x02, // external data (block content ID = 4) block header
+            0x04, 0x04,
+            0x00, 0x04, 0x0a, 0x02, 0x02, // external data (block content ID = 10) block header
+            0x00, 0x00,
+            0x00, 0x04, 0x1b, 0x08, 0x08, // external data (block content ID = 27) block header
+            0x41, 0x43, 0x47, 0x54, 0x54, 0x47, 0x43, 0x41,
+            0x00, 0x04, 0x01, 0x02, 0x02, // external data (block content ID = 1) block header
+            0x04, 0x04,
+            0x00, 0x04, 0x05, 0x02, 0x02, // external data (block content ID = 5) block header
+            0x00, 0x00,
+            0x00, 0x04, 0x07, 0x06, 0x06, // external data (block content ID = 7) block header
+            0x72, 0x30, 0x00, 0x72, 0x31, 0x00,
+            0x00, 0x04, 0x09, 0x0a, 0x0a, // external data (block content ID = 9) block header
+            0xff, 0xff, 0xff, 0xff, 0x0f, 0xff, 0xff, 0xff, 0xff, 0x0f,
+            0x00, 0x04, 0x1c, 0x08, 0x08, // external data (block content ID = 28) block header
+            0x2d, 0x23, 0x2b, 0x32, 0x32, 0x2b, 0x23, 0x2d,
+            0x00, 0x04, 0x06, 0x0a, 0x0a, // external data (block content ID = 6) block header
+            0xff, 0xff, 0xff, 0xff, 0x0f, 0xff, 0xff, 0xff, 0xff, 0x0f,
+            0x00, 0x04, 0x08, 0x02, 0x02, // external data (block content ID = 8) block header
+            0x00, 0x00,
+            // EOF container
+            0x0b, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0x0f, 0xe0, 0x45, 0x4f, 0x46, 0x00,
+            0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x01, 0x00, 0x06, 0x06, 0x01, 0x00, 0x01, 0x00,
+            0x01, 0x00,
+        ];
+
+        let mut reader = Reader::new(&data[..]);
+
+        let file_definition = reader.read_file_definition()?;
+        assert_eq!(file_definition.version(), Version::new(2, 1));
+
+        let header = reader.read_file_header()?;
+        assert_eq!(header, "@HD\tVN:1");
+
+        let repository = fasta::Repository::default();
+        let header = sam::Header::default();
+        let records: Vec<_> = reader
+            .records(&repository, &header)
+            .collect::<io::Result<_>>()?;
+
+        assert_eq!(records.len(), 2);
+
+        for (i, (record, (read_name, bases, quality_scores))) in records
+            .iter()
+            .zip([("r0", "ACGT", "NDLS"), ("r1", "TGCA", "SLDN")])
+            .enumerate()
+        {
+            assert_eq!(record.id(), (1 << 28) + i as u64);
+            assert!(record.bam_flags().is_unmapped());
+            assert_eq!(record.read_name(), Some(&read_name.parse()?));
+            assert_eq!(record.bases(), &bases.parse()?);
+            assert_eq!(record.quality_scores(), &quality_scores.parse()?);
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn test_read_magic_number() {
         let data = b"CRAM";
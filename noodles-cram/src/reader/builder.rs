@@ -0,0 +1,94 @@
+use std::{
+    fs::File,
+    io::{self, Read},
+    num::NonZeroUsize,
+    path::Path,
+};
+
+use bytes::BytesMut;
+
+use super::Reader;
+use crate::file_definition::Version;
+
+const DEFAULT_WORKER_COUNT: NonZeroUsize = match NonZeroUsize::new(1) {
+    Some(worker_count) => worker_count,
+    None => unreachable!(),
+};
+
+/// A CRAM reader builder.
+#[derive(Debug)]
+pub struct Builder {
+    worker_count: NonZeroUsize,
+}
+
+impl Builder {
+    /// Sets the worker count.
+    ///
+    /// When the worker count is greater than 1, the record iterator ([`Reader::records`])
+    /// decompresses blocks and decodes slices in parallel on worker threads. Records are still
+    /// returned in order. By default, the worker count is set to 1, i.e., slices are decoded on
+    /// the calling thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    ///
+    /// use noodles_cram as cram;
+    ///
+    /// let worker_count = NonZeroUsize::try_from(4)?;
+    /// let builder = cram::reader::Builder::default().set_worker_count(worker_count);
+    /// # Ok::<_, std::num::TryFromIntError>(())
+    /// ```
+    pub fn set_worker_count(mut self, worker_count: NonZeroUsize) -> Self {
+        self.worker_count = worker_count;
+        self
+    }
+
+    /// Builds a CRAM reader from a path.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io;
+    /// use noodles_cram as cram;
+    /// let reader = cram::reader::Builder::default().build_from_path("sample.cram")?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn build_from_path<P>(self, src: P) -> io::Result<Reader<File>>
+    where
+        P: AsRef<Path>,
+    {
+        let file = File::open(src)?;
+        Ok(self.build_from_reader(file))
+    }
+
+    /// Builds a CRAM reader from a reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_cram as cram;
+    /// let reader = cram::reader::Builder::default().build_from_reader(io::empty());
+    /// ```
+    pub fn build_from_reader<R>(self, reader: R) -> Reader<R>
+    where
+        R: Read,
+    {
+        Reader {
+            inner: reader,
+            buf: BytesMut::new(),
+            version: Version::default(),
+            worker_count: self.worker_count,
+        }
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            worker_count: DEFAULT_WORKER_COUNT,
+        }
+    }
+}
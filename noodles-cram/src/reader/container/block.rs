@@ -7,10 +7,11 @@ use crate::{
         block::{CompressionMethod, ContentId, ContentType},
         Block,
     },
+    file_definition::Version,
     reader::num::get_itf8,
 };
 
-pub fn read_block(src: &mut Bytes) -> io::Result<Block> {
+pub fn read_block(src: &mut Bytes, version: Version) -> io::Result<Block> {
     let original_src = src.clone();
 
     let method = get_compression_method(src)?;
@@ -31,6 +32,20 @@ pub fn read_block(src: &mut Bytes) -> io::Result<Block> {
 
     let data = src.split_to(size_in_bytes);
 
+    if version.has_crc32() {
+        read_crc32(&original_src, src)?;
+    }
+
+    Ok(Block::builder()
+        .set_compression_method(method)
+        .set_content_type(block_content_type)
+        .set_content_id(block_content_id)
+        .set_uncompressed_len(raw_size_in_bytes)
+        .set_data(data)
+        .build())
+}
+
+fn read_crc32(original_src: &Bytes, src: &mut Bytes) -> io::Result<()> {
     if src.remaining() < mem::size_of::<u32>() {
         return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
     }
@@ -50,13 +65,7 @@ pub fn read_block(src: &mut Bytes) -> io::Result<Block> {
         ));
     }
 
-    Ok(Block::builder()
-        .set_compression_method(method)
-        .set_content_type(block_content_type)
-        .set_content_id(block_content_id)
-        .set_uncompressed_len(raw_size_in_bytes)
-        .set_data(data)
-        .build())
+    Ok(())
 }
 
 fn get_compression_method<B>(src: &mut B) -> io::Result<CompressionMethod>
@@ -131,7 +140,32 @@ mod tests {
             0x6e, 0x64, 0x6c, 0x73, // data = b"ndls",
             0xd7, 0x12, 0x46, 0x3e, // CRC32 = 3e4612d7
         ]);
-        let actual = read_block(&mut data)?;
+        let actual = read_block(&mut data, Version::default())?;
+
+        let expected = Block::builder()
+            .set_compression_method(CompressionMethod::None)
+            .set_content_type(ContentType::ExternalData)
+            .set_content_id(ContentId::from(1))
+            .set_uncompressed_len(4)
+            .set_data(Bytes::from_static(b"ndls"))
+            .build();
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_block_with_cram_2_1() -> io::Result<()> {
+        let mut data = Bytes::from_static(&[
+            0x00, // compression method = none (0)
+            0x04, // content type = external data (4)
+            0x01, // block content ID = 1
+            0x04, // size in bytes = 4 bytes
+            0x04, // raw size in bytes = 4 bytes
+            0x6e, 0x64, 0x6c, 0x73, // data = b"ndls",
+        ]);
+        let actual = read_block(&mut data, Version::new(2, 1))?;
 
         let expected = Block::builder()
             .set_compression_method(CompressionMethod::None)
@@ -142,6 +176,7 @@ mod tests {
             .build();
 
         assert_eq!(actual, expected);
+        assert!(data.is_empty());
 
         Ok(())
     }
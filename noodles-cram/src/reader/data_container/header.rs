@@ -9,17 +9,20 @@ use noodles_core::Position;
 
 use crate::{
     data_container::{Header, ReferenceSequenceContext},
+    file_definition::Version,
     reader::num::{read_itf8, read_ltf8},
 };
 
 // § 9 "End of file container" (2022-04-12)
 const EOF_LENGTH: usize = 15;
+// CRAM 2.1 EOF container blocks do not have a CRC32 checksum.
+const EOF_LENGTH_2_1: usize = 11;
 const EOF_REFERENCE_SEQUENCE_ID: i32 = -1;
 const EOF_ALIGNMENT_START: i32 = 4_542_278;
 const EOF_BLOCK_COUNT: usize = 1;
 const EOF_CRC32: u32 = 0x4f_d9_bd_05;
 
-pub fn read_header<R>(reader: &mut R) -> io::Result<Option<Header>>
+pub fn read_header<R>(reader: &mut R, version: Version) -> io::Result<Option<Header>>
 where
     R: Read,
 {
@@ -35,9 +38,7 @@ where
 
     let number_of_records = read_itf8(&mut crc_reader)?;
 
-    let record_counter = read_ltf8(&mut crc_reader).and_then(|n| {
-        u64::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
-    })?;
+    let record_counter = read_record_counter(&mut crc_reader, version)?;
 
     let bases = read_ltf8(&mut crc_reader).and_then(|n| {
         u64::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
@@ -49,27 +50,33 @@ where
 
     let landmarks = read_landmarks(&mut crc_reader)?;
 
-    let actual_crc32 = crc_reader.crc().sum();
+    let crc32 = if version.has_crc32() {
+        let actual_crc32 = crc_reader.crc().sum();
 
-    let reader = crc_reader.into_inner();
-    let expected_crc32 = reader.read_u32::<LittleEndian>()?;
+        let reader = crc_reader.into_inner();
+        let expected_crc32 = reader.read_u32::<LittleEndian>()?;
 
-    if actual_crc32 != expected_crc32 {
-        return Err(io::Error::new(
-            io::ErrorKind::InvalidData,
-            format!(
-                "container header checksum mismatch: expected {:08x}, got {:08x}",
-                expected_crc32, actual_crc32
-            ),
-        ));
-    }
+        if actual_crc32 != expected_crc32 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "container header checksum mismatch: expected {:08x}, got {:08x}",
+                    expected_crc32, actual_crc32
+                ),
+            ));
+        }
+
+        Some(expected_crc32)
+    } else {
+        None
+    };
 
     if is_eof(
         length,
         reference_sequence_id,
         alignment_start,
         number_of_blocks,
-        expected_crc32,
+        crc32,
     ) {
         return Ok(None);
     }
@@ -90,6 +97,20 @@ where
     Ok(Some(header))
 }
 
+fn read_record_counter<R>(reader: &mut R, version: Version) -> io::Result<u64>
+where
+    R: Read,
+{
+    // The record counter is an ITF8 in CRAM 2.x and an LTF8 in CRAM 3.x.
+    let n = if version.major() < 3 {
+        read_itf8(reader).map(i64::from)?
+    } else {
+        read_ltf8(reader)?
+    };
+
+    u64::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
 fn read_landmarks<R>(reader: &mut R) -> io::Result<Vec<usize>>
 where
     R: Read,
@@ -116,13 +137,17 @@ pub(crate) fn is_eof(
     reference_sequence_id: i32,
     alignment_start: i32,
     block_count: usize,
-    crc32: u32,
+    crc32: Option<u32>,
 ) -> bool {
-    length == EOF_LENGTH
+    let is_eof_length_and_crc32 = match crc32 {
+        Some(crc32) => length == EOF_LENGTH && crc32 == EOF_CRC32,
+        None => length == EOF_LENGTH_2_1,
+    };
+
+    is_eof_length_and_crc32
         && reference_sequence_id == EOF_REFERENCE_SEQUENCE_ID
         && alignment_start == EOF_ALIGNMENT_START
         && block_count == EOF_BLOCK_COUNT
-        && crc32 == EOF_CRC32
 }
 
 pub(crate) fn build_reference_sequence_context(
@@ -182,7 +207,7 @@ mod tests {
             0x21, 0xf7, 0x9c, 0xed, // CRC32
         ];
         let mut reader = &data[..];
-        let actual = read_header(&mut reader)?;
+        let actual = read_header(&mut reader, Version::default())?;
 
         let expected = Header::builder()
             .set_length(144)
@@ -218,7 +243,66 @@ mod tests {
             0x05, 0xbd, 0xd9, 0x4f, // CRC32
         ];
         let mut reader = &data[..];
-        let actual = read_header(&mut reader)?;
+        let actual = read_header(&mut reader, Version::default())?;
+
+        assert!(actual.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_header_with_cram_2_1() -> Result<(), Box<dyn std::error::Error>> {
+        let data = [
+            0x90, 0x00, 0x00, 0x00, // length = 144 bytes
+            0x02, // reference sequence ID = 2
+            0x03, // starting position on the reference = 3
+            0x05, // alignment span = 5
+            0x08, // number of records = 8
+            0xf1, 0x00, 0x00, 0x00, 0x00, // record counter = 268435456 (ITF8)
+            0x15, // bases = 21
+            0x22, // number of blocks = 34
+            0x02, // landmark count = 2
+            0x37, // landmarks[0] = 55
+            0x59, // landmarks[1] = 89
+        ];
+        let mut reader = &data[..];
+        let actual = read_header(&mut reader, Version::new(2, 1))?;
+
+        let expected = Header::builder()
+            .set_length(144)
+            .set_reference_sequence_context(ReferenceSequenceContext::some(
+                2,
+                Position::try_from(3)?,
+                Position::try_from(7)?,
+            ))
+            .set_record_count(8)
+            .set_record_counter(268_435_456)
+            .set_base_count(21)
+            .set_block_count(34)
+            .set_landmarks(vec![55, 89])
+            .build();
+
+        assert_eq!(actual, Some(expected));
+        assert!(reader.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_header_with_cram_2_1_eof() -> io::Result<()> {
+        let data = [
+            0x0b, 0x00, 0x00, 0x00, // length = 11 bytes
+            0xff, 0xff, 0xff, 0xff, 0x0f, // reference sequence ID = None (-1)
+            0xe0, 0x45, 0x4f, 0x46, // starting position on the reference = 4542278
+            0x00, // alignment span = 0
+            0x00, // number of records = 0
+            0x00, // record counter = 0
+            0x00, // bases = 0
+            0x01, // number of blocks = 1
+            0x00, // landmark count = 0
+        ];
+        let mut reader = &data[..];
+        let actual = read_header(&mut reader, Version::new(2, 1))?;
 
         assert!(actual.is_none());
 
@@ -243,7 +327,7 @@ mod tests {
         let mut reader = &data[..];
 
         assert!(matches!(
-            read_header(&mut reader),
+            read_header(&mut reader, Version::default()),
             Err(e) if e.kind() == io::ErrorKind::InvalidData,
         ));
     }
@@ -9,22 +9,23 @@ use bytes::Bytes;
 use crate::{
     container::{block::ContentType, Block},
     data_container::{slice, Slice},
+    file_definition::Version,
     reader::container::read_block,
 };
 
-pub fn read_slice(src: &mut Bytes) -> io::Result<Slice> {
-    let header = read_header_from_block(src)?;
+pub fn read_slice(src: &mut Bytes, version: Version) -> io::Result<Slice> {
+    let header = read_header_from_block(src, version)?;
 
-    let core_data_block = read_core_data_block(src)?;
+    let core_data_block = read_core_data_block(src, version)?;
 
     let external_block_count = header.block_count() - 1;
-    let external_blocks = read_external_blocks(src, external_block_count)?;
+    let external_blocks = read_external_blocks(src, external_block_count, version)?;
 
     Ok(Slice::new(header, core_data_block, external_blocks))
 }
 
-fn read_header_from_block(src: &mut Bytes) -> io::Result<slice::Header> {
-    let block = read_block(src)?;
+fn read_header_from_block(src: &mut Bytes, version: Version) -> io::Result<slice::Header> {
+    let block = read_block(src, version)?;
 
     if block.content_type() != ContentType::SliceHeader {
         return Err(io::Error::new(
@@ -38,11 +39,11 @@ fn read_header_from_block(src: &mut Bytes) -> io::Result<slice::Header> {
     }
 
     let mut data = block.decompressed_data()?;
-    get_header(&mut data)
+    get_header(&mut data, version)
 }
 
-fn read_core_data_block(src: &mut Bytes) -> io::Result<Block> {
-    let block = read_block(src)?;
+fn read_core_data_block(src: &mut Bytes, version: Version) -> io::Result<Block> {
+    let block = read_block(src, version)?;
 
     if block.content_type() != ContentType::CoreData {
         return Err(io::Error::new(
@@ -58,11 +59,11 @@ fn read_core_data_block(src: &mut Bytes) -> io::Result<Block> {
     Ok(block)
 }
 
-fn read_external_blocks(src: &mut Bytes, len: usize) -> io::Result<Vec<Block>> {
+fn read_external_blocks(src: &mut Bytes, len: usize, version: Version) -> io::Result<Vec<Block>> {
     let mut external_blocks = Vec::with_capacity(len);
 
     for _ in 0..len {
-        let block = read_block(src)?;
+        let block = read_block(src, version)?;
 
         if block.content_type() != ContentType::ExternalData {
             return Err(io::Error::new(
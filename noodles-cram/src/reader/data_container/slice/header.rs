@@ -6,10 +6,11 @@ use noodles_core::Position;
 use crate::{
     container::block,
     data_container::{slice, ReferenceSequenceContext},
+    file_definition::Version,
     reader::num::{get_itf8, get_ltf8},
 };
 
-pub fn get_header<B>(src: &mut B) -> io::Result<slice::Header>
+pub fn get_header<B>(src: &mut B, version: Version) -> io::Result<slice::Header>
 where
     B: Buf,
 {
@@ -19,9 +20,7 @@ where
         usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
     })?;
 
-    let record_counter = get_ltf8(src).and_then(|n| {
-        u64::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
-    })?;
+    let record_counter = get_record_counter(src, version)?;
 
     let block_count = get_itf8(src).and_then(|n| {
         usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
@@ -100,6 +99,20 @@ where
     }
 }
 
+fn get_record_counter<B>(src: &mut B, version: Version) -> io::Result<u64>
+where
+    B: Buf,
+{
+    // The record counter is an ITF8 in CRAM 2.x and an LTF8 in CRAM 3.x.
+    let n = if version.major() < 3 {
+        get_itf8(src).map(i64::from)?
+    } else {
+        get_ltf8(src)?
+    };
+
+    u64::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
 fn get_block_content_ids<B>(src: &mut B) -> io::Result<Vec<block::ContentId>>
 where
     B: Buf,
@@ -174,7 +187,7 @@ mod tests {
             0x7e, 0xf7, // reference MD5 (b"ACGTA")
         ];
         let mut reader = &data[..];
-        let actual = get_header(&mut reader)?;
+        let actual = get_header(&mut reader, Version::default())?;
 
         let expected = slice::Header::builder()
             .set_reference_sequence_context(ReferenceSequenceContext::some(
@@ -196,4 +209,24 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_get_record_counter() -> io::Result<()> {
+        let data = [0xff, 0xff, 0xff, 0xff, 0x0f];
+        let mut reader = &data[..];
+        assert!(matches!(
+            get_record_counter(&mut reader, Version::new(2, 1)),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        let data = [0xf0, 0x00, 0x00, 0x00, 0x0d];
+        let mut reader = &data[..];
+        assert_eq!(get_record_counter(&mut reader, Version::new(2, 1))?, 13);
+
+        let data = [0xe0, 0x00, 0x00, 0x0d];
+        let mut reader = &data[..];
+        assert_eq!(get_record_counter(&mut reader, Version::default())?, 13);
+
+        Ok(())
+    }
 }
@@ -8,27 +8,32 @@ use std::{
 use bytes::{Buf, Bytes, BytesMut};
 
 use self::header::read_header;
+use crate::file_definition::Version;
 
-pub fn read_header_container<R>(reader: &mut R, buf: &mut BytesMut) -> io::Result<String>
+pub fn read_header_container<R>(
+    reader: &mut R,
+    buf: &mut BytesMut,
+    version: Version,
+) -> io::Result<String>
 where
     R: Read,
 {
-    let len = read_header(reader)?;
+    let len = read_header(reader, version)?;
 
     buf.resize(len, 0);
     reader.read_exact(buf)?;
     let mut buf = buf.split().freeze();
 
-    read_raw_sam_header_from_block(&mut buf)
+    read_raw_sam_header_from_block(&mut buf, version)
 }
 
-pub fn read_raw_sam_header_from_block(src: &mut Bytes) -> io::Result<String> {
+pub fn read_raw_sam_header_from_block(src: &mut Bytes, version: Version) -> io::Result<String> {
     use super::container::read_block;
     use crate::container::block::ContentType;
 
     const EXPECTED_CONTENT_TYPE: ContentType = ContentType::FileHeader;
 
-    let block = read_block(src)?;
+    let block = read_block(src, version)?;
 
     if block.content_type() != EXPECTED_CONTENT_TYPE {
         return Err(io::Error::new(
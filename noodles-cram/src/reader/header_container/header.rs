@@ -3,9 +3,12 @@ use std::io::{self, Read};
 use byteorder::{LittleEndian, ReadBytesExt};
 use flate2::CrcReader;
 
-use crate::reader::num::{read_itf8, read_ltf8};
+use crate::{
+    file_definition::Version,
+    reader::num::{read_itf8, read_ltf8},
+};
 
-pub(super) fn read_header<R>(reader: &mut R) -> io::Result<usize>
+pub(super) fn read_header<R>(reader: &mut R, version: Version) -> io::Result<usize>
 where
     R: Read,
 {
@@ -40,6 +43,10 @@ where
 
     read_landmarks(&mut crc_reader)?;
 
+    if !version.has_crc32() {
+        return Ok(length);
+    }
+
     let actual_crc32 = crc_reader.crc().sum();
 
     let reader = crc_reader.into_inner();
@@ -1,9 +0,0 @@
-mod itf8;
-mod ltf8;
-mod vlq;
-
-pub use self::{
-    itf8::{get_itf8, read_itf8},
-    ltf8::{get_ltf8, read_ltf8},
-    vlq::read_uint7,
-};
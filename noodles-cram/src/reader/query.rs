@@ -1,9 +1,10 @@
 use std::{
+    collections::HashSet,
     io::{self, Read, Seek, SeekFrom},
     slice, vec,
 };
 
-use noodles_core::region::Interval;
+use noodles_core::{region::Interval, Position};
 use noodles_fasta as fasta;
 use noodles_sam as sam;
 
@@ -27,6 +28,9 @@ where
     reference_sequence_id: usize,
     interval: Interval,
 
+    // A container can have more than one index record, e.g., for each reference sequence in a
+    // multi-reference slice.
+    container_positions: HashSet<u64>,
     records: vec::IntoIter<Record>,
 }
 
@@ -53,6 +57,7 @@ where
             reference_sequence_id,
             interval,
 
+            container_positions: HashSet::new(),
             records: Vec::new().into_iter(),
         }
     }
@@ -60,7 +65,10 @@ where
     fn read_next_container(&mut self) -> Option<io::Result<()>> {
         let index_record = self.index.next()?;
 
-        if index_record.reference_sequence_id() != Some(self.reference_sequence_id) {
+        if index_record.reference_sequence_id() != Some(self.reference_sequence_id)
+            || !intersects(index_record, self.interval)
+            || !self.container_positions.insert(index_record.offset())
+        {
             return Some(Ok(()));
         }
 
@@ -118,6 +126,10 @@ where
         loop {
             match self.records.next() {
                 Some(r) => {
+                    if r.reference_sequence_id() != Some(self.reference_sequence_id) {
+                        continue;
+                    }
+
                     if let (Some(start), Some(end)) = (r.alignment_start(), r.alignment_end()) {
                         let alignment_interval = (start..=end).into();
 
@@ -135,3 +147,13 @@ where
         }
     }
 }
+
+fn intersects(index_record: &crai::Record, interval: Interval) -> bool {
+    let start = match index_record.alignment_start() {
+        Some(start) => start,
+        None => return true,
+    };
+
+    let end = usize::from(start) + index_record.alignment_span().max(1) - 1;
+    Position::new(end).map_or(true, |end| interval.intersects((start..=end).into()))
+}
@@ -1,5 +1,6 @@
 use std::{
     io::{self, Read},
+    sync::Arc,
     vec,
 };
 
@@ -7,7 +8,11 @@ use noodles_fasta as fasta;
 use noodles_sam as sam;
 
 use super::Reader;
-use crate::Record;
+use crate::{
+    data_container::{CompressionHeader, Slice},
+    worker_pool::WorkerPool,
+    Record,
+};
 
 /// An iterator over records of a CRAM reader.
 ///
@@ -20,6 +25,8 @@ where
     reference_sequence_repository: &'a fasta::Repository,
     header: &'a sam::Header,
     records: vec::IntoIter<Record>,
+    worker_pool: Option<(WorkerPool<Vec<Record>>, Arc<sam::Header>)>,
+    is_eof: bool,
 }
 
 impl<'a, R> Records<'a, R>
@@ -31,15 +38,28 @@ where
         reference_sequence_repository: &'a fasta::Repository,
         header: &'a sam::Header,
     ) -> Self {
+        let worker_pool = if reader.worker_count.get() == 1 {
+            None
+        } else {
+            let worker_pool = WorkerPool::new(reader.worker_count);
+            Some((worker_pool, Arc::new(header.clone())))
+        };
+
         Self {
             reader,
             reference_sequence_repository,
             header,
             records: Vec::new().into_iter(),
+            worker_pool,
+            is_eof: false,
         }
     }
 
     fn read_container_records(&mut self) -> io::Result<bool> {
+        if self.worker_pool.is_some() {
+            return self.read_pending_slice_records();
+        }
+
         let container = match self.reader.read_data_container()? {
             Some(c) => c,
             None => return Ok(true),
@@ -49,18 +69,12 @@ where
             .slices()
             .iter()
             .map(|slice| {
-                let compression_header = container.compression_header();
-
-                slice.records(compression_header).and_then(|mut records| {
-                    slice.resolve_records(
-                        self.reference_sequence_repository,
-                        self.header,
-                        compression_header,
-                        &mut records,
-                    )?;
-
-                    Ok(records)
-                })
+                read_slice_records(
+                    self.reference_sequence_repository,
+                    self.header,
+                    container.compression_header(),
+                    slice,
+                )
             })
             .collect::<Result<Vec<_>, _>>()?
             .into_iter()
@@ -70,6 +84,48 @@ where
 
         Ok(false)
     }
+
+    // Queues the slices of upcoming containers to be decoded on worker threads until the pool is
+    // full and then takes the records of the oldest slice.
+    fn read_pending_slice_records(&mut self) -> io::Result<bool> {
+        let (worker_pool, header) = self.worker_pool.as_mut().unwrap();
+
+        while !self.is_eof && !worker_pool.is_full() {
+            let container = match self.reader.read_data_container()? {
+                Some(c) => c,
+                None => {
+                    self.is_eof = true;
+                    break;
+                }
+            };
+
+            let compression_header = Arc::new(container.compression_header().clone());
+
+            for slice in container.slices() {
+                let reference_sequence_repository = self.reference_sequence_repository.clone();
+                let header = header.clone();
+                let compression_header = compression_header.clone();
+                let slice = slice.clone();
+
+                worker_pool.send(move || {
+                    read_slice_records(
+                        &reference_sequence_repository,
+                        &header,
+                        &compression_header,
+                        &slice,
+                    )
+                });
+            }
+        }
+
+        match worker_pool.recv() {
+            Some(result) => {
+                self.records = result?.into_iter();
+                Ok(false)
+            }
+            None => Ok(true),
+        }
+    }
 }
 
 impl<'a, R> Iterator for Records<'a, R>
@@ -91,3 +147,21 @@ where
         }
     }
 }
+
+fn read_slice_records(
+    reference_sequence_repository: &fasta::Repository,
+    header: &sam::Header,
+    compression_header: &CompressionHeader,
+    slice: &Slice,
+) -> io::Result<Vec<Record>> {
+    let mut records = slice.records(compression_header)?;
+
+    slice.resolve_records(
+        reference_sequence_repository,
+        header,
+        compression_header,
+        &mut records,
+    )?;
+
+    Ok(records)
+}
@@ -19,6 +19,7 @@ use noodles_core::Position;
 use noodles_sam::{
     self as sam,
     header::record::value::{map::ReferenceSequence, Map},
+    record::quality_scores::transform::Transforms,
 };
 
 /// A CRAM record.
@@ -217,6 +218,50 @@ impl Record {
     pub fn quality_scores(&self) -> &sam::record::QualityScores {
         &self.quality_scores
     }
+
+    /// Applies the quality scores transform for the record flags to the quality scores and the
+    /// quality score features.
+    pub(crate) fn apply_quality_scores_transforms(&mut self, transforms: &Transforms) {
+        use sam::record::quality_scores::Transform;
+
+        let transform = match transforms.get(self.bam_bit_flags) {
+            Some(transform) => transform,
+            None => return,
+        };
+
+        transform.apply(&mut self.quality_scores);
+
+        if self.quality_scores.is_empty() {
+            self.cram_bit_flags
+                .remove(Flags::QUALITY_SCORES_STORED_AS_ARRAY);
+        }
+
+        if let Transform::Drop = transform {
+            self.features.retain(|feature| {
+                !matches!(feature, Feature::Scores(..) | Feature::QualityScore(..))
+            });
+
+            return;
+        }
+
+        for feature in self.features.iter_mut() {
+            match feature {
+                Feature::Scores(_, scores) => {
+                    for score in scores.iter_mut() {
+                        if let Some(value) = transform.get(*score) {
+                            *score = value;
+                        }
+                    }
+                }
+                Feature::QualityScore(_, score) => {
+                    if let Some(value) = transform.get(*score) {
+                        *score = value;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
 }
 
 impl Default for Record {
@@ -281,4 +326,43 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_apply_quality_scores_transforms() -> Result<(), Box<dyn std::error::Error>> {
+        use sam::record::quality_scores::{transform::Bins, Score, Transform};
+
+        let mut record = Record::builder()
+            .set_bam_flags(sam::record::Flags::empty())
+            .set_flags(Flags::QUALITY_SCORES_STORED_AS_ARRAY)
+            .set_features(Features::from(vec![
+                Feature::QualityScore(Position::try_from(1)?, Score::try_from(13)?),
+                Feature::Scores(Position::try_from(2)?, vec![Score::try_from(31)?]),
+            ]))
+            .set_quality_scores("+5?I".parse()?)
+            .build();
+
+        let mut transforms = Transforms::default();
+        transforms.set_default(Transform::Bin(Bins::illumina_8_level()));
+        transforms.push(sam::record::Flags::UNMAPPED, Transform::Drop);
+
+        record.apply_quality_scores_transforms(&transforms);
+
+        assert_eq!(record.quality_scores().to_string(), "07BI");
+        assert_eq!(
+            record.features(),
+            &Features::from(vec![
+                Feature::QualityScore(Position::try_from(1)?, Score::try_from(15)?),
+                Feature::Scores(Position::try_from(2)?, vec![Score::try_from(33)?]),
+            ])
+        );
+
+        record.bam_bit_flags = sam::record::Flags::UNMAPPED;
+        record.apply_quality_scores_transforms(&transforms);
+
+        assert!(record.quality_scores().is_empty());
+        assert!(!record.cram_flags().are_quality_scores_stored_as_array());
+        assert!(record.features().is_empty());
+
+        Ok(())
+    }
 }
@@ -0,0 +1,207 @@
+use std::{
+    collections::VecDeque,
+    io,
+    num::NonZeroUsize,
+    thread::{self, JoinHandle},
+};
+
+use crossbeam_channel::{Receiver, Sender};
+
+type Job<T> = Box<dyn FnOnce() -> io::Result<T> + Send>;
+type ResultTx<T> = Sender<io::Result<T>>;
+type ResultRx<T> = Receiver<io::Result<T>>;
+type JobTx<T> = Sender<(Job<T>, ResultTx<T>)>;
+type JobRx<T> = Receiver<(Job<T>, ResultTx<T>)>;
+
+/// A pool of workers that runs jobs in parallel.
+///
+/// Results are returned in the order their jobs were sent.
+#[derive(Debug)]
+pub(crate) struct WorkerPool<T> {
+    job_tx: Option<JobTx<T>>,
+    handles: Vec<JoinHandle<()>>,
+    queue: VecDeque<ResultRx<T>>,
+    capacity: usize,
+}
+
+impl<T> WorkerPool<T>
+where
+    T: Send + 'static,
+{
+    pub(crate) fn new(worker_count: NonZeroUsize) -> Self {
+        let worker_count = worker_count.get();
+
+        let (job_tx, job_rx) = crossbeam_channel::bounded(worker_count);
+        let handles = spawn_workers(worker_count, job_rx);
+
+        Self {
+            job_tx: Some(job_tx),
+            handles,
+            queue: VecDeque::with_capacity(worker_count),
+            capacity: worker_count,
+        }
+    }
+
+    /// Returns whether the number of pending results is at capacity.
+    pub(crate) fn is_full(&self) -> bool {
+        self.queue.len() >= self.capacity
+    }
+
+    /// Returns a spawner that queues jobs on this pool's workers.
+    ///
+    /// Results of spawned jobs are not added to the pool's ordered results. They are instead
+    /// received using the returned [`Pending`] handles.
+    pub(crate) fn spawner(&self) -> Spawner<T> {
+        Spawner {
+            job_tx: self.job_tx.clone().unwrap(),
+        }
+    }
+
+    /// Queues a job to be run on a worker.
+    pub(crate) fn send<F>(&mut self, f: F)
+    where
+        F: FnOnce() -> io::Result<T> + Send + 'static,
+    {
+        let Pending(result_rx) = send_job(self.job_tx.as_ref().unwrap(), f);
+        self.queue.push_back(result_rx);
+    }
+
+    /// Removes the oldest result, waiting for its job to finish if necessary.
+    pub(crate) fn recv(&mut self) -> Option<io::Result<T>> {
+        self.queue
+            .pop_front()
+            .map(|result_rx| Pending(result_rx).wait())
+    }
+}
+
+/// A handle that queues jobs on the workers of a [`WorkerPool`].
+///
+/// All spawners must be dropped before their pool is dropped, as the pool waits for its job
+/// queue to close.
+#[derive(Debug)]
+pub(crate) struct Spawner<T> {
+    job_tx: JobTx<T>,
+}
+
+impl<T> Spawner<T>
+where
+    T: Send + 'static,
+{
+    /// Queues a job to be run on a worker.
+    pub(crate) fn spawn<F>(&self, f: F) -> Pending<T>
+    where
+        F: FnOnce() -> io::Result<T> + Send + 'static,
+    {
+        send_job(&self.job_tx, f)
+    }
+}
+
+impl<T> Clone for Spawner<T> {
+    fn clone(&self) -> Self {
+        Self {
+            job_tx: self.job_tx.clone(),
+        }
+    }
+}
+
+/// The pending result of a spawned job.
+#[derive(Debug)]
+pub(crate) struct Pending<T>(ResultRx<T>);
+
+impl<T> Pending<T> {
+    /// Waits for the job to finish and returns its result.
+    pub(crate) fn wait(self) -> io::Result<T> {
+        self.0.recv().unwrap_or_else(|_| {
+            Err(io::Error::new(
+                io::ErrorKind::BrokenPipe,
+                "worker disconnected",
+            ))
+        })
+    }
+}
+
+fn send_job<T, F>(job_tx: &JobTx<T>, f: F) -> Pending<T>
+where
+    F: FnOnce() -> io::Result<T> + Send + 'static,
+{
+    let (result_tx, result_rx) = crossbeam_channel::bounded(1);
+    job_tx.send((Box::new(f), result_tx)).unwrap();
+    Pending(result_rx)
+}
+
+impl<T> Drop for WorkerPool<T> {
+    fn drop(&mut self) {
+        self.job_tx.take();
+
+        for handle in self.handles.drain(..) {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn spawn_workers<T>(worker_count: usize, job_rx: JobRx<T>) -> Vec<JoinHandle<()>>
+where
+    T: Send + 'static,
+{
+    let mut handles = Vec::with_capacity(worker_count);
+
+    for _ in 0..worker_count {
+        let job_rx = job_rx.clone();
+
+        handles.push(thread::spawn(move || {
+            while let Ok((job, result_tx)) = job_rx.recv() {
+                if result_tx.send(job()).is_err() {
+                    continue;
+                }
+            }
+        }));
+    }
+
+    handles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recv() -> Result<(), Box<dyn std::error::Error>> {
+        let worker_count = NonZeroUsize::try_from(2)?;
+        let mut pool = WorkerPool::new(worker_count);
+
+        assert!(!pool.is_full());
+
+        pool.send(|| {
+            thread::sleep(std::time::Duration::from_millis(10));
+            Ok(0)
+        });
+        pool.send(|| Ok(1));
+
+        assert!(pool.is_full());
+
+        assert_eq!(pool.recv().transpose()?, Some(0));
+        assert_eq!(pool.recv().transpose()?, Some(1));
+        assert!(pool.recv().is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_spawn() -> Result<(), Box<dyn std::error::Error>> {
+        let worker_count = NonZeroUsize::try_from(2)?;
+        let pool = WorkerPool::new(worker_count);
+        let spawner = pool.spawner();
+
+        let pending: Vec<_> = (0..8).map(|i| spawner.spawn(move || Ok(i))).collect();
+        assert!(!pool.is_full());
+
+        let results = pending
+            .into_iter()
+            .map(|p| p.wait())
+            .collect::<io::Result<Vec<_>>>()?;
+
+        assert_eq!(results, (0..8).collect::<Vec<_>>());
+
+        Ok(())
+    }
+}
@@ -1,30 +1,46 @@
 //! CRAM writer.
 
+mod block_content_encoder_selector;
 mod builder;
-mod container;
+mod compression_profile;
+pub(crate) mod container;
 pub(crate) mod data_container;
-mod header_container;
-pub(crate) mod num;
+pub(crate) mod header_container;
 mod options;
 pub(crate) mod record;
+mod workers;
 
-pub use self::builder::Builder;
-pub(crate) use self::options::Options;
+#[cfg(feature = "async")]
+pub(crate) use self::builder::finalize_options;
+pub(crate) use self::{
+    block_content_encoder_selector::BlockContentEncoderSelector, options::Options,
+};
+pub use self::{builder::Builder, compression_profile::CompressionProfile};
+
+pub(crate) use crate::num;
 
 use std::{
     io::{self, Write},
     mem,
+    sync::Arc,
 };
 
 use noodles_fasta as fasta;
 use noodles_sam::{self as sam, header::ReferenceSequences};
 
-use super::{file_definition::Version, DataContainer, FileDefinition, Record, MAGIC_NUMBER};
+use self::workers::Workers;
+use super::{file_definition::Version, FileDefinition, Record, MAGIC_NUMBER};
+
+pub(crate) const PROGRAM_NAME: &str = "noodles-cram";
 
 /// A CRAM writer.
 ///
 /// A call to [`Self::try_finish`] must be made before the writer is dropped.
 ///
+/// When the writer is built with a worker count greater than 1 (see
+/// [`Builder::set_worker_count`]), slices are encoded and their blocks are compressed in parallel
+/// on worker threads. Data containers are still written in order.
+///
 /// # Examples
 ///
 /// ```
@@ -54,6 +70,8 @@ where
     options: Options,
     data_container_builder: crate::data_container::Builder,
     record_counter: u64,
+    workers: Option<Workers>,
+    header: Option<Arc<sam::Header>>,
 }
 
 impl<W> Writer<W>
@@ -105,7 +123,10 @@ where
     /// ```
     pub fn try_finish(&mut self, header: &sam::Header) -> io::Result<()> {
         use self::container::write_eof_container;
+
         self.flush(header)?;
+        self.write_pending_data_containers()?;
+
         write_eof_container(&mut self.inner)
     }
 
@@ -143,7 +164,8 @@ where
     /// The position of the stream is expected to be directly after the file definition.
     ///
     /// Entries in the reference sequence dictionary that are missing MD5 checksums (`M5`) will
-    /// automatically be calculated and added to the written record.
+    /// automatically be calculated and added to the written record, unless an external reference
+    /// is not required (see [`Builder::require_reference_sequences`]).
     ///
     /// # Examples
     ///
@@ -162,16 +184,30 @@ where
     /// # Ok::<(), io::Error>(())
     /// ```
     pub fn write_file_header(&mut self, header: &sam::Header) -> io::Result<()> {
-        use self::header_container::write_header_container;
+        use self::header_container::{validate_reference_sequences, write_header_container};
 
         let mut header = header.clone();
 
-        add_missing_reference_sequence_checksums(
-            &self.reference_sequence_repository,
-            header.reference_sequences_mut(),
-        )?;
+        if self.options.uses_reference_sequences() {
+            add_missing_reference_sequence_checksums(
+                &self.reference_sequence_repository,
+                header.reference_sequences_mut(),
+            )?;
 
-        write_header_container(&mut self.inner, &header)
+            validate_reference_sequences(header.reference_sequences())?;
+        }
+
+        if !self.options.quality_scores_transforms.is_empty() {
+            self.options
+                .quality_scores_transforms
+                .add_program(&mut header, PROGRAM_NAME, env!("CARGO_PKG_VERSION"))
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        }
+
+        write_header_container(&mut self.inner, &header)?;
+        self.header = Some(Arc::new(header));
+
+        Ok(())
     }
 
     /// Writes a CRAM record.
@@ -200,8 +236,13 @@ where
     pub fn write_record(&mut self, header: &sam::Header, mut record: Record) -> io::Result<()> {
         use super::data_container::builder::AddRecordError;
 
+        record.apply_quality_scores_transforms(&self.options.quality_scores_transforms);
+
         loop {
-            match self.data_container_builder.add_record(record) {
+            match self
+                .data_container_builder
+                .add_record(&self.options, record)
+            {
                 Ok(_) => {
                     self.record_counter += 1;
                     return Ok(());
@@ -227,11 +268,32 @@ where
             return Ok(());
         }
 
+        let next_data_container_builder =
+            self.data_container_builder.successor(self.record_counter);
         let data_container_builder = mem::replace(
             &mut self.data_container_builder,
-            DataContainer::builder(self.record_counter),
+            next_data_container_builder,
         );
 
+        if let Some(workers) = self.workers.as_mut() {
+            // The header written by `write_file_header` is shared with the workers. If it was not
+            // called, the given header is copied once.
+            let header = self.header.get_or_insert_with(|| Arc::new(header.clone()));
+
+            workers.send(
+                data_container_builder,
+                &self.options,
+                &self.reference_sequence_repository,
+                header,
+            );
+
+            while workers.is_full() {
+                workers.write_pending_data_container(&mut self.inner)?;
+            }
+
+            return Ok(());
+        }
+
         let base_count = data_container_builder.base_count();
 
         let data_container = data_container_builder.build(
@@ -242,6 +304,14 @@ where
 
         write_data_container(&mut self.inner, &data_container, base_count)
     }
+
+    fn write_pending_data_containers(&mut self) -> io::Result<()> {
+        if let Some(workers) = self.workers.as_mut() {
+            while workers.write_pending_data_container(&mut self.inner)? {}
+        }
+
+        Ok(())
+    }
 }
 
 impl<W> sam::AlignmentWriter for Writer<W>
@@ -268,7 +338,29 @@ where
     }
 }
 
-fn write_file_definition<W>(writer: &mut W, file_definition: &FileDefinition) -> io::Result<()>
+pub(crate) fn encode_data_container(
+    data_container_builder: crate::data_container::Builder,
+    options: &Options,
+    reference_sequence_repository: &fasta::Repository,
+    header: &sam::Header,
+) -> io::Result<Vec<u8>> {
+    use self::data_container::write_data_container;
+
+    let base_count = data_container_builder.base_count();
+
+    let data_container =
+        data_container_builder.build(options, reference_sequence_repository, header)?;
+
+    let mut buf = Vec::new();
+    write_data_container(&mut buf, &data_container, base_count)?;
+
+    Ok(buf)
+}
+
+pub(crate) fn write_file_definition<W>(
+    writer: &mut W,
+    file_definition: &FileDefinition,
+) -> io::Result<()>
 where
     W: Write,
 {
@@ -286,7 +378,7 @@ where
     writer.write_all(&format)
 }
 
-fn add_missing_reference_sequence_checksums(
+pub(crate) fn add_missing_reference_sequence_checksums(
     reference_sequence_repository: &fasta::Repository,
     reference_sequences: &mut ReferenceSequences,
 ) -> io::Result<()> {
@@ -299,7 +391,12 @@ fn add_missing_reference_sequence_checksums(
             let sequence = reference_sequence_repository
                 .get(reference_sequence.name().as_str())
                 .transpose()?
-                .expect("missing reference sequence");
+                .ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("missing reference sequence: {}", reference_sequence.name()),
+                    )
+                })?;
 
             let checksum = calculate_normalized_sequence_digest(&sequence[..]);
 
@@ -356,4 +453,621 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_write_with_multiple_workers() -> Result<(), Box<dyn std::error::Error>> {
+        use std::num::NonZeroUsize;
+
+        use fasta::record::{Definition, Sequence};
+        use noodles_core::Position;
+        use sam::header::record::value::{map::ReferenceSequence, Map};
+
+        const RECORD_COUNT: usize = 25000;
+
+        let repository = fasta::Repository::new(vec![fasta::Record::new(
+            Definition::new("sq0", None),
+            Sequence::from(b"TTCACCCA".to_vec()),
+        )]);
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(Map::<ReferenceSequence>::new("sq0".parse()?, 8)?)
+            .build();
+
+        let worker_count = NonZeroUsize::try_from(2)?;
+
+        let mut writer = Builder::default()
+            .set_reference_sequence_repository(repository.clone())
+            .set_worker_count(worker_count)
+            .build_with_writer(Vec::new());
+
+        writer.write_file_definition()?;
+        writer.write_file_header(&header)?;
+
+        for i in 0..RECORD_COUNT {
+            let record = Record::builder()
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(i % 4 + 1)?)
+                .set_read_length(4)
+                .set_bases("CACC".parse()?)
+                .build();
+
+            writer.write_record(&header, record)?;
+        }
+
+        writer.try_finish(&header)?;
+
+        let data = writer.get_ref();
+
+        let mut reader = crate::reader::Builder::default()
+            .set_worker_count(worker_count)
+            .build_from_reader(&data[..]);
+
+        reader.read_file_definition()?;
+        reader.read_file_header()?;
+
+        let records: Vec<_> = reader
+            .records(&repository, &header)
+            .collect::<io::Result<_>>()?;
+
+        assert_eq!(records.len(), RECORD_COUNT);
+
+        for (i, record) in records.iter().enumerate() {
+            assert_eq!(record.id(), i as u64);
+            assert_eq!(
+                record.alignment_start(),
+                Some(Position::try_from(i % 4 + 1)?)
+            );
+        }
+
+        let mut reader = crate::Reader::new(&data[..]);
+        reader.read_file_definition()?;
+        reader.read_file_header()?;
+
+        let expected: Vec<_> = reader
+            .records(&repository, &header)
+            .collect::<io::Result<_>>()?;
+
+        assert_eq!(records, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_with_multiple_workers_and_slices() -> Result<(), Box<dyn std::error::Error>> {
+        use std::num::NonZeroUsize;
+
+        use fasta::record::{Definition, Sequence};
+        use noodles_core::Position;
+        use sam::header::record::value::{map::ReferenceSequence, Map};
+
+        const RECORD_COUNT: usize = 10000;
+
+        let repository = fasta::Repository::new(vec![fasta::Record::new(
+            Definition::new("sq0", None),
+            Sequence::from(b"TTCACCCA".to_vec()),
+        )]);
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(Map::<ReferenceSequence>::new("sq0".parse()?, 8)?)
+            .build();
+
+        let write = |worker_count| -> io::Result<Vec<u8>> {
+            let mut writer = Builder::default()
+                .set_reference_sequence_repository(repository.clone())
+                .set_records_per_slice(NonZeroUsize::try_from(1000).unwrap())
+                .set_slices_per_container(NonZeroUsize::try_from(4).unwrap())
+                .set_worker_count(NonZeroUsize::try_from(worker_count).unwrap())
+                .build_with_writer(Vec::new());
+
+            writer.write_file_definition()?;
+            writer.write_file_header(&header)?;
+
+            for i in 0..RECORD_COUNT {
+                let record = Record::builder()
+                    .set_reference_sequence_id(0)
+                    .set_alignment_start(Position::try_from(i % 4 + 1).unwrap())
+                    .set_read_length(4)
+                    .set_bases("CACC".parse().unwrap())
+                    .build();
+
+                writer.write_record(&header, record)?;
+            }
+
+            writer.try_finish(&header)?;
+
+            Ok(writer.get_ref().clone())
+        };
+
+        let read = |data: &[u8]| -> io::Result<Vec<Record>> {
+            let mut reader = crate::Reader::new(data);
+            reader.read_file_definition()?;
+            reader.read_file_header()?;
+            reader.records(&repository, &header).collect()
+        };
+
+        let actual = read(&write(3)?)?;
+        let expected = read(&write(1)?)?;
+
+        assert_eq!(actual.len(), RECORD_COUNT);
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_with_multi_reference_slices() -> Result<(), Box<dyn std::error::Error>> {
+        use std::num::NonZeroUsize;
+
+        use noodles_core::Position;
+        use sam::header::record::value::{map::ReferenceSequence, Map};
+
+        use crate::data_container::ReferenceSequenceContext;
+
+        let mut header_builder = sam::Header::builder();
+
+        for i in 0..7 {
+            let name = format!("sq{}", i);
+            header_builder = header_builder
+                .add_reference_sequence(Map::<ReferenceSequence>::new(name.parse()?, 8)?);
+        }
+
+        let header = header_builder.build();
+
+        let mapped_record = |reference_sequence_id, i: usize| -> io::Result<Record> {
+            let alignment_start = Position::try_from(i % 4 + 1)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+            Ok(Record::builder()
+                .set_bam_flags(sam::record::Flags::empty())
+                .set_reference_sequence_id(reference_sequence_id)
+                .set_alignment_start(alignment_start)
+                .set_read_length(4)
+                .set_bases("ACGT".parse().unwrap())
+                .build())
+        };
+
+        // sq0 has 40 records; sq1 to sq6, 2 records each; and 3 records are unmapped.
+        let mut records = Vec::new();
+
+        for i in 0..40 {
+            records.push(mapped_record(0, i)?);
+        }
+
+        for reference_sequence_id in 1..7 {
+            for i in 0..2 {
+                records.push(mapped_record(reference_sequence_id, i)?);
+            }
+        }
+
+        for _ in 0..3 {
+            records.push(
+                Record::builder()
+                    .set_bam_flags(sam::record::Flags::UNMAPPED)
+                    .set_read_length(4)
+                    .set_bases("ACGT".parse()?)
+                    .build(),
+            );
+        }
+
+        let mut writer = Builder::default()
+            .require_reference_sequences(false)
+            .set_records_per_slice(NonZeroUsize::try_from(20)?)
+            .build_with_writer(Vec::new());
+
+        writer.write_file_definition()?;
+        writer.write_file_header(&header)?;
+
+        for record in records.iter().cloned() {
+            writer.write_record(&header, record)?;
+        }
+
+        writer.try_finish(&header)?;
+
+        let data = writer.get_ref();
+
+        let mut reader = crate::Reader::new(io::Cursor::new(&data[..]));
+        reader.read_file_definition()?;
+        reader.read_file_header()?;
+
+        let mut contexts = Vec::new();
+
+        while let Some((container_header, container)) =
+            reader.read_data_container_with_container_header()?
+        {
+            let record_count: usize = container
+                .slices()
+                .iter()
+                .map(|slice| slice.header().record_count())
+                .sum();
+
+            contexts.push((container_header.reference_sequence_context(), record_count));
+        }
+
+        let context = |id, end| -> Result<_, Box<dyn std::error::Error>> {
+            Ok(ReferenceSequenceContext::some(
+                id,
+                Position::try_from(1)?,
+                Position::try_from(end)?,
+            ))
+        };
+
+        assert_eq!(
+            contexts,
+            [
+                (context(0, 7)?, 20),
+                (context(0, 7)?, 20),
+                (context(1, 5)?, 2),
+                (ReferenceSequenceContext::Many, 10),
+                (ReferenceSequenceContext::None, 3),
+            ]
+        );
+
+        let mut reader = crate::Reader::new(io::Cursor::new(&data[..]));
+        let index = crate::indexer::build_index(&mut reader)?;
+
+        let reference_sequence_ids: Vec<_> = index
+            .iter()
+            .map(|record| record.reference_sequence_id())
+            .collect();
+
+        assert_eq!(
+            reference_sequence_ids,
+            [
+                Some(0),
+                Some(0),
+                Some(1),
+                Some(2),
+                Some(3),
+                Some(4),
+                Some(5),
+                Some(6),
+                None
+            ]
+        );
+
+        let repository = fasta::Repository::default();
+
+        for (i, name) in ["sq0", "sq1", "sq2", "sq3", "sq6"].iter().enumerate() {
+            let region = name.parse()?;
+
+            let actual: Vec<_> = reader
+                .query(&repository, &header, &index, &region)?
+                .collect::<io::Result<_>>()?;
+
+            let expected_len = if i == 0 { 40 } else { 2 };
+            assert_eq!(actual.len(), expected_len);
+
+            let expected_id = header.reference_sequences().get_index_of(*name);
+            assert!(actual
+                .iter()
+                .all(|record| record.reference_sequence_id() == expected_id));
+        }
+
+        let region = "sq0:6-8".parse()?;
+        let actual = reader
+            .query(&repository, &header, &index, &region)?
+            .collect::<io::Result<Vec<_>>>()?;
+        assert_eq!(actual.len(), 20);
+
+        let actual = reader
+            .query_unmapped(&repository, &header, &index)?
+            .collect::<io::Result<Vec<_>>>()?;
+        assert_eq!(actual.len(), 3);
+
+        Ok(())
+    }
+
+    fn write_and_read_records(
+        builder: Builder,
+        reference_sequence_repository: &fasta::Repository,
+    ) -> Result<(Vec<Record>, Vec<Record>), Box<dyn std::error::Error>> {
+        use noodles_core::Position;
+        use sam::{
+            header::record::value::{map::ReferenceSequence, Map},
+            record::sequence::Base,
+        };
+
+        use crate::record::{
+            feature::{substitution, Feature},
+            Features,
+        };
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(Map::<ReferenceSequence>::new("sq0".parse()?, 8)?)
+            .build();
+
+        // sq0 = TTCACCCA
+        let records = vec![
+            Record::builder()
+                .set_bam_flags(sam::record::Flags::empty())
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(2)?)
+                .set_read_length(4)
+                .set_bases("TCAC".parse()?)
+                .build(),
+            // 1X
+            Record::builder()
+                .set_bam_flags(sam::record::Flags::empty())
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(2)?)
+                .set_read_length(4)
+                .set_bases("TGAC".parse()?)
+                .set_features(Features::from(vec![Feature::Substitution(
+                    Position::try_from(2)?,
+                    substitution::Value::Bases(substitution::Base::C, substitution::Base::G),
+                )]))
+                .build(),
+            // 2M2I2M
+            Record::builder()
+                .set_bam_flags(sam::record::Flags::empty())
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(3)?)
+                .set_read_length(6)
+                .set_bases("CAGGCC".parse()?)
+                .set_features(Features::from(vec![Feature::Insertion(
+                    Position::try_from(3)?,
+                    vec![Base::G, Base::G],
+                )]))
+                .build(),
+            // 2M1D2M
+            Record::builder()
+                .set_bam_flags(sam::record::Flags::empty())
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(3)?)
+                .set_read_length(4)
+                .set_bases("CACC".parse()?)
+                .set_features(Features::from(vec![Feature::Deletion(
+                    Position::try_from(3)?,
+                    1,
+                )]))
+                .build(),
+            Record::builder()
+                .set_bam_flags(sam::record::Flags::empty())
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(4)?)
+                .set_read_length(4)
+                .set_bases("ACCC".parse()?)
+                .build(),
+        ];
+
+        let mut writer = builder.build_with_writer(Vec::new());
+        writer.write_file_definition()?;
+        writer.write_file_header(&header)?;
+
+        for record in records.iter().cloned() {
+            writer.write_record(&header, record)?;
+        }
+
+        writer.try_finish(&header)?;
+
+        let mut reader = crate::Reader::new(&writer.get_ref()[..]);
+        reader.read_file_definition()?;
+        reader.read_file_header()?;
+
+        let actual = reader
+            .records(reference_sequence_repository, &header)
+            .collect::<io::Result<_>>()?;
+
+        Ok((actual, records))
+    }
+
+    #[test]
+    fn test_write_with_embedded_reference_sequences() -> Result<(), Box<dyn std::error::Error>> {
+        use fasta::record::{Definition, Sequence};
+
+        let repository = fasta::Repository::new(vec![fasta::Record::new(
+            Definition::new("sq0", None),
+            Sequence::from(b"TTCACCCA".to_vec()),
+        )]);
+
+        let builder = Builder::default()
+            .set_reference_sequence_repository(repository)
+            .embed_reference_sequences(true);
+
+        let (actual, expected) = write_and_read_records(builder, &fasta::Repository::default())?;
+
+        assert_eq!(actual.len(), expected.len());
+
+        for (a, b) in actual.iter().zip(&expected) {
+            assert_eq!(a.bases(), b.bases());
+            assert_eq!(a.alignment_start(), b.alignment_start());
+            assert_eq!(a.alignment_end(), b.alignment_end());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_with_embedded_reference_sequences_and_missing_reference_sequence(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let builder = Builder::default().embed_reference_sequences(true);
+
+        assert!(matches!(
+            write_and_read_records(builder, &fasta::Repository::default()),
+            Err(e) if e
+                .downcast_ref::<io::Error>()
+                .map(|e| e.kind() == io::ErrorKind::InvalidInput)
+                .unwrap_or_default()
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_without_reference_sequences() -> Result<(), Box<dyn std::error::Error>> {
+        let builder = Builder::default().require_reference_sequences(false);
+
+        let (actual, expected) = write_and_read_records(builder, &fasta::Repository::default())?;
+
+        assert_eq!(actual.len(), expected.len());
+
+        for (a, b) in actual.iter().zip(&expected) {
+            assert_eq!(a.bases(), b.bases());
+            assert_eq!(a.alignment_start(), b.alignment_start());
+            assert_eq!(a.alignment_end(), b.alignment_end());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_with_compression_profiles() -> Result<(), Box<dyn std::error::Error>> {
+        use std::num::NonZeroUsize;
+
+        use noodles_core::Position;
+        use sam::header::record::value::{map::ReferenceSequence, Map};
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(Map::<ReferenceSequence>::new("sq0".parse()?, 8)?)
+            .build();
+
+        let mut records = Vec::new();
+
+        for i in 0..9 {
+            let record = Record::builder()
+                .set_bam_flags(sam::record::Flags::empty())
+                .set_flags(crate::record::Flags::QUALITY_SCORES_STORED_AS_ARRAY)
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(i % 4 + 1)?)
+                .set_read_name(format!("r{}", i).parse()?)
+                .set_read_length(4)
+                .set_bases("ACGT".parse()?)
+                .set_quality_scores("NDLS".parse()?)
+                .build();
+
+            records.push(record);
+        }
+
+        for compression_profile in [
+            CompressionProfile::Fast,
+            CompressionProfile::Normal,
+            CompressionProfile::Small,
+            CompressionProfile::Archive,
+        ] {
+            for adaptive_compression in [false, true] {
+                let mut writer = Builder::default()
+                    .require_reference_sequences(false)
+                    .set_compression_profile(compression_profile)
+                    .set_records_per_slice(NonZeroUsize::try_from(2)?)
+                    .set_slices_per_container(NonZeroUsize::try_from(2)?)
+                    .adaptive_compression(adaptive_compression)
+                    .build_with_writer(Vec::new());
+
+                writer.write_file_definition()?;
+                writer.write_file_header(&header)?;
+
+                for record in records.iter().cloned() {
+                    writer.write_record(&header, record)?;
+                }
+
+                writer.try_finish(&header)?;
+
+                let mut reader = crate::Reader::new(&writer.get_ref()[..]);
+
+                let file_definition = reader.read_file_definition()?;
+                let expected_version = match compression_profile {
+                    CompressionProfile::Fast => Version::new(3, 0),
+                    _ => Version::new(3, 1),
+                };
+                assert_eq!(file_definition.version(), expected_version);
+
+                reader.read_file_header()?;
+
+                let mut data_container_count = 0;
+
+                while let Some(data_container) = reader.read_data_container()? {
+                    assert!(data_container.slices().len() <= 2);
+                    data_container_count += 1;
+                }
+
+                assert_eq!(data_container_count, 3);
+
+                let mut reader = crate::Reader::new(&writer.get_ref()[..]);
+                reader.read_file_definition()?;
+                reader.read_file_header()?;
+
+                let repository = fasta::Repository::default();
+                let actual: Vec<_> = reader
+                    .records(&repository, &header)
+                    .collect::<io::Result<_>>()?;
+
+                assert_eq!(actual.len(), records.len());
+
+                for (a, b) in actual.iter().zip(&records) {
+                    assert_eq!(a.read_name(), b.read_name());
+                    assert_eq!(a.alignment_start(), b.alignment_start());
+                    assert_eq!(a.bases(), b.bases());
+                    assert_eq!(a.quality_scores(), b.quality_scores());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_with_quality_scores_transforms() -> Result<(), Box<dyn std::error::Error>> {
+        use sam::record::{
+            quality_scores::{transform::Bins, Transform},
+            Flags,
+        };
+
+        let header = sam::Header::default();
+
+        let mut records = Vec::new();
+
+        for flags in [Flags::UNMAPPED, Flags::UNMAPPED | Flags::SECONDARY] {
+            let record = Record::builder()
+                .set_bam_flags(flags)
+                .set_flags(crate::record::Flags::QUALITY_SCORES_STORED_AS_ARRAY)
+                .set_read_length(4)
+                .set_bases("ACGT".parse()?)
+                .set_quality_scores("+5?I".parse()?)
+                .build();
+
+            records.push(record);
+        }
+
+        let mut writer = Builder::default()
+            .set_quality_scores_transform(Transform::Bin(Bins::illumina_8_level()))
+            .add_quality_scores_transform(Flags::SECONDARY, Transform::Drop)
+            .build_with_writer(Vec::new());
+
+        writer.write_file_definition()?;
+        writer.write_file_header(&header)?;
+
+        for record in records {
+            writer.write_record(&header, record)?;
+        }
+
+        writer.try_finish(&header)?;
+
+        let mut reader = crate::Reader::new(&writer.get_ref()[..]);
+        reader.read_file_definition()?;
+
+        let actual_header: sam::Header = reader.read_file_header()?.parse()?;
+        let program = actual_header
+            .programs()
+            .get(PROGRAM_NAME)
+            .ok_or("missing program")?;
+        assert_eq!(
+            program.description(),
+            Some("quality scores transformed: 0x100=drop;*=bin(2:6,10:15,20:22,25:27,30:33,35:37,40:40)")
+        );
+
+        let mut reader = crate::Reader::new(&writer.get_ref()[..]);
+        reader.read_file_definition()?;
+        reader.read_file_header()?;
+
+        let repository = fasta::Repository::default();
+        let actual: Vec<_> = reader
+            .records(&repository, &header)
+            .collect::<io::Result<_>>()?;
+
+        assert_eq!(actual.len(), 2);
+        assert_eq!(actual[0].quality_scores().to_string(), "07BI");
+        assert!(actual[1].quality_scores().is_empty());
+
+        Ok(())
+    }
 }
@@ -0,0 +1,155 @@
+use std::{collections::HashMap, io, sync::Mutex};
+
+use crate::{
+    codecs::Encoder,
+    container::{block, Block},
+};
+
+// The number of blocks per block content ID that are compressed with every candidate encoder.
+const TRIAL_COUNT: usize = 3;
+
+/// A block content encoder selector.
+///
+/// For each block content ID, the first blocks are compressed with each of the candidate encoders,
+/// and the smallest output is kept. After a number of trials, the encoder with the smallest total
+/// output size is used for the remaining blocks with the same block content ID.
+#[derive(Debug)]
+pub(crate) struct BlockContentEncoderSelector {
+    candidates: Vec<Encoder>,
+    trials: Mutex<HashMap<block::ContentId, Trial>>,
+}
+
+#[derive(Debug, Default)]
+struct Trial {
+    count: usize,
+    sizes: Vec<usize>,
+    selection: Option<usize>,
+}
+
+impl BlockContentEncoderSelector {
+    pub(crate) fn new(candidates: Vec<Encoder>) -> Self {
+        Self {
+            candidates,
+            trials: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Compresses the given data with the selected encoder and builds the block.
+    ///
+    /// The given encoder, which typically comes from the block content-encoder map, is always a
+    /// candidate.
+    pub(crate) fn build_block(
+        &self,
+        builder: block::Builder,
+        block_content_id: block::ContentId,
+        data: Vec<u8>,
+        encoder: Option<&Encoder>,
+    ) -> io::Result<Block> {
+        let candidates: Vec<_> = [encoder]
+            .into_iter()
+            .chain(self.candidates.iter().map(Some))
+            .collect();
+
+        if let Some(i) = self.selection(block_content_id) {
+            return builder
+                .set_data_with_encoder(data, candidates[i])
+                .map(|b| b.build());
+        }
+
+        let mut blocks = Vec::with_capacity(candidates.len());
+
+        for &candidate in &candidates {
+            let block = builder
+                .clone()
+                .set_data_with_encoder(data.clone(), candidate)?
+                .build();
+
+            blocks.push(block);
+        }
+
+        let sizes: Vec<_> = blocks.iter().map(|block| block.data().len()).collect();
+        self.add_trial(block_content_id, &sizes);
+
+        let i = min_index(&sizes);
+        Ok(blocks.swap_remove(i))
+    }
+
+    fn selection(&self, block_content_id: block::ContentId) -> Option<usize> {
+        let trials = self.trials.lock().unwrap();
+        trials
+            .get(&block_content_id)
+            .and_then(|trial| trial.selection)
+    }
+
+    fn add_trial(&self, block_content_id: block::ContentId, sizes: &[usize]) {
+        let mut trials = self.trials.lock().unwrap();
+        let trial = trials.entry(block_content_id).or_default();
+
+        if trial.selection.is_some() {
+            return;
+        }
+
+        if trial.sizes.is_empty() {
+            trial.sizes.resize(sizes.len(), 0);
+        }
+
+        for (total, size) in trial.sizes.iter_mut().zip(sizes) {
+            *total += size;
+        }
+
+        trial.count += 1;
+
+        if trial.count >= TRIAL_COUNT {
+            trial.selection = Some(min_index(&trial.sizes));
+        }
+    }
+}
+
+fn min_index(sizes: &[usize]) -> usize {
+    sizes
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, size)| **size)
+        .map(|(i, _)| i)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_block() -> io::Result<()> {
+        use crate::codecs::rans_4x8;
+
+        let selector = BlockContentEncoderSelector::new(vec![
+            Encoder::Gzip(Default::default()),
+            Encoder::Rans4x8(rans_4x8::Order::Zero),
+        ]);
+
+        let block_content_id = block::ContentId::from(1);
+        let data = vec![b'n'; 1024];
+
+        for _ in 0..TRIAL_COUNT {
+            let builder = Block::builder()
+                .set_content_type(block::ContentType::ExternalData)
+                .set_content_id(block_content_id);
+
+            let block = selector.build_block(builder, block_content_id, data.clone(), None)?;
+
+            assert!(block.data().len() < data.len());
+            assert_eq!(block.decompressed_data()?, data);
+        }
+
+        let selection = selector.selection(block_content_id);
+        assert!(matches!(selection, Some(1) | Some(2)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_min_index() {
+        assert_eq!(min_index(&[]), 0);
+        assert_eq!(min_index(&[8, 3, 5, 3]), 1);
+    }
+}
@@ -1,18 +1,31 @@
-use std::io::Write;
+use std::{io::Write, num::NonZeroUsize, sync::Arc};
 
 use noodles_fasta as fasta;
+use noodles_sam::record::{quality_scores::Transform, Flags};
 
-use super::{Options, Writer};
+use super::{
+    block_content_encoder_selector::BlockContentEncoderSelector, workers::Workers,
+    CompressionProfile, Options, Writer,
+};
 use crate::{
     codecs::Encoder, data_container::BlockContentEncoderMap, file_definition::Version,
     DataContainer,
 };
 
+const DEFAULT_WORKER_COUNT: NonZeroUsize = match NonZeroUsize::new(1) {
+    Some(worker_count) => worker_count,
+    None => unreachable!(),
+};
+
 /// A CRAM writer builder.
-#[derive(Default)]
 pub struct Builder {
     reference_sequence_repository: fasta::Repository,
     options: Options,
+    block_content_encoder_map: Option<BlockContentEncoderMap>,
+    records_per_slice: Option<usize>,
+    compression_profile: Option<CompressionProfile>,
+    adaptive_compression: bool,
+    worker_count: NonZeroUsize,
 }
 
 impl Builder {
@@ -45,9 +58,209 @@ impl Builder {
         self
     }
 
+    /// Sets whether to embed reference sequences.
+    ///
+    /// If `true`, the region of the reference sequence covered by a single-reference slice is
+    /// stored in the slice itself, and the reference is no longer required (`RR = false`) to
+    /// decode its records. The reference sequences are still read from the reference sequence
+    /// repository. Records in slices without a single reference sequence store their bases as
+    /// written.
+    ///
+    /// The default is `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let builder = cram::writer::Builder::default().embed_reference_sequences(true);
+    /// ```
+    pub fn embed_reference_sequences(mut self, value: bool) -> Self {
+        self.options.embed_reference_sequences = value;
+        self
+    }
+
+    /// Sets whether an external reference is required to decode records.
+    ///
+    /// If `false`, the reference sequence repository is not used, and records store their
+    /// bases explicitly rather than as differences to the reference sequence (`RR = false`).
+    /// Missing MD5 checksums (`M5`) in the reference sequence dictionary are also not
+    /// calculated. This has no effect when reference sequences are embedded (see
+    /// [`Self::embed_reference_sequences`]).
+    ///
+    /// The default is `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let builder = cram::writer::Builder::default().require_reference_sequences(false);
+    /// ```
+    pub fn require_reference_sequences(mut self, value: bool) -> Self {
+        self.options.reference_required = value;
+        self
+    }
+
     /// Sets the block content-encoder map.
     pub fn set_block_content_encoder_map(mut self, map: BlockContentEncoderMap) -> Self {
-        self.options.block_content_encoder_map = map;
+        self.block_content_encoder_map = Some(map);
+        self
+    }
+
+    /// Sets the compression profile.
+    ///
+    /// The profile provides the block content-encoder map and the number of records per slice,
+    /// unless they are set with [`Self::set_block_content_encoder_map`] or
+    /// [`Self::set_records_per_slice`], regardless of the order the options are set. The profile
+    /// also determines the candidate encoders when adaptive compression is enabled (see
+    /// [`Self::adaptive_compression`]).
+    ///
+    /// By default, no profile is applied, i.e., all blocks are compressed with gzip, and adaptive
+    /// compression uses the candidate encoders of [`CompressionProfile::Normal`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::{self as cram, writer::CompressionProfile};
+    /// let builder = cram::writer::Builder::default()
+    ///     .set_compression_profile(CompressionProfile::Small);
+    /// ```
+    pub fn set_compression_profile(mut self, compression_profile: CompressionProfile) -> Self {
+        self.compression_profile = Some(compression_profile);
+        self
+    }
+
+    /// Sets the maximum number of records per slice.
+    ///
+    /// The default is 10240.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    ///
+    /// use noodles_cram as cram;
+    ///
+    /// let records_per_slice = NonZeroUsize::try_from(25000)?;
+    /// let builder = cram::writer::Builder::default().set_records_per_slice(records_per_slice);
+    /// # Ok::<_, std::num::TryFromIntError>(())
+    /// ```
+    pub fn set_records_per_slice(mut self, records_per_slice: NonZeroUsize) -> Self {
+        self.records_per_slice = Some(records_per_slice.get());
+        self
+    }
+
+    /// Sets the maximum number of slices per container.
+    ///
+    /// The default is 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    ///
+    /// use noodles_cram as cram;
+    ///
+    /// let slices_per_container = NonZeroUsize::try_from(4)?;
+    /// let builder = cram::writer::Builder::default().set_slices_per_container(slices_per_container);
+    /// # Ok::<_, std::num::TryFromIntError>(())
+    /// ```
+    pub fn set_slices_per_container(mut self, slices_per_container: NonZeroUsize) -> Self {
+        self.options.slices_per_container = slices_per_container.get();
+        self
+    }
+
+    /// Sets whether to select block content encoders adaptively.
+    ///
+    /// If `true`, the first few blocks of each block content ID are trial compressed with the
+    /// encoder from the block content-encoder map and the candidate encoders of the compression
+    /// profile, and the smallest output is kept. The encoder with the smallest total output is
+    /// then used for the rest of the blocks with the same block content ID. CRAM 3.1 codecs are
+    /// only candidates when the block content-encoder map already uses them.
+    ///
+    /// The default is `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let builder = cram::writer::Builder::default().adaptive_compression(true);
+    /// ```
+    pub fn adaptive_compression(mut self, value: bool) -> Self {
+        self.adaptive_compression = value;
+        self
+    }
+
+    /// Sets the quality scores transform.
+    ///
+    /// The transform is applied to the quality scores of each record before it is encoded, e.g.,
+    /// to bin quality scores. It is used for records that do not match a transform added with
+    /// [`Self::add_quality_scores_transform`].
+    ///
+    /// When any transform is set, a program (`@PG`) that records the transforms is added to the
+    /// SAM header. This is the only record of the transforms. The CRAM specification has no
+    /// preservation map key for lossy quality scores (it only defines `RN`, `AP`, `RR`, `SM`, and
+    /// `TD`), so the compression header is unchanged. Readers, including this crate's, reject
+    /// unknown keys.
+    ///
+    /// By default, quality scores are unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// use noodles_sam::record::quality_scores::{transform::Bins, Transform};
+    ///
+    /// let builder = cram::writer::Builder::default()
+    ///     .set_quality_scores_transform(Transform::Bin(Bins::illumina_8_level()));
+    /// ```
+    pub fn set_quality_scores_transform(mut self, transform: Transform) -> Self {
+        self.options
+            .quality_scores_transforms
+            .set_default(transform);
+        self
+    }
+
+    /// Adds a quality scores transform for records with any of the given flags set.
+    ///
+    /// Transforms added this way take precedence over the one set by
+    /// [`Self::set_quality_scores_transform`] and are matched in the order they are added.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// use noodles_sam::record::{quality_scores::Transform, Flags};
+    ///
+    /// let builder = cram::writer::Builder::default()
+    ///     .add_quality_scores_transform(Flags::UNMAPPED | Flags::SECONDARY, Transform::Drop);
+    /// ```
+    pub fn add_quality_scores_transform(mut self, flags: Flags, transform: Transform) -> Self {
+        self.options
+            .quality_scores_transforms
+            .push(flags, transform);
+        self
+    }
+
+    /// Sets the worker count.
+    ///
+    /// When the worker count is greater than 1, the slices of data containers are encoded and
+    /// their blocks are compressed in parallel on worker threads. Containers are still written in
+    /// the order their records were given. By default, the worker count is set to 1, i.e.,
+    /// containers are built on the calling thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    ///
+    /// use noodles_cram as cram;
+    ///
+    /// let worker_count = NonZeroUsize::try_from(4)?;
+    /// let builder = cram::writer::Builder::default().set_worker_count(worker_count);
+    /// # Ok::<_, std::num::TryFromIntError>(())
+    /// ```
+    pub fn set_worker_count(mut self, worker_count: NonZeroUsize) -> Self {
+        self.worker_count = worker_count;
         self
     }
 
@@ -59,38 +272,114 @@ impl Builder {
     /// use noodles_cram as cram;
     /// let writer = cram::writer::Builder::default().build_with_writer(Vec::new());
     /// ```
-    pub fn build_with_writer<W>(mut self, writer: W) -> Writer<W>
+    pub fn build_with_writer<W>(self, writer: W) -> Writer<W>
     where
         W: Write,
     {
-        if uses_cram_3_1_codecs(&self.options.block_content_encoder_map) {
-            self.options.version = Version::new(3, 1);
-        }
+        let options = finalize_options(
+            self.options,
+            self.compression_profile,
+            self.block_content_encoder_map,
+            self.records_per_slice,
+            self.adaptive_compression,
+        );
 
         Writer {
             inner: writer,
             reference_sequence_repository: self.reference_sequence_repository,
-            options: self.options,
+            options,
             data_container_builder: DataContainer::builder(0),
             record_counter: 0,
+            workers: if self.worker_count.get() == 1 {
+                None
+            } else {
+                Some(Workers::new(self.worker_count))
+            },
+            header: None,
         }
     }
 }
 
-fn uses_cram_3_1_codecs(block_content_encoder_map: &BlockContentEncoderMap) -> bool {
-    fn is_cram_3_1_codec(encoder: &Encoder) -> bool {
-        matches!(
-            encoder,
-            Encoder::RansNx16(_) | Encoder::AdaptiveArithmeticCoding(_) | Encoder::NameTokenizer
-        )
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            reference_sequence_repository: fasta::Repository::default(),
+            options: Options::default(),
+            block_content_encoder_map: None,
+            records_per_slice: None,
+            compression_profile: None,
+            adaptive_compression: false,
+            worker_count: DEFAULT_WORKER_COUNT,
+        }
     }
+}
 
+// Applies the defaults of the compression profile to the options that were not explicitly set, and
+// sets the format version from the block content encoders and, when enabled, the adaptive block
+// content encoder selector.
+pub(crate) fn finalize_options(
+    mut options: Options,
+    compression_profile: Option<CompressionProfile>,
+    block_content_encoder_map: Option<BlockContentEncoderMap>,
+    records_per_slice: Option<usize>,
+    adaptive_compression: bool,
+) -> Options {
+    if let Some(map) = block_content_encoder_map
+        .or_else(|| compression_profile.map(|profile| profile.block_content_encoder_map()))
+    {
+        options.block_content_encoder_map = map;
+    }
+
+    if let Some(n) =
+        records_per_slice.or_else(|| compression_profile.map(|profile| profile.records_per_slice()))
+    {
+        options.records_per_slice = n;
+    }
+
+    if uses_cram_3_1_codecs(&options.block_content_encoder_map) {
+        options.version = Version::new(3, 1);
+    }
+
+    if adaptive_compression {
+        let is_cram_3_1 = options.version == Version::new(3, 1);
+
+        let candidates = compression_profile
+            .unwrap_or_default()
+            .candidate_encoders()
+            .into_iter()
+            .filter(|encoder| is_cram_3_1 || !is_cram_3_1_codec(encoder))
+            .collect();
+
+        options.block_content_encoder_selector =
+            Some(Arc::new(BlockContentEncoderSelector::new(candidates)));
+    }
+
+    options
+}
+
+pub(crate) fn is_cram_3_1_codec(encoder: &Encoder) -> bool {
+    matches!(
+        encoder,
+        Encoder::RansNx16(_)
+            | Encoder::AdaptiveArithmeticCoding(_)
+            | Encoder::NameTokenizer
+            | Encoder::Fqzcomp
+    )
+}
+
+fn uses_cram_3_1_codecs(block_content_encoder_map: &BlockContentEncoderMap) -> bool {
     if let Some(encoder) = block_content_encoder_map.core_data_encoder() {
         if is_cram_3_1_codec(encoder) {
             return true;
         }
     }
 
+    if let Some(encoder) = block_content_encoder_map.default_tag_values_encoder() {
+        if is_cram_3_1_codec(encoder) {
+            return true;
+        }
+    }
+
     block_content_encoder_map
         .data_series_encoders()
         .iter()
@@ -105,7 +394,10 @@ mod tests {
 
     #[test]
     fn test_uses_cram_3_1_codecs() {
-        use crate::codecs::rans_nx16::Flags;
+        use crate::{
+            codecs::rans_nx16::Flags,
+            data_container::compression_header::data_series_encoding_map::DataSeries,
+        };
 
         let block_content_encoder_map = BlockContentEncoderMap::default();
         assert!(!uses_cram_3_1_codecs(&block_content_encoder_map));
@@ -114,5 +406,71 @@ mod tests {
             .set_core_data_encoder(Some(Encoder::RansNx16(Flags::empty())))
             .build();
         assert!(uses_cram_3_1_codecs(&block_content_encoder_map));
+
+        let block_content_encoder_map = BlockContentEncoderMap::builder()
+            .set_data_series_encoder(DataSeries::QualityScores, Some(Encoder::Fqzcomp))
+            .build();
+        assert!(uses_cram_3_1_codecs(&block_content_encoder_map));
+    }
+
+    #[test]
+    fn test_finalize_options() {
+        let options = finalize_options(Options::default(), None, None, None, false);
+        assert_eq!(options.version, Version::new(3, 0));
+        assert_eq!(
+            options.records_per_slice,
+            Options::default().records_per_slice
+        );
+        assert!(options.block_content_encoder_selector.is_none());
+
+        let options = finalize_options(
+            Options::default(),
+            Some(CompressionProfile::Normal),
+            None,
+            None,
+            true,
+        );
+        assert_eq!(options.version, Version::new(3, 1));
+        assert_eq!(
+            options.records_per_slice,
+            CompressionProfile::Normal.records_per_slice()
+        );
+        assert!(options.block_content_encoder_selector.is_some());
+
+        let options = finalize_options(
+            Options::default(),
+            Some(CompressionProfile::Small),
+            Some(BlockContentEncoderMap::default()),
+            Some(8),
+            false,
+        );
+        assert_eq!(options.version, Version::new(3, 0));
+        assert_eq!(options.records_per_slice, 8);
+    }
+
+    #[test]
+    fn test_build_with_compression_profile() -> Result<(), std::num::TryFromIntError> {
+        let records_per_slice = NonZeroUsize::try_from(8)?;
+
+        let writer = Builder::default()
+            .set_records_per_slice(records_per_slice)
+            .set_compression_profile(CompressionProfile::Small)
+            .build_with_writer(Vec::new());
+
+        assert_eq!(writer.options.records_per_slice, 8);
+        assert_eq!(writer.options.version, Version::new(3, 1));
+
+        let writer = Builder::default()
+            .set_block_content_encoder_map(BlockContentEncoderMap::default())
+            .set_compression_profile(CompressionProfile::Small)
+            .build_with_writer(Vec::new());
+
+        assert_eq!(
+            writer.options.records_per_slice,
+            CompressionProfile::Small.records_per_slice()
+        );
+        assert_eq!(writer.options.version, Version::new(3, 0));
+
+        Ok(())
     }
 }
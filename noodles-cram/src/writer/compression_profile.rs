@@ -0,0 +1,199 @@
+use flate2::Compression;
+
+use crate::{
+    codecs::{aac, rans_4x8, rans_nx16, Encoder},
+    data_container::{
+        compression_header::data_series_encoding_map::{
+            data_series::STANDARD_DATA_SERIES, DataSeries,
+        },
+        BlockContentEncoderMap,
+    },
+};
+
+/// A CRAM writer compression profile.
+///
+/// A compression profile is a preset of block content encoders, for the core data, data series,
+/// and tag values blocks, and the number of records per slice, similar to the profiles in htslib.
+/// Profiles other than [`Self::Fast`] use CRAM 3.1 codecs.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum CompressionProfile {
+    /// Fast compression using gzip only (CRAM 3.0).
+    Fast,
+    /// rANS Nx16 for data series and the name tokenizer for read names.
+    #[default]
+    Normal,
+    /// [`Self::Normal`] with fqzcomp for quality scores and larger slices.
+    Small,
+    /// [`Self::Small`] with the adaptive arithmetic coder and the largest slices.
+    Archive,
+}
+
+impl CompressionProfile {
+    /// Returns the block content-encoder map of the profile.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::writer::CompressionProfile;
+    /// let block_content_encoder_map = CompressionProfile::Small.block_content_encoder_map();
+    /// ```
+    pub fn block_content_encoder_map(self) -> BlockContentEncoderMap {
+        let mut builder = BlockContentEncoderMap::builder()
+            .set_core_data_encoder(Some(self.core_data_encoder()))
+            .set_default_tag_values_encoder(Some(self.tag_values_encoder()));
+
+        for &data_series in STANDARD_DATA_SERIES {
+            let encoder = self.data_series_encoder(data_series);
+            builder = builder.set_data_series_encoder(data_series, Some(encoder));
+        }
+
+        builder.build()
+    }
+
+    /// Returns the number of records per slice of the profile.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::writer::CompressionProfile;
+    /// assert_eq!(CompressionProfile::Normal.records_per_slice(), 10000);
+    /// ```
+    pub fn records_per_slice(self) -> usize {
+        match self {
+            Self::Fast | Self::Normal => 10000,
+            Self::Small => 25000,
+            Self::Archive => 100000,
+        }
+    }
+
+    /// Returns the encoders that are tried when adaptive compression is enabled.
+    pub(crate) fn candidate_encoders(self) -> Vec<Encoder> {
+        let mut encoders = vec![
+            Encoder::Rans4x8(rans_4x8::Order::Zero),
+            Encoder::Rans4x8(rans_4x8::Order::One),
+        ];
+
+        if self == Self::Fast {
+            encoders.push(Encoder::Gzip(Compression::fast()));
+            return encoders;
+        }
+
+        encoders.extend([
+            Encoder::Gzip(Compression::default()),
+            Encoder::RansNx16(rans_nx16::Flags::empty()),
+            Encoder::RansNx16(rans_nx16::Flags::ORDER),
+            Encoder::RansNx16(rans_nx16::Flags::ORDER | rans_nx16::Flags::PACK),
+            Encoder::RansNx16(rans_nx16::Flags::ORDER | rans_nx16::Flags::RLE),
+        ]);
+
+        if matches!(self, Self::Small | Self::Archive) {
+            encoders.extend([
+                Encoder::Bzip2(bzip2::Compression::default()),
+                Encoder::AdaptiveArithmeticCoding(aac::Flags::ORDER),
+            ]);
+        }
+
+        if self == Self::Archive {
+            encoders.extend([
+                Encoder::Lzma(9),
+                Encoder::AdaptiveArithmeticCoding(aac::Flags::ORDER | aac::Flags::RLE),
+            ]);
+        }
+
+        encoders
+    }
+
+    fn core_data_encoder(self) -> Encoder {
+        match self {
+            Self::Fast => Encoder::Gzip(Compression::fast()),
+            Self::Normal => Encoder::Gzip(Compression::default()),
+            Self::Small => Encoder::Bzip2(bzip2::Compression::default()),
+            Self::Archive => Encoder::Lzma(9),
+        }
+    }
+
+    fn tag_values_encoder(self) -> Encoder {
+        match self {
+            Self::Fast => Encoder::Gzip(Compression::fast()),
+            Self::Normal => Encoder::RansNx16(rans_nx16::Flags::ORDER),
+            Self::Small => Encoder::Bzip2(bzip2::Compression::default()),
+            Self::Archive => Encoder::Lzma(9),
+        }
+    }
+
+    fn data_series_encoder(self, data_series: DataSeries) -> Encoder {
+        if self == Self::Fast {
+            return Encoder::Gzip(Compression::fast());
+        }
+
+        match data_series {
+            DataSeries::ReadNames => Encoder::NameTokenizer,
+            DataSeries::QualityScores => match self {
+                Self::Normal => Encoder::RansNx16(rans_nx16::Flags::ORDER),
+                _ => Encoder::Fqzcomp,
+            },
+            DataSeries::Bases
+            | DataSeries::StretchesOfBases
+            | DataSeries::StretchesOfQualityScores
+            | DataSeries::Insertion
+            | DataSeries::SoftClip => match self {
+                Self::Archive => {
+                    Encoder::AdaptiveArithmeticCoding(aac::Flags::ORDER | aac::Flags::PACK)
+                }
+                _ => Encoder::RansNx16(rans_nx16::Flags::ORDER | rans_nx16::Flags::PACK),
+            },
+            _ => match self {
+                Self::Archive => {
+                    Encoder::AdaptiveArithmeticCoding(aac::Flags::ORDER | aac::Flags::RLE)
+                }
+                _ => Encoder::RansNx16(rans_nx16::Flags::ORDER | rans_nx16::Flags::RLE),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::container::block;
+
+    #[test]
+    fn test_default() {
+        assert_eq!(CompressionProfile::default(), CompressionProfile::Normal);
+    }
+
+    #[test]
+    fn test_block_content_encoder_map() {
+        use crate::data_container::compression_header::preservation_map::tag_ids_dictionary::Key;
+        use noodles_sam::record::data::field::{value::Type, Tag};
+
+        let block_content_id = block::ContentId::from(Key::new(Tag::ReadGroup, Type::String));
+
+        let map = CompressionProfile::Fast.block_content_encoder_map();
+        assert!(matches!(
+            map.get_tag_values_encoder(block_content_id),
+            Some(Encoder::Gzip(compression)) if *compression == Compression::fast()
+        ));
+
+        let map = CompressionProfile::Archive.block_content_encoder_map();
+        assert!(matches!(
+            map.get_tag_values_encoder(block_content_id),
+            Some(Encoder::Lzma(9))
+        ));
+    }
+
+    #[test]
+    fn test_candidate_encoders() {
+        use crate::writer::builder::is_cram_3_1_codec;
+
+        assert!(!CompressionProfile::Fast
+            .candidate_encoders()
+            .iter()
+            .any(is_cram_3_1_codec));
+
+        assert!(CompressionProfile::Normal
+            .candidate_encoders()
+            .iter()
+            .any(is_cram_3_1_codec));
+    }
+}
@@ -13,8 +13,6 @@ pub fn write_header_container<W>(writer: &mut W, header: &sam::Header) -> io::Re
 where
     W: Write,
 {
-    validate_reference_sequences(header.reference_sequences())?;
-
     let header_data = header.to_string().into_bytes();
     let header_data_len = i32::try_from(header_data.len())
         .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
@@ -35,7 +33,7 @@ where
     Ok(())
 }
 
-fn validate_reference_sequences(
+pub(crate) fn validate_reference_sequences(
     reference_sequences: &sam::header::ReferenceSequences,
 ) -> io::Result<()> {
     for reference_sequence in reference_sequences.values() {
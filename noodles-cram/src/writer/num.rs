@@ -1,5 +0,0 @@
-mod itf8;
-mod ltf8;
-mod vlq;
-
-pub use self::{itf8::write_itf8, ltf8::write_ltf8, vlq::write_uint7};
@@ -1,11 +1,32 @@
+use std::sync::Arc;
+
+use noodles_sam::record::quality_scores::transform::Transforms;
+
+use super::block_content_encoder_selector::BlockContentEncoderSelector;
 use crate::{data_container::BlockContentEncoderMap, file_definition::Version};
 
+const DEFAULT_RECORDS_PER_SLICE: usize = 10240;
+const DEFAULT_SLICES_PER_CONTAINER: usize = 1;
+
 #[derive(Clone, Debug)]
 pub struct Options {
     pub preserve_read_names: bool,
     pub encode_alignment_start_positions_as_deltas: bool,
     pub version: Version,
     pub block_content_encoder_map: BlockContentEncoderMap,
+    pub embed_reference_sequences: bool,
+    pub reference_required: bool,
+    pub records_per_slice: usize,
+    pub slices_per_container: usize,
+    pub block_content_encoder_selector: Option<Arc<BlockContentEncoderSelector>>,
+    pub quality_scores_transforms: Transforms,
+}
+
+impl Options {
+    /// Returns whether reference sequences are read from the reference sequence repository.
+    pub fn uses_reference_sequences(&self) -> bool {
+        self.reference_required || self.embed_reference_sequences
+    }
 }
 
 impl Default for Options {
@@ -15,6 +36,12 @@ impl Default for Options {
             encode_alignment_start_positions_as_deltas: true,
             version: Version::default(),
             block_content_encoder_map: BlockContentEncoderMap::default(),
+            embed_reference_sequences: false,
+            reference_required: true,
+            records_per_slice: DEFAULT_RECORDS_PER_SLICE,
+            slices_per_container: DEFAULT_SLICES_PER_CONTAINER,
+            block_content_encoder_selector: None,
+            quality_scores_transforms: Transforms::default(),
         }
     }
 }
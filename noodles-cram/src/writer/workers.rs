@@ -0,0 +1,165 @@
+use std::{
+    collections::VecDeque,
+    io::{self, Write},
+    num::NonZeroUsize,
+    sync::Arc,
+};
+
+use noodles_fasta as fasta;
+use noodles_sam as sam;
+
+use super::{data_container::write_data_container, Options};
+use crate::{
+    container::Block,
+    data_container::{self, slice, CompressionHeader, Slice},
+    worker_pool::{Pending, Spawner, WorkerPool},
+    DataContainer,
+};
+
+/// Workers that encode the slices of data containers and compress their blocks in parallel.
+///
+/// Each slice is encoded on a slice worker, which then queues each of its blocks to be
+/// compressed on a block worker. Data containers are written in the order they were sent.
+#[derive(Debug)]
+pub(crate) struct Workers {
+    // The slice pool is dropped first, as its jobs hold spawners for the block pool.
+    slice_pool: WorkerPool<PendingSlice>,
+    block_pool: WorkerPool<Block>,
+    pending_data_containers: VecDeque<PendingDataContainer>,
+}
+
+#[derive(Debug)]
+struct PendingDataContainer {
+    compression_header: Arc<CompressionHeader>,
+    slice_count: usize,
+    base_count: u64,
+}
+
+#[derive(Debug)]
+struct PendingSlice {
+    header: slice::Header,
+    core_data_block: Pending<Block>,
+    external_blocks: Vec<Pending<Block>>,
+}
+
+impl PendingSlice {
+    fn spawn(encoded_slice: slice::builder::EncodedSlice, spawner: &Spawner<Block>) -> Self {
+        let (header, core_data_block, external_blocks) = encoded_slice.into_parts();
+
+        Self {
+            header,
+            core_data_block: spawner.spawn(move || core_data_block.compress()),
+            external_blocks: external_blocks
+                .into_iter()
+                .map(|block| spawner.spawn(move || block.compress()))
+                .collect(),
+        }
+    }
+
+    fn wait(self) -> io::Result<Slice> {
+        let core_data_block = self.core_data_block.wait()?;
+
+        let external_blocks = self
+            .external_blocks
+            .into_iter()
+            .map(|block| block.wait())
+            .collect::<io::Result<_>>()?;
+
+        Ok(Slice::new(self.header, core_data_block, external_blocks))
+    }
+}
+
+impl Workers {
+    pub(crate) fn new(worker_count: NonZeroUsize) -> Self {
+        Self {
+            slice_pool: WorkerPool::new(worker_count),
+            block_pool: WorkerPool::new(worker_count),
+            pending_data_containers: VecDeque::new(),
+        }
+    }
+
+    /// Returns whether the number of slices being encoded is at capacity.
+    pub(crate) fn is_full(&self) -> bool {
+        self.slice_pool.is_full()
+    }
+
+    /// Queues the slices of a data container to be encoded.
+    pub(crate) fn send(
+        &mut self,
+        data_container_builder: data_container::Builder,
+        options: &Options,
+        reference_sequence_repository: &fasta::Repository,
+        header: &Arc<sam::Header>,
+    ) {
+        let base_count = data_container_builder.base_count();
+
+        let (options, compression_header, slice_builders, record_counter) =
+            data_container_builder.into_parts(options);
+
+        let options = Arc::new(options);
+        let compression_header = Arc::new(compression_header);
+        let slice_count = slice_builders.len();
+
+        for slice_builder in slice_builders {
+            let options = options.clone();
+            let reference_sequence_repository = reference_sequence_repository.clone();
+            let header = header.clone();
+            let compression_header = compression_header.clone();
+            let spawner = self.block_pool.spawner();
+
+            self.slice_pool.send(move || {
+                slice_builder
+                    .encode(
+                        &options,
+                        &reference_sequence_repository,
+                        &header,
+                        &compression_header,
+                        record_counter,
+                    )
+                    .map(|encoded_slice| PendingSlice::spawn(encoded_slice, &spawner))
+            });
+        }
+
+        self.pending_data_containers
+            .push_back(PendingDataContainer {
+                compression_header,
+                slice_count,
+                base_count,
+            });
+    }
+
+    /// Waits for the oldest data container to be encoded and writes it.
+    ///
+    /// This returns whether a data container was written.
+    pub(crate) fn write_pending_data_container<W>(&mut self, writer: &mut W) -> io::Result<bool>
+    where
+        W: Write,
+    {
+        let pending_data_container = match self.pending_data_containers.pop_front() {
+            Some(pending_data_container) => pending_data_container,
+            None => return Ok(false),
+        };
+
+        let mut slices = Vec::with_capacity(pending_data_container.slice_count);
+
+        for _ in 0..pending_data_container.slice_count {
+            let pending_slice = self.slice_pool.recv().unwrap_or_else(|| {
+                Err(io::Error::new(
+                    io::ErrorKind::BrokenPipe,
+                    "missing pending slice",
+                ))
+            })?;
+
+            slices.push(pending_slice.wait()?);
+        }
+
+        let compression_header = Arc::try_unwrap(pending_data_container.compression_header)
+            .unwrap_or_else(|compression_header| (*compression_header).clone());
+
+        let data_container = DataContainer::new(compression_header, slices);
+
+        write_data_container(writer, &data_container, pending_data_container.base_count)?;
+
+        Ok(true)
+    }
+}
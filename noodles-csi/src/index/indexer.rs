@@ -0,0 +1,262 @@
+//! CSI indexer.
+
+use std::io;
+
+use noodles_core::Position;
+
+use super::{
+    reference_sequence::{self, bin::Chunk},
+    Index, ReferenceSequence,
+};
+
+/// A coordinate-sorted index (CSI) indexer.
+///
+/// An indexer creates an index from coordinate-sorted records and their chunks.
+#[derive(Debug)]
+pub struct Indexer {
+    min_shift: u8,
+    depth: u8,
+    aux: Vec<u8>,
+    reference_sequence_builders: Vec<reference_sequence::Builder>,
+    unplaced_unmapped_record_count: u64,
+    last_position: Option<(usize, Position)>,
+}
+
+impl Indexer {
+    /// Sets a min shift.
+    ///
+    /// This is the number of bits for the minimum interval. By default, this is 14.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi as csi;
+    /// let index = csi::index::Indexer::default().set_min_shift(13).build(0);
+    /// assert_eq!(index.min_shift(), 13);
+    /// ```
+    pub fn set_min_shift(mut self, min_shift: u8) -> Self {
+        self.min_shift = min_shift;
+        self
+    }
+
+    /// Sets a max depth.
+    ///
+    /// By default, this is 5.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi as csi;
+    /// let index = csi::index::Indexer::default().set_depth(8).build(0);
+    /// assert_eq!(index.depth(), 8);
+    /// ```
+    pub fn set_depth(mut self, depth: u8) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    /// Sets auxiliary data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi as csi;
+    /// let index = csi::index::Indexer::default().set_aux(b"ndls".to_vec()).build(0);
+    /// assert_eq!(index.aux(), b"ndls");
+    /// ```
+    pub fn set_aux(mut self, aux: Vec<u8>) -> Self {
+        self.aux = aux;
+        self
+    }
+
+    /// Adds a record.
+    ///
+    /// The alignment context is the reference sequence ID, start and end positions, and whether
+    /// the record is mapped. Records without an alignment context are counted as unplaced,
+    /// unmapped records.
+    ///
+    /// Records must be coordinate-sorted. This returns an error if the reference sequence ID and
+    /// start position of the record are less than those of the previous placed record or if the
+    /// end position exceeds the maximum position supported by the min shift and depth.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_core::Position;
+    /// use noodles_csi::{self as csi, index::reference_sequence::bin::Chunk};
+    ///
+    /// let mut indexer = csi::index::Indexer::default();
+    ///
+    /// let start = Position::try_from(8)?;
+    /// let end = Position::try_from(13)?;
+    ///
+    /// indexer.add_record(Some((0, start, end, true)), Chunk::new(
+    ///     bgzf::VirtualPosition::from(144),
+    ///     bgzf::VirtualPosition::from(233),
+    /// ))?;
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn add_record(
+        &mut self,
+        alignment_context: Option<(usize, Position, Position, bool)>,
+        chunk: Chunk,
+    ) -> io::Result<()> {
+        let (reference_sequence_id, start, end, is_mapped) = match alignment_context {
+            Some(context) => context,
+            None => {
+                self.unplaced_unmapped_record_count += 1;
+                return Ok(());
+            }
+        };
+
+        let position = (reference_sequence_id, start);
+
+        if matches!(self.last_position, Some(last_position) if position < last_position) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid record order: records must be coordinate-sorted",
+            ));
+        }
+
+        let max_position = ReferenceSequence::max_position(self.min_shift, self.depth)?;
+
+        if end > max_position {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "invalid end position: expected <= {}, got {}",
+                    max_position, end
+                ),
+            ));
+        }
+
+        self.last_position = Some(position);
+        self.add_reference_sequence_builders_until(reference_sequence_id);
+
+        // SAFETY: `reference_sequence_builders` has at least `reference_sequence_id + 1` entries.
+        let builder = &mut self.reference_sequence_builders[reference_sequence_id];
+        builder.add_record(start, end, is_mapped, chunk);
+
+        Ok(())
+    }
+
+    fn add_reference_sequence_builders_until(&mut self, reference_sequence_id: usize) {
+        while self.reference_sequence_builders.len() <= reference_sequence_id {
+            self.reference_sequence_builders
+                .push(reference_sequence::Builder::new(self.min_shift, self.depth));
+        }
+    }
+
+    /// Builds a coordinate-sorted index (CSI).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi::{self as csi, BinningIndex};
+    /// let index = csi::index::Indexer::default().build(2);
+    /// assert_eq!(index.reference_sequences().len(), 2);
+    /// ```
+    pub fn build(mut self, reference_sequence_count: usize) -> Index {
+        if let Some(last_reference_sequence_id) = reference_sequence_count.checked_sub(1) {
+            self.add_reference_sequence_builders_until(last_reference_sequence_id);
+        }
+
+        let reference_sequences = self
+            .reference_sequence_builders
+            .into_iter()
+            .map(|builder| builder.build())
+            .collect();
+
+        Index::builder()
+            .set_min_shift(self.min_shift)
+            .set_depth(self.depth)
+            .set_aux(self.aux)
+            .set_reference_sequences(reference_sequences)
+            .set_unplaced_unmapped_record_count(self.unplaced_unmapped_record_count)
+            .build()
+    }
+}
+
+impl Default for Indexer {
+    fn default() -> Self {
+        Self {
+            min_shift: 14,
+            depth: 5,
+            aux: Vec::new(),
+            reference_sequence_builders: Vec::new(),
+            unplaced_unmapped_record_count: 0,
+            last_position: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_bgzf as bgzf;
+
+    use super::*;
+    use crate::BinningIndex;
+
+    #[test]
+    fn test_build() -> Result<(), Box<dyn std::error::Error>> {
+        let mut indexer = Indexer::default();
+
+        let chunk = |start, end| {
+            Chunk::new(
+                bgzf::VirtualPosition::from(start),
+                bgzf::VirtualPosition::from(end),
+            )
+        };
+
+        let start = Position::try_from(8)?;
+        let end = Position::try_from(13)?;
+
+        indexer.add_record(Some((1, start, end, true)), chunk(55, 89))?;
+        indexer.add_record(None, chunk(89, 144))?;
+
+        assert!(matches!(
+            indexer.add_record(Some((0, start, end, true)), chunk(144, 233)),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        let index = indexer.build(3);
+
+        assert_eq!(index.reference_sequences().len(), 3);
+        assert!(index.reference_sequences()[0].bins().is_empty());
+        assert_eq!(index.reference_sequences()[1].bins().len(), 1);
+        assert_eq!(index.unplaced_unmapped_record_count(), Some(1));
+
+        let chunks = index.query(1, start..=end)?;
+        assert_eq!(chunks, [chunk(55, 89)]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_record_with_unsorted_positions() -> Result<(), Box<dyn std::error::Error>> {
+        let mut indexer = Indexer::default();
+
+        let chunk = |start, end| {
+            Chunk::new(
+                bgzf::VirtualPosition::from(start),
+                bgzf::VirtualPosition::from(end),
+            )
+        };
+
+        let start = Position::try_from(13)?;
+        let end = Position::try_from(21)?;
+        indexer.add_record(Some((0, start, end, true)), chunk(55, 89))?;
+
+        // Equal start positions are in order.
+        indexer.add_record(Some((0, start, end, true)), chunk(89, 144))?;
+
+        let start = Position::try_from(8)?;
+        assert!(matches!(
+            indexer.add_record(Some((0, start, end, true)), chunk(144, 233)),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+}
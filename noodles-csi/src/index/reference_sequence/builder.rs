@@ -0,0 +1,238 @@
+use std::{cmp, collections::HashMap};
+
+use noodles_bgzf as bgzf;
+use noodles_core::Position;
+
+use super::{bin::Chunk, reg2bin, Bin, Metadata, ReferenceSequence};
+
+/// A CSI reference sequence builder.
+///
+/// Chunks of each bin are merged when they overlap. The linear offset of a bin is the start
+/// position of the first record that overlaps the first `2^min_shift` window of the bin.
+#[derive(Debug)]
+pub struct Builder {
+    min_shift: u8,
+    depth: u8,
+    bins: HashMap<usize, Vec<Chunk>>,
+    intervals: Vec<Option<bgzf::VirtualPosition>>,
+    start_position: bgzf::VirtualPosition,
+    end_position: bgzf::VirtualPosition,
+    mapped_record_count: u64,
+    unmapped_record_count: u64,
+}
+
+impl Builder {
+    pub fn new(min_shift: u8, depth: u8) -> Self {
+        Self {
+            min_shift,
+            depth,
+            bins: HashMap::new(),
+            intervals: Vec::new(),
+            start_position: bgzf::VirtualPosition::max(),
+            end_position: bgzf::VirtualPosition::default(),
+            mapped_record_count: 0,
+            unmapped_record_count: 0,
+        }
+    }
+
+    pub fn add_record(&mut self, start: Position, end: Position, is_mapped: bool, chunk: Chunk) {
+        self.update_bins(start, end, chunk);
+        self.update_linear_index(start, end, chunk);
+        self.update_metadata(is_mapped, chunk);
+    }
+
+    pub fn build(self) -> ReferenceSequence {
+        if self.bins.is_empty() {
+            return ReferenceSequence::new(Vec::new(), None);
+        }
+
+        let intervals = fill_intervals(self.intervals);
+
+        let mut bins: Vec<_> = self
+            .bins
+            .into_iter()
+            .map(|(id, chunks)| {
+                let window = bin_start_window(id, self.depth);
+                let loffset = intervals.get(window).copied().unwrap_or_default();
+                Bin::new(id, loffset, chunks)
+            })
+            .collect();
+
+        bins.sort_unstable_by_key(|bin| bin.id());
+
+        let metadata = Metadata::new(
+            self.start_position,
+            self.end_position,
+            self.mapped_record_count,
+            self.unmapped_record_count,
+        );
+
+        ReferenceSequence::new(bins, Some(metadata))
+    }
+
+    fn update_bins(&mut self, start: Position, end: Position, chunk: Chunk) {
+        let id = reg2bin(start, end, self.min_shift, self.depth);
+        let chunks = self.bins.entry(id).or_default();
+
+        // See § 5.1.2 "Reducing small chunks" in `SAMv1.pdf` (2020-07-19).
+        if let Some(last_chunk) = chunks.last_mut() {
+            if chunk.start() <= last_chunk.end() {
+                *last_chunk = Chunk::new(last_chunk.start(), chunk.end());
+                return;
+            }
+        }
+
+        chunks.push(chunk);
+    }
+
+    fn update_linear_index(&mut self, start: Position, end: Position, chunk: Chunk) {
+        let start_window = (usize::from(start) - 1) >> self.min_shift;
+        let end_window = (usize::from(end) - 1) >> self.min_shift;
+
+        if end_window >= self.intervals.len() {
+            self.intervals.resize(end_window + 1, None);
+        }
+
+        for interval in &mut self.intervals[start_window..=end_window] {
+            interval.get_or_insert(chunk.start());
+        }
+    }
+
+    fn update_metadata(&mut self, is_mapped: bool, chunk: Chunk) {
+        if is_mapped {
+            self.mapped_record_count += 1;
+        } else {
+            self.unmapped_record_count += 1;
+        }
+
+        self.start_position = cmp::min(self.start_position, chunk.start());
+        self.end_position = cmp::max(self.end_position, chunk.end());
+    }
+}
+
+// Empty windows take the offset of the next nonempty window.
+fn fill_intervals(intervals: Vec<Option<bgzf::VirtualPosition>>) -> Vec<bgzf::VirtualPosition> {
+    let mut filled = vec![bgzf::VirtualPosition::default(); intervals.len()];
+    let mut next = bgzf::VirtualPosition::default();
+
+    for (dst, src) in filled.iter_mut().zip(intervals).rev() {
+        if let Some(position) = src {
+            next = position;
+        }
+
+        *dst = next;
+    }
+
+    filled
+}
+
+// Returns the index of the first `2^min_shift` window covered by the given bin.
+fn bin_start_window(id: usize, depth: u8) -> usize {
+    let mut level_start = 0;
+
+    for level in 0..=depth {
+        let level_size = 1 << (3 * usize::from(level));
+
+        if id < level_start + level_size {
+            let offset = id - level_start;
+            return offset << (3 * usize::from(depth - level));
+        }
+
+        level_start += level_size;
+    }
+
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIN_SHIFT: u8 = 14;
+    const DEPTH: u8 = 5;
+
+    #[test]
+    fn test_build() -> Result<(), noodles_core::position::TryFromIntError> {
+        let mut builder = Builder::new(MIN_SHIFT, DEPTH);
+
+        builder.add_record(
+            Position::try_from(8)?,
+            Position::try_from(13)?,
+            true,
+            Chunk::new(
+                bgzf::VirtualPosition::from(55),
+                bgzf::VirtualPosition::from(89),
+            ),
+        );
+
+        builder.add_record(
+            Position::try_from(21)?,
+            Position::try_from(34)?,
+            false,
+            Chunk::new(
+                bgzf::VirtualPosition::from(89),
+                bgzf::VirtualPosition::from(144),
+            ),
+        );
+
+        builder.add_record(
+            Position::try_from(32768)?,
+            Position::try_from(65536)?,
+            true,
+            Chunk::new(
+                bgzf::VirtualPosition::from(233),
+                bgzf::VirtualPosition::from(377),
+            ),
+        );
+
+        let actual = builder.build();
+
+        let expected = ReferenceSequence::new(
+            vec![
+                Bin::new(
+                    585,
+                    bgzf::VirtualPosition::from(55),
+                    vec![Chunk::new(
+                        bgzf::VirtualPosition::from(233),
+                        bgzf::VirtualPosition::from(377),
+                    )],
+                ),
+                Bin::new(
+                    4681,
+                    bgzf::VirtualPosition::from(55),
+                    vec![Chunk::new(
+                        bgzf::VirtualPosition::from(55),
+                        bgzf::VirtualPosition::from(144),
+                    )],
+                ),
+            ],
+            Some(Metadata::new(
+                bgzf::VirtualPosition::from(55),
+                bgzf::VirtualPosition::from(377),
+                2,
+                1,
+            )),
+        );
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_with_no_bins() {
+        let reference_sequence = Builder::new(MIN_SHIFT, DEPTH).build();
+        assert_eq!(reference_sequence, ReferenceSequence::new(Vec::new(), None));
+    }
+
+    #[test]
+    fn test_bin_start_window() {
+        assert_eq!(bin_start_window(0, DEPTH), 0);
+        assert_eq!(bin_start_window(1, DEPTH), 0);
+        assert_eq!(bin_start_window(2, DEPTH), 4096);
+        assert_eq!(bin_start_window(585, DEPTH), 0);
+        assert_eq!(bin_start_window(586, DEPTH), 8);
+        assert_eq!(bin_start_window(4681, DEPTH), 0);
+        assert_eq!(bin_start_window(4682, DEPTH), 1);
+    }
+}
@@ -24,7 +24,10 @@ impl Builder {
     }
 
     /// Builds an indexed FASTA reader from a path.
-    pub fn build_from_path<P>(self, src: P) -> io::Result<IndexedReader<Box<dyn BufReadSeek>>>
+    pub fn build_from_path<P>(
+        self,
+        src: P,
+    ) -> io::Result<IndexedReader<Box<dyn BufReadSeek + Send + Sync>>>
     where
         P: AsRef<Path>,
     {
@@ -38,12 +41,13 @@ impl Builder {
             }
         };
 
-        let reader: Box<dyn BufReadSeek> = match src.extension().and_then(|ext| ext.to_str()) {
-            Some("gz") => bgzf::indexed_reader::Builder::default()
-                .build_from_path(src)
-                .map(Box::new)?,
-            _ => File::open(src).map(BufReader::new).map(Box::new)?,
-        };
+        let reader: Box<dyn BufReadSeek + Send + Sync> =
+            match src.extension().and_then(|ext| ext.to_str()) {
+                Some("gz") => bgzf::indexed_reader::Builder::default()
+                    .build_from_path(src)
+                    .map(Box::new)?,
+                _ => File::open(src).map(BufReader::new).map(Box::new)?,
+            };
 
         Ok(IndexedReader::new(reader, index))
     }
@@ -3,7 +3,10 @@ use std::io;
 use crate::Record;
 
 /// A repository adapter.
-pub trait Adapter {
+///
+/// Adapters are required to be `Send` and `Sync` so that a repository can be shared across
+/// threads.
+pub trait Adapter: Send + Sync {
     /// Returns the record with the given name.
     fn get(&mut self, name: &str) -> Option<io::Result<Record>>;
 }
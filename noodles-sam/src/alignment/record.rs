@@ -1,6 +1,7 @@
 //! Alignment record.
 
 mod builder;
+pub mod calmd;
 
 pub use self::builder::Builder;
 
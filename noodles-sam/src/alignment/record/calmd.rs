@@ -0,0 +1,400 @@
+//! Calculation of the mismatched positions (`MD`), edit distance (`NM`), and segment likelihood
+//! (`UQ`) data fields.
+//!
+//! These are calculated from the CIGAR, sequence, and quality scores of a record and the
+//! reference sequence it is aligned to. The result can either be written to the data of the
+//! record or compared against its stored values, similar to `samtools calmd`.
+//!
+//! # Examples
+//!
+//! ```
+//! # use std::io;
+//! use noodles_core::Position;
+//! use noodles_fasta as fasta;
+//! use noodles_sam::alignment::{record::calmd, Record};
+//!
+//! let reference_sequence = fasta::record::Sequence::from(b"ACGTACGT".to_vec());
+//!
+//! let mut record = Record::builder()
+//!     .set_alignment_start(Position::MIN)
+//!     .set_cigar("4M".parse()?)
+//!     .set_sequence("ACTT".parse()?)
+//!     .build();
+//!
+//! let fields = calmd::calculate(&record, &reference_sequence)?;
+//! assert_eq!(fields.mismatched_positions(), "2G1");
+//! assert_eq!(fields.edit_distance(), 1);
+//!
+//! fields.update(record.data_mut());
+//! assert!(fields.verify(record.data()).is_empty());
+//! # Ok::<_, Box<dyn std::error::Error>>(())
+//! ```
+
+use std::{fmt::Write, io};
+
+use noodles_fasta as fasta;
+
+use super::Record;
+use crate::record::{
+    cigar::op::Kind,
+    data::{
+        field::{Tag, Value},
+        Field,
+    },
+    sequence::Base,
+    Data,
+};
+
+/// Calculated `MD`, `NM`, and `UQ` data field values.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Fields {
+    mismatched_positions: String,
+    edit_distance: u32,
+    segment_likelihood: Option<u32>,
+}
+
+impl Fields {
+    /// Returns the mismatched positions (`MD`).
+    pub fn mismatched_positions(&self) -> &str {
+        &self.mismatched_positions
+    }
+
+    /// Returns the edit distance (`NM`).
+    ///
+    /// This is the number of mismatched, inserted, and deleted bases.
+    pub fn edit_distance(&self) -> u32 {
+        self.edit_distance
+    }
+
+    /// Returns the segment likelihood (`UQ`).
+    ///
+    /// This is the sum of the quality scores of the mismatched bases. It is `None` if the record
+    /// has no quality scores.
+    pub fn segment_likelihood(&self) -> Option<u32> {
+        self.segment_likelihood
+    }
+
+    /// Inserts or replaces the mismatched positions (`MD`) and edit distance (`NM`) data fields.
+    pub fn update(&self, data: &mut Data) {
+        data.insert(Field::new(
+            Tag::MismatchedPositions,
+            Value::String(self.mismatched_positions.clone()),
+        ));
+
+        data.insert(Field::new(Tag::EditDistance, int_value(self.edit_distance)));
+    }
+
+    /// Inserts or replaces the segment likelihood (`UQ`) data field.
+    ///
+    /// This does nothing if the segment likelihood was not calculated.
+    pub fn update_segment_likelihood(&self, data: &mut Data) {
+        if let Some(segment_likelihood) = self.segment_likelihood {
+            data.insert(Field::new(
+                Tag::SegmentLikelihood,
+                int_value(segment_likelihood),
+            ));
+        }
+    }
+
+    /// Compares the calculated values with the ones stored in the data.
+    ///
+    /// Only data fields that are present are compared. This returns a discrepancy for each stored
+    /// value that differs from the calculated value.
+    pub fn verify(&self, data: &Data) -> Vec<Discrepancy> {
+        let mut discrepancies = Vec::new();
+
+        if let Some(field) = data.get(Tag::MismatchedPositions) {
+            if field.value().as_str() != Some(self.mismatched_positions.as_str()) {
+                discrepancies.push(Discrepancy {
+                    tag: Tag::MismatchedPositions,
+                    expected: Value::String(self.mismatched_positions.clone()),
+                    actual: field.value().clone(),
+                });
+            }
+        }
+
+        if let Some(field) = data.get(Tag::EditDistance) {
+            if field.value().as_int() != Some(i64::from(self.edit_distance)) {
+                discrepancies.push(Discrepancy {
+                    tag: Tag::EditDistance,
+                    expected: int_value(self.edit_distance),
+                    actual: field.value().clone(),
+                });
+            }
+        }
+
+        if let (Some(field), Some(segment_likelihood)) =
+            (data.get(Tag::SegmentLikelihood), self.segment_likelihood)
+        {
+            if field.value().as_int() != Some(i64::from(segment_likelihood)) {
+                discrepancies.push(Discrepancy {
+                    tag: Tag::SegmentLikelihood,
+                    expected: int_value(segment_likelihood),
+                    actual: field.value().clone(),
+                });
+            }
+        }
+
+        discrepancies
+    }
+}
+
+/// A difference between a calculated and stored data field value.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Discrepancy {
+    tag: Tag,
+    expected: Value,
+    actual: Value,
+}
+
+impl Discrepancy {
+    /// Returns the data field tag.
+    pub fn tag(&self) -> Tag {
+        self.tag
+    }
+
+    /// Returns the calculated value.
+    pub fn expected(&self) -> &Value {
+        &self.expected
+    }
+
+    /// Returns the stored value.
+    pub fn actual(&self) -> &Value {
+        &self.actual
+    }
+}
+
+/// Calculates the `MD`, `NM`, and `UQ` data field values of a record.
+///
+/// The reference sequence is the entire sequence of the reference sequence the record is aligned
+/// to, e.g., from a [`fasta::Repository`].
+///
+/// A read base matches a reference base if it is `=` or is the same base, case-insensitive, and
+/// not `N`. Reference skips (`N`), soft clips, hard clips, and padding do not contribute to the
+/// values.
+///
+/// This returns an error if the record is missing an alignment start or sequence or if the
+/// alignment extends past the end of the reference sequence.
+pub fn calculate(
+    record: &Record,
+    reference_sequence: &fasta::record::Sequence,
+) -> io::Result<Fields> {
+    let alignment_start = record
+        .alignment_start()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "missing alignment start"))?;
+
+    let sequence = record.sequence().as_ref();
+
+    if sequence.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "missing sequence",
+        ));
+    }
+
+    let quality_scores = record.quality_scores().as_ref();
+    let reference_bases: &[u8] = reference_sequence.as_ref();
+
+    let mut mismatched_positions = String::new();
+    let mut match_count = 0;
+    let mut edit_distance = 0;
+    let mut segment_likelihood = 0;
+
+    let mut reference_position = usize::from(alignment_start) - 1;
+    let mut read_position = 0;
+
+    for op in record.cigar().iter() {
+        let len = op.len();
+
+        match op.kind() {
+            Kind::Match | Kind::SequenceMatch | Kind::SequenceMismatch => {
+                for _ in 0..len {
+                    let read_base = sequence
+                        .get(read_position)
+                        .copied()
+                        .ok_or_else(invalid_sequence_length)?;
+
+                    let reference_base = get_reference_base(reference_bases, reference_position)?;
+
+                    if is_match(read_base, reference_base) {
+                        match_count += 1;
+                    } else {
+                        write!(
+                            mismatched_positions,
+                            "{}{}",
+                            match_count,
+                            char::from(reference_base)
+                        )
+                        .ok();
+
+                        match_count = 0;
+                        edit_distance += 1;
+
+                        if let Some(score) = quality_scores.get(read_position) {
+                            segment_likelihood += u32::from(score.get());
+                        }
+                    }
+
+                    read_position += 1;
+                    reference_position += 1;
+                }
+            }
+            Kind::Insertion => {
+                read_position += len;
+                edit_distance += len_to_u32(len)?;
+            }
+            Kind::Deletion => {
+                write!(mismatched_positions, "{}^", match_count).ok();
+
+                for _ in 0..len {
+                    let reference_base = get_reference_base(reference_bases, reference_position)?;
+                    mismatched_positions.push(char::from(reference_base));
+                    reference_position += 1;
+                }
+
+                match_count = 0;
+                edit_distance += len_to_u32(len)?;
+            }
+            Kind::Skip => reference_position += len,
+            Kind::SoftClip => read_position += len,
+            Kind::HardClip | Kind::Pad => {}
+        }
+    }
+
+    write!(mismatched_positions, "{}", match_count).ok();
+
+    Ok(Fields {
+        mismatched_positions,
+        edit_distance,
+        segment_likelihood: if quality_scores.is_empty() {
+            None
+        } else {
+            Some(segment_likelihood)
+        },
+    })
+}
+
+fn is_match(read_base: Base, reference_base: u8) -> bool {
+    read_base == Base::Eq
+        || (u8::from(read_base).to_ascii_uppercase() == reference_base && reference_base != b'N')
+}
+
+fn get_reference_base(reference_bases: &[u8], i: usize) -> io::Result<u8> {
+    reference_bases
+        .get(i)
+        .map(|base| base.to_ascii_uppercase())
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                "alignment extends past the end of the reference sequence",
+            )
+        })
+}
+
+fn invalid_sequence_length() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        "sequence length does not match CIGAR",
+    )
+}
+
+fn len_to_u32(len: usize) -> io::Result<u32> {
+    u32::try_from(len).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+fn int_value(n: u32) -> Value {
+    i32::try_from(n)
+        .map(Value::Int32)
+        .unwrap_or(Value::UInt32(n))
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_core::Position;
+
+    use super::*;
+
+    fn build_record(
+        alignment_start: usize,
+        cigar: &str,
+        sequence: &str,
+        quality_scores: &str,
+    ) -> Result<Record, Box<dyn std::error::Error>> {
+        let mut builder = Record::builder()
+            .set_alignment_start(Position::try_from(alignment_start)?)
+            .set_cigar(cigar.parse()?)
+            .set_sequence(sequence.parse()?);
+
+        if !quality_scores.is_empty() {
+            builder = builder.set_quality_scores(quality_scores.parse()?);
+        }
+
+        Ok(builder.build())
+    }
+
+    #[test]
+    fn test_calculate() -> Result<(), Box<dyn std::error::Error>> {
+        let reference_sequence = fasta::record::Sequence::from(b"acgtACGTNNGTACGT".to_vec());
+
+        let record = build_record(1, "4M", "ACGT", "NDLS")?;
+        let fields = calculate(&record, &reference_sequence)?;
+        assert_eq!(fields.mismatched_positions(), "4");
+        assert_eq!(fields.edit_distance(), 0);
+        assert_eq!(fields.segment_likelihood(), Some(0));
+
+        // mismatch, insertion, deletion, and soft clip
+        let record = build_record(2, "1S2M1I1M2D2M", "TCATTTT", "!!#!!(!")?;
+        let fields = calculate(&record, &reference_sequence)?;
+        assert_eq!(fields.mismatched_positions(), "1G1^AC0G1");
+        assert_eq!(fields.edit_distance(), 5);
+        assert_eq!(fields.segment_likelihood(), Some(9));
+
+        // reference skip, `=`, and `N`
+        let record = build_record(5, "2M4N2M", "A=GN", "")?;
+        let fields = calculate(&record, &reference_sequence)?;
+        assert_eq!(fields.mismatched_positions(), "3T0");
+        assert_eq!(fields.edit_distance(), 1);
+        assert_eq!(fields.segment_likelihood(), None);
+
+        let record = build_record(15, "4M", "ACGT", "NDLS")?;
+        assert!(matches!(
+            calculate(&record, &reference_sequence),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_update_and_verify() -> Result<(), Box<dyn std::error::Error>> {
+        let reference_sequence = fasta::record::Sequence::from(b"ACGT".to_vec());
+
+        let mut record = build_record(1, "4M", "ACTT", "NDLS")?;
+        *record.data_mut() = "MD:Z:4\tNM:i:0\tUQ:i:5".parse()?;
+
+        let fields = calculate(&record, &reference_sequence)?;
+
+        let tags: Vec<_> = fields
+            .verify(record.data())
+            .iter()
+            .map(|discrepancy| discrepancy.tag())
+            .collect();
+
+        assert_eq!(
+            tags,
+            [
+                Tag::MismatchedPositions,
+                Tag::EditDistance,
+                Tag::SegmentLikelihood
+            ]
+        );
+
+        fields.update(record.data_mut());
+        fields.update_segment_likelihood(record.data_mut());
+
+        assert_eq!(record.data().to_string(), "MD:Z:2G1\tNM:i:1\tUQ:i:43");
+        assert!(fields.verify(record.data()).is_empty());
+
+        Ok(())
+    }
+}
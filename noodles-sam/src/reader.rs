@@ -307,6 +307,54 @@ where
             region.interval(),
         ))
     }
+
+    /// Returns an iterator of unmapped records after querying for the unmapped region.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_csi as csi;
+    /// use noodles_sam as sam;
+    ///
+    /// let mut reader = File::open("sample.sam.gz")
+    ///     .map(bgzf::Reader::new)
+    ///     .map(sam::Reader::new)?;
+    ///
+    /// let header = reader.read_header()?.parse()?;
+    ///
+    /// let index = csi::read("sample.sam.gz.csi")?;
+    /// let query = reader.query_unmapped(&header, &index)?;
+    ///
+    /// for result in query {
+    ///     let record = result?;
+    ///     // ...
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn query_unmapped<'a, I>(
+        &'a mut self,
+        header: &'a Header,
+        index: &I,
+    ) -> io::Result<impl Iterator<Item = io::Result<Record>> + 'a>
+    where
+        I: BinningIndex,
+    {
+        if let Some(pos) = index.first_record_in_last_linear_bin_start_position() {
+            self.seek(pos)?;
+        } else {
+            self.seek(bgzf::VirtualPosition::default())?;
+            self.read_header()?;
+        }
+
+        Ok(self.records(header).filter(|result| {
+            result
+                .as_ref()
+                .map(|record| record.flags().is_unmapped())
+                .unwrap_or(true)
+        }))
+    }
 }
 
 impl<R> From<R> for Reader<R>
@@ -73,8 +73,7 @@ pub(crate) fn parse_record(mut src: &[u8], header: &Header, record: &mut Record)
     let field = next_field(&mut src);
     *record.quality_scores_mut() = parse_quality_scores(field)?;
 
-    let field = next_field(&mut src);
-    *record.data_mut() = parse_data(field)?;
+    *record.data_mut() = parse_data(src)?;
 
     Ok(())
 }
@@ -170,6 +169,27 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_parse_record_with_data() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::record::data::field::Tag;
+
+        let header = Header::default();
+        let mut record = Record::default();
+
+        parse_record(
+            b"*\t4\t*\t0\t255\t*\t*\t0\t0\t*\t*\tRG:Z:rg0\tNM:i:0",
+            &header,
+            &mut record,
+        )?;
+
+        let data = record.data();
+        assert_eq!(data.len(), 2);
+        assert!(data.get(Tag::ReadGroup).is_some());
+        assert!(data.get(Tag::EditDistance).is_some());
+
+        Ok(())
+    }
+
     #[test]
     fn test_parse_mate_reference_sequence_id() -> Result<(), Box<dyn std::error::Error>> {
         let header = Header::builder()
@@ -1,8 +1,9 @@
 //! SAM record quality scores and score.
 
 pub mod score;
+pub mod transform;
 
-pub use self::score::Score;
+pub use self::{score::Score, transform::Transform};
 
 use std::{
     error, fmt,
@@ -0,0 +1,524 @@
+//! SAM record quality scores transform.
+
+use std::fmt;
+
+use super::{QualityScores, Score};
+use crate::{
+    header::record::value::{
+        map::{builder::BuildError, Program},
+        Map,
+    },
+    record::Flags,
+    Header,
+};
+
+// Illumina 8-level binning, as (lower bound, value) pairs. Scores below the first lower bound
+// (i.e., 0 and 1) are not binned.
+static ILLUMINA_8_LEVEL_BINS: &[(Score, Score)] = &[
+    (Score(2), Score(6)),
+    (Score(10), Score(15)),
+    (Score(20), Score(22)),
+    (Score(25), Score(27)),
+    (Score(30), Score(33)),
+    (Score(35), Score(37)),
+    (Score(40), Score(40)),
+];
+
+/// A quality scores binning table.
+///
+/// Each bin is a lower bound and the value that scores greater than or equal to the lower bound
+/// (but less than the next lower bound) are replaced with. Scores less than the smallest lower
+/// bound are unchanged.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Bins(Vec<(Score, Score)>);
+
+impl Bins {
+    /// Returns the Illumina 8-level binning table.
+    ///
+    /// | scores | value |
+    /// | ------ | ----- |
+    /// | 0–1    | 0–1   |
+    /// | 2–9    | 6     |
+    /// | 10–19  | 15    |
+    /// | 20–24  | 22    |
+    /// | 25–29  | 27    |
+    /// | 30–34  | 33    |
+    /// | 35–39  | 37    |
+    /// | ≥ 40   | 40    |
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::quality_scores::{transform::Bins, Score};
+    /// let bins = Bins::illumina_8_level();
+    /// assert_eq!(bins.get(Score::try_from(13)?), Score::try_from(15)?);
+    /// # Ok::<_, noodles_sam::record::quality_scores::score::TryFromUByteError>(())
+    /// ```
+    pub fn illumina_8_level() -> Self {
+        Self(ILLUMINA_8_LEVEL_BINS.to_vec())
+    }
+
+    /// Returns the binned value of the given score.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::quality_scores::{transform::Bins, Score};
+    ///
+    /// let bins = Bins::from(vec![(Score::try_from(20)?, Score::try_from(30)?)]);
+    ///
+    /// assert_eq!(bins.get(Score::try_from(8)?), Score::try_from(8)?);
+    /// assert_eq!(bins.get(Score::try_from(25)?), Score::try_from(30)?);
+    /// # Ok::<_, noodles_sam::record::quality_scores::score::TryFromUByteError>(())
+    /// ```
+    pub fn get(&self, score: Score) -> Score {
+        match self
+            .0
+            .partition_point(|(lower_bound, _)| *lower_bound <= score)
+        {
+            0 => score,
+            i => self.0[i - 1].1,
+        }
+    }
+}
+
+impl AsRef<[(Score, Score)]> for Bins {
+    fn as_ref(&self) -> &[(Score, Score)] {
+        &self.0
+    }
+}
+
+impl From<Vec<(Score, Score)>> for Bins {
+    /// Creates a binning table from a list of lower bounds and values.
+    ///
+    /// The bins are sorted by lower bound.
+    fn from(mut bins: Vec<(Score, Score)>) -> Self {
+        bins.sort_by_key(|(lower_bound, _)| *lower_bound);
+        Self(bins)
+    }
+}
+
+/// A quality scores transform.
+///
+/// This is a lossy transform typically applied to quality scores before they are encoded.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Transform {
+    /// Replaces each score with its value in a binning table.
+    Bin(Bins),
+    /// Replaces each score with a fixed value.
+    Fixed(Score),
+    /// Removes all scores.
+    Drop,
+}
+
+impl Transform {
+    /// Returns the transformed value of a score.
+    ///
+    /// This returns `None` if the score is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::quality_scores::{Score, Transform};
+    ///
+    /// let score = Score::try_from(13)?;
+    ///
+    /// let transform = Transform::Fixed(Score::try_from(20)?);
+    /// assert_eq!(transform.get(score), Some(Score::try_from(20)?));
+    ///
+    /// assert!(Transform::Drop.get(score).is_none());
+    /// # Ok::<_, noodles_sam::record::quality_scores::score::TryFromUByteError>(())
+    /// ```
+    pub fn get(&self, score: Score) -> Option<Score> {
+        match self {
+            Self::Bin(bins) => Some(bins.get(score)),
+            Self::Fixed(value) => Some(*value),
+            Self::Drop => None,
+        }
+    }
+
+    /// Applies the transform to a list of quality scores.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::{
+    ///     quality_scores::{transform::Bins, Transform},
+    ///     QualityScores,
+    /// };
+    ///
+    /// let mut quality_scores: QualityScores = "+5?I".parse()?;
+    /// Transform::Bin(Bins::illumina_8_level()).apply(&mut quality_scores);
+    /// assert_eq!(quality_scores.to_string(), "07BI");
+    ///
+    /// Transform::Drop.apply(&mut quality_scores);
+    /// assert!(quality_scores.is_empty());
+    /// # Ok::<_, noodles_sam::record::quality_scores::ParseError>(())
+    /// ```
+    pub fn apply(&self, quality_scores: &mut QualityScores) {
+        match self {
+            Self::Drop => quality_scores.clear(),
+            _ => {
+                for score in quality_scores.0.iter_mut() {
+                    if let Some(value) = self.get(*score) {
+                        *score = value;
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Transform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bin(bins) => {
+                f.write_str("bin(")?;
+
+                for (i, (lower_bound, value)) in bins.as_ref().iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(",")?;
+                    }
+
+                    write!(f, "{}:{}", lower_bound.get(), value.get())?;
+                }
+
+                f.write_str(")")
+            }
+            Self::Fixed(value) => write!(f, "fixed({})", value.get()),
+            Self::Drop => f.write_str("drop"),
+        }
+    }
+}
+
+/// A set of quality scores transforms selected by record flags.
+///
+/// A record uses the transform of the first rule with flags that intersect the record flags. If
+/// no rules match, the default transform, if any, is used.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Transforms {
+    default: Option<Transform>,
+    rules: Vec<(Flags, Transform)>,
+}
+
+impl Transforms {
+    /// Returns whether there are no transforms.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::quality_scores::transform::Transforms;
+    /// let transforms = Transforms::default();
+    /// assert!(transforms.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.default.is_none() && self.rules.is_empty()
+    }
+
+    /// Sets the transform used for records that do not match any rule.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::quality_scores::{
+    ///     transform::{Bins, Transforms},
+    ///     Transform,
+    /// };
+    ///
+    /// let mut transforms = Transforms::default();
+    /// transforms.set_default(Transform::Bin(Bins::illumina_8_level()));
+    /// assert!(!transforms.is_empty());
+    /// ```
+    pub fn set_default(&mut self, transform: Transform) {
+        self.default = Some(transform);
+    }
+
+    /// Adds a transform used for records with any of the given flags set.
+    ///
+    /// Rules are matched in the order they are added.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::{
+    ///     quality_scores::{transform::Transforms, Transform},
+    ///     Flags,
+    /// };
+    ///
+    /// let mut transforms = Transforms::default();
+    /// transforms.push(Flags::UNMAPPED | Flags::SECONDARY, Transform::Drop);
+    ///
+    /// assert_eq!(transforms.get(Flags::SECONDARY), Some(&Transform::Drop));
+    /// assert!(transforms.get(Flags::empty()).is_none());
+    /// ```
+    pub fn push(&mut self, flags: Flags, transform: Transform) {
+        self.rules.push((flags, transform));
+    }
+
+    /// Returns the transform for a record with the given flags.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::{quality_scores::transform::Transforms, Flags};
+    /// let transforms = Transforms::default();
+    /// assert!(transforms.get(Flags::UNMAPPED).is_none());
+    /// ```
+    pub fn get(&self, flags: Flags) -> Option<&Transform> {
+        self.rules
+            .iter()
+            .find(|(rule_flags, _)| rule_flags.intersects(flags))
+            .map(|(_, transform)| transform)
+            .or(self.default.as_ref())
+    }
+
+    /// Applies the transform for a record with the given flags to its quality scores.
+    ///
+    /// This returns whether a transform was applied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::{
+    ///     quality_scores::{transform::Transforms, Transform},
+    ///     Flags, QualityScores,
+    /// };
+    ///
+    /// let mut transforms = Transforms::default();
+    /// transforms.push(Flags::UNMAPPED, Transform::Drop);
+    ///
+    /// let mut quality_scores: QualityScores = "NDLS".parse()?;
+    ///
+    /// assert!(!transforms.apply(Flags::empty(), &mut quality_scores));
+    /// assert_eq!(quality_scores.len(), 4);
+    ///
+    /// assert!(transforms.apply(Flags::UNMAPPED, &mut quality_scores));
+    /// assert!(quality_scores.is_empty());
+    /// # Ok::<_, noodles_sam::record::quality_scores::ParseError>(())
+    /// ```
+    pub fn apply(&self, flags: Flags, quality_scores: &mut QualityScores) -> bool {
+        match self.get(flags) {
+            Some(transform) => {
+                transform.apply(quality_scores);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Adds a program (`@PG`) that records the transforms to the end of the program chain of the
+    /// given header.
+    ///
+    /// The program ID is the given program name. If it is already used, a numeric suffix is
+    /// appended, e.g., `<name>.1`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{
+    ///     self as sam,
+    ///     record::quality_scores::{transform::Transforms, Transform},
+    /// };
+    ///
+    /// let mut transforms = Transforms::default();
+    /// transforms.set_default(Transform::Drop);
+    ///
+    /// let mut header = sam::Header::default();
+    /// transforms.add_program(&mut header, "noodles", "0.1.0")?;
+    ///
+    /// let program = header.programs().get("noodles").expect("missing program");
+    /// assert_eq!(program.description(), Some("quality scores transformed: *=drop"));
+    /// # Ok::<_, sam::header::record::value::map::builder::BuildError>(())
+    /// ```
+    pub fn add_program(
+        &self,
+        header: &mut Header,
+        name: &str,
+        version: &str,
+    ) -> Result<(), BuildError> {
+        let programs = header.programs();
+
+        let mut id = String::from(name);
+        let mut i = 0;
+
+        while programs.contains_key(&id) {
+            i += 1;
+            id = format!("{}.{}", name, i);
+        }
+
+        let previous_id = programs
+            .keys()
+            .rev()
+            .find(|id| {
+                !programs
+                    .values()
+                    .any(|program| program.previous_id() == Some(id.as_str()))
+            })
+            .cloned();
+
+        let mut builder = Map::<Program>::builder()
+            .set_id(id.clone())
+            .set_name(name)
+            .set_version(version)
+            .set_description(format!("quality scores transformed: {}", self));
+
+        if let Some(previous_id) = previous_id {
+            builder = builder.set_previous_id(previous_id);
+        }
+
+        let program = builder.build()?;
+        header.programs_mut().insert(id, program);
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Transforms {
+    /// Formats the transforms as a list of `<flags>=<transform>` rules separated by semicolons.
+    ///
+    /// The default transform, if set, is last and uses `*` for flags.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut is_first = true;
+
+        for (flags, transform) in &self.rules {
+            if !is_first {
+                f.write_str(";")?;
+            }
+
+            write!(f, "{:#x}={}", flags.bits(), transform)?;
+            is_first = false;
+        }
+
+        if let Some(transform) = &self.default {
+            if !is_first {
+                f.write_str(";")?;
+            }
+
+            write!(f, "*={}", transform)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_illumina_8_level() -> Result<(), Box<dyn std::error::Error>> {
+        let bins = Bins::illumina_8_level();
+
+        let cases = [
+            (0, 0),
+            (1, 1),
+            (2, 6),
+            (9, 6),
+            (10, 15),
+            (19, 15),
+            (20, 22),
+            (24, 22),
+            (25, 27),
+            (30, 33),
+            (35, 37),
+            (39, 37),
+            (40, 40),
+            (93, 40),
+        ];
+
+        for (score, expected) in cases {
+            assert_eq!(
+                bins.get(Score::try_from(score)?),
+                Score::try_from(expected)?
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_vec_score_score_for_bins() -> Result<(), Box<dyn std::error::Error>> {
+        let bins = Bins::from(vec![
+            (Score::try_from(20)?, Score::try_from(30)?),
+            (Score::try_from(10)?, Score::try_from(15)?),
+        ]);
+
+        assert_eq!(bins.get(Score::try_from(12)?), Score::try_from(15)?);
+        assert_eq!(bins.get(Score::try_from(25)?), Score::try_from(30)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_apply() -> Result<(), Box<dyn std::error::Error>> {
+        let mut quality_scores: QualityScores = "NDLS".parse()?;
+        Transform::Fixed(Score::try_from(10)?).apply(&mut quality_scores);
+        assert_eq!(quality_scores.to_string(), "++++");
+        Ok(())
+    }
+
+    #[test]
+    fn test_transforms_get() {
+        let mut transforms = Transforms::default();
+        transforms.set_default(Transform::Bin(Bins::illumina_8_level()));
+        transforms.push(Flags::UNMAPPED | Flags::SECONDARY, Transform::Drop);
+
+        assert_eq!(transforms.get(Flags::UNMAPPED), Some(&Transform::Drop));
+        assert_eq!(
+            transforms.get(Flags::SEGMENTED | Flags::SECONDARY),
+            Some(&Transform::Drop)
+        );
+        assert_eq!(
+            transforms.get(Flags::SEGMENTED),
+            Some(&Transform::Bin(Bins::illumina_8_level()))
+        );
+    }
+
+    #[test]
+    fn test_fmt() -> Result<(), Box<dyn std::error::Error>> {
+        let mut transforms = Transforms::default();
+        assert!(transforms.to_string().is_empty());
+
+        transforms.push(Flags::UNMAPPED | Flags::SECONDARY, Transform::Drop);
+        transforms.set_default(Transform::Bin(Bins::from(vec![
+            (Score::try_from(10)?, Score::try_from(15)?),
+            (Score::try_from(20)?, Score::try_from(30)?),
+        ])));
+
+        assert_eq!(transforms.to_string(), "0x104=drop;*=bin(10:15,20:30)");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_program() -> Result<(), Box<dyn std::error::Error>> {
+        let mut header = Header::builder()
+            .add_program(Map::<Program>::new("noodles"))
+            .add_program(
+                Map::<Program>::builder()
+                    .set_id("pg1")
+                    .set_previous_id("noodles")
+                    .build()?,
+            )
+            .build();
+
+        let mut transforms = Transforms::default();
+        transforms.set_default(Transform::Drop);
+        transforms.add_program(&mut header, "noodles", "0.1.0")?;
+
+        let program = header
+            .programs()
+            .get("noodles.1")
+            .ok_or("missing program")?;
+
+        assert_eq!(program.name(), Some("noodles"));
+        assert_eq!(program.version(), Some("0.1.0"));
+        assert_eq!(program.previous_id(), Some("pg1"));
+        assert_eq!(
+            program.description(),
+            Some("quality scores transformed: *=drop")
+        );
+
+        Ok(())
+    }
+}
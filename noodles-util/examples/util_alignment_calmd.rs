@@ -0,0 +1,78 @@
+//! Calculates and sets the `MD` and `NM` data fields of records in an alignment file.
+//!
+//! The output format is determined from the extension of the destination, e.g., to convert a
+//! CRAM to a BAM with `MD` and `NM` data fields. Stored values that differ from the calculated
+//! values are reported to stderr.
+//!
+//! The result is similar to the output of `samtools calmd <src> <fasta-src>`.
+
+use std::{env, io};
+
+use noodles_fasta::{self as fasta, repository::adapters::IndexedReader};
+use noodles_sam::alignment::record::calmd;
+use noodles_util::alignment;
+
+fn main() -> io::Result<()> {
+    let mut args = env::args().skip(1);
+
+    let src = args.next().expect("missing src");
+    let dst = args.next().expect("missing dst");
+    let fasta_src = args.next().expect("missing fasta src");
+
+    let repository = fasta::indexed_reader::Builder::default()
+        .build_from_path(fasta_src)
+        .map(IndexedReader::new)
+        .map(fasta::Repository::new)?;
+
+    let mut reader = alignment::reader::Builder::default()
+        .set_reference_sequence_repository(repository.clone())
+        .build_from_path(src)?;
+
+    let header = reader.read_header()?;
+
+    let mut writer = alignment::writer::Builder::default()
+        .set_reference_sequence_repository(repository.clone())
+        .build_from_path(dst)?;
+
+    writer.write_header(&header)?;
+
+    for result in reader.records(&header) {
+        let mut record = result?;
+
+        if !record.flags().is_unmapped() && !record.sequence().is_empty() {
+            let reference_sequence_name = record
+                .reference_sequence(&header)
+                .transpose()?
+                .map(|reference_sequence| reference_sequence.name().to_string())
+                .expect("missing reference sequence");
+
+            let reference_sequence = repository
+                .get(&reference_sequence_name)
+                .transpose()?
+                .expect("missing reference sequence");
+
+            let fields = calmd::calculate(&record, &reference_sequence)?;
+
+            for discrepancy in fields.verify(record.data()) {
+                eprintln!(
+                    "{}: {}: expected {}, got {}",
+                    record
+                        .read_name()
+                        .map(|name| name.to_string())
+                        .unwrap_or_default(),
+                    discrepancy.tag(),
+                    discrepancy.expected(),
+                    discrepancy.actual()
+                );
+            }
+
+            fields.update(record.data_mut());
+        }
+
+        writer.write_record(&header, &record)?;
+    }
+
+    writer.finish(&header)?;
+
+    Ok(())
+}
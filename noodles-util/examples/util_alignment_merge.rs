@@ -0,0 +1,54 @@
+//! Merges coordinate-sorted alignment files.
+//!
+//! The output format is determined from the extension of the destination. Reference sequences in
+//! the FASTA format are only required for CRAM inputs and outputs that require them.
+//!
+//! The result is similar to the output of `samtools merge --no-PG -o <dst> <srcs...>`.
+
+use std::{env, io};
+
+use noodles_fasta::{self as fasta, repository::adapters::IndexedReader};
+use noodles_util::alignment;
+
+fn main() -> io::Result<()> {
+    let mut args = env::args().skip(1);
+
+    let dst = args.next().expect("missing dst");
+    let srcs: Vec<_> = args.collect();
+
+    let fasta_src = env::var("REF_PATH").ok();
+
+    let repository = fasta_src
+        .map(|src| fasta::indexed_reader::Builder::default().build_from_path(src))
+        .transpose()?
+        .map(IndexedReader::new)
+        .map(fasta::Repository::new)
+        .unwrap_or_default();
+
+    let readers = srcs
+        .iter()
+        .map(|src| {
+            alignment::reader::Builder::default()
+                .set_reference_sequence_repository(repository.clone())
+                .build_from_path(src)
+        })
+        .collect::<io::Result<_>>()?;
+
+    let mut merger = alignment::merge::Builder::default().build(readers)?;
+    let header = merger.header().clone();
+
+    let mut writer = alignment::writer::Builder::default()
+        .set_reference_sequence_repository(repository)
+        .build_from_path(dst)?;
+
+    writer.write_header(&header)?;
+
+    for result in merger.records()? {
+        let record = result?;
+        writer.write_record(&header, &record)?;
+    }
+
+    writer.finish(&header)?;
+
+    Ok(())
+}
@@ -0,0 +1,154 @@
+//! Prints a pileup of a coordinate-sorted alignment file.
+//!
+//! Each line is the reference sequence name, position, reference base, depth, read bases, and
+//! base quality scores. The reference base is `N` unless reference sequences in the FASTA format
+//! are given using the `REF_PATH` environment variable.
+//!
+//! The result is similar to the output of `samtools mpileup --no-BAQ [--fasta-ref <ref>] <src>`.
+
+use std::{
+    env,
+    io::{self, BufWriter, Write},
+};
+
+use noodles_fasta::{self as fasta, repository::adapters::IndexedReader};
+use noodles_util::alignment::{
+    self,
+    pileup::{self, Entry, Indel},
+};
+
+fn write_entry<W>(writer: &mut W, entry: &Entry, reference_base: Option<u8>) -> io::Result<()>
+where
+    W: Write,
+{
+    let is_reverse_complemented = entry.record().flags().is_reverse_complemented();
+
+    if entry.is_head() {
+        let mapping_quality = entry
+            .record()
+            .mapping_quality()
+            .map(u8::from)
+            .unwrap_or(255);
+        write!(writer, "^{}", char::from(mapping_quality.min(93) + b'!'))?;
+    }
+
+    if let Some(base) = entry.base() {
+        let base = u8::from(base);
+
+        let is_reference_match = reference_base
+            .map(|reference_base| reference_base.eq_ignore_ascii_case(&base))
+            .unwrap_or(false);
+
+        let c = if is_reference_match {
+            if is_reverse_complemented {
+                b','
+            } else {
+                b'.'
+            }
+        } else if is_reverse_complemented {
+            base.to_ascii_lowercase()
+        } else {
+            base.to_ascii_uppercase()
+        };
+
+        writer.write_all(&[c])?;
+    } else if entry.is_reference_skip() {
+        let c = if is_reverse_complemented { b'<' } else { b'>' };
+        writer.write_all(&[c])?;
+    } else {
+        writer.write_all(b"*")?;
+    }
+
+    match entry.indel() {
+        Some(Indel::Insertion(len)) => {
+            let start = entry.query_position().map(|i| i + 1).unwrap_or_default();
+            let bases: String = entry
+                .record()
+                .sequence()
+                .as_ref()
+                .get(start..start + len)
+                .unwrap_or_default()
+                .iter()
+                .map(|&base| char::from(base))
+                .collect();
+
+            write!(writer, "+{}{}", len, bases)?;
+        }
+        Some(Indel::Deletion(len)) => write!(writer, "-{}{}", len, "N".repeat(len))?,
+        None => {}
+    }
+
+    if entry.is_tail() {
+        writer.write_all(b"$")?;
+    }
+
+    Ok(())
+}
+
+fn main() -> io::Result<()> {
+    let src = env::args().nth(1).expect("missing src");
+
+    let fasta_src = env::var("REF_PATH").ok();
+
+    let repository = fasta_src
+        .map(|src| fasta::indexed_reader::Builder::default().build_from_path(src))
+        .transpose()?
+        .map(IndexedReader::new)
+        .map(fasta::Repository::new);
+
+    let mut reader = alignment::reader::Builder::default()
+        .set_reference_sequence_repository(repository.clone().unwrap_or_default())
+        .build_from_path(src)?;
+
+    let header = reader.read_header()?;
+
+    let mut builder = pileup::Builder::default();
+
+    if let Some(repository) = repository {
+        builder = builder.set_reference_sequence_repository(repository);
+    }
+
+    let stdout = io::stdout().lock();
+    let mut writer = BufWriter::new(stdout);
+
+    for result in builder.build(&header, reader.records(&header)) {
+        let column = result?;
+
+        let name = header
+            .reference_sequences()
+            .get_index(column.reference_sequence_id())
+            .map(|(name, _)| name.as_str())
+            .unwrap_or_default();
+
+        let reference_base = column.reference_base();
+
+        write!(
+            writer,
+            "{}\t{}\t{}\t{}\t",
+            name,
+            column.position(),
+            char::from(reference_base.unwrap_or(b'N')),
+            column.depth()
+        )?;
+
+        for entry in column.entries() {
+            write_entry(&mut writer, entry, reference_base)?;
+        }
+
+        writer.write_all(b"\t")?;
+
+        for entry in column.entries() {
+            let c = if entry.is_reference_skip() || entry.is_deletion() {
+                '~'
+            } else {
+                entry.quality_score().map(char::from).unwrap_or('~')
+            };
+
+            write!(writer, "{}", c)?;
+        }
+
+        writeln!(writer)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+//! Sorts an alignment file by coordinate.
+//!
+//! The output format is determined from the extension of the destination. Reference sequences in
+//! the FASTA format are only required for CRAM inputs and outputs that require them.
+//!
+//! The result is similar to the output of `samtools sort --no-PG -o <dst> <src>`.
+
+use std::{env, io};
+
+use noodles_fasta::{self as fasta, repository::adapters::IndexedReader};
+use noodles_util::alignment;
+
+fn main() -> io::Result<()> {
+    let mut args = env::args().skip(1);
+
+    let src = args.next().expect("missing src");
+    let dst = args.next().expect("missing dst");
+    let fasta_src = args.next();
+
+    let repository = fasta_src
+        .map(|src| fasta::indexed_reader::Builder::default().build_from_path(src))
+        .transpose()?
+        .map(IndexedReader::new)
+        .map(fasta::Repository::new)
+        .unwrap_or_default();
+
+    let mut reader = alignment::reader::Builder::default()
+        .set_reference_sequence_repository(repository.clone())
+        .build_from_path(src)?;
+
+    let header = reader.read_header()?;
+
+    let mut sorter = alignment::sort::Builder::default().build(header.clone());
+
+    for result in reader.records(&header) {
+        let record = result?;
+        sorter.push(record)?;
+    }
+
+    let header = sorter.header().clone();
+
+    let mut writer = alignment::writer::Builder::default()
+        .set_reference_sequence_repository(repository)
+        .build_from_path(dst)?;
+
+    writer.write_header(&header)?;
+
+    for result in sorter.finish()? {
+        let record = result?;
+        writer.write_record(&header, &record)?;
+    }
+
+    writer.finish(&header)?;
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+//! Verifies that the records of an alignment file round trip through CRAM without loss.
+//!
+//! Each record is encoded to CRAM, decoded, and compared to the original. Differences are
+//! printed to stdout, followed by the number of differences per category. The process exits with
+//! a nonzero status if any difference is found.
+//!
+//! A reference sequence FASTA can be given as the second argument, in which case records are
+//! encoded against it.
+
+use std::{env, io, process};
+
+use noodles_fasta::{self as fasta, repository::adapters::IndexedReader};
+use noodles_util::alignment::{self, verify};
+
+fn main() -> io::Result<()> {
+    let mut args = env::args().skip(1);
+
+    let src = args.next().expect("missing src");
+    let fasta_src = args.next();
+
+    let mut reader_builder = alignment::reader::Builder::default();
+    let mut verifier_builder = verify::Builder::default();
+
+    if let Some(fasta_src) = fasta_src {
+        let repository = fasta::indexed_reader::Builder::default()
+            .build_from_path(fasta_src)
+            .map(IndexedReader::new)
+            .map(fasta::Repository::new)?;
+
+        reader_builder = reader_builder.set_reference_sequence_repository(repository.clone());
+        verifier_builder = verifier_builder.set_reference_sequence_repository(repository);
+    }
+
+    let mut reader = reader_builder.build_from_path(src)?;
+    let header = reader.read_header()?;
+
+    let verifier = verifier_builder.build();
+    let report = verifier.verify(&header, reader.records(&header))?;
+
+    for difference in report.differences() {
+        println!(
+            "{}\t{}\texpected: {}\tactual: {}",
+            difference.record_index(),
+            difference.category(),
+            difference.expected(),
+            difference.actual()
+        );
+    }
+
+    println!("records: {}", report.record_count());
+
+    for (category, count) in report.category_counts() {
+        println!("{}: {}", category, count);
+    }
+
+    if !report.is_lossless() {
+        process::exit(1);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,36 @@
+//! Concatenates BGZF-compressed VCF or BCF files and writes the result to stdout.
+//!
+//! Inputs are concatenated by copying their compressed blocks. Pass `--allow-overlaps` as the
+//! first argument to instead merge the records of overlapping inputs by position.
+//!
+//! The result is similar to the output of `bcftools concat --naive <srcs...>` or
+//! `bcftools concat --no-version --allow-overlaps --output-type z <srcs...>`.
+
+use std::{
+    env,
+    fs::File,
+    io::{self, BufWriter},
+};
+
+use noodles_util::variant::concat::{self, Mode};
+
+fn main() -> io::Result<()> {
+    let mut args = env::args().skip(1).peekable();
+
+    let mode = if args.next_if(|arg| arg == "--allow-overlaps").is_some() {
+        Mode::Overlapping
+    } else {
+        Mode::Naive
+    };
+
+    let readers = args.map(File::open).collect::<io::Result<_>>()?;
+
+    let concatenator = concat::Builder::default().set_mode(mode).build(readers)?;
+
+    let stdout = io::stdout().lock();
+    let mut writer = BufWriter::new(stdout);
+
+    concatenator.write(&mut writer)?;
+
+    Ok(())
+}
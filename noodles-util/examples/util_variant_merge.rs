@@ -0,0 +1,41 @@
+//! Merges the samples of sorted variant files and prints the result in the VCF format.
+//!
+//! The inputs can be VCF or BCF, uncompressed or compressed.
+//!
+//! The result is similar to the output of `bcftools merge --no-version -m none <srcs...>`.
+
+use std::{
+    env,
+    io::{self, BufWriter},
+};
+
+use noodles_util::variant::{self, Format};
+
+fn main() -> io::Result<()> {
+    let srcs: Vec<_> = env::args().skip(1).collect();
+
+    let readers = srcs
+        .iter()
+        .map(|src| variant::reader::Builder::default().build_from_path(src))
+        .collect::<io::Result<_>>()?;
+
+    let mut merger = variant::merge::Merger::new(readers)?;
+    let header = merger.header().clone();
+
+    let stdout = io::stdout().lock();
+
+    let mut writer = variant::writer::Builder::default()
+        .set_format(Format::Vcf)
+        .build_from_writer(BufWriter::new(stdout));
+
+    writer.write_header(&header)?;
+
+    for result in merger.records() {
+        let record = result?;
+        writer.write_record(&header, &record)?;
+    }
+
+    writer.finish()?;
+
+    Ok(())
+}
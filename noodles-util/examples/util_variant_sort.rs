@@ -0,0 +1,44 @@
+//! Sorts a variant file by position and prints the result in the VCF format.
+//!
+//! The input can be a VCF or BCF, uncompressed or compressed. All reference sequences must be
+//! defined as contigs in the header.
+//!
+//! The result is similar to the output of `bcftools sort <src>`.
+
+use std::{
+    env,
+    io::{self, BufWriter},
+};
+
+use noodles_util::variant::{self, Format};
+
+fn main() -> io::Result<()> {
+    let src = env::args().nth(1).expect("missing src");
+
+    let mut reader = variant::reader::Builder::default().build_from_path(src)?;
+    let header = reader.read_header()?;
+
+    let mut sorter = variant::sort::Builder::default().build(header.clone());
+
+    for result in reader.records(&header) {
+        let record = result?;
+        sorter.push(record)?;
+    }
+
+    let stdout = io::stdout().lock();
+
+    let mut writer = variant::writer::Builder::default()
+        .set_format(Format::Vcf)
+        .build_from_writer(BufWriter::new(stdout));
+
+    writer.write_header(&header)?;
+
+    for result in sorter.finish()? {
+        let record = result?;
+        writer.write_record(&header, &record)?;
+    }
+
+    writer.finish()?;
+
+    Ok(())
+}
@@ -0,0 +1,36 @@
+//! Prints a variant file in the VCF format.
+//!
+//! The input can be a VCF or BCF, uncompressed or compressed.
+//!
+//! The result matches the output of `bcftools view --no-version <src>`.
+
+use std::{
+    env,
+    io::{self, BufWriter},
+};
+
+use noodles_util::variant::{self, Format};
+
+fn main() -> io::Result<()> {
+    let src = env::args().nth(1).expect("missing src");
+
+    let mut reader = variant::reader::Builder::default().build_from_path(src)?;
+    let header = reader.read_header()?;
+
+    let stdout = io::stdout().lock();
+
+    let mut writer = variant::writer::Builder::default()
+        .set_format(Format::Vcf)
+        .build_from_writer(BufWriter::new(stdout));
+
+    writer.write_header(&header)?;
+
+    for result in reader.records(&header) {
+        let record = result?;
+        writer.write_record(&header, &record)?;
+    }
+
+    writer.finish()?;
+
+    Ok(())
+}
@@ -1,7 +1,11 @@
 //! I/O for alignment formats.
 
 mod format;
+pub mod merge;
+pub mod pileup;
 pub mod reader;
+pub mod sort;
+pub mod verify;
 pub mod writer;
 
 pub use self::{format::Format, reader::Reader, writer::Writer};
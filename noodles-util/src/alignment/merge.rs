@@ -0,0 +1,211 @@
+//! Alignment file merging.
+//!
+//! Inputs must already be sorted in the same order. Their records are k-way merged, and their
+//! headers are reconciled into a single output header: reference sequences (`@SQ`) are unioned,
+//! and read groups (`@RG`) and programs (`@PG`) with clashing IDs are renamed. The reference
+//! sequence IDs and `RG` and `PG` data fields of merged records are rewritten to match the output
+//! header.
+//!
+//! # Examples
+//!
+//! ```
+//! # use std::io::{self, Cursor};
+//! use noodles_util::alignment::{self, merge};
+//!
+//! let readers = [
+//!     &b"@SQ\tSN:sq0\tLN:8\n*\t4\t*\t0\t255\t*\t*\t0\t0\t*\t*\n"[..],
+//!     &b"@SQ\tSN:sq1\tLN:13\n*\t4\t*\t0\t255\t*\t*\t0\t0\t*\t*\n"[..],
+//! ]
+//! .into_iter()
+//! .map(|data| alignment::reader::Builder::default().build_from_reader(Cursor::new(data)))
+//! .collect::<io::Result<_>>()?;
+//!
+//! let mut merger = merge::Builder::default().build(readers)?;
+//!
+//! let names: Vec<_> = merger.header().reference_sequences().keys().collect();
+//! assert_eq!(names, ["sq0", "sq1"]);
+//!
+//! let records: Vec<_> = merger.records()?.collect::<io::Result<_>>()?;
+//! assert_eq!(records.len(), 2);
+//! # Ok::<_, io::Error>(())
+//! ```
+
+mod builder;
+mod header;
+
+pub use self::builder::Builder;
+
+use std::io::{self, Read};
+
+use noodles_sam::{self as sam, alignment::Record};
+
+use self::header::Mapping;
+use super::{
+    sort::{key::Key, Order},
+    Reader,
+};
+use crate::sort::{self, Chunk, SortKey};
+
+/// An alignment file merger.
+///
+/// This is created using a [`Builder`].
+pub struct Merger<R> {
+    readers: Vec<Reader<R>>,
+    headers: Vec<sam::Header>,
+    mappings: Vec<Mapping>,
+    header: sam::Header,
+    order: Order,
+}
+
+impl<R> Merger<R>
+where
+    R: Read,
+{
+    /// Returns the merged header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Cursor};
+    /// use noodles_util::alignment::{self, merge};
+    ///
+    /// let data = Cursor::new(b"@SQ\tSN:sq0\tLN:8\n");
+    /// let reader = alignment::reader::Builder::default().build_from_reader(data)?;
+    ///
+    /// let merger = merge::Builder::default().build(vec![reader])?;
+    ///
+    /// let names: Vec<_> = merger.header().reference_sequences().keys().collect();
+    /// assert_eq!(names, ["sq0"]);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn header(&self) -> &sam::Header {
+        &self.header
+    }
+
+    /// Returns an iterator over the merged records.
+    ///
+    /// Records that compare equal are returned in input order.
+    pub fn records(&mut self) -> io::Result<Records<'_>> {
+        let mut chunks: Vec<Chunk<'_, Record>> = Vec::with_capacity(self.readers.len());
+
+        for ((reader, header), mapping) in self
+            .readers
+            .iter_mut()
+            .zip(&self.headers)
+            .zip(&self.mappings)
+        {
+            let records = reader.records(header).map(move |result| {
+                result.and_then(|mut record| {
+                    mapping.apply(&mut record)?;
+                    Ok(record)
+                })
+            });
+
+            chunks.push(Box::new(records));
+        }
+
+        let sort_key = MergeKey {
+            order: self.order,
+            header: &self.header,
+        };
+
+        sort::Merge::new(sort_key, chunks, Vec::new()).map(|inner| Records { inner })
+    }
+}
+
+/// An iterator over merged alignment records.
+pub struct Records<'a> {
+    inner: sort::Merge<'a, MergeKey<'a>>,
+}
+
+impl<'a> Iterator for Records<'a> {
+    type Item = io::Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+struct MergeKey<'a> {
+    order: Order,
+    header: &'a sam::Header,
+}
+
+impl<'a> SortKey for MergeKey<'a> {
+    type Record = Record;
+    type Key = Key;
+
+    fn key(&self, record: &Self::Record) -> io::Result<Self::Key> {
+        Key::new(self.order, self.header, record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::alignment;
+
+    fn build_reader(data: &'static [u8]) -> io::Result<Reader<Cursor<&'static [u8]>>> {
+        alignment::reader::Builder::default().build_from_reader(Cursor::new(data))
+    }
+
+    #[test]
+    fn test_records() -> Result<(), Box<dyn std::error::Error>> {
+        let readers = vec![
+            build_reader(
+                b"@SQ\tSN:sq0\tLN:100\n@SQ\tSN:sq1\tLN:100\n@RG\tID:rg0\tLB:lb0
+r0\t0\tsq0\t5\t255\t4M\t*\t0\t0\tACGT\tNDLS\tRG:Z:rg0
+r1\t0\tsq1\t3\t255\t4M\t*\t0\t0\tACGT\tNDLS\tRG:Z:rg0
+",
+            )?,
+            build_reader(
+                b"@SQ\tSN:sq1\tLN:100\n@SQ\tSN:sq2\tLN:100\n@RG\tID:rg0\tLB:lb1
+r2\t0\tsq1\t1\t255\t4M\t*\t0\t0\tACGT\tNDLS\tRG:Z:rg0
+r3\t0\tsq2\t8\t255\t4M\t*\t0\t0\tACGT\tNDLS\tRG:Z:rg0
+",
+            )?,
+        ];
+
+        let mut merger = Builder::default().build(readers)?;
+
+        let names: Vec<_> = merger.header().reference_sequences().keys().collect();
+        assert_eq!(names, ["sq0", "sq1", "sq2"]);
+
+        let read_group_ids: Vec<_> = merger.header().read_groups().keys().collect();
+        assert_eq!(read_group_ids, ["rg0", "rg0-1"]);
+
+        let actual: Vec<_> = merger
+            .records()?
+            .map(|result| {
+                result.map(|record| {
+                    (
+                        record.read_name().map(|name| name.to_string()),
+                        record.reference_sequence_id(),
+                        record.data().to_string(),
+                    )
+                })
+            })
+            .collect::<io::Result<_>>()?;
+
+        let expected = [
+            (Some(String::from("r0")), Some(0), String::from("RG:Z:rg0")),
+            (
+                Some(String::from("r2")),
+                Some(1),
+                String::from("RG:Z:rg0-1"),
+            ),
+            (Some(String::from("r1")), Some(1), String::from("RG:Z:rg0")),
+            (
+                Some(String::from("r3")),
+                Some(2),
+                String::from("RG:Z:rg0-1"),
+            ),
+        ];
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+}
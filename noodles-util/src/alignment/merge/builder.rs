@@ -0,0 +1,116 @@
+use std::io::{self, Read};
+
+use super::{header::merge_headers, Merger};
+use crate::alignment::{sort::Order, Reader};
+
+/// An alignment file merger builder.
+#[derive(Debug, Default)]
+pub struct Builder {
+    order: Order,
+}
+
+impl Builder {
+    /// Sets the sort order of the inputs.
+    ///
+    /// All inputs must be sorted in this order. By default, inputs are expected to be sorted by
+    /// coordinate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::{merge, sort::Order};
+    /// let builder = merge::Builder::default().set_order(Order::QueryName);
+    /// ```
+    pub fn set_order(mut self, order: Order) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Builds an alignment file merger from readers.
+    ///
+    /// This reads the header of each reader and merges them. The sort order fields (`SO`, `GO`,
+    /// and `SS`) of the merged header are set to the sort order.
+    ///
+    /// This returns an [`io::ErrorKind::InvalidData`] error if the reference sequence
+    /// dictionaries of the inputs are incompatible, i.e., a reference sequence with the same name
+    /// has a different length or MD5 checksum or, when merging by position, the reference
+    /// sequences of an input are in a different relative order than in the merged header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Cursor};
+    /// use noodles_util::alignment::{self, merge};
+    ///
+    /// let data = Cursor::new(b"@SQ\tSN:sq0\tLN:8\n");
+    /// let reader = alignment::reader::Builder::default().build_from_reader(data)?;
+    ///
+    /// let merger = merge::Builder::default().build(vec![reader])?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn build<R>(self, mut readers: Vec<Reader<R>>) -> io::Result<Merger<R>>
+    where
+        R: Read,
+    {
+        let headers = readers
+            .iter_mut()
+            .map(|reader| reader.read_header())
+            .collect::<io::Result<Vec<_>>>()?;
+
+        let (mut header, mappings) = merge_headers(&headers)?;
+
+        if self.order != Order::QueryName
+            && !mappings
+                .iter()
+                .all(|mapping| mapping.is_reference_sequence_order_preserved())
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "reference sequence dictionary mismatch: reference sequences are in a different order",
+            ));
+        }
+
+        self.order.update_header(&mut header);
+
+        Ok(Merger {
+            readers,
+            headers,
+            mappings,
+            header,
+            order: self.order,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::alignment;
+
+    #[test]
+    fn test_build_with_reference_sequences_in_different_order() -> io::Result<()> {
+        let build_readers = || {
+            [
+                &b"@SQ\tSN:sq0\tLN:8\n@SQ\tSN:sq1\tLN:13\n"[..],
+                &b"@SQ\tSN:sq1\tLN:13\n@SQ\tSN:sq0\tLN:8\n"[..],
+            ]
+            .into_iter()
+            .map(|data| alignment::reader::Builder::default().build_from_reader(Cursor::new(data)))
+            .collect::<io::Result<Vec<_>>>()
+        };
+
+        assert!(matches!(
+            Builder::default().build(build_readers()?),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        assert!(Builder::default()
+            .set_order(Order::QueryName)
+            .build(build_readers()?)
+            .is_ok());
+
+        Ok(())
+    }
+}
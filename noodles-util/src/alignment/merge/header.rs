@@ -0,0 +1,335 @@
+use std::{collections::HashMap, io};
+
+use noodles_sam::{
+    self as sam,
+    alignment::Record,
+    header::{Programs, ReadGroups},
+    record::data::field::{Tag, Value},
+};
+
+/// A mapping of the IDs of an input header to the IDs of the merged header.
+#[derive(Debug, Default, Eq, PartialEq)]
+pub(crate) struct Mapping {
+    reference_sequence_ids: Vec<usize>,
+    read_group_ids: HashMap<String, String>,
+    program_ids: HashMap<String, String>,
+}
+
+impl Mapping {
+    /// Returns whether the reference sequences of the input are in the same relative order in
+    /// the merged header.
+    pub(crate) fn is_reference_sequence_order_preserved(&self) -> bool {
+        self.reference_sequence_ids.windows(2).all(|w| w[0] < w[1])
+    }
+
+    /// Rewrites the reference sequence IDs and read group (`RG`) and program (`PG`) data fields
+    /// of a record.
+    pub(crate) fn apply(&self, record: &mut Record) -> io::Result<()> {
+        if let Some(id) = record.reference_sequence_id() {
+            *record.reference_sequence_id_mut() = Some(self.map_reference_sequence_id(id)?);
+        }
+
+        if let Some(id) = record.mate_reference_sequence_id() {
+            *record.mate_reference_sequence_id_mut() = Some(self.map_reference_sequence_id(id)?);
+        }
+
+        rename_data_field(record, Tag::ReadGroup, &self.read_group_ids);
+        rename_data_field(record, Tag::Program, &self.program_ids);
+
+        Ok(())
+    }
+
+    fn map_reference_sequence_id(&self, id: usize) -> io::Result<usize> {
+        self.reference_sequence_ids.get(id).copied().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid reference sequence ID: {}", id),
+            )
+        })
+    }
+}
+
+fn rename_data_field(record: &mut Record, tag: Tag, ids: &HashMap<String, String>) {
+    if ids.is_empty() {
+        return;
+    }
+
+    let new_id = record
+        .data()
+        .get(tag)
+        .and_then(|field| field.value().as_str())
+        .and_then(|id| ids.get(id));
+
+    if let Some(new_id) = new_id {
+        let field = sam::record::data::Field::new(tag, Value::String(new_id.clone()));
+        record.data_mut().insert(field);
+    }
+}
+
+/// Merges headers.
+///
+/// Reference sequences (`@SQ`) are unioned. A reference sequence with the same name in multiple
+/// headers must have the same length and, if set, MD5 checksum. Identical read groups (`@RG`) and
+/// programs (`@PG`) are deduplicated, and those with clashing IDs are renamed with a numeric
+/// suffix. Comments (`@CO`) are deduplicated. The header (`@HD`) is taken from the first header
+/// that has one.
+pub(crate) fn merge_headers(headers: &[sam::Header]) -> io::Result<(sam::Header, Vec<Mapping>)> {
+    let mut merged_header = sam::Header::default();
+    let mut mappings = Vec::with_capacity(headers.len());
+
+    *merged_header.header_mut() = headers.iter().find_map(|header| header.header().cloned());
+
+    for header in headers {
+        let reference_sequence_ids = merge_reference_sequences(&mut merged_header, header)?;
+        let read_group_ids = merge_read_groups(merged_header.read_groups_mut(), header);
+        let program_ids = merge_programs(merged_header.programs_mut(), header);
+
+        for comment in header.comments() {
+            if !merged_header.comments().contains(comment) {
+                merged_header.add_comment(comment.clone());
+            }
+        }
+
+        mappings.push(Mapping {
+            reference_sequence_ids,
+            read_group_ids,
+            program_ids,
+        });
+    }
+
+    Ok((merged_header, mappings))
+}
+
+fn merge_reference_sequences(
+    merged_header: &mut sam::Header,
+    header: &sam::Header,
+) -> io::Result<Vec<usize>> {
+    let reference_sequences = merged_header.reference_sequences_mut();
+    let mut ids = Vec::with_capacity(header.reference_sequences().len());
+
+    for (name, reference_sequence) in header.reference_sequences() {
+        if let Some((i, _, merged_reference_sequence)) = reference_sequences.get_full(name) {
+            let is_length_eq = reference_sequence.length() == merged_reference_sequence.length();
+
+            let is_checksum_eq = match (
+                reference_sequence.md5_checksum(),
+                merged_reference_sequence.md5_checksum(),
+            ) {
+                (Some(a), Some(b)) => a == b,
+                _ => true,
+            };
+
+            if !is_length_eq || !is_checksum_eq {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "reference sequence dictionary mismatch: {} (LN:{}) differs from {} (LN:{})",
+                        name,
+                        reference_sequence.length(),
+                        name,
+                        merged_reference_sequence.length(),
+                    ),
+                ));
+            }
+
+            ids.push(i);
+        } else {
+            ids.push(reference_sequences.len());
+            reference_sequences.insert(name.clone(), reference_sequence.clone());
+        }
+    }
+
+    Ok(ids)
+}
+
+fn merge_read_groups(
+    read_groups: &mut ReadGroups,
+    header: &sam::Header,
+) -> HashMap<String, String> {
+    let mut ids = HashMap::new();
+
+    for (id, read_group) in header.read_groups() {
+        match read_groups.get(id) {
+            Some(merged_read_group) if merged_read_group == read_group => {}
+            Some(_) => {
+                let new_id = unique_id(id, |id| read_groups.contains_key(id));
+
+                let mut read_group = read_group.clone();
+                *read_group.id_mut() = new_id.clone();
+                read_groups.insert(new_id.clone(), read_group);
+
+                ids.insert(id.clone(), new_id);
+            }
+            None => {
+                read_groups.insert(id.clone(), read_group.clone());
+            }
+        }
+    }
+
+    ids
+}
+
+fn merge_programs(programs: &mut Programs, header: &sam::Header) -> HashMap<String, String> {
+    let mut ids = HashMap::new();
+
+    for (id, program) in header.programs() {
+        let mut program = program.clone();
+
+        if let Some(new_previous_id) = program.previous_id().and_then(|pp| ids.get(pp)).cloned() {
+            *program.previous_id_mut() = Some(new_previous_id);
+        }
+
+        match programs.get(id) {
+            Some(merged_program) if merged_program == &program => {}
+            Some(_) => {
+                let new_id = unique_id(id, |id| programs.contains_key(id));
+                *program.id_mut() = new_id.clone();
+                programs.insert(new_id.clone(), program);
+                ids.insert(id.clone(), new_id);
+            }
+            None => {
+                programs.insert(id.clone(), program);
+            }
+        }
+    }
+
+    ids
+}
+
+fn unique_id<F>(id: &str, exists: F) -> String
+where
+    F: Fn(&str) -> bool,
+{
+    (1..)
+        .map(|n| format!("{}-{}", id, n))
+        .find(|new_id| !exists(new_id))
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use sam::header::record::value::{
+        map::{Program, ReadGroup, ReferenceSequence},
+        Map,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_merge_headers() -> Result<(), Box<dyn std::error::Error>> {
+        let header_0 = sam::Header::builder()
+            .add_reference_sequence(Map::<ReferenceSequence>::new("sq0".parse()?, 8)?)
+            .add_reference_sequence(Map::<ReferenceSequence>::new("sq1".parse()?, 13)?)
+            .add_read_group(Map::<ReadGroup>::new("rg0"))
+            .add_program(Map::<Program>::new("pg0"))
+            .add_comment("noodles")
+            .build();
+
+        let mut program = Map::<Program>::new("pg1");
+        *program.previous_id_mut() = Some(String::from("pg0"));
+
+        let header_1 = sam::Header::builder()
+            .add_reference_sequence(Map::<ReferenceSequence>::new("sq1".parse()?, 13)?)
+            .add_reference_sequence(Map::<ReferenceSequence>::new("sq2".parse()?, 21)?)
+            .add_read_group(
+                Map::<ReadGroup>::builder()
+                    .set_id("rg0")
+                    .set_library("lb0")
+                    .build()?,
+            )
+            .add_read_group(Map::<ReadGroup>::new("rg1"))
+            .add_program(
+                Map::<Program>::builder()
+                    .set_id("pg0")
+                    .set_name("noodles")
+                    .build()?,
+            )
+            .add_program(program)
+            .add_comment("noodles")
+            .build();
+
+        let (actual, mappings) = merge_headers(&[header_0, header_1])?;
+
+        let names: Vec<_> = actual.reference_sequences().keys().collect();
+        assert_eq!(names, ["sq0", "sq1", "sq2"]);
+
+        let read_group_ids: Vec<_> = actual.read_groups().keys().collect();
+        assert_eq!(read_group_ids, ["rg0", "rg0-1", "rg1"]);
+
+        let program_ids: Vec<_> = actual.programs().keys().collect();
+        assert_eq!(program_ids, ["pg0", "pg0-1", "pg1"]);
+        assert_eq!(actual.programs()["pg1"].previous_id(), Some("pg0-1"));
+
+        assert_eq!(actual.comments(), ["noodles"]);
+
+        assert_eq!(
+            mappings[0],
+            Mapping {
+                reference_sequence_ids: vec![0, 1],
+                ..Default::default()
+            }
+        );
+
+        assert_eq!(mappings[1].reference_sequence_ids, [1, 2]);
+        assert_eq!(
+            mappings[1].read_group_ids.get("rg0").map(|s| s.as_str()),
+            Some("rg0-1")
+        );
+        assert_eq!(
+            mappings[1].program_ids.get("pg0").map(|s| s.as_str()),
+            Some("pg0-1")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_headers_with_mismatched_reference_sequences(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let header_0 = sam::Header::builder()
+            .add_reference_sequence(Map::<ReferenceSequence>::new("sq0".parse()?, 8)?)
+            .build();
+
+        let header_1 = sam::Header::builder()
+            .add_reference_sequence(Map::<ReferenceSequence>::new("sq0".parse()?, 13)?)
+            .build();
+
+        assert!(matches!(
+            merge_headers(&[header_0, header_1]),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_apply() -> Result<(), Box<dyn std::error::Error>> {
+        let mapping = Mapping {
+            reference_sequence_ids: vec![1, 2],
+            read_group_ids: [(String::from("rg0"), String::from("rg0-1"))]
+                .into_iter()
+                .collect(),
+            program_ids: HashMap::new(),
+        };
+
+        let mut record = Record::builder()
+            .set_reference_sequence_id(0)
+            .set_mate_reference_sequence_id(1)
+            .set_data("RG:Z:rg0\tPG:Z:pg0".parse()?)
+            .build();
+
+        mapping.apply(&mut record)?;
+
+        assert_eq!(record.reference_sequence_id(), Some(1));
+        assert_eq!(record.mate_reference_sequence_id(), Some(2));
+        assert_eq!(record.data().to_string(), "RG:Z:rg0-1\tPG:Z:pg0");
+
+        let mut record = Record::builder().set_reference_sequence_id(2).build();
+        assert!(matches!(
+            mapping.apply(&mut record),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,523 @@
+//! Alignment pileup.
+//!
+//! A pileup walks coordinate-sorted records and yields a [`Column`] for each covered reference
+//! position. In [`Mode::Full`], each column has an [`Entry`] for each overlapping record with the
+//! offset of the aligned base in the read and whether the position is a deletion, reference skip,
+//! or read end or is followed by an insertion or deletion. In [`Mode::Depth`], only the depth is
+//! computed.
+//!
+//! # Examples
+//!
+//! ```
+//! # use std::io::{self, Cursor};
+//! use noodles_util::alignment::{self, pileup};
+//!
+//! let data = b"@SQ\tSN:sq0\tLN:8
+//! r0\t0\tsq0\t1\t60\t2M1D1M\t*\t0\t0\tACG\tNDL
+//! r1\t0\tsq0\t2\t60\t2M\t*\t0\t0\tCT\tLS
+//! ";
+//!
+//! let mut reader = alignment::reader::Builder::default().build_from_reader(Cursor::new(data))?;
+//! let header = reader.read_header()?;
+//!
+//! let depths: Vec<_> = pileup::Builder::default()
+//!     .build(&header, reader.records(&header))
+//!     .map(|result| result.map(|column| (usize::from(column.position()), column.depth())))
+//!     .collect::<io::Result<_>>()?;
+//!
+//! assert_eq!(depths, [(1, 1), (2, 2), (3, 2), (4, 1)]);
+//! # Ok::<_, io::Error>(())
+//! ```
+
+mod builder;
+mod column;
+mod entry;
+mod mode;
+
+pub use self::{
+    builder::Builder,
+    column::Column,
+    entry::{Entry, Indel},
+    mode::Mode,
+};
+
+use std::{collections::VecDeque, io, iter::Fuse, sync::Arc};
+
+use noodles_core::Position;
+use noodles_fasta as fasta;
+use noodles_sam::{
+    self as sam,
+    alignment::Record,
+    record::{cigar::op::Kind, Cigar, Flags},
+};
+
+/// An alignment pileup.
+///
+/// This is an iterator over columns. It is created using a [`Builder`].
+pub struct Pileup<'a, I> {
+    records: Fuse<I>,
+    header: &'a sam::Header,
+    mode: Mode,
+    min_base_quality: u8,
+    min_mapping_quality: u8,
+    max_depth: Option<usize>,
+    excluded_flags: Flags,
+    reference_sequence_repository: Option<fasta::Repository>,
+    reference_sequence: Option<(usize, fasta::record::Sequence)>,
+    pending_record: Option<(usize, usize, Record)>,
+    last_key: Option<(usize, usize)>,
+    active_records: VecDeque<ActiveRecord>,
+    reference_sequence_id: usize,
+    position: usize,
+}
+
+impl<'a, I> Pileup<'a, I>
+where
+    I: Iterator<Item = io::Result<Record>>,
+{
+    // Reads the next record that passes the filters, returning it with its reference sequence ID
+    // and alignment start.
+    fn read_record(&mut self) -> io::Result<Option<(usize, usize, Record)>> {
+        for result in self.records.by_ref() {
+            let record = result?;
+
+            if record.flags().intersects(self.excluded_flags) {
+                continue;
+            }
+
+            let mapping_quality = record.mapping_quality().map(u8::from).unwrap_or(255);
+
+            if mapping_quality < self.min_mapping_quality {
+                continue;
+            }
+
+            let (reference_sequence_id, alignment_start) =
+                match (record.reference_sequence_id(), record.alignment_start()) {
+                    (Some(id), Some(start)) => (id, usize::from(start)),
+                    _ => continue,
+                };
+
+            let key = (reference_sequence_id, alignment_start);
+
+            if let Some(last_key) = self.last_key {
+                if key < last_key {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "invalid record order: records must be coordinate-sorted",
+                    ));
+                }
+            }
+
+            self.last_key = Some(key);
+
+            return Ok(Some((reference_sequence_id, alignment_start, record)));
+        }
+
+        Ok(None)
+    }
+
+    fn fill_pending_record(&mut self) -> io::Result<()> {
+        if self.pending_record.is_none() {
+            self.pending_record = self.read_record()?;
+        }
+
+        Ok(())
+    }
+
+    // Adds the pending records that start at the current position.
+    fn add_records(&mut self) -> io::Result<()> {
+        loop {
+            self.fill_pending_record()?;
+
+            match &self.pending_record {
+                Some((id, start, _))
+                    if *id == self.reference_sequence_id && *start == self.position => {}
+                _ => break,
+            }
+
+            // SAFETY: `pending_record` is `Some`.
+            let (_, start, record) = self.pending_record.take().unwrap();
+
+            if let Some(max_depth) = self.max_depth {
+                if self.active_records.len() >= max_depth {
+                    continue;
+                }
+            }
+
+            let events = build_events(record.cigar());
+
+            if !events.is_empty() {
+                self.active_records.push_back(ActiveRecord {
+                    record: Arc::new(record),
+                    start,
+                    events,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn reference_base(&mut self) -> io::Result<Option<u8>> {
+        let repository = match &self.reference_sequence_repository {
+            Some(repository) => repository,
+            None => return Ok(None),
+        };
+
+        let is_cached = matches!(
+            &self.reference_sequence,
+            Some((id, _)) if *id == self.reference_sequence_id
+        );
+
+        if !is_cached {
+            let name = self
+                .header
+                .reference_sequences()
+                .get_index(self.reference_sequence_id)
+                .map(|(name, _)| name)
+                .ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!(
+                            "invalid reference sequence ID: {}",
+                            self.reference_sequence_id
+                        ),
+                    )
+                })?;
+
+            let sequence = repository.get(name).transpose()?.ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("missing reference sequence: {}", name),
+                )
+            })?;
+
+            self.reference_sequence = Some((self.reference_sequence_id, sequence));
+        }
+
+        let position = Position::new(self.position);
+
+        Ok(self
+            .reference_sequence
+            .as_ref()
+            .zip(position)
+            .and_then(|((_, sequence), position)| sequence.get(position))
+            .copied())
+    }
+
+    fn build_column(&mut self) -> io::Result<Column> {
+        let position = Position::new(self.position)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid position"))?;
+
+        let reference_base = self.reference_base()?;
+
+        let mut depth = 0;
+        let mut entries = Vec::new();
+
+        for active_record in &self.active_records {
+            let i = self.position - active_record.start;
+            let event = active_record.events[i];
+
+            if let Some(query_position) = event.query_position {
+                let score = active_record
+                    .record
+                    .quality_scores()
+                    .as_ref()
+                    .get(query_position)
+                    .map(|score| score.get());
+
+                if matches!(score, Some(s) if s < self.min_base_quality) {
+                    continue;
+                }
+            }
+
+            if !event.is_reference_skip {
+                depth += 1;
+            }
+
+            if self.mode == Mode::Full {
+                entries.push(Entry {
+                    record: active_record.record.clone(),
+                    query_position: event.query_position,
+                    is_reference_skip: event.is_reference_skip,
+                    is_head: i == 0,
+                    is_tail: i == active_record.events.len() - 1,
+                    indel: event.indel,
+                });
+            }
+        }
+
+        Ok(Column {
+            reference_sequence_id: self.reference_sequence_id,
+            position,
+            reference_base,
+            depth,
+            entries,
+        })
+    }
+}
+
+impl<'a, I> Iterator for Pileup<'a, I>
+where
+    I: Iterator<Item = io::Result<Record>>,
+{
+    type Item = io::Result<Column>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.active_records.is_empty() {
+            if let Err(e) = self.fill_pending_record() {
+                return Some(Err(e));
+            }
+
+            let (id, start, _) = self.pending_record.as_ref()?;
+            self.reference_sequence_id = *id;
+            self.position = *start;
+        }
+
+        if let Err(e) = self.add_records() {
+            return Some(Err(e));
+        }
+
+        let column = self.build_column();
+
+        self.position += 1;
+
+        let position = self.position;
+        self.active_records
+            .retain(|active_record| active_record.end() >= position);
+
+        Some(column)
+    }
+}
+
+struct ActiveRecord {
+    record: Arc<Record>,
+    start: usize,
+    events: Vec<Event>,
+}
+
+impl ActiveRecord {
+    fn end(&self) -> usize {
+        self.start + self.events.len() - 1
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+struct Event {
+    query_position: Option<usize>,
+    is_reference_skip: bool,
+    indel: Option<Indel>,
+}
+
+// Builds an event for each reference position consumed by the alignment.
+fn build_events(cigar: &Cigar) -> Vec<Event> {
+    let mut events: Vec<Event> = Vec::with_capacity(cigar.alignment_span());
+    let mut query_position = 0;
+
+    for op in cigar.iter() {
+        let len = op.len();
+
+        match op.kind() {
+            Kind::Match | Kind::SequenceMatch | Kind::SequenceMismatch => {
+                events.extend((query_position..query_position + len).map(|i| Event {
+                    query_position: Some(i),
+                    ..Default::default()
+                }));
+
+                query_position += len;
+            }
+            Kind::Insertion => {
+                if let Some(event) = events.last_mut() {
+                    event.indel = Some(Indel::Insertion(len));
+                }
+
+                query_position += len;
+            }
+            Kind::Deletion => {
+                if let Some(event) = events.last_mut() {
+                    event.indel = Some(Indel::Deletion(len));
+                }
+
+                events.extend((0..len).map(|_| Event::default()));
+            }
+            Kind::Skip => {
+                events.extend((0..len).map(|_| Event {
+                    is_reference_skip: true,
+                    ..Default::default()
+                }));
+            }
+            Kind::SoftClip => query_position += len,
+            Kind::HardClip | Kind::Pad => {}
+        }
+    }
+
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::alignment;
+
+    #[test]
+    fn test_build_events() -> Result<(), sam::record::cigar::ParseError> {
+        let cigar = "2S2M1I1M1D1N1M".parse()?;
+        let actual = build_events(&cigar);
+
+        let expected = [
+            Event {
+                query_position: Some(2),
+                ..Default::default()
+            },
+            Event {
+                query_position: Some(3),
+                indel: Some(Indel::Insertion(1)),
+                ..Default::default()
+            },
+            Event {
+                query_position: Some(5),
+                indel: Some(Indel::Deletion(1)),
+                ..Default::default()
+            },
+            Event::default(),
+            Event {
+                is_reference_skip: true,
+                ..Default::default()
+            },
+            Event {
+                query_position: Some(6),
+                ..Default::default()
+            },
+        ];
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    fn read_columns(data: &'static [u8], builder: Builder) -> io::Result<Vec<Column>> {
+        let mut reader =
+            alignment::reader::Builder::default().build_from_reader(Cursor::new(data))?;
+        let header = reader.read_header()?;
+        let records: Vec<_> = reader.records(&header).collect::<io::Result<_>>()?;
+        builder
+            .build(&header, records.into_iter().map(Ok))
+            .collect()
+    }
+
+    #[test]
+    fn test_next() -> io::Result<()> {
+        const DATA: &[u8] = b"@SQ\tSN:sq0\tLN:8\n@SQ\tSN:sq1\tLN:8
+r0\t0\tsq0\t1\t60\t2M1D1M\t*\t0\t0\tACG\tNDL
+r1\t0\tsq0\t2\t10\t2M\t*\t0\t0\tCT\t!S
+r2\t1024\tsq0\t2\t60\t2M\t*\t0\t0\tCT\tLS
+r3\t0\tsq1\t5\t60\t1M\t*\t0\t0\tA\tN
+";
+
+        let columns = read_columns(DATA, Builder::default())?;
+
+        let actual: Vec<_> = columns
+            .iter()
+            .map(|column| {
+                (
+                    column.reference_sequence_id(),
+                    usize::from(column.position()),
+                    column.depth(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            actual,
+            [(0, 1, 1), (0, 2, 2), (0, 3, 2), (0, 4, 1), (1, 5, 1)]
+        );
+
+        let entries = columns[1].entries();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].query_position(), Some(1));
+        assert_eq!(entries[0].indel(), Some(Indel::Deletion(1)));
+        assert!(entries[1].is_head());
+
+        let entries = columns[2].entries();
+        assert!(entries[0].is_deletion());
+        assert!(entries[1].is_tail());
+
+        let columns = read_columns(
+            DATA,
+            Builder::default()
+                .set_mode(Mode::Depth)
+                .set_min_base_quality(1)
+                .set_min_mapping_quality(20),
+        )?;
+
+        let actual: Vec<_> = columns
+            .iter()
+            .map(|column| (usize::from(column.position()), column.depth()))
+            .collect();
+
+        assert_eq!(actual, [(1, 1), (2, 1), (3, 1), (4, 1), (5, 1)]);
+        assert!(columns.iter().all(|column| column.entries().is_empty()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_with_max_depth() -> io::Result<()> {
+        const DATA: &[u8] = b"@SQ\tSN:sq0\tLN:8
+r0\t0\tsq0\t1\t60\t2M\t*\t0\t0\tAC\tND
+r1\t0\tsq0\t1\t60\t2M\t*\t0\t0\tAC\tND
+r2\t0\tsq0\t2\t60\t1M\t*\t0\t0\tC\tN
+";
+
+        let columns = read_columns(DATA, Builder::default().set_max_depth(1))?;
+        let depths: Vec<_> = columns.iter().map(|column| column.depth()).collect();
+        assert_eq!(depths, [1, 1]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_with_unsorted_records() -> io::Result<()> {
+        const DATA: &[u8] = b"@SQ\tSN:sq0\tLN:8
+r0\t0\tsq0\t5\t60\t1M\t*\t0\t0\tA\tN
+r1\t0\tsq0\t1\t60\t1M\t*\t0\t0\tA\tN
+";
+
+        assert!(matches!(
+            read_columns(DATA, Builder::default()),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_with_reference_sequence_repository() -> io::Result<()> {
+        use fasta::repository::adapters::IndexedReader;
+
+        const DATA: &[u8] = b"@SQ\tSN:sq0\tLN:4
+r0\t0\tsq0\t2\t60\t2M\t*\t0\t0\tCA\tND
+";
+
+        let fasta_data = b">sq0\nACGT\n";
+        let index = vec![fasta::fai::Record::new(String::from("sq0"), 4, 5, 4, 5)];
+        let reader = fasta::IndexedReader::new(Cursor::new(fasta_data), index);
+        let repository = fasta::Repository::new(IndexedReader::new(reader));
+
+        let columns = read_columns(
+            DATA,
+            Builder::default().set_reference_sequence_repository(repository),
+        )?;
+
+        let reference_bases: Vec<_> = columns
+            .iter()
+            .map(|column| column.reference_base())
+            .collect();
+
+        assert_eq!(reference_bases, [Some(b'C'), Some(b'G')]);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,173 @@
+use std::{collections::VecDeque, io};
+
+use noodles_fasta as fasta;
+use noodles_sam::{self as sam, alignment::Record, record::Flags};
+
+use super::{Mode, Pileup};
+
+/// An alignment pileup builder.
+#[derive(Debug)]
+pub struct Builder {
+    mode: Mode,
+    min_base_quality: u8,
+    min_mapping_quality: u8,
+    max_depth: Option<usize>,
+    excluded_flags: Flags,
+    reference_sequence_repository: Option<fasta::Repository>,
+}
+
+impl Builder {
+    /// Sets the pileup mode.
+    ///
+    /// By default, columns include their entries ([`Mode::Full`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::pileup::{self, Mode};
+    /// let builder = pileup::Builder::default().set_mode(Mode::Depth);
+    /// ```
+    pub fn set_mode(mut self, mode: Mode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets the minimum base quality.
+    ///
+    /// Aligned bases with a quality score less than this are excluded from columns. By default,
+    /// this is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::pileup;
+    /// let builder = pileup::Builder::default().set_min_base_quality(13);
+    /// ```
+    pub fn set_min_base_quality(mut self, min_base_quality: u8) -> Self {
+        self.min_base_quality = min_base_quality;
+        self
+    }
+
+    /// Sets the minimum mapping quality.
+    ///
+    /// Records with a mapping quality less than this are skipped. A missing mapping quality is
+    /// treated as 255. By default, this is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::pileup;
+    /// let builder = pileup::Builder::default().set_min_mapping_quality(20);
+    /// ```
+    pub fn set_min_mapping_quality(mut self, min_mapping_quality: u8) -> Self {
+        self.min_mapping_quality = min_mapping_quality;
+        self
+    }
+
+    /// Sets the maximum depth.
+    ///
+    /// A record is skipped if the number of records already overlapping its alignment start is
+    /// at least this. By default, depth is unlimited.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::pileup;
+    /// let builder = pileup::Builder::default().set_max_depth(8000);
+    /// ```
+    pub fn set_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Sets the flags of records to skip.
+    ///
+    /// A record is skipped if any of these flags are set. By default, these are unmapped,
+    /// secondary, QC fail, and duplicate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::Flags;
+    /// use noodles_util::alignment::pileup;
+    /// let builder = pileup::Builder::default().set_excluded_flags(Flags::UNMAPPED);
+    /// ```
+    pub fn set_excluded_flags(mut self, excluded_flags: Flags) -> Self {
+        self.excluded_flags = excluded_flags;
+        self
+    }
+
+    /// Sets a reference sequence repository.
+    ///
+    /// When set, each column includes its reference base.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fasta as fasta;
+    /// use noodles_util::alignment::pileup;
+    ///
+    /// let repository = fasta::Repository::default();
+    /// let builder = pileup::Builder::default().set_reference_sequence_repository(repository);
+    /// ```
+    pub fn set_reference_sequence_repository(
+        mut self,
+        reference_sequence_repository: fasta::Repository,
+    ) -> Self {
+        self.reference_sequence_repository = Some(reference_sequence_repository);
+        self
+    }
+
+    /// Builds a pileup from an iterator of records.
+    ///
+    /// Records must be coordinate-sorted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_sam as sam;
+    /// use noodles_util::alignment::pileup;
+    ///
+    /// let header = sam::Header::default();
+    /// let records = Vec::new();
+    ///
+    /// let mut pileup = pileup::Builder::default().build(&header, records);
+    /// assert!(pileup.next().is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn build<I>(self, header: &sam::Header, records: I) -> Pileup<'_, I::IntoIter>
+    where
+        I: IntoIterator<Item = io::Result<Record>>,
+    {
+        Pileup {
+            records: records.into_iter().fuse(),
+            header,
+            mode: self.mode,
+            min_base_quality: self.min_base_quality,
+            min_mapping_quality: self.min_mapping_quality,
+            max_depth: self.max_depth,
+            excluded_flags: self.excluded_flags,
+            reference_sequence_repository: self.reference_sequence_repository,
+            reference_sequence: None,
+            pending_record: None,
+            last_key: None,
+            active_records: VecDeque::new(),
+            reference_sequence_id: 0,
+            position: 0,
+        }
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            mode: Mode::default(),
+            min_base_quality: 0,
+            min_mapping_quality: 0,
+            max_depth: None,
+            excluded_flags: Flags::UNMAPPED | Flags::SECONDARY | Flags::QC_FAIL | Flags::DUPLICATE,
+            reference_sequence_repository: None,
+        }
+    }
+}
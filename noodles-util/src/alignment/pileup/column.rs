@@ -0,0 +1,49 @@
+use noodles_core::Position;
+
+use super::Entry;
+
+/// A pileup column.
+///
+/// A column is a single reference position and the records that overlap it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Column {
+    pub(super) reference_sequence_id: usize,
+    pub(super) position: Position,
+    pub(super) reference_base: Option<u8>,
+    pub(super) depth: usize,
+    pub(super) entries: Vec<Entry>,
+}
+
+impl Column {
+    /// Returns the reference sequence ID.
+    pub fn reference_sequence_id(&self) -> usize {
+        self.reference_sequence_id
+    }
+
+    /// Returns the reference position.
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    /// Returns the reference base.
+    ///
+    /// This is only set when the pileup is built with a reference sequence repository.
+    pub fn reference_base(&self) -> Option<u8> {
+        self.reference_base
+    }
+
+    /// Returns the depth.
+    ///
+    /// This is the number of records with an aligned base or deletion at this position. Reference
+    /// skips and bases below the minimum base quality are not counted.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns the entries.
+    ///
+    /// This is empty when the pileup is built in [`super::Mode::Depth`].
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+}
@@ -0,0 +1,79 @@
+use std::sync::Arc;
+
+use noodles_sam::{
+    alignment::Record,
+    record::{quality_scores::Score, sequence::Base},
+};
+
+/// An insertion or deletion following a pileup entry.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Indel {
+    /// An insertion of the given length into the reference.
+    Insertion(usize),
+    /// A deletion of the given length from the reference.
+    Deletion(usize),
+}
+
+/// A record overlapping a pileup column.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Entry {
+    pub(super) record: Arc<Record>,
+    pub(super) query_position: Option<usize>,
+    pub(super) is_reference_skip: bool,
+    pub(super) is_head: bool,
+    pub(super) is_tail: bool,
+    pub(super) indel: Option<Indel>,
+}
+
+impl Entry {
+    /// Returns the record.
+    pub fn record(&self) -> &Record {
+        &self.record
+    }
+
+    /// Returns the 0-based offset of the aligned base in the read sequence.
+    ///
+    /// This is `None` if the reference position is deleted or skipped in the read.
+    pub fn query_position(&self) -> Option<usize> {
+        self.query_position
+    }
+
+    /// Returns the aligned read base.
+    pub fn base(&self) -> Option<Base> {
+        self.query_position
+            .and_then(|i| self.record.sequence().as_ref().get(i))
+            .copied()
+    }
+
+    /// Returns the quality score of the aligned read base.
+    pub fn quality_score(&self) -> Option<Score> {
+        self.query_position
+            .and_then(|i| self.record.quality_scores().as_ref().get(i))
+            .copied()
+    }
+
+    /// Returns whether the reference position is deleted in the read (`D`).
+    pub fn is_deletion(&self) -> bool {
+        self.query_position.is_none() && !self.is_reference_skip
+    }
+
+    /// Returns whether the reference position is skipped in the read (`N`).
+    pub fn is_reference_skip(&self) -> bool {
+        self.is_reference_skip
+    }
+
+    /// Returns whether this is the first reference position of the alignment.
+    pub fn is_head(&self) -> bool {
+        self.is_head
+    }
+
+    /// Returns whether this is the last reference position of the alignment.
+    pub fn is_tail(&self) -> bool {
+        self.is_tail
+    }
+
+    /// Returns the insertion or deletion immediately following this reference position, if any.
+    pub fn indel(&self) -> Option<Indel> {
+        self.indel
+    }
+}
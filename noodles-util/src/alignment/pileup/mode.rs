@@ -0,0 +1,11 @@
+/// A pileup mode.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Mode {
+    /// Each column includes the entries of the overlapping records.
+    #[default]
+    Full,
+    /// Each column only includes the depth.
+    ///
+    /// This avoids building the entries of each column when only coverage is needed.
+    Depth,
+}
@@ -4,21 +4,78 @@ mod builder;
 
 pub use self::builder::Builder;
 
-use std::io::{self, Read};
+use std::io::{self, BufReader, Read, Seek};
 
+use noodles_bam::{self as bam, bai};
+use noodles_bgzf as bgzf;
+use noodles_core::Region;
+use noodles_cram::{self as cram, crai};
+use noodles_csi as csi;
 use noodles_fasta as fasta;
 use noodles_sam::{self as sam, alignment::Record, AlignmentReader};
 
+/// An index associated with an alignment file.
+#[derive(Debug)]
+pub enum Index {
+    /// A BAM index (BAI).
+    Bai(bai::Index),
+    /// A coordinate-sorted index (CSI).
+    Csi(csi::Index),
+    /// A CRAM index (CRAI).
+    Crai(crai::Index),
+}
+
+impl From<bai::Index> for Index {
+    fn from(index: bai::Index) -> Self {
+        Self::Bai(index)
+    }
+}
+
+impl From<csi::Index> for Index {
+    fn from(index: csi::Index) -> Self {
+        Self::Csi(index)
+    }
+}
+
+impl From<crai::Index> for Index {
+    fn from(index: crai::Index) -> Self {
+        Self::Crai(index)
+    }
+}
+
+enum Inner<R> {
+    Sam(sam::Reader<BufReader<R>>),
+    BgzfSam(sam::Reader<bgzf::Reader<R>>),
+    Bam(bam::Reader<bgzf::Reader<R>>),
+    Cram(cram::Reader<BufReader<R>>),
+}
+
 /// An alignment reader.
 pub struct Reader<R> {
-    inner: Box<dyn AlignmentReader<R>>,
+    inner: Inner<R>,
     reference_sequence_repository: fasta::Repository,
+    index: Option<Index>,
 }
 
 impl<R> Reader<R>
 where
     R: Read,
 {
+    /// Returns the associated index, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_util::alignment;
+    /// let reader = alignment::reader::Builder::default().build_from_reader(io::empty())?;
+    /// assert!(reader.index().is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn index(&self) -> Option<&Index> {
+        self.index.as_ref()
+    }
+
     /// Reads and parses an alignment header.
     ///
     /// # Examples
@@ -46,7 +103,12 @@ where
     /// # Ok::<_, io::Error>(())
     /// ```
     pub fn read_header(&mut self) -> io::Result<sam::Header> {
-        self.inner.read_alignment_header()
+        match &mut self.inner {
+            Inner::Sam(reader) => reader.read_alignment_header(),
+            Inner::BgzfSam(reader) => reader.read_alignment_header(),
+            Inner::Bam(reader) => reader.read_alignment_header(),
+            Inner::Cram(reader) => reader.read_alignment_header(),
+        }
     }
 
     /// Returns an iterator over records starting from the current stream position.
@@ -75,7 +137,165 @@ where
         &'a mut self,
         header: &'a sam::Header,
     ) -> impl Iterator<Item = io::Result<Record>> + 'a {
-        self.inner
-            .alignment_records(&self.reference_sequence_repository, header)
+        let repository = &self.reference_sequence_repository;
+
+        match &mut self.inner {
+            Inner::Sam(reader) => reader.alignment_records(repository, header),
+            Inner::BgzfSam(reader) => reader.alignment_records(repository, header),
+            Inner::Bam(reader) => reader.alignment_records(repository, header),
+            Inner::Cram(reader) => reader.alignment_records(repository, header),
+        }
+    }
+}
+
+impl<R> Reader<R>
+where
+    R: Read + Seek,
+{
+    /// Returns an iterator over records that intersect the given region.
+    ///
+    /// This requires an associated index, either set using [`Builder::set_index`] or found next
+    /// to the input when using [`Builder::build_from_path`]. Uncompressed SAM inputs cannot be
+    /// queried.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use noodles_util::alignment;
+    ///
+    /// let mut reader = alignment::reader::Builder::default().build_from_path("sample.bam")?;
+    /// let header = reader.read_header()?;
+    ///
+    /// let region = "sq0:8-13".parse()?;
+    /// let query = reader.query(&header, &region)?;
+    ///
+    /// for result in query {
+    ///     let record = result?;
+    ///     // ...
+    /// }
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn query<'a>(
+        &'a mut self,
+        header: &'a sam::Header,
+        region: &Region,
+    ) -> io::Result<Box<dyn Iterator<Item = io::Result<Record>> + 'a>> {
+        let index = self.index.as_ref().ok_or_else(missing_index)?;
+        let repository = &self.reference_sequence_repository;
+
+        match (&mut self.inner, index) {
+            (Inner::Sam(_), _) => Err(unsupported_query()),
+            (Inner::BgzfSam(reader), Index::Csi(index)) => reader
+                .query(header, index, region)
+                .map(|query| Box::new(query) as Box<dyn Iterator<Item = _>>),
+            (Inner::Bam(reader), Index::Bai(index)) => reader
+                .query(header.reference_sequences(), index, region)
+                .map(|query| Box::new(query) as Box<dyn Iterator<Item = _>>),
+            (Inner::Bam(reader), Index::Csi(index)) => reader
+                .query(header.reference_sequences(), index, region)
+                .map(|query| Box::new(query) as Box<dyn Iterator<Item = _>>),
+            (Inner::Cram(reader), Index::Crai(index)) => {
+                let query = reader.query(repository, header, index, region)?;
+
+                Ok(Box::new(query.map(|result| {
+                    result.and_then(|record| record.try_into_alignment_record(header))
+                })))
+            }
+            _ => Err(invalid_index()),
+        }
+    }
+
+    /// Returns an iterator of unmapped records after querying for the unmapped region.
+    ///
+    /// Like [`Self::query`], this requires an associated index.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io;
+    /// use noodles_util::alignment;
+    ///
+    /// let mut reader = alignment::reader::Builder::default().build_from_path("sample.bam")?;
+    /// let header = reader.read_header()?;
+    ///
+    /// for result in reader.query_unmapped(&header)? {
+    ///     let record = result?;
+    ///     // ...
+    /// }
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn query_unmapped<'a>(
+        &'a mut self,
+        header: &'a sam::Header,
+    ) -> io::Result<Box<dyn Iterator<Item = io::Result<Record>> + 'a>> {
+        let index = self.index.as_ref().ok_or_else(missing_index)?;
+        let repository = &self.reference_sequence_repository;
+
+        match (&mut self.inner, index) {
+            (Inner::Sam(_), _) => Err(unsupported_query()),
+            (Inner::BgzfSam(reader), Index::Csi(index)) => reader
+                .query_unmapped(header, index)
+                .map(|query| Box::new(query) as Box<dyn Iterator<Item = _>>),
+            (Inner::Bam(reader), Index::Bai(index)) => reader
+                .query_unmapped(index)
+                .map(|query| Box::new(query) as Box<dyn Iterator<Item = _>>),
+            (Inner::Bam(reader), Index::Csi(index)) => reader
+                .query_unmapped(index)
+                .map(|query| Box::new(query) as Box<dyn Iterator<Item = _>>),
+            (Inner::Cram(reader), Index::Crai(index)) => {
+                let query = reader.query_unmapped(repository, header, index)?;
+
+                Ok(Box::new(query.map(|result| {
+                    result.and_then(|record| record.try_into_alignment_record(header))
+                })))
+            }
+            _ => Err(invalid_index()),
+        }
+    }
+}
+
+fn missing_index() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, "missing index")
+}
+
+fn invalid_index() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        "index type does not match the input format",
+    )
+}
+
+fn unsupported_query() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        "uncompressed SAM cannot be queried",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[test]
+    fn test_query_without_index() -> io::Result<()> {
+        let data = Cursor::new(b"@HD\tVN:1.6\n");
+        let mut reader = Builder::default().build_from_reader(data)?;
+        let header = reader.read_header()?;
+
+        let region = Region::new("sq0", ..);
+
+        assert!(matches!(
+            reader.query(&header, &region),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        assert!(matches!(
+            reader.query_unmapped(&header),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
     }
 }
@@ -1,17 +1,18 @@
 use std::{
+    ffi::{OsStr, OsString},
     fs::File,
-    io::{self, BufRead, BufReader, Read, Seek},
-    path::Path,
+    io::{self, BufReader, Read, Seek},
+    path::{Path, PathBuf},
 };
 
-use noodles_bam as bam;
+use noodles_bam::{self as bam, bai};
 use noodles_bgzf as bgzf;
-use noodles_cram as cram;
+use noodles_cram::{self as cram, crai};
+use noodles_csi as csi;
 use noodles_fasta as fasta;
 use noodles_sam as sam;
-use sam::AlignmentReader;
 
-use super::Reader;
+use super::{Index, Inner, Reader};
 use crate::alignment::Format;
 
 /// An alignment reader builder.
@@ -19,6 +20,7 @@ use crate::alignment::Format;
 pub struct Builder {
     format: Option<Format>,
     reference_sequence_repository: fasta::Repository,
+    index: Option<Index>,
 }
 
 impl Builder {
@@ -58,10 +60,36 @@ impl Builder {
         self
     }
 
+    /// Sets an index.
+    ///
+    /// This can be a BAM index (BAI), a coordinate-sorted index (CSI), or a CRAM index (CRAI). It
+    /// is used by [`Reader::query`] and [`Reader::query_unmapped`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::bai;
+    /// use noodles_util::alignment;
+    ///
+    /// let index = bai::Index::default();
+    /// let builder = alignment::reader::Builder::default().set_index(index);
+    /// ```
+    pub fn set_index<I>(mut self, index: I) -> Self
+    where
+        I: Into<Index>,
+    {
+        self.index = Some(index.into());
+        self
+    }
+
     /// Builds an alignment reader from a path.
     ///
     /// By default, the format will be autodetected. This can be overridden by using
-    /// [`set_format`]. An associated index will also attempt to be loaded.
+    /// [`set_format`].
+    ///
+    /// If no index is set, this attempts to read an associated index for the detected format:
+    /// `<src>.bai` or `<src>.csi` for BAM, `<src>.csi` for bgzipped SAM, and `<src>.crai` for
+    /// CRAM. A missing index is not an error, but the resulting reader cannot be queried.
     ///
     /// # Examples
     ///
@@ -71,11 +99,23 @@ impl Builder {
     /// let reader = alignment::reader::Builder::default().build_from_path("sample.bam")?;
     /// # Ok::<_, io::Error>(())
     /// ```
-    pub fn build_from_path<P>(self, path: P) -> io::Result<Reader<Box<dyn BufRead>>>
+    pub fn build_from_path<P>(mut self, src: P) -> io::Result<Reader<File>>
     where
         P: AsRef<Path>,
     {
-        let file = File::open(path)?;
+        let src = src.as_ref();
+        let mut file = File::open(src)?;
+
+        if self.index.is_none() {
+            let format = match self.format {
+                Some(format) => format,
+                None => detect_format(&mut file)?,
+            };
+
+            self.format = Some(format);
+            self.index = read_associated_index(src, format)?;
+        }
+
         self.build_from_reader(file)
     }
 
@@ -92,37 +132,91 @@ impl Builder {
     /// let reader = alignment::reader::Builder::default().build_from_reader(io::empty())?;
     /// # Ok::<_, io::Error>(())
     /// ```
-    pub fn build_from_reader<R>(self, mut reader: R) -> io::Result<Reader<Box<dyn BufRead>>>
+    pub fn build_from_reader<R>(self, mut reader: R) -> io::Result<Reader<R>>
     where
-        R: Read + Seek + 'static,
+        R: Read + Seek,
     {
         let format = self
             .format
             .map(Ok)
             .unwrap_or_else(|| detect_format(&mut reader))?;
 
-        let inner: Box<dyn AlignmentReader<_>> = match format {
+        let inner = match format {
             Format::Sam => {
-                let inner: Box<dyn BufRead> = Box::new(BufReader::new(reader));
-                Box::new(sam::Reader::from(inner))
-            }
-            Format::Bam => {
-                let inner: Box<dyn BufRead> = Box::new(bgzf::Reader::new(reader));
-                Box::new(bam::Reader::from(inner))
-            }
-            Format::Cram => {
-                let inner: Box<dyn BufRead> = Box::new(BufReader::new(reader));
-                Box::new(cram::Reader::new(inner))
+                if is_gzip_compressed(&mut reader)? {
+                    Inner::BgzfSam(sam::Reader::new(bgzf::Reader::new(reader)))
+                } else {
+                    Inner::Sam(sam::Reader::new(BufReader::new(reader)))
+                }
             }
+            Format::Bam => Inner::Bam(bam::Reader::new(reader)),
+            Format::Cram => Inner::Cram(cram::Reader::new(BufReader::new(reader))),
         };
 
         Ok(Reader {
             inner,
             reference_sequence_repository: self.reference_sequence_repository,
+            index: self.index,
         })
     }
 }
 
+fn read_associated_index<P>(src: P, format: Format) -> io::Result<Option<Index>>
+where
+    P: AsRef<Path>,
+{
+    let src = src.as_ref();
+
+    if format == Format::Bam {
+        let bai_src = push_ext(src.into(), "bai");
+
+        if bai_src.exists() {
+            return bai::read(bai_src).map(Index::from).map(Some);
+        }
+    }
+
+    if format == Format::Cram {
+        let crai_src = push_ext(src.into(), "crai");
+
+        if crai_src.exists() {
+            return crai::read(crai_src).map(Index::from).map(Some);
+        }
+
+        return Ok(None);
+    }
+
+    let csi_src = push_ext(src.into(), "csi");
+
+    if csi_src.exists() {
+        return csi::read(csi_src).map(Index::from).map(Some);
+    }
+
+    Ok(None)
+}
+
+fn push_ext<S>(path: PathBuf, ext: S) -> PathBuf
+where
+    S: AsRef<OsStr>,
+{
+    let mut s = OsString::from(path);
+    s.push(".");
+    s.push(ext);
+    PathBuf::from(s)
+}
+
+fn is_gzip_compressed<R>(reader: &mut R) -> io::Result<bool>
+where
+    R: Read + Seek,
+{
+    const GZIP_MAGIC_NUMBER: [u8; 2] = [0x1f, 0x8b];
+
+    let mut buf = [0; 2];
+    let is_compressed = reader.read_exact(&mut buf).is_ok() && buf == GZIP_MAGIC_NUMBER;
+    reader.rewind()?;
+
+    Ok(is_compressed)
+}
+
 fn detect_format<R>(reader: &mut R) -> io::Result<Format>
 where
     R: Read + Seek,
@@ -151,3 +245,33 @@ where
 
     Ok(Format::Sam)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[test]
+    fn test_push_ext() {
+        assert_eq!(
+            push_ext(PathBuf::from("sample.bam"), "bai"),
+            PathBuf::from("sample.bam.bai")
+        );
+    }
+
+    #[test]
+    fn test_is_gzip_compressed() -> io::Result<()> {
+        let mut reader = Cursor::new([0x1f, 0x8b, 0x08, 0x04]);
+        assert!(is_gzip_compressed(&mut reader)?);
+        assert_eq!(reader.position(), 0);
+
+        let mut reader = Cursor::new(b"@HD\tVN:1.6\n");
+        assert!(!is_gzip_compressed(&mut reader)?);
+
+        let mut reader = Cursor::new([]);
+        assert!(!is_gzip_compressed(&mut reader)?);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,264 @@
+//! Alignment record sorting.
+//!
+//! Records are sorted using an external merge sort. They are buffered in memory until a memory
+//! limit is reached, after which the buffer is sorted and spilled to a temporary BAM file. The
+//! temporary files and remaining in-memory records are then k-way merged.
+//!
+//! # Examples
+//!
+//! ```
+//! # use std::io;
+//! use noodles_sam::{self as sam, alignment::Record};
+//! use noodles_util::alignment::sort::{self, Order};
+//!
+//! let header = sam::Header::default();
+//!
+//! let mut sorter = sort::Builder::default()
+//!     .set_order(Order::QueryName)
+//!     .build(header);
+//!
+//! sorter.push(Record::builder().set_read_name("r10".parse()?).build())?;
+//! sorter.push(Record::builder().set_read_name("r2".parse()?).build())?;
+//!
+//! let records: Vec<_> = sorter.finish()?.collect::<io::Result<_>>()?;
+//!
+//! let names: Vec<_> = records
+//!     .iter()
+//!     .filter_map(|record| record.read_name())
+//!     .map(|name| name.to_string())
+//!     .collect();
+//!
+//! assert_eq!(names, ["r2", "r10"]);
+//! # Ok::<_, Box<dyn std::error::Error>>(())
+//! ```
+
+mod builder;
+pub(crate) mod key;
+mod order;
+
+pub use self::{builder::Builder, order::Order};
+
+use std::{
+    fs::File,
+    io::{self, BufWriter},
+    iter, mem,
+};
+
+use noodles_bam as bam;
+use noodles_bgzf::{self as bgzf, writer::CompressionLevel};
+use noodles_sam::{
+    self as sam,
+    alignment::Record,
+    record::{cigar::Op, data::field::Value},
+    AlignmentWriter,
+};
+
+use self::key::Key;
+use crate::sort::{self, Chunk, SortKey, Spill};
+
+/// An alignment record sorter.
+///
+/// This is created using a [`Builder`].
+pub struct Sorter {
+    inner: sort::Sorter<BamSpill>,
+    header: sam::Header,
+}
+
+impl Sorter {
+    /// Returns the output header.
+    ///
+    /// This is the input header with its sort order fields (`SO`, `GO`, and `SS`) set to the sort
+    /// order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, header::record::value::map::header::SortOrder};
+    /// use noodles_util::alignment::sort;
+    ///
+    /// let sorter = sort::Builder::default().build(sam::Header::default());
+    ///
+    /// let sort_order = sorter.header().header().and_then(|hd| hd.sort_order());
+    /// assert_eq!(sort_order, Some(SortOrder::Coordinate));
+    /// ```
+    pub fn header(&self) -> &sam::Header {
+        &self.header
+    }
+
+    /// Adds a record to be sorted.
+    ///
+    /// When the buffered records exceed the memory limit, they are sorted and written to a
+    /// temporary file.
+    pub fn push(&mut self, record: Record) -> io::Result<()> {
+        self.inner.push(record)
+    }
+
+    /// Finishes sorting and returns an iterator over the sorted records.
+    ///
+    /// Records that compare equal are returned in the order they were pushed. Temporary files are
+    /// removed when the iterator is dropped.
+    pub fn finish(self) -> io::Result<Records> {
+        self.inner.finish().map(|inner| Records { inner })
+    }
+}
+
+/// An iterator over sorted alignment records.
+pub struct Records {
+    inner: sort::Merge<'static, BamSpill>,
+}
+
+impl Iterator for Records {
+    type Item = io::Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+struct BamSpill {
+    order: Order,
+    header: sam::Header,
+}
+
+impl SortKey for BamSpill {
+    type Record = Record;
+    type Key = Key;
+
+    fn key(&self, record: &Self::Record) -> io::Result<Self::Key> {
+        Key::new(self.order, &self.header, record)
+    }
+}
+
+impl Spill for BamSpill {
+    const EXTENSION: &'static str = "bam";
+
+    fn estimate_size(&self, record: &Self::Record) -> usize {
+        estimate_record_size(record)
+    }
+
+    fn write_chunk(&self, file: File, records: Chunk<'_, Self::Record>) -> io::Result<()> {
+        let inner = bgzf::Writer::builder(BufWriter::new(file))
+            .set_compression_level(CompressionLevel::fast())
+            .build();
+
+        let mut writer = bam::Writer::from(inner);
+
+        writer.write_header(&self.header)?;
+        writer.write_reference_sequences(self.header.reference_sequences())?;
+
+        for result in records {
+            let record = result?;
+            writer.write_alignment_record(&self.header, &record)?;
+        }
+
+        writer.try_finish()
+    }
+
+    fn read_chunk(&self, file: File) -> io::Result<Chunk<'static, Self::Record>> {
+        let mut reader = bam::Reader::new(file);
+        reader.read_header()?;
+        reader.read_reference_sequences()?;
+
+        Ok(Box::new(iter::from_fn(move || {
+            let mut record = Record::default();
+
+            match reader.read_record(&mut record) {
+                Ok(0) => None,
+                Ok(_) => Some(Ok(record)),
+                Err(e) => Some(Err(e)),
+            }
+        })))
+    }
+}
+
+fn estimate_record_size(record: &Record) -> usize {
+    let data_size: usize = record
+        .data()
+        .values()
+        .map(|field| mem::size_of_val(field) + estimate_value_heap_size(field.value()))
+        .sum();
+
+    mem::size_of::<Record>()
+        + record
+            .read_name()
+            .map(|name| name.len())
+            .unwrap_or_default()
+        + record.cigar().len() * mem::size_of::<Op>()
+        + record.sequence().len()
+        + record.quality_scores().len()
+        + data_size
+}
+
+fn estimate_value_heap_size(value: &Value) -> usize {
+    match value {
+        Value::String(s) | Value::Hex(s) => s.len(),
+        Value::Int8Array(values) => values.len(),
+        Value::UInt8Array(values) => values.len(),
+        Value::Int16Array(values) => mem::size_of_val(&values[..]),
+        Value::UInt16Array(values) => mem::size_of_val(&values[..]),
+        Value::Int32Array(values) => mem::size_of_val(&values[..]),
+        Value::UInt32Array(values) => mem::size_of_val(&values[..]),
+        Value::FloatArray(values) => mem::size_of_val(&values[..]),
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_core::Position;
+    use noodles_sam::header::record::value::{map::ReferenceSequence, Map};
+
+    use super::*;
+
+    #[test]
+    fn test_sort_by_coordinate_with_spills() -> Result<(), Box<dyn std::error::Error>> {
+        let header = sam::Header::builder()
+            .add_reference_sequence(Map::<ReferenceSequence>::new("sq0".parse()?, 1000)?)
+            .add_reference_sequence(Map::<ReferenceSequence>::new("sq1".parse()?, 1000)?)
+            .build();
+
+        // Each record exceeds the memory limit, so every record is spilled to its own chunk.
+        let mut sorter = Builder::default().set_memory_limit(1).build(header);
+
+        let positions = [(1, 5), (0, 13), (0, 8), (1, 2)];
+
+        for (i, (reference_sequence_id, alignment_start)) in positions.into_iter().enumerate() {
+            let record = Record::builder()
+                .set_read_name(format!("r{}", i).parse()?)
+                .set_flags(sam::record::Flags::empty())
+                .set_reference_sequence_id(reference_sequence_id)
+                .set_alignment_start(Position::try_from(alignment_start)?)
+                .set_cigar("4M".parse()?)
+                .set_sequence("ACGT".parse()?)
+                .build();
+
+            sorter.push(record)?;
+        }
+
+        sorter.push(Record::default())?;
+
+        let actual: Vec<_> = sorter
+            .finish()?
+            .map(|result| {
+                result.map(|record| {
+                    (
+                        record.reference_sequence_id(),
+                        record.alignment_start().map(usize::from),
+                    )
+                })
+            })
+            .collect::<io::Result<_>>()?;
+
+        let expected = [
+            (Some(0), Some(8)),
+            (Some(0), Some(13)),
+            (Some(1), Some(2)),
+            (Some(1), Some(5)),
+            (None, None),
+        ];
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+}
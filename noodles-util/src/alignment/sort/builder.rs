@@ -0,0 +1,106 @@
+use std::{env, path::PathBuf};
+
+use noodles_sam as sam;
+
+use super::{BamSpill, Order, Sorter};
+use crate::sort;
+
+// 768 MiB
+const DEFAULT_MEMORY_LIMIT: usize = 768 << 20;
+
+/// An alignment record sorter builder.
+#[derive(Debug)]
+pub struct Builder {
+    order: Order,
+    memory_limit: usize,
+    temp_dir: Option<PathBuf>,
+}
+
+impl Builder {
+    /// Sets the sort order.
+    ///
+    /// By default, records are sorted by coordinate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::sort::{self, Order};
+    /// let builder = sort::Builder::default().set_order(Order::QueryName);
+    /// ```
+    pub fn set_order(mut self, order: Order) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Sets the approximate maximum amount of memory, in bytes, used to buffer records.
+    ///
+    /// When the limit is reached, buffered records are sorted and spilled to a temporary file. By
+    /// default, this is 768 MiB.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::sort;
+    /// let builder = sort::Builder::default().set_memory_limit(1 << 30);
+    /// ```
+    pub fn set_memory_limit(mut self, memory_limit: usize) -> Self {
+        self.memory_limit = memory_limit;
+        self
+    }
+
+    /// Sets the directory where temporary files are written.
+    ///
+    /// By default, this is the system temporary directory ([`std::env::temp_dir`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::sort;
+    /// let builder = sort::Builder::default().set_temp_dir("/tmp");
+    /// ```
+    pub fn set_temp_dir<P>(mut self, temp_dir: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        self.temp_dir = Some(temp_dir.into());
+        self
+    }
+
+    /// Builds an alignment record sorter.
+    ///
+    /// The given header is the header of the input records.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam as sam;
+    /// use noodles_util::alignment::sort;
+    /// let sorter = sort::Builder::default().build(sam::Header::default());
+    /// ```
+    pub fn build(self, header: sam::Header) -> Sorter {
+        let mut output_header = header.clone();
+        self.order.update_header(&mut output_header);
+
+        let spill = BamSpill {
+            order: self.order,
+            header,
+        };
+
+        let temp_dir = self.temp_dir.unwrap_or_else(env::temp_dir);
+
+        Sorter {
+            inner: sort::Sorter::new(spill, self.memory_limit, temp_dir),
+            header: output_header,
+        }
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            order: Order::default(),
+            memory_limit: DEFAULT_MEMORY_LIMIT,
+            temp_dir: None,
+        }
+    }
+}
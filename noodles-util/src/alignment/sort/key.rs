@@ -0,0 +1,422 @@
+use std::{cmp::Ordering, io};
+
+use noodles_sam::{
+    self as sam,
+    alignment::Record,
+    record::{cigar::op::Kind, data::field::Tag, Cigar, Flags},
+};
+
+use super::Order;
+
+#[derive(Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub(crate) enum Key {
+    Coordinate {
+        reference_sequence_id: usize,
+        alignment_start: usize,
+        is_reverse_complemented: bool,
+    },
+    QueryName {
+        name: Name,
+        segment: u16,
+    },
+    TemplateCoordinate(Box<TemplateCoordinateKey>),
+}
+
+impl Key {
+    pub(crate) fn new(order: Order, header: &sam::Header, record: &Record) -> io::Result<Self> {
+        match order {
+            Order::Coordinate => Ok(coordinate_key(record)),
+            Order::QueryName => Ok(query_name_key(record)),
+            Order::TemplateCoordinate => template_coordinate_key(header, record)
+                .map(Box::new)
+                .map(Self::TemplateCoordinate),
+        }
+    }
+}
+
+fn coordinate_key(record: &Record) -> Key {
+    Key::Coordinate {
+        // Unplaced records are sorted last.
+        reference_sequence_id: record.reference_sequence_id().unwrap_or(usize::MAX),
+        alignment_start: record
+            .alignment_start()
+            .map(usize::from)
+            .unwrap_or_default(),
+        is_reverse_complemented: record.flags().is_reverse_complemented(),
+    }
+}
+
+fn query_name_key(record: &Record) -> Key {
+    const SEGMENT_MASK: u16 = 0xc0;
+
+    let name = record
+        .read_name()
+        .map(|name| Name(AsRef::<[u8]>::as_ref(name).to_vec()))
+        .unwrap_or_default();
+
+    Key::QueryName {
+        name,
+        segment: u16::from(record.flags()) & SEGMENT_MASK,
+    }
+}
+
+/// A read name that is compared using a natural order.
+///
+/// Runs of digits are compared by their numeric value, e.g., `r2` sorts before `r10`.
+#[derive(Debug, Default, Eq, PartialEq)]
+pub(crate) struct Name(Vec<u8>);
+
+impl Ord for Name {
+    fn cmp(&self, other: &Self) -> Ordering {
+        natural_cmp(&self.0, &other.0)
+    }
+}
+
+impl PartialOrd for Name {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn natural_cmp(a: &[u8], b: &[u8]) -> Ordering {
+    let (mut i, mut j) = (0, 0);
+
+    while i < a.len() && j < b.len() {
+        if a[i].is_ascii_digit() && b[j].is_ascii_digit() {
+            let (a_digits, next_i) = digits(a, i);
+            let (b_digits, next_j) = digits(b, j);
+
+            let ordering = a_digits
+                .len()
+                .cmp(&b_digits.len())
+                .then_with(|| a_digits.cmp(b_digits));
+
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+
+            i = next_i;
+            j = next_j;
+        } else {
+            match a[i].cmp(&b[j]) {
+                Ordering::Equal => {
+                    i += 1;
+                    j += 1;
+                }
+                ordering => return ordering,
+            }
+        }
+    }
+
+    (a.len() - i).cmp(&(b.len() - j)).then_with(|| a.cmp(b))
+}
+
+// Returns the run of digits starting at `start`, without leading zeros, and the position after
+// the run.
+fn digits(buf: &[u8], start: usize) -> (&[u8], usize) {
+    let end = buf[start..]
+        .iter()
+        .position(|b| !b.is_ascii_digit())
+        .map(|n| start + n)
+        .unwrap_or(buf.len());
+
+    let run = &buf[start..end];
+    let leading_zeros = run.iter().take_while(|&&b| b == b'0').count();
+
+    (&run[leading_zeros..], end)
+}
+
+#[derive(Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub(crate) struct TemplateCoordinateKey {
+    reference_sequence_id_1: usize,
+    reference_sequence_id_2: usize,
+    position_1: i64,
+    position_2: i64,
+    is_reverse_complemented_1: bool,
+    is_reverse_complemented_2: bool,
+    library: String,
+    molecular_identifier: String,
+    name: Vec<u8>,
+    is_upper: bool,
+}
+
+type End = (usize, i64, bool);
+
+const UNMAPPED_END: End = (usize::MAX, i64::MAX, false);
+
+fn template_coordinate_key(
+    header: &sam::Header,
+    record: &Record,
+) -> io::Result<TemplateCoordinateKey> {
+    let flags = record.flags();
+
+    let end = if flags.is_unmapped() {
+        UNMAPPED_END
+    } else {
+        read_end(record)?
+    };
+
+    let mate_end = if flags.is_segmented() && !flags.is_mate_unmapped() {
+        mate_end(record)?
+    } else {
+        UNMAPPED_END
+    };
+
+    let (lower, upper, is_upper) = if mate_end < end {
+        (mate_end, end, true)
+    } else {
+        (end, mate_end, false)
+    };
+
+    let library = get_str(record, Tag::ReadGroup)
+        .and_then(|id| header.read_groups().get(id))
+        .and_then(|read_group| read_group.library())
+        .map(String::from)
+        .unwrap_or_default();
+
+    let molecular_identifier = get_str(record, Tag::UmiId)
+        .map(String::from)
+        .unwrap_or_default();
+
+    let name = record
+        .read_name()
+        .map(|name| AsRef::<[u8]>::as_ref(name).to_vec())
+        .unwrap_or_default();
+
+    Ok(TemplateCoordinateKey {
+        reference_sequence_id_1: lower.0,
+        reference_sequence_id_2: upper.0,
+        position_1: lower.1,
+        position_2: upper.1,
+        is_reverse_complemented_1: lower.2,
+        is_reverse_complemented_2: upper.2,
+        library,
+        molecular_identifier,
+        name,
+        is_upper,
+    })
+}
+
+fn read_end(record: &Record) -> io::Result<End> {
+    let reference_sequence_id = record
+        .reference_sequence_id()
+        .ok_or_else(|| missing_field("reference sequence ID"))?;
+
+    let alignment_start = record
+        .alignment_start()
+        .ok_or_else(|| missing_field("alignment start"))?;
+
+    let is_reverse_complemented = record.flags().is_reverse_complemented();
+
+    let position = unclipped_5_prime_position(
+        usize::from(alignment_start),
+        record.cigar(),
+        is_reverse_complemented,
+    );
+
+    Ok((reference_sequence_id, position, is_reverse_complemented))
+}
+
+fn mate_end(record: &Record) -> io::Result<End> {
+    let reference_sequence_id = record
+        .mate_reference_sequence_id()
+        .ok_or_else(|| missing_field("mate reference sequence ID"))?;
+
+    let alignment_start = record
+        .mate_alignment_start()
+        .ok_or_else(|| missing_field("mate alignment start"))?;
+
+    let is_reverse_complemented = record.flags().contains(Flags::MATE_REVERSE_COMPLEMENTED);
+
+    // Without the mate CIGAR (`MC`), the mate's clips and alignment end are unknown, and its
+    // alignment start is used as is.
+    let position = match get_str(record, Tag::MateCigar) {
+        Some(s) => {
+            let cigar: Cigar = s
+                .parse()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+            unclipped_5_prime_position(
+                usize::from(alignment_start),
+                &cigar,
+                is_reverse_complemented,
+            )
+        }
+        None => usize::from(alignment_start) as i64,
+    };
+
+    Ok((reference_sequence_id, position, is_reverse_complemented))
+}
+
+fn unclipped_5_prime_position(
+    alignment_start: usize,
+    cigar: &Cigar,
+    is_reverse_complemented: bool,
+) -> i64 {
+    fn is_clip(kind: Kind) -> bool {
+        matches!(kind, Kind::SoftClip | Kind::HardClip)
+    }
+
+    fn clip_len<'a, I>(ops: I) -> i64
+    where
+        I: Iterator<Item = &'a sam::record::cigar::Op>,
+    {
+        ops.take_while(|op| is_clip(op.kind()))
+            .map(|op| op.len() as i64)
+            .sum()
+    }
+
+    let alignment_start = alignment_start as i64;
+
+    if is_reverse_complemented {
+        let span: i64 = cigar
+            .iter()
+            .filter(|op| op.kind().consumes_reference())
+            .map(|op| op.len() as i64)
+            .sum();
+
+        let alignment_end = alignment_start + span - 1;
+        alignment_end + clip_len(cigar.iter().rev())
+    } else {
+        alignment_start - clip_len(cigar.iter())
+    }
+}
+
+fn get_str(record: &Record, tag: Tag) -> Option<&str> {
+    record
+        .data()
+        .get(tag)
+        .and_then(|field| field.value().as_str())
+}
+
+fn missing_field(name: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("invalid record: missing {}", name),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_core::Position;
+    use sam::record::{cigar::Op, ReadName};
+
+    use super::*;
+
+    #[test]
+    fn test_natural_cmp() {
+        assert_eq!(natural_cmp(b"r2", b"r10"), Ordering::Less);
+        assert_eq!(natural_cmp(b"r10", b"r2"), Ordering::Greater);
+        assert_eq!(natural_cmp(b"r10", b"r10"), Ordering::Equal);
+        assert_eq!(natural_cmp(b"a", b"b"), Ordering::Less);
+        assert_eq!(natural_cmp(b"r1:5", b"r1:12"), Ordering::Less);
+        assert_eq!(natural_cmp(b"r", b"r1"), Ordering::Less);
+        assert_eq!(natural_cmp(b"r01", b"r1"), Ordering::Less);
+        assert_eq!(natural_cmp(b"r1a", b"r01b"), Ordering::Less);
+    }
+
+    #[test]
+    fn test_coordinate_key() -> Result<(), Box<dyn std::error::Error>> {
+        let a = Record::builder()
+            .set_flags(Flags::empty())
+            .set_reference_sequence_id(0)
+            .set_alignment_start(Position::try_from(8)?)
+            .build();
+
+        let b = Record::builder()
+            .set_flags(Flags::REVERSE_COMPLEMENTED)
+            .set_reference_sequence_id(0)
+            .set_alignment_start(Position::try_from(8)?)
+            .build();
+
+        let c = Record::builder()
+            .set_flags(Flags::empty())
+            .set_reference_sequence_id(1)
+            .set_alignment_start(Position::try_from(1)?)
+            .build();
+
+        let d = Record::default();
+
+        assert!(coordinate_key(&a) < coordinate_key(&b));
+        assert!(coordinate_key(&b) < coordinate_key(&c));
+        assert!(coordinate_key(&c) < coordinate_key(&d));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_query_name_key() -> Result<(), Box<dyn std::error::Error>> {
+        let build = |name: &str, flags| -> Result<Record, Box<dyn std::error::Error>> {
+            Ok(Record::builder()
+                .set_read_name(name.parse::<ReadName>()?)
+                .set_flags(flags)
+                .build())
+        };
+
+        let a = build("r2", Flags::SEGMENTED | Flags::FIRST_SEGMENT)?;
+        let b = build("r2", Flags::SEGMENTED | Flags::LAST_SEGMENT)?;
+        let c = build("r10", Flags::empty())?;
+
+        assert!(query_name_key(&a) < query_name_key(&b));
+        assert!(query_name_key(&b) < query_name_key(&c));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unclipped_5_prime_position() -> Result<(), Box<dyn std::error::Error>> {
+        let cigar = Cigar::try_from(vec![
+            Op::new(Kind::HardClip, 2),
+            Op::new(Kind::SoftClip, 3),
+            Op::new(Kind::Match, 10),
+            Op::new(Kind::SoftClip, 4),
+        ])?;
+
+        assert_eq!(unclipped_5_prime_position(100, &cigar, false), 95);
+        assert_eq!(unclipped_5_prime_position(100, &cigar, true), 113);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_template_coordinate_key() -> Result<(), Box<dyn std::error::Error>> {
+        let header = sam::Header::default();
+
+        let read_1 = Record::builder()
+            .set_read_name("r0".parse()?)
+            .set_flags(Flags::SEGMENTED | Flags::FIRST_SEGMENT | Flags::MATE_REVERSE_COMPLEMENTED)
+            .set_reference_sequence_id(0)
+            .set_alignment_start(Position::try_from(8)?)
+            .set_cigar("4M".parse()?)
+            .set_mate_reference_sequence_id(0)
+            .set_mate_alignment_start(Position::try_from(13)?)
+            .set_data("MC:Z:4M".parse()?)
+            .build();
+
+        let read_2 = Record::builder()
+            .set_read_name("r0".parse()?)
+            .set_flags(Flags::SEGMENTED | Flags::LAST_SEGMENT | Flags::REVERSE_COMPLEMENTED)
+            .set_reference_sequence_id(0)
+            .set_alignment_start(Position::try_from(13)?)
+            .set_cigar("4M".parse()?)
+            .set_mate_reference_sequence_id(0)
+            .set_mate_alignment_start(Position::try_from(8)?)
+            .build();
+
+        let key_1 = template_coordinate_key(&header, &read_1)?;
+        let key_2 = template_coordinate_key(&header, &read_2)?;
+
+        assert_eq!(key_1.position_1, 8);
+        assert_eq!(key_1.position_2, 16);
+        assert!(!key_1.is_upper);
+
+        // Without `MC`, the mate's alignment start is used.
+        assert_eq!(key_2.position_1, 8);
+        assert_eq!(key_2.position_2, 16);
+        assert!(key_2.is_upper);
+
+        assert!(key_1 < key_2);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+use noodles_sam::{
+    self as sam,
+    header::record::value::{
+        map::{
+            self,
+            header::{GroupOrder, SortOrder, SubsortOrder, Version},
+        },
+        Map,
+    },
+};
+
+/// An alignment record sort order.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Order {
+    /// Sort by reference sequence, alignment start, and strand.
+    #[default]
+    Coordinate,
+    /// Sort by read name, using a natural order, and then by segment.
+    QueryName,
+    /// Sort by the unclipped 5' positions of the read and its mate.
+    ///
+    /// This groups records of the same template together. It is the order used by `samtools sort
+    /// --template-coordinate`.
+    TemplateCoordinate,
+}
+
+impl Order {
+    /// Sets the sort order fields (`SO`, `GO`, and `SS`) of a header.
+    pub(crate) fn update_header(self, header: &mut sam::Header) {
+        let hd = header
+            .header_mut()
+            .get_or_insert_with(|| Map::<map::Header>::new(Version::default()));
+
+        let (sort_order, group_order, subsort_order) = match self {
+            Self::Coordinate => (SortOrder::Coordinate, None, None),
+            Self::QueryName => (SortOrder::QueryName, None, None),
+            Self::TemplateCoordinate => (
+                SortOrder::Unsorted,
+                Some(GroupOrder::Query),
+                Some(SubsortOrder::Unsorted(vec![String::from(
+                    "template-coordinate",
+                )])),
+            ),
+        };
+
+        *hd.sort_order_mut() = Some(sort_order);
+        *hd.group_order_mut() = group_order;
+        *hd.subsort_order_mut() = subsort_order;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_header() {
+        let mut header = sam::Header::default();
+        Order::Coordinate.update_header(&mut header);
+        let hd = header.header().expect("missing header");
+        assert_eq!(hd.sort_order(), Some(SortOrder::Coordinate));
+        assert!(hd.group_order().is_none());
+
+        Order::TemplateCoordinate.update_header(&mut header);
+        let hd = header.header().expect("missing header");
+        assert_eq!(hd.sort_order(), Some(SortOrder::Unsorted));
+        assert_eq!(hd.group_order(), Some(GroupOrder::Query));
+        assert_eq!(
+            hd.subsort_order()
+                .map(|subsort_order| subsort_order.to_string()),
+            Some(String::from("unsorted:template-coordinate"))
+        );
+    }
+}
@@ -0,0 +1,476 @@
+//! Lossless round trip verification.
+//!
+//! A verifier encodes alignment records to CRAM, decodes them, and compares the result to the
+//! original records field by field. Each difference is reported with a category, e.g., read
+//! names, quality scores, or mate fields. The BAM encodings of both records are also compared, so
+//! a record without differences is byte-for-byte identical when converted back to BAM.
+//!
+//! # Examples
+//!
+//! ```
+//! # use std::io;
+//! use noodles_sam::{self as sam, alignment::Record};
+//! use noodles_util::alignment::verify;
+//!
+//! let header = sam::Header::default();
+//! let records = [Ok(Record::default())];
+//!
+//! let verifier = verify::Builder::default().build();
+//! let report = verifier.verify(&header, records)?;
+//!
+//! assert_eq!(report.record_count(), 1);
+//! assert!(report.is_lossless());
+//! # Ok::<_, io::Error>(())
+//! ```
+
+mod builder;
+mod category;
+
+pub use self::{builder::Builder, category::Category};
+
+use std::{collections::BTreeMap, fmt::Debug, io};
+
+use noodles_bam as bam;
+use noodles_cram as cram;
+use noodles_fasta as fasta;
+use noodles_sam::{
+    self as sam,
+    alignment::Record,
+    record::data::field::{Field, Tag},
+};
+
+// The number of records encoded to and decoded from each in-memory CRAM file.
+const BATCH_SIZE: usize = 1 << 16;
+
+/// A CRAM round trip verifier.
+///
+/// This is created using a [`Builder`].
+pub struct Verifier {
+    reference_sequence_repository: Option<fasta::Repository>,
+}
+
+impl Verifier {
+    /// Encodes the given records to CRAM, decodes them, and reports the differences.
+    ///
+    /// Records are processed in batches, each of which is written to and read from an in-memory
+    /// CRAM file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_sam::{self as sam, alignment::Record};
+    /// use noodles_util::alignment::verify;
+    ///
+    /// let header = sam::Header::default();
+    /// let records = [Ok(Record::default())];
+    ///
+    /// let verifier = verify::Builder::default().build();
+    /// let report = verifier.verify(&header, records)?;
+    /// assert!(report.is_lossless());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn verify<I>(&self, header: &sam::Header, records: I) -> io::Result<Report>
+    where
+        I: IntoIterator<Item = io::Result<Record>>,
+    {
+        let mut report = Report::default();
+        let mut batch = Vec::with_capacity(BATCH_SIZE);
+
+        for result in records {
+            batch.push(result?);
+
+            if batch.len() >= BATCH_SIZE {
+                self.verify_batch(header, &batch, &mut report)?;
+                batch.clear();
+            }
+        }
+
+        if !batch.is_empty() {
+            self.verify_batch(header, &batch, &mut report)?;
+        }
+
+        Ok(report)
+    }
+
+    fn verify_batch(
+        &self,
+        header: &sam::Header,
+        records: &[Record],
+        report: &mut Report,
+    ) -> io::Result<()> {
+        let data = self.encode(header, records)?;
+        let actual_records = self.decode(header, &data)?;
+
+        if actual_records.len() != records.len() {
+            report.push(Difference::new(
+                report.record_count,
+                Category::RecordCount,
+                &records.len(),
+                &actual_records.len(),
+            ));
+        }
+
+        for (expected, actual) in records.iter().zip(&actual_records) {
+            let index = report.record_count;
+            let difference_count = report.differences.len();
+
+            compare_records(index, expected, actual, report);
+
+            if report.differences.len() == difference_count {
+                let (a, b) = (
+                    encode_bam_record(header, expected)?,
+                    encode_bam_record(header, actual)?,
+                );
+
+                if a != b {
+                    report.push(Difference::new(index, Category::Encoding, &a, &b));
+                }
+            }
+
+            report.record_count += 1;
+        }
+
+        Ok(())
+    }
+
+    fn encode(&self, header: &sam::Header, records: &[Record]) -> io::Result<Vec<u8>> {
+        let builder = match &self.reference_sequence_repository {
+            Some(repository) => cram::writer::Builder::default()
+                .set_reference_sequence_repository(repository.clone()),
+            None => cram::writer::Builder::default().require_reference_sequences(false),
+        };
+
+        let mut writer = builder.build_with_writer(Vec::new());
+
+        writer.write_file_definition()?;
+        writer.write_file_header(header)?;
+
+        for record in records {
+            let cram_record = cram::Record::try_from_alignment_record(header, record)?;
+            writer.write_record(header, cram_record)?;
+        }
+
+        writer.try_finish(header)?;
+
+        Ok(writer.get_ref().clone())
+    }
+
+    fn decode(&self, header: &sam::Header, data: &[u8]) -> io::Result<Vec<Record>> {
+        let repository = self
+            .reference_sequence_repository
+            .clone()
+            .unwrap_or_default();
+
+        let mut reader = cram::Reader::new(data);
+        reader.read_file_definition()?;
+        reader.read_file_header()?;
+
+        reader
+            .records(&repository, header)
+            .map(|result| result.and_then(|record| record.try_into_alignment_record(header)))
+            .collect()
+    }
+}
+
+/// A difference between an original record and its round-tripped record.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Difference {
+    record_index: u64,
+    category: Category,
+    expected: String,
+    actual: String,
+}
+
+impl Difference {
+    fn new<T>(record_index: u64, category: Category, expected: &T, actual: &T) -> Self
+    where
+        T: Debug + ?Sized,
+    {
+        Self {
+            record_index,
+            category,
+            expected: format!("{:?}", expected),
+            actual: format!("{:?}", actual),
+        }
+    }
+
+    /// Returns the 0-based index of the record in the input.
+    pub fn record_index(&self) -> u64 {
+        self.record_index
+    }
+
+    /// Returns the category of the difference.
+    pub fn category(&self) -> Category {
+        self.category
+    }
+
+    /// Returns the original value.
+    pub fn expected(&self) -> &str {
+        &self.expected
+    }
+
+    /// Returns the round-tripped value.
+    pub fn actual(&self) -> &str {
+        &self.actual
+    }
+}
+
+/// A round trip verification report.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Report {
+    record_count: u64,
+    differences: Vec<Difference>,
+}
+
+impl Report {
+    /// Returns the number of records verified.
+    pub fn record_count(&self) -> u64 {
+        self.record_count
+    }
+
+    /// Returns the differences, in record order.
+    pub fn differences(&self) -> &[Difference] {
+        &self.differences
+    }
+
+    /// Returns whether all records round trip without differences.
+    pub fn is_lossless(&self) -> bool {
+        self.differences.is_empty()
+    }
+
+    /// Returns the number of differences in each category.
+    pub fn category_counts(&self) -> BTreeMap<Category, usize> {
+        let mut counts = BTreeMap::new();
+
+        for difference in &self.differences {
+            *counts.entry(difference.category()).or_default() += 1;
+        }
+
+        counts
+    }
+
+    fn push(&mut self, difference: Difference) {
+        self.differences.push(difference);
+    }
+}
+
+fn compare_records(index: u64, expected: &Record, actual: &Record, report: &mut Report) {
+    macro_rules! compare {
+        ($category:expr, $f:expr) => {{
+            let (a, b) = ($f(expected), $f(actual));
+
+            if a != b {
+                report.push(Difference::new(index, $category, &a, &b));
+            }
+        }};
+    }
+
+    compare!(Category::ReadName, |r: &Record| r.read_name().cloned());
+    compare!(Category::Flags, |r: &Record| r.flags());
+    compare!(Category::Position, |r: &Record| (
+        r.reference_sequence_id(),
+        r.alignment_start()
+    ));
+    compare!(Category::MappingQuality, |r: &Record| r.mapping_quality());
+    compare!(Category::Cigar, |r: &Record| r.cigar().clone());
+    compare!(Category::Mate, |r: &Record| (
+        r.mate_reference_sequence_id(),
+        r.mate_alignment_start()
+    ));
+    compare!(Category::TemplateLength, |r: &Record| r.template_length());
+    compare!(Category::Sequence, |r: &Record| r.sequence().clone());
+    compare!(Category::QualityScores, |r: &Record| r
+        .quality_scores()
+        .clone());
+
+    compare_data(index, expected.data(), actual.data(), report);
+}
+
+// `MD` and `NM` are compared separately from the other data fields because CRAM encoders may
+// drop and regenerate them.
+fn compare_data(
+    index: u64,
+    expected: &sam::record::Data,
+    actual: &sam::record::Data,
+    report: &mut Report,
+) {
+    const MD_NM: [Tag; 2] = [Tag::MismatchedPositions, Tag::EditDistance];
+
+    for tag in MD_NM {
+        let (a, b) = (expected.get(tag), actual.get(tag));
+
+        if a != b {
+            report.push(Difference::new(index, Category::MdNm, &a, &b));
+        }
+    }
+
+    let other_fields = |data: &sam::record::Data| -> Vec<Field> {
+        let mut fields: Vec<_> = data
+            .values()
+            .filter(|field| !MD_NM.contains(&field.tag()))
+            .cloned()
+            .collect();
+
+        fields.sort_by_key(|field| *field.tag().as_ref());
+
+        fields
+    };
+
+    let (a, b) = (other_fields(expected), other_fields(actual));
+
+    if a != b {
+        report.push(Difference::new(index, Category::DataFields, &a, &b));
+    } else {
+        let (a, b) = (
+            expected.keys().collect::<Vec<_>>(),
+            actual.keys().collect::<Vec<_>>(),
+        );
+
+        if a.len() == b.len() && a != b {
+            report.push(Difference::new(index, Category::DataFieldOrder, &a, &b));
+        }
+    }
+}
+
+fn encode_bam_record(header: &sam::Header, record: &Record) -> io::Result<Vec<u8>> {
+    let mut writer = bam::Writer::from(Vec::new());
+    writer.write_record(header, record)?;
+    Ok(writer.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_core::Position;
+    use noodles_sam::record::{data::field::Value, Flags, MappingQuality};
+
+    use super::*;
+
+    #[test]
+    fn test_compare_records() -> Result<(), Box<dyn std::error::Error>> {
+        let expected = Record::builder()
+            .set_read_name("r0".parse()?)
+            .set_flags(Flags::UNMAPPED)
+            .set_mate_alignment_start(Position::try_from(8)?)
+            .set_template_length(13)
+            .set_sequence("ACGT".parse()?)
+            .set_quality_scores("NDLS".parse()?)
+            .set_data("NM:i:0\tMD:Z:4\tCO:Z:noodles".parse()?)
+            .build();
+
+        let mut report = Report::default();
+        compare_records(0, &expected, &expected.clone(), &mut report);
+        assert!(report.is_lossless());
+
+        let mut actual = expected.clone();
+        *actual.read_name_mut() = None;
+        *actual.mapping_quality_mut() = MappingQuality::new(8);
+        *actual.mate_alignment_start_mut() = None;
+        *actual.template_length_mut() = 0;
+        *actual.quality_scores_mut() = "!!!!".parse()?;
+        actual.data_mut().remove(Tag::EditDistance);
+
+        let mut report = Report::default();
+        compare_records(0, &expected, &actual, &mut report);
+
+        let categories: Vec<_> = report
+            .differences()
+            .iter()
+            .map(|difference| difference.category())
+            .collect();
+
+        assert_eq!(
+            categories,
+            [
+                Category::ReadName,
+                Category::MappingQuality,
+                Category::Mate,
+                Category::TemplateLength,
+                Category::QualityScores,
+                Category::MdNm,
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_compare_data() -> Result<(), Box<dyn std::error::Error>> {
+        let expected: sam::record::Data = "RG:Z:rg0\tCO:Z:noodles".parse()?;
+
+        let actual: sam::record::Data = "CO:Z:noodles\tRG:Z:rg0".parse()?;
+        let mut report = Report::default();
+        compare_data(0, &expected, &actual, &mut report);
+        assert_eq!(
+            report.category_counts(),
+            [(Category::DataFieldOrder, 1)].into_iter().collect()
+        );
+
+        let mut actual = expected.clone();
+        actual.insert(Field::new(
+            Tag::Comment,
+            Value::String(String::from("ndls")),
+        ));
+        let mut report = Report::default();
+        compare_data(0, &expected, &actual, &mut report);
+        assert_eq!(
+            report.category_counts(),
+            [(Category::DataFields, 1)].into_iter().collect()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify() -> Result<(), Box<dyn std::error::Error>> {
+        use sam::header::record::value::{
+            map::{ReadGroup, ReferenceSequence},
+            Map,
+        };
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(Map::<ReferenceSequence>::new("sq0".parse()?, 8)?)
+            .add_read_group(Map::<ReadGroup>::new("rg0"))
+            .build();
+
+        let records = [
+            Record::builder()
+                .set_read_name("r0".parse()?)
+                .set_flags(Flags::empty())
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(1)?)
+                .set_mapping_quality(MappingQuality::try_from(13)?)
+                .set_cigar("4M".parse()?)
+                .set_sequence("ACGT".parse()?)
+                .set_quality_scores("NDLS".parse()?)
+                .set_data("CO:Z:noodles".parse()?)
+                .build(),
+            Record::builder()
+                .set_read_name("r1".parse()?)
+                .set_sequence("ACGT".parse()?)
+                .set_quality_scores("NDLS".parse()?)
+                .build(),
+        ];
+
+        let verifier = Builder::default().build();
+        let report = verifier.verify(&header, records.into_iter().map(Ok))?;
+
+        assert_eq!(report.record_count(), 2);
+        assert!(report.is_lossless(), "{:?}", report.differences());
+
+        // The read group is stored separately and moved to the end of the data fields.
+        let record = Record::builder()
+            .set_read_name("r2".parse()?)
+            .set_sequence("ACGT".parse()?)
+            .set_data("RG:Z:rg0\tCO:Z:noodles".parse()?)
+            .build();
+
+        let report = verifier.verify(&header, [Ok(record)])?;
+        assert_eq!(
+            report.category_counts(),
+            [(Category::DataFieldOrder, 1)].into_iter().collect()
+        );
+
+        Ok(())
+    }
+}
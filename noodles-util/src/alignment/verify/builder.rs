@@ -0,0 +1,47 @@
+use noodles_fasta as fasta;
+
+use super::Verifier;
+
+/// A CRAM round trip verifier builder.
+#[derive(Debug, Default)]
+pub struct Builder {
+    reference_sequence_repository: Option<fasta::Repository>,
+}
+
+impl Builder {
+    /// Sets the reference sequence repository.
+    ///
+    /// Records are encoded against these reference sequences. By default, no repository is used,
+    /// and bases are stored without a reference.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fasta as fasta;
+    /// use noodles_util::alignment::verify;
+    ///
+    /// let repository = fasta::Repository::default();
+    /// let builder = verify::Builder::default().set_reference_sequence_repository(repository);
+    /// ```
+    pub fn set_reference_sequence_repository(
+        mut self,
+        reference_sequence_repository: fasta::Repository,
+    ) -> Self {
+        self.reference_sequence_repository = Some(reference_sequence_repository);
+        self
+    }
+
+    /// Builds a CRAM round trip verifier.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::verify;
+    /// let verifier = verify::Builder::default().build();
+    /// ```
+    pub fn build(self) -> Verifier {
+        Verifier {
+            reference_sequence_repository: self.reference_sequence_repository,
+        }
+    }
+}
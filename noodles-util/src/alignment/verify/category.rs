@@ -0,0 +1,55 @@
+use std::fmt;
+
+/// A round trip difference category.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Category {
+    /// The number of records differs.
+    RecordCount,
+    /// The read name (`QNAME`) differs.
+    ReadName,
+    /// The flags (`FLAG`) differ.
+    Flags,
+    /// The reference sequence ID (`RNAME`) or alignment start (`POS`) differs.
+    Position,
+    /// The mapping quality (`MAPQ`) differs.
+    MappingQuality,
+    /// The CIGAR operations (`CIGAR`) differ.
+    Cigar,
+    /// The mate reference sequence ID (`RNEXT`) or mate alignment start (`PNEXT`) differs.
+    Mate,
+    /// The template length (`TLEN`) differs.
+    TemplateLength,
+    /// The sequence (`SEQ`) differs.
+    Sequence,
+    /// The quality scores (`QUAL`) differ.
+    QualityScores,
+    /// The `MD` or `NM` data field differs, e.g., it was dropped or regenerated.
+    MdNm,
+    /// Data fields, other than `MD` and `NM`, differ.
+    DataFields,
+    /// The data fields are the same but in a different order.
+    DataFieldOrder,
+    /// All fields are the same, but the BAM encodings of the records differ.
+    Encoding,
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RecordCount => f.write_str("record count"),
+            Self::ReadName => f.write_str("read name"),
+            Self::Flags => f.write_str("flags"),
+            Self::Position => f.write_str("position"),
+            Self::MappingQuality => f.write_str("mapping quality"),
+            Self::Cigar => f.write_str("CIGAR"),
+            Self::Mate => f.write_str("mate"),
+            Self::TemplateLength => f.write_str("template length"),
+            Self::Sequence => f.write_str("sequence"),
+            Self::QualityScores => f.write_str("quality scores"),
+            Self::MdNm => f.write_str("MD/NM"),
+            Self::DataFields => f.write_str("data fields"),
+            Self::DataFieldOrder => f.write_str("data field order"),
+            Self::Encoding => f.write_str("encoding"),
+        }
+    }
+}
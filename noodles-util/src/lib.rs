@@ -4,3 +4,9 @@
 
 #[cfg(feature = "alignment")]
 pub mod alignment;
+
+#[cfg(feature = "variant")]
+pub mod variant;
+
+#[cfg(any(feature = "alignment", feature = "variant"))]
+mod sort;
@@ -0,0 +1,393 @@
+//! External merge sort.
+//!
+//! Records are buffered in memory until a memory limit is reached. The buffer is then sorted and
+//! spilled to a temporary file. When all records are pushed, the spilled chunks and the remaining
+//! in-memory records are k-way merged.
+//!
+//! At most [`MAX_MERGE_WIDTH`] chunks are merged at once, which bounds the number of open files.
+//! When there are more spilled chunks, consecutive runs of them are first merged into new
+//! temporary files, in as many passes as needed.
+
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    fs::{self, File, OpenOptions},
+    io, mem,
+    path::{Path, PathBuf},
+    process,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// The maximum number of chunks that are merged at once.
+pub(crate) const MAX_MERGE_WIDTH: usize = 64;
+
+pub(crate) type Chunk<'a, T> = Box<dyn Iterator<Item = io::Result<T>> + 'a>;
+
+/// A sort order of records.
+pub(crate) trait SortKey {
+    type Record: 'static;
+    type Key: Ord;
+
+    /// Returns the sort key of a record.
+    fn key(&self, record: &Self::Record) -> io::Result<Self::Key>;
+}
+
+impl<S> SortKey for &S
+where
+    S: SortKey,
+{
+    type Record = S::Record;
+    type Key = S::Key;
+
+    fn key(&self, record: &Self::Record) -> io::Result<Self::Key> {
+        (**self).key(record)
+    }
+}
+
+/// A format used to spill sorted records to temporary files.
+pub(crate) trait Spill: SortKey {
+    /// The extension of temporary files.
+    const EXTENSION: &'static str;
+
+    /// Returns the approximate amount of memory used by a record.
+    fn estimate_size(&self, record: &Self::Record) -> usize;
+
+    /// Writes a chunk of sorted records.
+    fn write_chunk(&self, file: File, records: Chunk<'_, Self::Record>) -> io::Result<()>;
+
+    /// Reads a chunk of records written by [`Self::write_chunk`].
+    fn read_chunk(&self, file: File) -> io::Result<Chunk<'static, Self::Record>>;
+}
+
+pub(crate) struct Sorter<S>
+where
+    S: Spill,
+{
+    spill: S,
+    memory_limit: usize,
+    temp_dir: PathBuf,
+    buf: Vec<(S::Key, S::Record)>,
+    buf_size: usize,
+    chunks: Vec<TempFile>,
+}
+
+impl<S> Sorter<S>
+where
+    S: Spill,
+{
+    pub(crate) fn new(spill: S, memory_limit: usize, temp_dir: PathBuf) -> Self {
+        Self {
+            spill,
+            memory_limit,
+            temp_dir,
+            buf: Vec::new(),
+            buf_size: 0,
+            chunks: Vec::new(),
+        }
+    }
+
+    pub(crate) fn push(&mut self, record: S::Record) -> io::Result<()> {
+        let key = self.spill.key(&record)?;
+
+        self.buf_size += mem::size_of::<S::Key>() + self.spill.estimate_size(&record);
+        self.buf.push((key, record));
+
+        if self.buf_size >= self.memory_limit {
+            self.flush()?;
+        }
+
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if self.buf.is_empty() {
+            return Ok(());
+        }
+
+        let (temp_file, file) = TempFile::create(&self.temp_dir, S::EXTENSION)?;
+
+        let records = sort(mem::take(&mut self.buf)).map(|(_, record)| Ok(record));
+        self.spill.write_chunk(file, Box::new(records))?;
+
+        self.chunks.push(temp_file);
+        self.buf_size = 0;
+
+        Ok(())
+    }
+
+    pub(crate) fn finish(mut self) -> io::Result<Merge<'static, S>> {
+        // The in-memory records are a chunk in the final merge.
+        while self.chunks.len() >= MAX_MERGE_WIDTH {
+            self.merge_chunks()?;
+        }
+
+        let mut chunks = Vec::with_capacity(self.chunks.len() + 1);
+
+        for temp_file in &self.chunks {
+            let file = File::open(temp_file.path())?;
+            chunks.push(self.spill.read_chunk(file)?);
+        }
+
+        let records: Vec<_> = sort(self.buf).map(|(_, record)| Ok(record)).collect();
+        chunks.push(Box::new(records.into_iter()));
+
+        Merge::new(self.spill, chunks, self.chunks)
+    }
+
+    // Merges each run of up to `MAX_MERGE_WIDTH` consecutive spilled chunks into a new spilled
+    // chunk. Runs are consecutive so that records with equal keys keep their input order.
+    fn merge_chunks(&mut self) -> io::Result<()> {
+        let mut temp_files = mem::take(&mut self.chunks).into_iter();
+
+        loop {
+            let run: Vec<_> = temp_files.by_ref().take(MAX_MERGE_WIDTH).collect();
+
+            if run.len() <= 1 {
+                self.chunks.extend(run);
+                break;
+            }
+
+            let mut chunks = Vec::with_capacity(run.len());
+
+            for temp_file in &run {
+                let file = File::open(temp_file.path())?;
+                chunks.push(self.spill.read_chunk(file)?);
+            }
+
+            let records = Merge::new(&self.spill, chunks, run)?;
+
+            let (temp_file, file) = TempFile::create(&self.temp_dir, S::EXTENSION)?;
+            self.spill.write_chunk(file, Box::new(records))?;
+            self.chunks.push(temp_file);
+        }
+
+        Ok(())
+    }
+}
+
+fn sort<K, T>(mut buf: Vec<(K, T)>) -> impl Iterator<Item = (K, T)>
+where
+    K: Ord,
+{
+    // The sort is stable so that records with equal keys keep their input order.
+    buf.sort_by(|(a, _), (b, _)| a.cmp(b));
+    buf.into_iter()
+}
+
+/// A k-way merge of sorted chunks.
+///
+/// Records with equal keys are returned in chunk order.
+pub(crate) struct Merge<'a, S>
+where
+    S: SortKey,
+{
+    sort_key: S,
+    chunks: Vec<Chunk<'a, S::Record>>,
+    heads: Vec<Option<S::Record>>,
+    heap: BinaryHeap<Reverse<(S::Key, usize)>>,
+    _temp_files: Vec<TempFile>,
+}
+
+impl<'a, S> Merge<'a, S>
+where
+    S: SortKey,
+{
+    pub(crate) fn new(
+        sort_key: S,
+        chunks: Vec<Chunk<'a, S::Record>>,
+        temp_files: Vec<TempFile>,
+    ) -> io::Result<Self> {
+        let mut heads = Vec::with_capacity(chunks.len());
+        heads.resize_with(chunks.len(), || None);
+
+        let mut merge = Self {
+            sort_key,
+            chunks,
+            heads,
+            heap: BinaryHeap::new(),
+            _temp_files: temp_files,
+        };
+
+        for i in 0..merge.chunks.len() {
+            merge.advance(i)?;
+        }
+
+        Ok(merge)
+    }
+
+    fn advance(&mut self, i: usize) -> io::Result<()> {
+        if let Some(record) = self.chunks[i].next().transpose()? {
+            let key = self.sort_key.key(&record)?;
+            self.heads[i] = Some(record);
+            self.heap.push(Reverse((key, i)));
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a, S> Iterator for Merge<'a, S>
+where
+    S: SortKey,
+{
+    type Item = io::Result<S::Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Reverse((_, i)) = self.heap.pop()?;
+        let record = self.heads[i].take()?;
+
+        match self.advance(i) {
+            Ok(()) => Some(Ok(record)),
+            Err(e) => {
+                self.heap.clear();
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+/// A temporary file that is removed when dropped.
+pub(crate) struct TempFile {
+    path: PathBuf,
+}
+
+impl TempFile {
+    fn create(dir: &Path, extension: &str) -> io::Result<(Self, File)> {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        loop {
+            let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+            let name = format!("noodles-sort-{}-{}.{}", process::id(), n, extension);
+            let path = dir.join(name);
+
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(file) => return Ok((Self { path }, file)),
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Write};
+
+    use super::*;
+
+    struct Lines;
+
+    impl SortKey for Lines {
+        type Record = (u32, String);
+        type Key = u32;
+
+        fn key(&self, record: &Self::Record) -> io::Result<Self::Key> {
+            Ok(record.0)
+        }
+    }
+
+    impl Spill for Lines {
+        const EXTENSION: &'static str = "txt";
+
+        fn estimate_size(&self, record: &Self::Record) -> usize {
+            record.1.len()
+        }
+
+        fn write_chunk(&self, mut file: File, records: Chunk<'_, Self::Record>) -> io::Result<()> {
+            for result in records {
+                let (key, value) = result?;
+                writeln!(file, "{}\t{}", key, value)?;
+            }
+
+            Ok(())
+        }
+
+        fn read_chunk(&self, file: File) -> io::Result<Chunk<'static, Self::Record>> {
+            Ok(Box::new(BufReader::new(file).lines().map(|result| {
+                result.map(|line| {
+                    let (key, value) = line.split_once('\t').unwrap();
+                    (key.parse().unwrap(), value.into())
+                })
+            })))
+        }
+    }
+
+    #[test]
+    fn test_sort_with_spills() -> io::Result<()> {
+        let temp_dir = std::env::temp_dir();
+        let mut sorter = Sorter::new(Lines, 8, temp_dir);
+
+        let records = [
+            (5, "a"),
+            (3, "b"),
+            (8, "c"),
+            (3, "d"),
+            (1, "e"),
+            (5, "f"),
+            (2, "g"),
+        ];
+
+        for (key, value) in records {
+            sorter.push((key, value.into()))?;
+        }
+
+        assert!(sorter.chunks.len() > 1);
+
+        let paths: Vec<_> = sorter.chunks.iter().map(|t| t.path().to_owned()).collect();
+
+        let actual: Vec<_> = sorter
+            .finish()?
+            .map(|result| result.map(|(_, value)| value))
+            .collect::<io::Result<_>>()?;
+
+        assert_eq!(actual, ["e", "g", "b", "d", "a", "f", "c"]);
+
+        for path in paths {
+            assert!(!path.exists());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sort_with_more_spills_than_max_merge_width() -> io::Result<()> {
+        let temp_dir = std::env::temp_dir().join(format!(
+            "noodles-sort-test-{}-max-merge-width",
+            process::id()
+        ));
+        fs::create_dir_all(&temp_dir)?;
+
+        // Each record is 5 to 7 bytes, so every 2 records are spilled.
+        let mut sorter = Sorter::new(Lines, 8, temp_dir.clone());
+
+        const N: u32 = 4 * MAX_MERGE_WIDTH as u32 * 2 + 1;
+        let records: Vec<_> = (0..N).map(|i| ((i * 7) % 13, i.to_string())).collect();
+
+        for record in &records {
+            sorter.push(record.clone())?;
+        }
+
+        assert!(sorter.chunks.len() > MAX_MERGE_WIDTH);
+
+        let mut expected = records;
+        expected.sort_by_key(|(key, _)| *key);
+
+        let actual: Vec<_> = sorter.finish()?.collect::<io::Result<_>>()?;
+        assert_eq!(actual, expected);
+
+        assert!(fs::read_dir(&temp_dir)?.next().is_none());
+        fs::remove_dir(&temp_dir)?;
+
+        Ok(())
+    }
+}
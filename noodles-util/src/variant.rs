@@ -0,0 +1,12 @@
+//! I/O for variant formats.
+
+mod compression;
+pub mod concat;
+mod format;
+pub mod merge;
+pub mod normalize;
+pub mod reader;
+pub mod sort;
+pub mod writer;
+
+pub use self::{compression::Compression, format::Format, reader::Reader, writer::Writer};
@@ -0,0 +1,10 @@
+/// A variant format compression method.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Compression {
+    /// Blocked gzip format (BGZF).
+    Bgzf,
+    /// gzip.
+    ///
+    /// Unlike BGZF, a gzip stream is not randomly accessible.
+    Gzip,
+}
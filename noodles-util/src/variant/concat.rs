@@ -0,0 +1,595 @@
+//! Variant file concatenation.
+//!
+//! Inputs must be BGZF-compressed and all in the same format (VCF or BCF). Their headers must be
+//! compatible, i.e., they must have the same sample names in the same order, and their
+//! definitions must not conflict (see [`vcf::Header::merge`]). BCF inputs must also have the same
+//! string and contig dictionaries, as their records are not re-encoded.
+//!
+//! By default, inputs are concatenated without decoding their records ([`Mode::Naive`]). The
+//! compressed blocks of each input are copied verbatim, except for the block that contains the end
+//! of the header, which is decompressed and recompressed without the header. Inputs must be given
+//! in order and must not overlap, e.g., one per chromosome.
+//!
+//! Inputs that overlap can be concatenated using [`Mode::Overlapping`], which decodes the records
+//! of all inputs and merges them by position. Each input must be sorted.
+//!
+//! # Examples
+//!
+//! ```
+//! # use std::io::{self, Write};
+//! use noodles_bgzf as bgzf;
+//! use noodles_util::variant::concat;
+//! use noodles_vcf as vcf;
+//!
+//! fn compress(data: &[u8]) -> io::Result<Vec<u8>> {
+//!     let mut writer = bgzf::Writer::new(Vec::new());
+//!     writer.write_all(data)?;
+//!     writer.finish()
+//! }
+//!
+//! let inputs = [
+//!     compress(b"##fileformat=VCFv4.3
+//! ###contig=<ID=sq0>
+//! ###contig=<ID=sq1>
+//! #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+//! sq0\t8\t.\tA\tC\t.\tPASS\t.
+//! ")?,
+//!     compress(b"##fileformat=VCFv4.3
+//! ###contig=<ID=sq0>
+//! ###contig=<ID=sq1>
+//! #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+//! sq1\t13\t.\tG\tT\t.\tPASS\t.
+//! ")?,
+//! ];
+//!
+//! let readers = inputs.iter().map(|data| &data[..]).collect();
+//! let concatenator = concat::Builder::default().build(readers)?;
+//!
+//! let mut dst = Vec::new();
+//! concatenator.write(&mut dst)?;
+//!
+//! let mut reader = vcf::Reader::new(bgzf::Reader::new(&dst[..]));
+//! let header = reader.read_header()?.parse()?;
+//!
+//! let chromosomes: Vec<_> = reader
+//!     .records(&header)
+//!     .map(|result| result.map(|record| record.chromosome().to_string()))
+//!     .collect::<io::Result<_>>()?;
+//!
+//! assert_eq!(chromosomes, ["sq0", "sq1"]);
+//! # Ok::<_, Box<dyn std::error::Error>>(())
+//! ```
+
+mod builder;
+
+pub use self::builder::Builder;
+
+use std::io::{self, BufRead, Read, Write};
+
+use noodles_bcf as bcf;
+use noodles_bgzf as bgzf;
+use noodles_vcf::{self as vcf, record::Position};
+
+use super::Format;
+use crate::sort::{self, Chunk, SortKey};
+
+const NUL: u8 = 0x00;
+
+/// A concatenation mode.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Mode {
+    /// Copies the compressed blocks of the inputs without decoding their records.
+    ///
+    /// This is similar to `bcftools concat --naive`.
+    #[default]
+    Naive,
+    /// Decodes the records of the inputs and merges them by position.
+    ///
+    /// This is similar to `bcftools concat --allow-overlaps`.
+    Overlapping,
+}
+
+struct Input<R> {
+    reader: bgzf::Reader<R>,
+    header: vcf::Header,
+    raw_header: String,
+}
+
+/// A variant file concatenator.
+///
+/// This is created using a [`Builder`].
+pub struct Concatenator<R> {
+    mode: Mode,
+    format: Format,
+    header: vcf::Header,
+    inputs: Vec<Input<R>>,
+}
+
+impl<R> Concatenator<R>
+where
+    R: Read,
+{
+    /// Returns the format of the inputs and output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_util::variant::{concat, Format};
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// writer.write_all(b"##fileformat=VCFv4.3\n#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\n")?;
+    /// let data = writer.finish()?;
+    ///
+    /// let concatenator = concat::Builder::default().build(vec![&data[..]])?;
+    /// assert_eq!(concatenator.format(), Format::Vcf);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    /// Returns the output header.
+    ///
+    /// For VCF inputs, this is the merged header of the inputs. For BCF inputs, this is the header
+    /// of the first input.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_util::variant::concat;
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// writer.write_all(b"##fileformat=VCFv4.3\n#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\n")?;
+    /// let data = writer.finish()?;
+    ///
+    /// let concatenator = concat::Builder::default().build(vec![&data[..]])?;
+    /// assert!(concatenator.header().sample_names().is_empty());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn header(&self) -> &vcf::Header {
+        &self.header
+    }
+
+    /// Writes the concatenated inputs as a BGZF-compressed stream.
+    ///
+    /// In overlapping mode, an [`io::ErrorKind::InvalidInput`] error is returned if the chromosome
+    /// of a VCF record is not a contig in the output header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_util::variant::concat;
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// writer.write_all(b"##fileformat=VCFv4.3\n#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\n")?;
+    /// let data = writer.finish()?;
+    ///
+    /// let concatenator = concat::Builder::default().build(vec![&data[..]])?;
+    ///
+    /// let mut dst = Vec::new();
+    /// concatenator.write(&mut dst)?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn write<W>(self, writer: &mut W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        match (self.mode, self.format) {
+            (Mode::Naive, _) => write_naive(writer, self.format, &self.header, self.inputs),
+            (Mode::Overlapping, Format::Vcf) => {
+                write_overlapping_vcf(writer, &self.header, self.inputs)
+            }
+            (Mode::Overlapping, Format::Bcf) => write_overlapping_bcf(writer, self.inputs),
+        }
+    }
+}
+
+fn write_naive<R, W>(
+    writer: &mut W,
+    format: Format,
+    header: &vcf::Header,
+    inputs: Vec<Input<R>>,
+) -> io::Result<()>
+where
+    R: Read,
+    W: Write,
+{
+    for (i, mut input) in inputs.into_iter().enumerate() {
+        let mut block_writer = bgzf::Writer::new(&mut *writer);
+
+        if i == 0 {
+            match format {
+                Format::Vcf => vcf::Writer::new(&mut block_writer).write_header(header)?,
+                Format::Bcf => {
+                    bcf::Writer::from(&mut block_writer).write_file_format()?;
+                    write_raw_bcf_header(&mut block_writer, &input.raw_header)?;
+                }
+            }
+        }
+
+        // The block that contains the end of the header is the only block of an input that is
+        // recompressed. The reader is then at the start of the next block.
+        let buf = input.reader.fill_buf()?;
+        let len = buf.len();
+        block_writer.write_all(buf)?;
+        input.reader.consume(len);
+
+        block_writer.flush()?;
+        block_writer.into_inner();
+
+        copy_frames(&mut input.reader, writer)?;
+    }
+
+    // Writes the BGZF EOF block.
+    bgzf::Writer::new(writer).finish()?;
+
+    Ok(())
+}
+
+fn write_raw_bcf_header<W>(writer: &mut W, raw_header: &str) -> io::Result<()>
+where
+    W: Write,
+{
+    let l_text = u32::try_from(raw_header.len() + 1)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+    writer.write_all(&l_text.to_le_bytes())?;
+    writer.write_all(raw_header.as_bytes())?;
+    writer.write_all(&[NUL])?;
+
+    Ok(())
+}
+
+/// Copies the remaining BGZF blocks of a reader, skipping empty blocks (e.g., the EOF block).
+fn copy_frames<R, W>(reader: &mut bgzf::Reader<R>, writer: &mut W) -> io::Result<()>
+where
+    R: Read,
+    W: Write,
+{
+    let mut buf = Vec::new();
+
+    while let Some(uncompressed_size) = reader.read_raw_frame(&mut buf)? {
+        if uncompressed_size > 0 {
+            writer.write_all(&buf)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn write_overlapping_vcf<R, W>(
+    writer: &mut W,
+    header: &vcf::Header,
+    inputs: Vec<Input<R>>,
+) -> io::Result<()>
+where
+    R: Read,
+    W: Write,
+{
+    let mut readers: Vec<_> = inputs
+        .into_iter()
+        .map(|input| (vcf::Reader::new(input.reader), input.header))
+        .collect();
+
+    let chunks = readers
+        .iter_mut()
+        .map(|(reader, input_header)| {
+            Box::new(reader.records(input_header)) as Chunk<'_, vcf::Record>
+        })
+        .collect();
+
+    let records = sort::Merge::new(VcfPositionKey { header }, chunks, Vec::new())?;
+
+    let mut writer = vcf::Writer::new(bgzf::Writer::new(writer));
+    writer.write_header(header)?;
+
+    for result in records {
+        let record = result?;
+        writer.write_record(&record)?;
+    }
+
+    writer.into_inner().finish()?;
+
+    Ok(())
+}
+
+fn write_overlapping_bcf<R, W>(writer: &mut W, inputs: Vec<Input<R>>) -> io::Result<()>
+where
+    R: Read,
+    W: Write,
+{
+    let raw_header = inputs
+        .first()
+        .map(|input| input.raw_header.clone())
+        .unwrap_or_default();
+
+    let mut readers: Vec<_> = inputs
+        .into_iter()
+        .map(|input| bcf::Reader::from(input.reader))
+        .collect();
+
+    let chunks = readers
+        .iter_mut()
+        .map(|reader| Box::new(reader.records()) as Chunk<'_, bcf::Record>)
+        .collect();
+
+    let records = sort::Merge::new(BcfPositionKey, chunks, Vec::new())?;
+
+    let mut writer = bcf::Writer::new(writer);
+    writer.write_file_format()?;
+    write_raw_bcf_header(writer.get_mut(), &raw_header)?;
+
+    for result in records {
+        let record = result?;
+        writer.write_record(&record)?;
+    }
+
+    writer.into_inner().finish()?;
+
+    Ok(())
+}
+
+struct VcfPositionKey<'h> {
+    header: &'h vcf::Header,
+}
+
+impl<'h> SortKey for VcfPositionKey<'h> {
+    type Record = vcf::Record;
+    type Key = (usize, Position);
+
+    fn key(&self, record: &Self::Record) -> io::Result<Self::Key> {
+        let name = record.chromosome().to_string();
+
+        let i = self
+            .header
+            .contigs()
+            .get_index_of(name.as_str())
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("missing contig in header: {}", name),
+                )
+            })?;
+
+        Ok((i, record.position()))
+    }
+}
+
+// BCF inputs have the same contig dictionary, so chromosome IDs are comparable across inputs.
+struct BcfPositionKey;
+
+impl SortKey for BcfPositionKey {
+    type Record = bcf::Record;
+    type Key = (usize, Position);
+
+    fn key(&self, record: &Self::Record) -> io::Result<Self::Key> {
+        Ok((record.chromosome_id(), record.position()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_bcf::header::StringMaps;
+
+    use super::*;
+
+    const BGZF_EOF: [u8; 28] = [
+        0x1f, 0x8b, 0x08, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0xff, 0x06, 0x00, 0x42, 0x43, 0x02,
+        0x00, 0x1b, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
+
+    const HEADER: &str = "##fileformat=VCFv4.3
+##FORMAT=<ID=GT,Number=1,Type=String,Description=\"Genotype\">
+##contig=<ID=sq0>
+##contig=<ID=sq1>
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample0
+";
+
+    fn build_vcf_data(records: &[&str]) -> io::Result<Vec<u8>> {
+        let mut writer = bgzf::Writer::new(Vec::new());
+        writer.write_all(HEADER.as_bytes())?;
+
+        for record in records {
+            // Each record is written in its own block.
+            writer.flush()?;
+            writeln!(writer, "{}", record)?;
+        }
+
+        writer.finish()
+    }
+
+    fn build_bcf_data(records: &[&str]) -> io::Result<Vec<u8>> {
+        let header: vcf::Header = HEADER
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let string_maps = StringMaps::from(&header);
+
+        let mut writer = bcf::Writer::new(Vec::new());
+        writer.write_file_format()?;
+        writer.write_header(&header)?;
+
+        for record in records {
+            let record: vcf::Record = record
+                .parse()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            writer.write_vcf_record(&header, &string_maps, &record)?;
+        }
+
+        writer.into_inner().finish()
+    }
+
+    fn read_vcf_records(src: &[u8]) -> io::Result<Vec<String>> {
+        let mut reader = vcf::Reader::new(bgzf::Reader::new(src));
+        let header: vcf::Header = reader
+            .read_header()?
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        reader
+            .records(&header)
+            .map(|result| result.map(|record| record.to_string()))
+            .collect()
+    }
+
+    fn read_bcf_records(src: &[u8]) -> io::Result<Vec<String>> {
+        let mut reader = bcf::Reader::new(src);
+        reader.read_file_format()?;
+
+        let raw_header = reader.read_header()?;
+        let header: vcf::Header = raw_header
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let string_maps: StringMaps = raw_header
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        reader
+            .records()
+            .map(|result| {
+                result
+                    .and_then(|record| record.try_into_vcf_record(&header, &string_maps))
+                    .map(|record| record.to_string())
+            })
+            .collect()
+    }
+
+    fn concat(mode: Mode, inputs: &[Vec<u8>]) -> io::Result<Vec<u8>> {
+        let readers = inputs.iter().map(|data| &data[..]).collect();
+        let concatenator = Builder::default().set_mode(mode).build(readers)?;
+
+        let mut dst = Vec::new();
+        concatenator.write(&mut dst)?;
+
+        Ok(dst)
+    }
+
+    #[test]
+    fn test_write_naive_with_vcf_inputs() -> io::Result<()> {
+        let inputs = [
+            build_vcf_data(&[
+                "sq0\t8\t.\tA\tC\t.\tPASS\t.\tGT\t0/1",
+                "sq0\t13\t.\tG\tT\t.\tPASS\t.\tGT\t1/1",
+            ])?,
+            build_vcf_data(&[])?,
+            build_vcf_data(&["sq1\t5\t.\tT\tA\t.\tPASS\t.\tGT\t0|1"])?,
+        ];
+
+        let dst = concat(Mode::Naive, &inputs)?;
+
+        // The last block of the output is the only EOF block.
+        assert_eq!(
+            dst.windows(BGZF_EOF.len())
+                .filter(|w| w == &BGZF_EOF)
+                .count(),
+            1
+        );
+        assert!(dst.ends_with(&BGZF_EOF));
+
+        let actual = read_vcf_records(&dst)?;
+        let expected = [
+            "sq0\t8\t.\tA\tC\t.\tPASS\t.\tGT\t0/1",
+            "sq0\t13\t.\tG\tT\t.\tPASS\t.\tGT\t1/1",
+            "sq1\t5\t.\tT\tA\t.\tPASS\t.\tGT\t0|1",
+        ];
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_naive_with_header_ending_mid_block() -> io::Result<()> {
+        // Unlike `build_vcf_data`, the records directly follow the header in the same block.
+        fn compress(records: &[String]) -> io::Result<Vec<u8>> {
+            let mut writer = bgzf::Writer::new(Vec::new());
+            writer.write_all(HEADER.as_bytes())?;
+
+            for record in records {
+                writeln!(writer, "{}", record)?;
+            }
+
+            writer.finish()
+        }
+
+        let build_records = |chromosome: &str, count: usize| -> Vec<String> {
+            (1..=count)
+                .map(|position| {
+                    format!("{}\t{}\t.\tA\tC\t.\tPASS\t.\tGT\t0/1", chromosome, position)
+                })
+                .collect()
+        };
+
+        // The second input spans multiple blocks.
+        let records = [build_records("sq0", 2), build_records("sq1", 8192)];
+
+        let inputs = records
+            .iter()
+            .map(|records| compress(records))
+            .collect::<io::Result<Vec<_>>>()?;
+
+        let dst = concat(Mode::Naive, &inputs)?;
+
+        let actual = read_vcf_records(&dst)?;
+        let expected: Vec<_> = records.concat();
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_naive_with_bcf_inputs() -> io::Result<()> {
+        let inputs = [
+            build_bcf_data(&["sq0\t8\t.\tA\tC\t.\tPASS\t.\tGT\t0/1"])?,
+            build_bcf_data(&["sq1\t5\t.\tT\tA\t.\tPASS\t.\tGT\t0|1"])?,
+        ];
+
+        let dst = concat(Mode::Naive, &inputs)?;
+
+        let actual = read_bcf_records(&dst)?;
+        let expected = [
+            "sq0\t8\t.\tA\tC\t.\tPASS\t.\tGT\t0/1",
+            "sq1\t5\t.\tT\tA\t.\tPASS\t.\tGT\t0|1",
+        ];
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_overlapping() -> io::Result<()> {
+        let records = [
+            vec![
+                "sq0\t8\t.\tA\tC\t.\tPASS\t.\tGT\t0/1",
+                "sq1\t5\t.\tT\tA\t.\tPASS\t.\tGT\t0|1",
+            ],
+            vec!["sq0\t13\t.\tG\tT\t.\tPASS\t.\tGT\t1/1"],
+        ];
+
+        let expected = [
+            "sq0\t8\t.\tA\tC\t.\tPASS\t.\tGT\t0/1",
+            "sq0\t13\t.\tG\tT\t.\tPASS\t.\tGT\t1/1",
+            "sq1\t5\t.\tT\tA\t.\tPASS\t.\tGT\t0|1",
+        ];
+
+        let inputs = records
+            .iter()
+            .map(|records| build_vcf_data(records))
+            .collect::<io::Result<Vec<_>>>()?;
+        let dst = concat(Mode::Overlapping, &inputs)?;
+        assert_eq!(read_vcf_records(&dst)?, expected);
+
+        let inputs = records
+            .iter()
+            .map(|records| build_bcf_data(records))
+            .collect::<io::Result<Vec<_>>>()?;
+        let dst = concat(Mode::Overlapping, &inputs)?;
+        assert_eq!(read_bcf_records(&dst)?, expected);
+
+        Ok(())
+    }
+}
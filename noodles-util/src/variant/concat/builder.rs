@@ -0,0 +1,299 @@
+use std::io::{self, BufRead, Read};
+
+use noodles_bcf::{self as bcf, header::StringMaps};
+use noodles_bgzf as bgzf;
+use noodles_vcf as vcf;
+
+use super::{Concatenator, Input, Mode};
+use crate::variant::Format;
+
+const BCF_MAGIC_NUMBER: &[u8] = b"BCF";
+
+/// A variant file concatenator builder.
+#[derive(Debug, Default)]
+pub struct Builder {
+    mode: Mode,
+}
+
+impl Builder {
+    /// Sets the concatenation mode.
+    ///
+    /// By default, this is [`Mode::Naive`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::variant::concat::{self, Mode};
+    /// let builder = concat::Builder::default().set_mode(Mode::Overlapping);
+    /// ```
+    pub fn set_mode(mut self, mode: Mode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Builds a variant file concatenator from BGZF-compressed VCF or BCF readers.
+    ///
+    /// This reads the header of each reader and checks that they are compatible.
+    ///
+    /// An [`io::ErrorKind::InvalidInput`] error is returned if there are no readers or if the
+    /// readers are not all in the same format, and an [`io::ErrorKind::InvalidData`] error is
+    /// returned if the headers are not compatible. Readers that are not BGZF-compressed also
+    /// return an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_util::variant::concat;
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// writer.write_all(b"##fileformat=VCFv4.3\n#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\n")?;
+    /// let data = writer.finish()?;
+    ///
+    /// let concatenator = concat::Builder::default().build(vec![&data[..]])?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn build<R>(self, readers: Vec<R>) -> io::Result<Concatenator<R>>
+    where
+        R: Read,
+    {
+        let mut format = None;
+        let mut inputs = Vec::with_capacity(readers.len());
+
+        for reader in readers {
+            let mut reader = bgzf::Reader::new(reader);
+            let input_format = detect_format(&mut reader)?;
+
+            match format {
+                Some(format) if format != input_format => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "inputs have different formats",
+                    ));
+                }
+                Some(_) => {}
+                None => format = Some(input_format),
+            }
+
+            inputs.push(read_input(reader, input_format)?);
+        }
+
+        let format =
+            format.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "no inputs"))?;
+
+        let header = build_header(format, &inputs)?;
+
+        Ok(Concatenator {
+            mode: self.mode,
+            format,
+            header,
+            inputs,
+        })
+    }
+}
+
+fn detect_format<R>(reader: &mut bgzf::Reader<R>) -> io::Result<Format>
+where
+    R: Read,
+{
+    let buf = reader.fill_buf()?;
+
+    if buf.starts_with(BCF_MAGIC_NUMBER) {
+        Ok(Format::Bcf)
+    } else {
+        Ok(Format::Vcf)
+    }
+}
+
+fn read_input<R>(reader: bgzf::Reader<R>, format: Format) -> io::Result<Input<R>>
+where
+    R: Read,
+{
+    let (reader, raw_header) = match format {
+        Format::Vcf => {
+            let mut reader = vcf::Reader::new(reader);
+            let raw_header = reader.read_header()?;
+            (reader.into_inner(), raw_header)
+        }
+        Format::Bcf => {
+            let mut reader = bcf::Reader::from(reader);
+            reader.read_file_format()?;
+            let raw_header = reader.read_header()?;
+            (reader.into_inner(), raw_header)
+        }
+    };
+
+    let header = raw_header
+        .parse()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    Ok(Input {
+        reader,
+        header,
+        raw_header,
+    })
+}
+
+fn build_header<R>(format: Format, inputs: &[Input<R>]) -> io::Result<vcf::Header> {
+    let (first, rest) = match inputs.split_first() {
+        Some((first, rest)) => (first, rest),
+        None => return Ok(vcf::Header::default()),
+    };
+
+    let mut header = first.header.clone();
+
+    for input in rest {
+        if !input
+            .header
+            .sample_names()
+            .iter()
+            .eq(first.header.sample_names())
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "sample names mismatch",
+            ));
+        }
+
+        let mut other = input.header.clone();
+        other.sample_names_mut().clear();
+
+        header
+            .merge(&other)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        if format == Format::Bcf && parse_string_maps(input)? != parse_string_maps(first)? {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "header dictionaries mismatch",
+            ));
+        }
+    }
+
+    // BCF records are copied as is, so the header of the first input is kept.
+    if format == Format::Bcf {
+        header = first.header.clone();
+    }
+
+    Ok(header)
+}
+
+fn parse_string_maps<R>(input: &Input<R>) -> io::Result<StringMaps> {
+    input
+        .raw_header
+        .parse()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    fn compress(data: &[u8]) -> io::Result<Vec<u8>> {
+        let mut writer = bgzf::Writer::new(Vec::new());
+        writer.write_all(data)?;
+        writer.finish()
+    }
+
+    fn build(inputs: &[Vec<u8>]) -> io::Result<Concatenator<&[u8]>> {
+        let readers = inputs.iter().map(|data| &data[..]).collect();
+        Builder::default().build(readers)
+    }
+
+    #[test]
+    fn test_build() -> io::Result<()> {
+        let inputs = [
+            compress(
+                b"##fileformat=VCFv4.3
+##INFO=<ID=DP,Number=1,Type=Integer,Description=\"Combined depth across samples\">
+##contig=<ID=sq0>
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample0
+",
+            )?,
+            compress(
+                b"##fileformat=VCFv4.3
+##contig=<ID=sq0>
+##contig=<ID=sq1>
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample0
+",
+            )?,
+        ];
+
+        let concatenator = build(&inputs)?;
+        assert_eq!(concatenator.format(), Format::Vcf);
+
+        let header = concatenator.header();
+        assert_eq!(header.infos().len(), 1);
+        assert_eq!(header.contigs().len(), 2);
+        assert_eq!(header.sample_names().len(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_with_incompatible_inputs() -> io::Result<()> {
+        const VCF_DATA: &[u8] = b"##fileformat=VCFv4.3
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample0\tsample1
+";
+
+        let inputs = [
+            compress(VCF_DATA)?,
+            compress(
+                b"##fileformat=VCFv4.3
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample1\tsample0
+",
+            )?,
+        ];
+        assert!(matches!(
+            build(&inputs),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        let inputs = [
+            compress(
+                b"##fileformat=VCFv4.3
+##INFO=<ID=DP,Number=1,Type=Integer,Description=\"Combined depth across samples\">
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+",
+            )?,
+            compress(
+                b"##fileformat=VCFv4.3
+##INFO=<ID=DP,Number=1,Type=Float,Description=\"Combined depth across samples\">
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+",
+            )?,
+        ];
+        assert!(matches!(
+            build(&inputs),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        let header = String::from_utf8(VCF_DATA.to_vec())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let mut writer = bcf::Writer::new(Vec::new());
+        writer.write_file_format()?;
+        writer.write_header(&header)?;
+        let bcf_data = writer.into_inner().finish()?;
+
+        let inputs = [compress(VCF_DATA)?, bcf_data];
+        assert!(matches!(
+            build(&inputs),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        let inputs = [VCF_DATA.to_vec()];
+        assert!(build(&inputs).is_err());
+
+        assert!(matches!(
+            build(&[]),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+}
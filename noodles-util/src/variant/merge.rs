@@ -0,0 +1,490 @@
+//! Variant file merging.
+//!
+//! Inputs must be sorted by position, in the order of the contigs of the merged header. Their
+//! headers are merged (see [`vcf::Header::merge`]), which concatenates their samples. Records at
+//! identical sites, i.e., with the same chromosome, position, reference bases, and alternate
+//! bases, are combined into a single record with the samples of all inputs. Samples of inputs
+//! without a record at a site have a missing genotype (`./.`).
+//!
+//! The fixed fields and info fields of a combined record are taken from the first input that has
+//! the site, except for the IDs, which are unioned. Records at the same position with different
+//! alleles are not combined.
+//!
+//! # Examples
+//!
+//! ```
+//! # use std::io;
+//! use noodles_util::variant::{self, merge};
+//!
+//! let readers = [
+//!     &b"##fileformat=VCFv4.3
+//! ###contig=<ID=sq0>
+//! #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample0
+//! sq0\t8\t.\tA\tC\t.\tPASS\t.\tGT\t0/1
+//! "[..],
+//!     &b"##fileformat=VCFv4.3
+//! ###contig=<ID=sq0>
+//! #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample1
+//! sq0\t8\t.\tA\tC\t.\tPASS\t.\tGT\t1/1
+//! sq0\t13\t.\tG\tT\t.\tPASS\t.\tGT\t0/1
+//! "[..],
+//! ]
+//! .into_iter()
+//! .map(|data| variant::reader::Builder::default().build_from_reader(data))
+//! .collect::<io::Result<_>>()?;
+//!
+//! let mut merger = merge::Merger::new(readers)?;
+//! assert_eq!(merger.header().sample_names().len(), 2);
+//!
+//! let genotypes: Vec<_> = merger
+//!     .records()
+//!     .map(|result| result.map(|record| record.genotypes().to_string()))
+//!     .collect::<io::Result<_>>()?;
+//!
+//! assert_eq!(genotypes, ["GT\t0/1\t1/1", "GT\t./.\t0/1"]);
+//! # Ok::<_, io::Error>(())
+//! ```
+
+use std::{
+    collections::VecDeque,
+    io::{self, BufRead},
+};
+
+use noodles_vcf::{
+    self as vcf,
+    header::format::Key,
+    record::{
+        genotypes::{
+            genotype::{field::Value, Field},
+            Genotype, Keys,
+        },
+        Genotypes,
+    },
+};
+
+use super::Reader;
+
+const MISSING_GENOTYPE: &str = "./.";
+
+/// A variant file merger.
+pub struct Merger<R> {
+    readers: Vec<Reader<R>>,
+    headers: Vec<vcf::Header>,
+    header: vcf::Header,
+}
+
+impl<R> Merger<R>
+where
+    R: BufRead,
+{
+    /// Creates a variant file merger from readers.
+    ///
+    /// This reads the header of each reader and merges them.
+    ///
+    /// This returns an [`io::ErrorKind::InvalidData`] error if the headers cannot be merged, e.g.,
+    /// an info or format definition conflicts or a sample name is in multiple inputs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_util::variant::{self, merge};
+    ///
+    /// let data = b"##fileformat=VCFv4.3
+    /// #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+    /// ";
+    /// let reader = variant::reader::Builder::default().build_from_reader(&data[..])?;
+    ///
+    /// let merger = merge::Merger::new(vec![reader])?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn new(mut readers: Vec<Reader<R>>) -> io::Result<Self> {
+        let headers = readers
+            .iter_mut()
+            .map(|reader| reader.read_header())
+            .collect::<io::Result<Vec<_>>>()?;
+
+        let mut header = headers.first().cloned().unwrap_or_default();
+
+        for other in headers.iter().skip(1) {
+            header
+                .merge(other)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        }
+
+        Ok(Self {
+            readers,
+            headers,
+            header,
+        })
+    }
+
+    /// Returns the merged header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_util::variant::{self, merge};
+    ///
+    /// let data = b"##fileformat=VCFv4.3
+    /// #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample0
+    /// ";
+    /// let reader = variant::reader::Builder::default().build_from_reader(&data[..])?;
+    ///
+    /// let merger = merge::Merger::new(vec![reader])?;
+    /// assert_eq!(merger.header().sample_names().len(), 1);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn header(&self) -> &vcf::Header {
+        &self.header
+    }
+
+    /// Returns an iterator over the merged records.
+    ///
+    /// An [`io::ErrorKind::InvalidInput`] error is returned if the chromosome of a record is not
+    /// a contig in the merged header, and an [`io::ErrorKind::InvalidData`] error is returned if
+    /// an input is not sorted.
+    pub fn records(&mut self) -> Records<'_> {
+        let header = &self.header;
+
+        let sources = self
+            .readers
+            .iter_mut()
+            .zip(&self.headers)
+            .map(|(reader, input_header)| Source {
+                records: Box::new(reader.records(input_header)),
+                sample_count: input_header.sample_names().len(),
+                peeked: None,
+                last_key: None,
+            })
+            .collect();
+
+        Records {
+            header,
+            sources,
+            queue: VecDeque::new(),
+        }
+    }
+}
+
+type Position = (usize, usize);
+
+struct Source<'a> {
+    records: Box<dyn Iterator<Item = io::Result<vcf::Record>> + 'a>,
+    sample_count: usize,
+    peeked: Option<(Position, vcf::Record)>,
+    last_key: Option<Position>,
+}
+
+impl<'a> Source<'a> {
+    fn peek(&mut self, header: &vcf::Header) -> io::Result<Option<Position>> {
+        if self.peeked.is_none() {
+            if let Some(record) = self.records.next().transpose()? {
+                let key = position_key(header, &record)?;
+
+                if self
+                    .last_key
+                    .map(|last_key| key < last_key)
+                    .unwrap_or(false)
+                {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "input is not sorted",
+                    ));
+                }
+
+                self.last_key = Some(key);
+                self.peeked = Some((key, record));
+            }
+        }
+
+        Ok(self.peeked.as_ref().map(|(key, _)| *key))
+    }
+}
+
+/// An iterator over merged variant records.
+pub struct Records<'a> {
+    header: &'a vcf::Header,
+    sources: Vec<Source<'a>>,
+    queue: VecDeque<vcf::Record>,
+}
+
+impl<'a> Records<'a> {
+    fn read_position(&mut self) -> io::Result<()> {
+        let header = self.header;
+        let source_count = self.sources.len();
+
+        let mut min_key = None;
+
+        for source in &mut self.sources {
+            if let Some(key) = source.peek(header)? {
+                if min_key.map(|min_key| key < min_key).unwrap_or(true) {
+                    min_key = Some(key);
+                }
+            }
+        }
+
+        let min_key = match min_key {
+            Some(key) => key,
+            None => return Ok(()),
+        };
+
+        // Each site has one slot per input.
+        let mut sites: Vec<Vec<Option<vcf::Record>>> = Vec::new();
+
+        for (i, source) in self.sources.iter_mut().enumerate() {
+            while source.peek(header)? == Some(min_key) {
+                let (_, record) = source.peeked.take().expect("missing peeked record");
+
+                let slot = sites
+                    .iter_mut()
+                    .find(|site| site[i].is_none() && is_same_site(first_record(site), &record));
+
+                match slot {
+                    Some(site) => site[i] = Some(record),
+                    None => {
+                        let mut site = vec![None; source_count];
+                        site[i] = Some(record);
+                        sites.push(site);
+                    }
+                }
+            }
+        }
+
+        for site in sites {
+            let record = combine_records(header, &self.sources, site)?;
+            self.queue.push_back(record);
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> Iterator for Records<'a> {
+    type Item = io::Result<vcf::Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.queue.is_empty() {
+            if let Err(e) = self.read_position() {
+                return Some(Err(e));
+            }
+        }
+
+        self.queue.pop_front().map(Ok)
+    }
+}
+
+fn position_key(header: &vcf::Header, record: &vcf::Record) -> io::Result<Position> {
+    let name = record.chromosome().to_string();
+
+    let i = header
+        .contigs()
+        .get_index_of(name.as_str())
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("missing contig in header: {}", name),
+            )
+        })?;
+
+    Ok((i, usize::from(record.position())))
+}
+
+fn first_record(site: &[Option<vcf::Record>]) -> &vcf::Record {
+    site.iter().flatten().next().expect("site has no records")
+}
+
+fn is_same_site(a: &vcf::Record, b: &vcf::Record) -> bool {
+    a.reference_bases() == b.reference_bases() && a.alternate_bases() == b.alternate_bases()
+}
+
+fn combine_records(
+    header: &vcf::Header,
+    sources: &[Source<'_>],
+    site: Vec<Option<vcf::Record>>,
+) -> io::Result<vcf::Record> {
+    let mut record = first_record(&site).clone();
+
+    for other in site.iter().flatten().skip(1) {
+        for id in other.ids().iter() {
+            record.ids_mut().insert(id.clone());
+        }
+
+        for (key, field) in other.info().as_ref() {
+            if record.info().get(key).is_none() {
+                record.info_mut().insert(field.clone());
+            }
+        }
+    }
+
+    let mut keys: Vec<Key> = Vec::new();
+
+    for other in site.iter().flatten() {
+        for key in other.genotypes().keys().iter() {
+            if !keys.contains(key) {
+                keys.push(key.clone());
+            }
+        }
+    }
+
+    if !header.sample_names().is_empty() && keys.is_empty() {
+        keys.push(Key::Genotype);
+    }
+
+    // The genotype (`GT`) must be the first key.
+    if let Some(i) = keys.iter().position(|key| key == &Key::Genotype) {
+        let key = keys.remove(i);
+        keys.insert(0, key);
+    }
+
+    let mut genotypes = Vec::with_capacity(header.sample_names().len());
+
+    for (source, input_record) in sources.iter().zip(&site) {
+        for j in 0..source.sample_count {
+            let input_genotype = input_record
+                .as_ref()
+                .and_then(|input_record| input_record.genotypes().get(j));
+
+            let fields: Vec<_> = keys
+                .iter()
+                .map(|key| match input_genotype {
+                    Some(genotype) => genotype
+                        .get(key)
+                        .cloned()
+                        .unwrap_or_else(|| Field::new(key.clone(), None)),
+                    None if key == &Key::Genotype => {
+                        Field::new(key.clone(), Some(Value::String(MISSING_GENOTYPE.into())))
+                    }
+                    None => Field::new(key.clone(), None),
+                })
+                .collect();
+
+            let genotype = Genotype::try_from(fields)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+            genotypes.push(genotype);
+        }
+    }
+
+    *record.genotypes_mut() = if genotypes.is_empty() {
+        Genotypes::default()
+    } else {
+        let keys =
+            Keys::try_from(keys).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        Genotypes::new(keys, genotypes)
+    };
+
+    Ok(record)
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_bcf::{self as bcf, header::StringMaps};
+
+    use super::*;
+    use crate::variant;
+
+    const VCF_DATA: &[u8] = b"##fileformat=VCFv4.3
+##FORMAT=<ID=GT,Number=1,Type=String,Description=\"Genotype\">
+##FORMAT=<ID=DP,Number=1,Type=Integer,Description=\"Read depth\">
+##contig=<ID=sq0>
+##contig=<ID=sq1>
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample0
+sq0\t8\tid0\tA\tC\t.\tPASS\t.\tGT:DP\t0/1:13
+sq0\t8\t.\tA\tG\t.\tPASS\t.\tGT:DP\t1/1:8
+sq1\t5\t.\tT\tA\t.\tPASS\t.\tGT:DP\t0|1:21
+";
+
+    fn build_bcf_data() -> io::Result<Vec<u8>> {
+        let data = b"##fileformat=VCFv4.3
+##FORMAT=<ID=GT,Number=1,Type=String,Description=\"Genotype\">
+##contig=<ID=sq0>
+##contig=<ID=sq1>
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample1
+sq0\t8\tid1\tA\tG\t.\tPASS\t.\tGT\t0/1
+sq0\t8\t.\tA\tC\t.\tPASS\t.\tGT\t1/1
+sq0\t13\t.\tG\tT\t.\tPASS\t.\tGT\t0/1
+";
+
+        let mut reader = variant::reader::Builder::default().build_from_reader(&data[..])?;
+        let header = reader.read_header()?;
+        let string_maps = StringMaps::from(&header);
+
+        let mut writer = bcf::Writer::new(Vec::new());
+        writer.write_file_format()?;
+        writer.write_header(&header)?;
+
+        for result in reader.records(&header) {
+            let record = result?;
+            writer.write_vcf_record(&header, &string_maps, &record)?;
+        }
+
+        writer.into_inner().finish()
+    }
+
+    #[test]
+    fn test_records() -> io::Result<()> {
+        let readers = vec![
+            variant::reader::Builder::default().build_from_reader(VCF_DATA)?,
+            variant::reader::Builder::default()
+                .build_from_reader(io::Cursor::new(build_bcf_data()?))?,
+        ];
+
+        let mut merger = Merger::new(readers)?;
+
+        let sample_names: Vec<_> = merger.header().sample_names().iter().collect();
+        assert_eq!(sample_names, ["sample0", "sample1"]);
+
+        let actual: Vec<_> = merger
+            .records()
+            .map(|result| result.map(|record| record.to_string()))
+            .collect::<io::Result<_>>()?;
+
+        let expected = [
+            "sq0\t8\tid0\tA\tC\t.\tPASS\t.\tGT:DP\t0/1:13\t1/1:.",
+            "sq0\t8\tid1\tA\tG\t.\tPASS\t.\tGT:DP\t1/1:8\t0/1:.",
+            "sq0\t13\t.\tG\tT\t.\tPASS\t.\tGT\t./.\t0/1",
+            "sq1\t5\t.\tT\tA\t.\tPASS\t.\tGT:DP\t0|1:21\t./.:.",
+        ];
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_records_with_unsorted_input() -> io::Result<()> {
+        let data = b"##fileformat=VCFv4.3
+##contig=<ID=sq0>
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+sq0\t13\t.\tA\tC\t.\tPASS\t.
+sq0\t8\t.\tA\tC\t.\tPASS\t.
+";
+
+        let reader = variant::reader::Builder::default().build_from_reader(&data[..])?;
+        let mut merger = Merger::new(vec![reader])?;
+
+        assert!(matches!(
+            merger.records().collect::<io::Result<Vec<_>>>(),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_new_with_duplicate_sample_names() -> io::Result<()> {
+        let readers = vec![
+            variant::reader::Builder::default().build_from_reader(VCF_DATA)?,
+            variant::reader::Builder::default().build_from_reader(VCF_DATA)?,
+        ];
+
+        assert!(matches!(
+            Merger::new(readers),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,105 @@
+//! Variant record normalization.
+//!
+//! This splits multiallelic records into biallelic records ([`split`]), joins biallelic records
+//! back into multiallelic records ([`join`]), and left-aligns and trims alleles against a
+//! reference sequence repository ([`left_align`]).
+//!
+//! Fields are subset and merged by allele using the number (`Number`) of their header
+//! definitions. Fields that are not defined in the header use the number of their reserved
+//! definitions, if any.
+//!
+//! # Examples
+//!
+//! ```
+//! # use std::io;
+//! use noodles_fasta as fasta;
+//! use noodles_util::variant::normalize;
+//! use noodles_vcf::{self as vcf, record::Position};
+//!
+//! let repository = fasta::Repository::new(vec![fasta::Record::new(
+//!     fasta::record::Definition::new("sq0", None),
+//!     fasta::record::Sequence::from(b"ACGTTTTAC".to_vec()),
+//! )]);
+//!
+//! let header = vcf::Header::default();
+//!
+//! let record = vcf::Record::builder()
+//!     .set_chromosome("sq0".parse()?)
+//!     .set_position(Position::from(6))
+//!     .set_reference_bases("TT".parse()?)
+//!     .set_alternate_bases("T,TTT".parse()?)
+//!     .build()?;
+//!
+//! let mut records = normalize::split(&header, &record)?;
+//!
+//! for record in &mut records {
+//!     normalize::left_align(record, &repository)?;
+//! }
+//!
+//! let alleles: Vec<_> = records
+//!     .iter()
+//!     .map(|record| {
+//!         (
+//!             usize::from(record.position()),
+//!             record.reference_bases().to_string(),
+//!             record.alternate_bases().to_string(),
+//!         )
+//!     })
+//!     .collect();
+//!
+//! assert_eq!(alleles, [
+//!     (3, String::from("GT"), String::from("G")),
+//!     (3, String::from("G"), String::from("GT")),
+//! ]);
+//! # Ok::<_, Box<dyn std::error::Error>>(())
+//! ```
+
+mod join;
+mod left_align;
+mod split;
+mod values;
+
+pub use self::{join::join, left_align::left_align, split::split};
+
+use std::io;
+
+use noodles_vcf::{
+    header::format::Key,
+    record::genotypes::{
+        genotype::field::{value::genotype::Allele, Value},
+        Genotype,
+    },
+};
+
+const MISSING_POSITION: &str = ".";
+
+fn genotype_alleles(genotype: &Genotype) -> io::Result<Option<Vec<Allele>>> {
+    genotype
+        .genotype()
+        .transpose()
+        .map(|gt| gt.map(|gt| gt.to_vec()))
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+fn set_genotype_alleles(genotype: &mut Genotype, alleles: &[Allele]) {
+    if let Some(field) = genotype.get_mut(&Key::Genotype) {
+        *field.value_mut() = Some(Value::String(format_genotype_alleles(alleles)));
+    }
+}
+
+fn format_genotype_alleles(alleles: &[Allele]) -> String {
+    let mut s = String::new();
+
+    for allele in alleles {
+        if let Some(phasing) = allele.phasing() {
+            s.push_str(phasing.as_ref());
+        }
+
+        match allele.position() {
+            Some(position) => s.push_str(&position.to_string()),
+            None => s.push_str(MISSING_POSITION),
+        }
+    }
+
+    s
+}
@@ -0,0 +1,464 @@
+use std::{io, iter};
+
+use noodles_vcf::{
+    self as vcf,
+    header::{format::Key as FormatKey, info::Key as InfoKey, Number},
+    record::{
+        genotypes::{
+            genotype::{
+                field::{value::genotype::Allele, Value as GenotypeFieldValue},
+                Field as GenotypeField,
+            },
+            Genotype, Keys,
+        },
+        info::{field::Value as InfoFieldValue, Field as InfoField},
+        AlternateBases, Genotypes, Info,
+    },
+};
+
+use super::{
+    format_genotype_alleles, genotype_alleles,
+    values::{format_number, infer_ploidy, info_number, Elements, FieldValue},
+};
+
+/// Joins biallelic records at the same site into a multiallelic record.
+///
+/// This is the inverse of [`super::split`]. The records must have the same chromosome, position,
+/// and reference bases, and each record must have exactly one alternate allele. The alternate
+/// alleles are joined in the order of the records.
+///
+/// Fields with `Number=A`, `Number=R`, and `Number=G` are merged by allele. Reference allele
+/// values are taken from the first record, and values for genotypes of two different alternate
+/// alleles, which are not known by any of the records, are set as missing. Other fields are taken
+/// from the first record that has them.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_util::variant::normalize;
+/// use noodles_vcf::{self as vcf, record::Position};
+///
+/// let header = vcf::Header::default();
+///
+/// let records = ["C", "G"]
+///     .into_iter()
+///     .map(|alternate_bases| {
+///         vcf::Record::builder()
+///             .set_chromosome("sq0".parse()?)
+///             .set_position(Position::from(8))
+///             .set_reference_bases("A".parse()?)
+///             .set_alternate_bases(alternate_bases.parse()?)
+///             .build()
+///             .map_err(Box::<dyn std::error::Error>::from)
+///     })
+///     .collect::<Result<Vec<_>, _>>()?;
+///
+/// let record = normalize::join(&header, &records)?;
+/// assert_eq!(record.alternate_bases().to_string(), "C,G");
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn join(header: &vcf::Header, records: &[vcf::Record]) -> io::Result<vcf::Record> {
+    let (first, rest) = records
+        .split_first()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "no records to join"))?;
+
+    if rest.is_empty() {
+        return Ok(first.clone());
+    }
+
+    if records
+        .iter()
+        .any(|record| record.alternate_bases().len() != 1)
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "record is not biallelic",
+        ));
+    }
+
+    if rest.iter().any(|record| {
+        record.chromosome() != first.chromosome()
+            || record.position() != first.position()
+            || record.reference_bases() != first.reference_bases()
+    }) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "records are not at the same site",
+        ));
+    }
+
+    let mut joined_record = first.clone();
+
+    let alternate_alleles: Vec<_> = records
+        .iter()
+        .map(|record| record.alternate_bases()[0].clone())
+        .collect();
+    *joined_record.alternate_bases_mut() = AlternateBases::from(alternate_alleles);
+
+    for record in rest {
+        for id in record.ids().iter() {
+            joined_record.ids_mut().insert(id.clone());
+        }
+    }
+
+    *joined_record.info_mut() = join_info(header, records)?;
+    *joined_record.genotypes_mut() = join_genotypes(header, records)?;
+
+    Ok(joined_record)
+}
+
+fn join_info(header: &vcf::Header, records: &[vcf::Record]) -> io::Result<Info> {
+    let mut keys: Vec<&InfoKey> = Vec::new();
+
+    for record in records {
+        for key in record.info().keys() {
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+    }
+
+    let fields: Vec<_> = keys
+        .into_iter()
+        .map(|key| {
+            let values: Vec<_> = records
+                .iter()
+                .map(|record| record.info().get(key).and_then(|field| field.value()))
+                .collect();
+
+            let value = join_values::<InfoFieldValue>(info_number(header, key), &values, None);
+
+            InfoField::new(key.clone(), value)
+        })
+        .collect();
+
+    Info::try_from(fields).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+fn join_genotypes(header: &vcf::Header, records: &[vcf::Record]) -> io::Result<Genotypes> {
+    let sample_count = records[0].genotypes().len();
+
+    if records
+        .iter()
+        .any(|record| record.genotypes().len() != sample_count)
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "records have different numbers of samples",
+        ));
+    }
+
+    let mut keys: Vec<FormatKey> = Vec::new();
+
+    for record in records {
+        for key in record.genotypes().keys().iter() {
+            if !keys.contains(key) {
+                keys.push(key.clone());
+            }
+        }
+    }
+
+    // The genotype (`GT`) must be the first key.
+    if let Some(i) = keys.iter().position(|key| key == &FormatKey::Genotype) {
+        let key = keys.remove(i);
+        keys.insert(0, key);
+    }
+
+    let mut genotypes = Vec::with_capacity(sample_count);
+
+    for i in 0..sample_count {
+        let sample_genotypes: Vec<&Genotype> = records
+            .iter()
+            .map(|record| &record.genotypes()[i])
+            .collect();
+
+        let sample_alleles = sample_genotypes
+            .iter()
+            .map(|genotype| genotype_alleles(genotype))
+            .collect::<io::Result<Vec<_>>>()?;
+
+        let alleles = join_genotype_alleles(&sample_alleles);
+        let ploidy = alleles.as_ref().map(|alleles| alleles.len());
+
+        let fields: Vec<_> = keys
+            .iter()
+            .map(|key| {
+                let value = if key == &FormatKey::Genotype {
+                    alleles
+                        .as_deref()
+                        .map(|alleles| GenotypeFieldValue::String(format_genotype_alleles(alleles)))
+                } else {
+                    let values: Vec<_> = sample_genotypes
+                        .iter()
+                        .map(|genotype| genotype.get(key).and_then(|field| field.value()))
+                        .collect();
+
+                    join_values::<GenotypeFieldValue>(format_number(header, key), &values, ploidy)
+                };
+
+                GenotypeField::new(key.clone(), value)
+            })
+            .collect();
+
+        let genotype = Genotype::try_from(fields)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        genotypes.push(genotype);
+    }
+
+    let keys = Keys::try_from(keys).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    Ok(Genotypes::new(keys, genotypes))
+}
+
+fn join_values<V>(number: Number, values: &[Option<&V>], ploidy: Option<usize>) -> Option<V>
+where
+    V: Clone + FieldValue,
+{
+    let sources: Vec<_> = values
+        .iter()
+        .map(|value| value.and_then(|v| v.to_elements()))
+        .collect();
+
+    let ploidy = ploidy
+        .or_else(|| {
+            sources
+                .iter()
+                .flatten()
+                .next()
+                .and_then(|elements| infer_ploidy(elements.len(), 2))
+        })
+        .unwrap_or(2);
+
+    match join_picks(number, values.len(), ploidy) {
+        Some(picks) if sources.iter().any(|source| source.is_some()) => {
+            Elements::gather(&sources, &picks).and_then(V::from_elements)
+        }
+        _ => values.iter().flatten().next().map(|&value| value.clone()),
+    }
+}
+
+/// Returns the (record index, value index) pairs of the values of a joined field.
+///
+/// A `None` pick is a value that is unknown by any of the records.
+fn join_picks(
+    number: Number,
+    record_count: usize,
+    ploidy: usize,
+) -> Option<Vec<Option<(usize, usize)>>> {
+    match number {
+        Number::A => Some((0..record_count).map(|i| Some((i, 0))).collect()),
+        Number::R => Some(reference_and_alternate_picks(record_count)),
+        Number::G => match ploidy {
+            1 => Some(reference_and_alternate_picks(record_count)),
+            2 => {
+                let mut picks = Vec::new();
+
+                for b in 0..=record_count {
+                    for a in 0..=b {
+                        let pick = match (a, b) {
+                            (0, 0) => Some((0, 0)),
+                            (0, k) => Some((k - 1, 1)),
+                            (a, b) if a == b => Some((a - 1, 2)),
+                            _ => None,
+                        };
+
+                        picks.push(pick);
+                    }
+                }
+
+                Some(picks)
+            }
+            // Polyploid genotype values are unsupported and set as missing.
+            _ => Some(Vec::new()),
+        },
+        Number::Count(_) | Number::Unknown => None,
+    }
+}
+
+fn reference_and_alternate_picks(record_count: usize) -> Vec<Option<(usize, usize)>> {
+    iter::once(Some((0, 0)))
+        .chain((0..record_count).map(|i| Some((i, 1))))
+        .collect()
+}
+
+/// Joins the genotype alleles of the records.
+///
+/// The alternate allele of the record at index `i` is the joined allele `i + 1`. The alleles of
+/// the first record with a genotype are used as the base, and the alternate alleles of the
+/// following records replace reference alleles, preferably at the same index.
+fn join_genotype_alleles(sample_alleles: &[Option<Vec<Allele>>]) -> Option<Vec<Allele>> {
+    let mut joined_alleles: Option<Vec<Allele>> = None;
+
+    for (i, alleles) in sample_alleles.iter().enumerate() {
+        let alleles = match alleles {
+            Some(alleles) => alleles,
+            None => continue,
+        };
+
+        let k = i + 1;
+
+        let joined_alleles = match joined_alleles.as_mut() {
+            Some(joined_alleles) => joined_alleles,
+            None => {
+                let mut alleles = alleles.clone();
+
+                for allele in &mut alleles {
+                    if allele.position() == Some(1) {
+                        *allele.position_mut() = Some(k);
+                    }
+                }
+
+                joined_alleles = Some(alleles);
+
+                continue;
+            }
+        };
+
+        for (j, allele) in alleles.iter().enumerate() {
+            if allele.position() != Some(1) {
+                continue;
+            }
+
+            let is_reference = |allele: &Allele| allele.position() == Some(0);
+
+            let target = if joined_alleles.get(j).map(is_reference).unwrap_or(false) {
+                Some(j)
+            } else {
+                joined_alleles.iter().position(is_reference)
+            };
+
+            if let Some(target) = target {
+                *joined_alleles[target].position_mut() = Some(k);
+            }
+        }
+    }
+
+    joined_alleles
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_vcf::{
+        header::record::value::Map,
+        record::{genotypes::genotype::field::value::genotype::allele::Phasing, Position},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_join() -> Result<(), Box<dyn std::error::Error>> {
+        let header = vcf::Header::builder()
+            .add_info(Map::from(InfoKey::AlleleCount))
+            .add_info(Map::from(InfoKey::TotalDepth))
+            .add_format(Map::from(FormatKey::Genotype))
+            .add_format(Map::from(FormatKey::ReadDepths))
+            .add_format(Map::from(FormatKey::RoundedGenotypeLikelihoods))
+            .add_sample_name("sample0")
+            .add_sample_name("sample1")
+            .build();
+
+        let records = [
+            "sq0\t8\tid0\tA\tC\t.\t.\tAC=1;DP=13\tGT:AD:PL\t0|0:5,0:40,30,20\t1/0:.:.",
+            "sq0\t8\tid1\tA\tG\t.\t.\tAC=2;DP=21\tGT:AD:PL\t0|1:5,8:40,10,0\t0/1:.:.",
+        ]
+        .into_iter()
+        .map(|s| vcf::Record::try_from_str(s, &header))
+        .collect::<Result<Vec<_>, _>>()?;
+
+        let record = join(&header, &records)?;
+
+        assert_eq!(
+            record.to_string(),
+            "sq0\t8\tid0;id1\tA\tC,G\t.\t.\tAC=1,2;DP=13\tGT:AD:PL\t0|2:5,0,8:40,30,20,10,.,0\t1/2:.:."
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_join_with_invalid_records() -> Result<(), Box<dyn std::error::Error>> {
+        let header = vcf::Header::default();
+
+        assert!(matches!(
+            join(&header, &[]),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        let build_record = |position, alternate_bases: &str| {
+            vcf::Record::builder()
+                .set_chromosome("sq0".parse()?)
+                .set_position(Position::from(position))
+                .set_reference_bases("A".parse()?)
+                .set_alternate_bases(alternate_bases.parse()?)
+                .build()
+                .map_err(Box::<dyn std::error::Error>::from)
+        };
+
+        let records = [build_record(8, "C")?, build_record(13, "G")?];
+        assert!(matches!(
+            join(&header, &records),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        let records = [build_record(8, "C")?, build_record(8, "G,T")?];
+        assert!(matches!(
+            join(&header, &records),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_join_picks() {
+        assert_eq!(
+            join_picks(Number::A, 2, 2),
+            Some(vec![Some((0, 0)), Some((1, 0))])
+        );
+
+        assert_eq!(
+            join_picks(Number::R, 2, 2),
+            Some(vec![Some((0, 0)), Some((0, 1)), Some((1, 1))])
+        );
+
+        assert_eq!(
+            join_picks(Number::G, 2, 2),
+            Some(vec![
+                Some((0, 0)),
+                Some((0, 1)),
+                Some((0, 2)),
+                Some((1, 1)),
+                None,
+                Some((1, 2)),
+            ])
+        );
+
+        assert_eq!(join_picks(Number::Count(1), 2, 2), None);
+    }
+
+    #[test]
+    fn test_join_genotype_alleles() {
+        let alleles = [
+            Some(vec![
+                Allele::new(Some(0), None),
+                Allele::new(Some(1), Some(Phasing::Phased)),
+            ]),
+            Some(vec![
+                Allele::new(Some(1), None),
+                Allele::new(Some(0), Some(Phasing::Phased)),
+            ]),
+        ];
+
+        assert_eq!(
+            join_genotype_alleles(&alleles),
+            Some(vec![
+                Allele::new(Some(2), None),
+                Allele::new(Some(1), Some(Phasing::Phased)),
+            ])
+        );
+
+        assert!(join_genotype_alleles(&[None, None]).is_none());
+    }
+}
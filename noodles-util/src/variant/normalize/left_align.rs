@@ -0,0 +1,366 @@
+use std::io;
+
+use noodles_fasta as fasta;
+use noodles_vcf::{
+    self as vcf,
+    record::{
+        alternate_bases::Allele, reference_bases::Base, AlternateBases, Chromosome, Position,
+        ReferenceBases,
+    },
+};
+
+// The initial number of reference sequence bases preceding a record that its alleles can be
+// shifted over. This doubles while the alleles continue to shift past its start.
+const INITIAL_WINDOW_LEN: usize = 64;
+
+/// Left-aligns and trims the alleles of a record.
+///
+/// Bases shared by the ends of all alleles are trimmed, and the alleles are shifted left while
+/// their last bases are the same, extending them with the preceding reference bases. Bases shared
+/// by the starts of all alleles are then trimmed, while keeping at least one base per allele.
+///
+/// Records with symbolic or breakend alternate alleles, no alternate alleles, an alternate allele
+/// that is the same as the reference allele, or a symbolic chromosome are left unchanged.
+///
+/// Reference sequence bases are compared case-insensitively.
+///
+/// This returns whether the record was changed. An error is returned if the reference sequence is
+/// missing from the repository or the reference bases do not match the reference sequence.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_fasta as fasta;
+/// use noodles_util::variant::normalize;
+/// use noodles_vcf::{self as vcf, record::Position};
+///
+/// let repository = fasta::Repository::new(vec![fasta::Record::new(
+///     fasta::record::Definition::new("sq0", None),
+///     fasta::record::Sequence::from(b"ACGTTTTAC".to_vec()),
+/// )]);
+///
+/// // sq0:6 TT>T is the same deletion as sq0:3 GT>G.
+/// let mut record = vcf::Record::builder()
+///     .set_chromosome("sq0".parse()?)
+///     .set_position(Position::from(6))
+///     .set_reference_bases("TT".parse()?)
+///     .set_alternate_bases("T".parse()?)
+///     .build()?;
+///
+/// assert!(normalize::left_align(&mut record, &repository)?);
+/// assert_eq!(usize::from(record.position()), 3);
+/// assert_eq!(record.reference_bases().to_string(), "GT");
+/// assert_eq!(record.alternate_bases().to_string(), "G");
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn left_align(record: &mut vcf::Record, repository: &fasta::Repository) -> io::Result<bool> {
+    let name = match record.chromosome() {
+        Chromosome::Name(name) => name.clone(),
+        Chromosome::Symbol(_) => return Ok(false),
+    };
+
+    if record.alternate_bases().is_empty() {
+        return Ok(false);
+    }
+
+    let mut alleles = Vec::with_capacity(record.alternate_bases().len() + 1);
+    alleles.push(bases_to_vec(record.reference_bases()));
+
+    for allele in record.alternate_bases().iter() {
+        match allele {
+            Allele::Bases(bases) => alleles.push(bases_to_vec(bases)),
+            _ => return Ok(false),
+        }
+    }
+
+    // A degenerate allele would otherwise be shifted to the start of the reference sequence.
+    if alleles[1..].iter().any(|allele| allele == &alleles[0]) {
+        return Ok(false);
+    }
+
+    let sequence = repository.get(&name).transpose()?.ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("missing reference sequence: {}", name),
+        )
+    })?;
+
+    let sequence = sequence.as_ref();
+
+    let position = usize::from(record.position());
+    let original_start = position
+        .checked_sub(1)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid position"))?;
+
+    let reference_end = original_start + alleles[0].len();
+
+    let is_reference_match = sequence
+        .get(original_start..reference_end)
+        .map(|bases| {
+            bases
+                .iter()
+                .map(|&b| normalize_base(b))
+                .eq(alleles[0].iter().copied())
+        })
+        .unwrap_or(false);
+
+    if !is_reference_match {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "reference bases mismatch at {}:{}: expected {}",
+                name,
+                position,
+                record.reference_bases()
+            ),
+        ));
+    }
+
+    let mut window_len = INITIAL_WINDOW_LEN;
+
+    let (start, aligned_alleles) = loop {
+        let window_start = original_start.saturating_sub(window_len);
+
+        let window: Vec<_> = sequence[window_start..original_start]
+            .iter()
+            .map(|&b| normalize_base(b))
+            .collect();
+
+        let mut aligned_alleles = alleles.clone();
+
+        if let Some(start) = align(&window, window_start, &mut aligned_alleles, original_start) {
+            break (start, aligned_alleles);
+        }
+
+        window_len *= 2;
+    };
+
+    if start == original_start && aligned_alleles == alleles {
+        return Ok(false);
+    }
+
+    *record.position_mut() = Position::from(start + 1);
+
+    let mut alleles = aligned_alleles
+        .into_iter()
+        .map(|allele| vec_to_bases(&allele));
+
+    let reference_bases = alleles.next().unwrap_or_default();
+    *record.reference_bases_mut() = ReferenceBases::try_from(reference_bases)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    *record.alternate_bases_mut() =
+        AlternateBases::from(alleles.map(Allele::Bases).collect::<Vec<_>>());
+
+    Ok(true)
+}
+
+// Left-aligns and trims the alleles, returning the new 0-based start position.
+//
+// `window` is the reference sequence that precedes `start`, beginning at `window_start`. This
+// returns `None` if the alleles shift past the start of the window but not the start of the
+// reference sequence.
+fn align(
+    window: &[u8],
+    window_start: usize,
+    alleles: &mut [Vec<u8>],
+    mut start: usize,
+) -> Option<usize> {
+    loop {
+        let mut is_modified = false;
+
+        // An allele may only become empty if it can be extended to the left.
+        let can_trim = alleles
+            .iter()
+            .all(|allele| allele.len() > 1 || start > 0 && !allele.is_empty());
+
+        if can_trim && have_same_last_base(alleles) {
+            for allele in alleles.iter_mut() {
+                allele.pop();
+            }
+
+            is_modified = true;
+        }
+
+        if alleles.iter().any(|allele| allele.is_empty()) {
+            start -= 1;
+
+            let base = start
+                .checked_sub(window_start)
+                .and_then(|i| window.get(i).copied())?;
+
+            for allele in alleles.iter_mut() {
+                allele.insert(0, base);
+            }
+
+            is_modified = true;
+        }
+
+        if !is_modified {
+            break;
+        }
+    }
+
+    while alleles.iter().all(|allele| allele.len() > 1) && have_same_first_base(alleles) {
+        for allele in alleles.iter_mut() {
+            allele.remove(0);
+        }
+
+        start += 1;
+    }
+
+    Some(start)
+}
+
+fn have_same_last_base(alleles: &[Vec<u8>]) -> bool {
+    let base = alleles.first().and_then(|allele| allele.last());
+    base.is_some() && alleles.iter().all(|allele| allele.last() == base)
+}
+
+fn have_same_first_base(alleles: &[Vec<u8>]) -> bool {
+    let base = alleles.first().and_then(|allele| allele.first());
+    base.is_some() && alleles.iter().all(|allele| allele.first() == base)
+}
+
+fn bases_to_vec(bases: &[Base]) -> Vec<u8> {
+    bases.iter().map(|&base| char::from(base) as u8).collect()
+}
+
+fn vec_to_bases(bases: &[u8]) -> Vec<Base> {
+    bases
+        .iter()
+        .map(|&b| Base::try_from(char::from(b)).unwrap_or(Base::N))
+        .collect()
+}
+
+fn normalize_base(b: u8) -> u8 {
+    match b.to_ascii_uppercase() {
+        b @ (b'A' | b'C' | b'G' | b'T') => b,
+        _ => b'N',
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_align() {
+        fn t(sequence: &[u8], alleles: &[&[u8]], start: usize, expected: (&[&[u8]], usize)) {
+            let mut actual_alleles: Vec<_> = alleles.iter().map(|allele| allele.to_vec()).collect();
+            let actual_start = align(&sequence[..start], 0, &mut actual_alleles, start);
+
+            let expected_alleles: Vec<_> =
+                expected.0.iter().map(|allele| allele.to_vec()).collect();
+            assert_eq!(
+                (actual_alleles, actual_start),
+                (expected_alleles, Some(expected.1))
+            );
+        }
+
+        let sequence = b"ACGTTTTAC";
+
+        // deletion in a homopolymer
+        t(sequence, &[b"TT", b"T"], 5, (&[b"GT", b"G"], 2));
+        // insertion in a homopolymer
+        t(sequence, &[b"T", b"TT"], 6, (&[b"G", b"GT"], 2));
+        // right-padded deletion
+        t(sequence, &[b"TTA", b"TA"], 5, (&[b"GT", b"G"], 2));
+        // shared prefix
+        t(sequence, &[b"GTTT", b"GTTA"], 2, (&[b"T", b"A"], 5));
+        // SNV
+        t(sequence, &[b"T", b"A"], 3, (&[b"T", b"A"], 3));
+        // deletion at the start of the sequence
+        t(b"AAAC", &[b"AA", b"A"], 1, (&[b"AA", b"A"], 0));
+        // multiallelic
+        t(
+            sequence,
+            &[b"TTT", b"TT", b"T"],
+            4,
+            (&[b"GTT", b"GT", b"G"], 2),
+        );
+
+        // shifting past the start of the window
+        let mut alleles = vec![b"TT".to_vec(), b"T".to_vec()];
+        assert!(align(b"TTT", 3, &mut alleles, 6).is_none());
+    }
+
+    #[test]
+    fn test_left_align() -> Result<(), Box<dyn std::error::Error>> {
+        let repository = fasta::Repository::new(vec![fasta::Record::new(
+            fasta::record::Definition::new("sq0", None),
+            fasta::record::Sequence::from(b"acgtttTAC".to_vec()),
+        )]);
+
+        let build_record =
+            |chromosome: &str, position, reference_bases: &str, alternate_bases: &str| {
+                vcf::Record::builder()
+                    .set_chromosome(chromosome.parse()?)
+                    .set_position(Position::from(position))
+                    .set_reference_bases(reference_bases.parse()?)
+                    .set_alternate_bases(alternate_bases.parse()?)
+                    .build()
+                    .map_err(Box::<dyn std::error::Error>::from)
+            };
+
+        let mut record = build_record("sq0", 6, "TT", "T")?;
+        assert!(left_align(&mut record, &repository)?);
+        assert_eq!(record, build_record("sq0", 3, "GT", "G")?);
+
+        let mut record = build_record("sq0", 4, "T", "A")?;
+        assert!(!left_align(&mut record, &repository)?);
+
+        let mut record = build_record("sq0", 4, "T", "<DEL>")?;
+        assert!(!left_align(&mut record, &repository)?);
+
+        let mut record = build_record("sq0", 6, "T", "T")?;
+        assert!(!left_align(&mut record, &repository)?);
+        assert_eq!(record, build_record("sq0", 6, "T", "T")?);
+
+        let mut record = build_record("sq0", 6, "TT", "A,TT")?;
+        assert!(!left_align(&mut record, &repository)?);
+
+        let mut record = build_record("sq0", 4, "A", "T")?;
+        assert!(matches!(
+            left_align(&mut record, &repository),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        let mut record = build_record("sq1", 4, "T", "TT")?;
+        assert!(matches!(
+            left_align(&mut record, &repository),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_left_align_with_long_repeat() -> Result<(), Box<dyn std::error::Error>> {
+        let repeat_len = INITIAL_WINDOW_LEN * 3;
+
+        let mut sequence = b"ac".to_vec();
+        sequence.extend((0..repeat_len).map(|_| b't'));
+        sequence.push(b'g');
+
+        let repository = fasta::Repository::new(vec![fasta::Record::new(
+            fasta::record::Definition::new("sq0", None),
+            fasta::record::Sequence::from(sequence),
+        )]);
+
+        let mut record = vcf::Record::builder()
+            .set_chromosome("sq0".parse()?)
+            .set_position(Position::from(repeat_len + 1))
+            .set_reference_bases("TT".parse()?)
+            .set_alternate_bases("T".parse()?)
+            .build()?;
+
+        assert!(left_align(&mut record, &repository)?);
+        assert_eq!(usize::from(record.position()), 2);
+        assert_eq!(record.reference_bases().to_string(), "CT");
+        assert_eq!(record.alternate_bases().to_string(), "C");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,279 @@
+use std::io;
+
+use noodles_vcf::{
+    self as vcf,
+    header::{format::Key as FormatKey, Number},
+    record::{genotypes::genotype::field::Value as GenotypeFieldValue, AlternateBases},
+};
+
+use super::{
+    genotype_alleles, set_genotype_alleles,
+    values::{
+        diploid_genotype_index, format_number, genotype_count, infer_ploidy, info_number,
+        FieldValue,
+    },
+};
+
+/// Splits a multiallelic record into biallelic records.
+///
+/// There is one record per alternate allele, in the order of the alternate bases. Fields with
+/// `Number=A`, `Number=R`, and `Number=G` are subset to the reference allele and the given
+/// alternate allele. Genotype (`GT`) allele indices of the given alternate allele are set to 1;
+/// the indices of the other alternate alleles are set to the reference allele (0).
+///
+/// A record with fewer than two alternate alleles is returned as is.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_util::variant::normalize;
+/// use noodles_vcf::{self as vcf, header::format::Key, record::{Genotypes, Position}};
+///
+/// let header = vcf::Header::default();
+///
+/// let record = vcf::Record::builder()
+///     .set_chromosome("sq0".parse()?)
+///     .set_position(Position::from(8))
+///     .set_reference_bases("A".parse()?)
+///     .set_alternate_bases("C,G".parse()?)
+///     .set_genotypes(Genotypes::parse("GT\t1/2", &header)?)
+///     .build()?;
+///
+/// let records = normalize::split(&header, &record)?;
+///
+/// let genotypes: Vec<_> = records
+///     .iter()
+///     .map(|record| record.genotypes()[0][&Key::Genotype].to_string())
+///     .collect();
+///
+/// assert_eq!(genotypes, ["1/0", "0/1"]);
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn split(header: &vcf::Header, record: &vcf::Record) -> io::Result<Vec<vcf::Record>> {
+    let alternate_allele_count = record.alternate_bases().len();
+
+    if alternate_allele_count < 2 {
+        return Ok(vec![record.clone()]);
+    }
+
+    (1..=alternate_allele_count)
+        .map(|k| split_allele(header, record, k))
+        .collect()
+}
+
+fn split_allele(header: &vcf::Header, record: &vcf::Record, k: usize) -> io::Result<vcf::Record> {
+    let allele_count = record.alternate_bases().len() + 1;
+
+    let mut split_record = record.clone();
+
+    let alternate_allele = record.alternate_bases()[k - 1].clone();
+    *split_record.alternate_bases_mut() = AlternateBases::from(vec![alternate_allele]);
+
+    for (key, field) in split_record.info_mut().as_mut().iter_mut() {
+        let number = info_number(header, key);
+
+        if let Some(elements) = field.value().and_then(|value| value.to_elements()) {
+            let ploidy = infer_ploidy(elements.len(), allele_count).unwrap_or(2);
+
+            if let Some(indices) = split_indices(number, elements.len(), allele_count, ploidy, k)
+                .map_err(|expected_len| invalid_value_count(key.as_ref(), expected_len))?
+            {
+                *field.value_mut() = FieldValue::from_elements(elements.select(&indices));
+            }
+        }
+    }
+
+    for genotype in split_record.genotypes_mut().iter_mut() {
+        let alleles = genotype_alleles(genotype)?;
+        let gt_ploidy = alleles.as_ref().map(|alleles| alleles.len());
+
+        for (key, field) in genotype.iter_mut() {
+            if key == &FormatKey::Genotype {
+                continue;
+            }
+
+            let number = format_number(header, key);
+
+            if let Some(elements) = field.value().and_then(|value| value.to_elements()) {
+                let ploidy = gt_ploidy
+                    .or_else(|| infer_ploidy(elements.len(), allele_count))
+                    .unwrap_or(2);
+
+                if let Some(indices) =
+                    split_indices(number, elements.len(), allele_count, ploidy, k)
+                        .map_err(|expected_len| invalid_value_count(key.as_ref(), expected_len))?
+                {
+                    *field.value_mut() =
+                        GenotypeFieldValue::from_elements(elements.select(&indices));
+                }
+            }
+        }
+
+        if let Some(mut alleles) = alleles {
+            for allele in alleles.iter_mut() {
+                if let Some(position) = allele.position_mut() {
+                    *position = usize::from(*position == k);
+                }
+            }
+
+            set_genotype_alleles(genotype, &alleles);
+        }
+    }
+
+    Ok(split_record)
+}
+
+/// Returns the indices of the values to keep for the alternate allele `k`.
+///
+/// This returns `None` if the field is not subset by allele and, if the number of values does not
+/// match the number of alleles, an error with the expected number of values.
+fn split_indices(
+    number: Number,
+    len: usize,
+    allele_count: usize,
+    ploidy: usize,
+    k: usize,
+) -> Result<Option<Vec<usize>>, usize> {
+    let (expected_len, indices) = match number {
+        Number::A => (allele_count - 1, vec![k - 1]),
+        Number::R => (allele_count, vec![0, k]),
+        Number::G => match genotype_count(allele_count, ploidy) {
+            Some(expected_len) if ploidy == 1 => (expected_len, vec![0, k]),
+            Some(expected_len) => (
+                expected_len,
+                vec![
+                    diploid_genotype_index(0, 0),
+                    diploid_genotype_index(0, k),
+                    diploid_genotype_index(k, k),
+                ],
+            ),
+            // Polyploid genotype values are unsupported and set as missing.
+            None => return Ok(Some(Vec::new())),
+        },
+        Number::Count(_) | Number::Unknown => return Ok(None),
+    };
+
+    if len == expected_len {
+        Ok(Some(indices))
+    } else {
+        Err(expected_len)
+    }
+}
+
+fn invalid_value_count(key: &str, expected_len: usize) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!(
+            "invalid number of values for {}: expected {}",
+            key, expected_len
+        ),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_vcf::{
+        header::{info::Key as InfoKey, record::value::Map},
+        record::{info::field::Value as InfoFieldValue, Genotypes, Position},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_split() -> Result<(), Box<dyn std::error::Error>> {
+        let header = vcf::Header::builder()
+            .add_info(Map::from(InfoKey::AlleleCount))
+            .add_info(Map::from(InfoKey::TotalDepth))
+            .add_format(Map::from(FormatKey::Genotype))
+            .add_format(Map::from(FormatKey::ReadDepths))
+            .add_format(Map::from(FormatKey::RoundedGenotypeLikelihoods))
+            .add_sample_name("sample0")
+            .add_sample_name("sample1")
+            .build();
+
+        let record = vcf::Record::builder()
+            .set_chromosome("sq0".parse()?)
+            .set_position(Position::from(8))
+            .set_reference_bases("A".parse()?)
+            .set_alternate_bases("C,G".parse()?)
+            .set_info("AC=1,2;DP=13".parse()?)
+            .set_genotypes(Genotypes::parse(
+                "GT:AD:PL\t0|2:5,0,8:40,30,20,10,5,0\t1/2:.:.",
+                &header,
+            )?)
+            .build()?;
+
+        let records = split(&header, &record)?;
+        assert_eq!(records.len(), 2);
+
+        let actual: Vec<_> = records.iter().map(|record| record.to_string()).collect();
+        let expected = [
+            "sq0\t8\t.\tA\tC\t.\t.\tAC=1;DP=13\tGT:AD:PL\t0|0:5,0:40,30,20\t1/0:.:.",
+            "sq0\t8\t.\tA\tG\t.\t.\tAC=2;DP=13\tGT:AD:PL\t0|1:5,8:40,10,0\t0/1:.:.",
+        ];
+        assert_eq!(actual, expected);
+
+        assert_eq!(
+            records[1]
+                .info()
+                .get(&InfoKey::AlleleCount)
+                .and_then(|field| field.value()),
+            Some(&InfoFieldValue::Integer(2))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_with_biallelic_record() -> Result<(), Box<dyn std::error::Error>> {
+        let header = vcf::Header::default();
+
+        let record = vcf::Record::builder()
+            .set_chromosome("sq0".parse()?)
+            .set_position(Position::from(8))
+            .set_reference_bases("A".parse()?)
+            .set_alternate_bases("C".parse()?)
+            .build()?;
+
+        assert_eq!(split(&header, &record)?, [record]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_with_invalid_value_count() -> Result<(), Box<dyn std::error::Error>> {
+        let header = vcf::Header::builder()
+            .add_info(Map::from(InfoKey::AlleleCount))
+            .build();
+
+        let record = vcf::Record::builder()
+            .set_chromosome("sq0".parse()?)
+            .set_position(Position::from(8))
+            .set_reference_bases("A".parse()?)
+            .set_alternate_bases("C,G".parse()?)
+            .set_info("AC=1".parse()?)
+            .build()?;
+
+        assert!(matches!(
+            split(&header, &record),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_indices() {
+        assert_eq!(split_indices(Number::A, 2, 3, 2, 2), Ok(Some(vec![1])));
+        assert_eq!(split_indices(Number::R, 3, 3, 2, 2), Ok(Some(vec![0, 2])));
+        assert_eq!(
+            split_indices(Number::G, 6, 3, 2, 2),
+            Ok(Some(vec![0, 3, 5]))
+        );
+        assert_eq!(split_indices(Number::G, 3, 3, 1, 2), Ok(Some(vec![0, 2])));
+        assert_eq!(split_indices(Number::G, 10, 3, 3, 2), Ok(Some(Vec::new())));
+        assert_eq!(split_indices(Number::Count(1), 1, 3, 2, 2), Ok(None));
+        assert_eq!(split_indices(Number::A, 3, 3, 2, 2), Err(2));
+    }
+}
@@ -0,0 +1,256 @@
+use noodles_vcf::{
+    self as vcf,
+    header::{
+        format::Key as FormatKey,
+        info::Key as InfoKey,
+        record::value::{
+            map::{Format, Info},
+            Map,
+        },
+        Number,
+    },
+    record::{
+        genotypes::genotype::field::Value as GenotypeFieldValue,
+        info::field::Value as InfoFieldValue,
+    },
+};
+
+/// The values of a field as a list of optional elements.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum Elements {
+    Integer(Vec<Option<i32>>),
+    Float(Vec<Option<f32>>),
+    Character(Vec<Option<char>>),
+    String(Vec<Option<String>>),
+}
+
+impl Elements {
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Integer(values) => values.len(),
+            Self::Float(values) => values.len(),
+            Self::Character(values) => values.len(),
+            Self::String(values) => values.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Selects the elements at the given indices. Out of range indices are set as missing.
+    pub fn select(&self, indices: &[usize]) -> Self {
+        fn select<T: Clone>(values: &[Option<T>], indices: &[usize]) -> Vec<Option<T>> {
+            indices
+                .iter()
+                .map(|&i| values.get(i).cloned().flatten())
+                .collect()
+        }
+
+        match self {
+            Self::Integer(values) => Self::Integer(select(values, indices)),
+            Self::Float(values) => Self::Float(select(values, indices)),
+            Self::Character(values) => Self::Character(select(values, indices)),
+            Self::String(values) => Self::String(select(values, indices)),
+        }
+    }
+
+    /// Gathers elements from a list of sources.
+    ///
+    /// Each pick is a (source index, element index) pair. A source may be missing, and a pick may
+    /// be `None`; both produce a missing element. This returns `None` when all sources are
+    /// missing.
+    pub fn gather(sources: &[Option<Self>], picks: &[Option<(usize, usize)>]) -> Option<Self> {
+        fn gather<T, F>(
+            sources: &[Option<Elements>],
+            picks: &[Option<(usize, usize)>],
+            f: F,
+        ) -> Vec<Option<T>>
+        where
+            T: Clone,
+            F: Fn(&Elements) -> Option<&[Option<T>]>,
+        {
+            picks
+                .iter()
+                .map(|pick| {
+                    pick.and_then(|(i, j)| {
+                        sources
+                            .get(i)
+                            .and_then(|source| source.as_ref())
+                            .and_then(&f)
+                            .and_then(|values| values.get(j).cloned().flatten())
+                    })
+                })
+                .collect()
+        }
+
+        let first = sources.iter().flatten().next()?;
+
+        let elements = match first {
+            Self::Integer(_) => Self::Integer(gather(sources, picks, |elements| match elements {
+                Self::Integer(values) => Some(values),
+                _ => None,
+            })),
+            Self::Float(_) => Self::Float(gather(sources, picks, |elements| match elements {
+                Self::Float(values) => Some(values),
+                _ => None,
+            })),
+            Self::Character(_) => {
+                Self::Character(gather(sources, picks, |elements| match elements {
+                    Self::Character(values) => Some(values),
+                    _ => None,
+                }))
+            }
+            Self::String(_) => Self::String(gather(sources, picks, |elements| match elements {
+                Self::String(values) => Some(values),
+                _ => None,
+            })),
+        };
+
+        Some(elements)
+    }
+}
+
+/// A field value that can be converted to and from a list of elements.
+pub(super) trait FieldValue: Sized {
+    /// Returns the value as elements or `None` if the value is not a list of elements (e.g., a
+    /// flag).
+    fn to_elements(&self) -> Option<Elements>;
+
+    /// Builds a value from a list of elements. No elements or a single missing element is a missing
+    /// value.
+    fn from_elements(elements: Elements) -> Option<Self>;
+}
+
+macro_rules! impl_field_value {
+    ($ty:ident) => {
+        impl FieldValue for $ty {
+            fn to_elements(&self) -> Option<Elements> {
+                match self {
+                    Self::Integer(n) => Some(Elements::Integer(vec![Some(*n)])),
+                    Self::Float(n) => Some(Elements::Float(vec![Some(*n)])),
+                    Self::Character(c) => Some(Elements::Character(vec![Some(*c)])),
+                    Self::String(s) => Some(Elements::String(vec![Some(s.clone())])),
+                    Self::IntegerArray(values) => Some(Elements::Integer(values.clone())),
+                    Self::FloatArray(values) => Some(Elements::Float(values.clone())),
+                    Self::CharacterArray(values) => Some(Elements::Character(values.clone())),
+                    Self::StringArray(values) => Some(Elements::String(values.clone())),
+                    #[allow(unreachable_patterns)]
+                    _ => None,
+                }
+            }
+
+            fn from_elements(elements: Elements) -> Option<Self> {
+                if elements.is_empty() {
+                    None
+                } else if elements.len() == 1 {
+                    match elements {
+                        Elements::Integer(mut values) => values.pop()?.map(Self::Integer),
+                        Elements::Float(mut values) => values.pop()?.map(Self::Float),
+                        Elements::Character(mut values) => values.pop()?.map(Self::Character),
+                        Elements::String(mut values) => values.pop()?.map(Self::String),
+                    }
+                } else {
+                    match elements {
+                        Elements::Integer(values) => Some(Self::IntegerArray(values)),
+                        Elements::Float(values) => Some(Self::FloatArray(values)),
+                        Elements::Character(values) => Some(Self::CharacterArray(values)),
+                        Elements::String(values) => Some(Self::StringArray(values)),
+                    }
+                }
+            }
+        }
+    };
+}
+
+impl_field_value!(InfoFieldValue);
+impl_field_value!(GenotypeFieldValue);
+
+pub(super) fn info_number(header: &vcf::Header, key: &InfoKey) -> Number {
+    header
+        .infos()
+        .get(key)
+        .map(|info| info.number())
+        .unwrap_or_else(|| Map::<Info>::from(key.clone()).number())
+}
+
+pub(super) fn format_number(header: &vcf::Header, key: &FormatKey) -> Number {
+    header
+        .formats()
+        .get(key)
+        .map(|format| format.number())
+        .unwrap_or_else(|| Map::<Format>::from(key.clone()).number())
+}
+
+/// Returns the number of genotypes for the given number of alleles and ploidy.
+///
+/// Only haploid and diploid genotypes are supported.
+pub(super) fn genotype_count(allele_count: usize, ploidy: usize) -> Option<usize> {
+    match ploidy {
+        1 => Some(allele_count),
+        2 => Some(allele_count * (allele_count + 1) / 2),
+        _ => None,
+    }
+}
+
+/// Returns the index of the diploid genotype `a/b`, where `a <= b`.
+pub(super) fn diploid_genotype_index(a: usize, b: usize) -> usize {
+    b * (b + 1) / 2 + a
+}
+
+/// Infers the ploidy from the number of `Number=G` values.
+pub(super) fn infer_ploidy(len: usize, allele_count: usize) -> Option<usize> {
+    [2, 1]
+        .into_iter()
+        .find(|&ploidy| genotype_count(allele_count, ploidy) == Some(len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gather() {
+        let sources = [
+            Some(Elements::Integer(vec![Some(1), Some(2)])),
+            None,
+            Some(Elements::Integer(vec![Some(5), Some(8)])),
+        ];
+
+        let picks = [Some((0, 0)), Some((0, 1)), Some((1, 1)), None, Some((2, 1))];
+
+        assert_eq!(
+            Elements::gather(&sources, &picks),
+            Some(Elements::Integer(vec![
+                Some(1),
+                Some(2),
+                None,
+                None,
+                Some(8)
+            ]))
+        );
+
+        assert!(Elements::gather(&[None, None], &picks).is_none());
+    }
+
+    #[test]
+    fn test_from_elements() {
+        assert_eq!(
+            InfoFieldValue::from_elements(Elements::Integer(vec![Some(8)])),
+            Some(InfoFieldValue::Integer(8))
+        );
+        assert!(InfoFieldValue::from_elements(Elements::Integer(vec![None])).is_none());
+        assert_eq!(
+            InfoFieldValue::from_elements(Elements::Integer(vec![Some(8), None])),
+            Some(InfoFieldValue::IntegerArray(vec![Some(8), None]))
+        );
+    }
+
+    #[test]
+    fn test_infer_ploidy() {
+        assert_eq!(infer_ploidy(3, 2), Some(2));
+        assert_eq!(infer_ploidy(2, 2), Some(1));
+        assert_eq!(infer_ploidy(6, 3), Some(2));
+        assert_eq!(infer_ploidy(4, 3), None);
+    }
+}
@@ -0,0 +1,111 @@
+//! Variant reader.
+
+mod builder;
+
+pub use self::builder::Builder;
+
+use std::io::{self, BufRead};
+
+use noodles_bcf::{self as bcf, header::StringMaps};
+use noodles_vcf as vcf;
+
+enum Inner<R> {
+    Vcf(vcf::Reader<R>),
+    Bcf(bcf::Reader<R>, StringMaps),
+}
+
+/// A variant reader.
+///
+/// This reads VCF records from either a VCF or BCF.
+pub struct Reader<R> {
+    inner: Inner<R>,
+}
+
+impl<R> Reader<R>
+where
+    R: BufRead,
+{
+    /// Reads and parses a VCF header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_util::variant;
+    ///
+    /// let data = b"##fileformat=VCFv4.3
+    /// #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+    /// ";
+    ///
+    /// let mut reader = variant::reader::Builder::default().build_from_reader(&data[..])?;
+    /// let header = reader.read_header()?;
+    ///
+    /// assert!(header.infos().is_empty());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn read_header(&mut self) -> io::Result<vcf::Header> {
+        match &mut self.inner {
+            Inner::Vcf(reader) => reader.read_header().and_then(|s| {
+                s.parse()
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            }),
+            Inner::Bcf(reader, string_maps) => {
+                reader.read_file_format()?;
+
+                let raw_header = reader.read_header()?;
+
+                let header = raw_header
+                    .parse()
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+                *string_maps = raw_header
+                    .parse()
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+                Ok(header)
+            }
+        }
+    }
+
+    /// Returns an iterator over records starting from the current stream position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_util::variant;
+    ///
+    /// let data = b"##fileformat=VCFv4.3
+    /// ###contig=<ID=sq0>
+    /// #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+    /// sq0\t1\t.\tA\t.\t.\tPASS\t.
+    /// ";
+    ///
+    /// let mut reader = variant::reader::Builder::default().build_from_reader(&data[..])?;
+    /// let header = reader.read_header()?;
+    ///
+    /// let mut records = reader.records(&header);
+    ///
+    /// assert!(records.next().transpose()?.is_some());
+    /// assert!(records.next().is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn records<'a>(
+        &'a mut self,
+        header: &'a vcf::Header,
+    ) -> impl Iterator<Item = io::Result<vcf::Record>> + 'a {
+        let records: Box<dyn Iterator<Item = io::Result<vcf::Record>> + 'a> = match &mut self.inner
+        {
+            Inner::Vcf(reader) => Box::new(reader.records(header)),
+            Inner::Bcf(reader, string_maps) => {
+                let string_maps = &*string_maps;
+
+                Box::new(reader.records().map(move |result| {
+                    result.and_then(|record| record.try_into_vcf_record(header, string_maps))
+                }))
+            }
+        };
+
+        records
+    }
+}
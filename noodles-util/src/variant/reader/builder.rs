@@ -0,0 +1,180 @@
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, Read},
+    path::Path,
+};
+
+use flate2::bufread::MultiGzDecoder;
+use noodles_bcf as bcf;
+use noodles_bgzf as bgzf;
+use noodles_vcf as vcf;
+
+use super::{Inner, Reader};
+use crate::variant::{Compression, Format};
+
+/// A variant reader builder.
+#[derive(Default)]
+pub struct Builder {
+    compression: Option<Option<Compression>>,
+    format: Option<Format>,
+}
+
+impl Builder {
+    /// Sets the compression of the input.
+    ///
+    /// By default, the compression is autodetected on build. This can be used to override it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::variant::{self, Compression};
+    /// let builder = variant::reader::Builder::default().set_compression(Some(Compression::Bgzf));
+    /// ```
+    pub fn set_compression(mut self, compression: Option<Compression>) -> Self {
+        self.compression = Some(compression);
+        self
+    }
+
+    /// Sets the format of the input.
+    ///
+    /// By default, the format is autodetected on build. This can be used to override it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::variant::{self, Format};
+    /// let builder = variant::reader::Builder::default().set_format(Format::Vcf);
+    /// ```
+    pub fn set_format(mut self, format: Format) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    /// Builds a variant reader from a path.
+    ///
+    /// By default, the format and compression will be autodetected. This can be overridden by
+    /// using [`Self::set_format`] and [`Self::set_compression`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io;
+    /// use noodles_util::variant;
+    /// let reader = variant::reader::Builder::default().build_from_path("sample.vcf")?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn build_from_path<P>(self, src: P) -> io::Result<Reader<Box<dyn BufRead>>>
+    where
+        P: AsRef<Path>,
+    {
+        let file = File::open(src)?;
+        self.build_from_reader(file)
+    }
+
+    /// Builds a variant reader from a reader.
+    ///
+    /// By default, the format and compression will be autodetected. This can be overridden by
+    /// using [`Self::set_format`] and [`Self::set_compression`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_util::variant;
+    /// let reader = variant::reader::Builder::default().build_from_reader(io::empty())?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn build_from_reader<R>(self, reader: R) -> io::Result<Reader<Box<dyn BufRead>>>
+    where
+        R: Read + 'static,
+    {
+        let mut reader = BufReader::new(reader);
+
+        let compression = match self.compression {
+            Some(compression) => compression,
+            None => detect_compression(reader.fill_buf()?),
+        };
+
+        let mut reader: Box<dyn BufRead> = match compression {
+            None => Box::new(reader),
+            Some(Compression::Bgzf) => Box::new(bgzf::Reader::new(reader)),
+            Some(Compression::Gzip) => Box::new(BufReader::new(MultiGzDecoder::new(reader))),
+        };
+
+        let format = match self.format {
+            Some(format) => format,
+            None => detect_format(reader.fill_buf()?),
+        };
+
+        let inner = match format {
+            Format::Vcf => Inner::Vcf(vcf::Reader::new(reader)),
+            Format::Bcf => Inner::Bcf(bcf::Reader::from(reader), Default::default()),
+        };
+
+        Ok(Reader { inner })
+    }
+}
+
+fn detect_compression(src: &[u8]) -> Option<Compression> {
+    const GZIP_MAGIC_NUMBER: [u8; 2] = [0x1f, 0x8b];
+    const GZIP_FLG_FEXTRA: u8 = 0x04;
+    const BGZF_SUBFIELD_ID: [u8; 2] = [b'B', b'C'];
+
+    if !src.starts_with(&GZIP_MAGIC_NUMBER) {
+        return None;
+    }
+
+    // § 4.1 "The BGZF compression format" (2021-06-03): BGZF is gzip with an extra field
+    // containing a `BC` subfield.
+    let is_bgzf = src.get(3).map(|&flg| flg & GZIP_FLG_FEXTRA != 0) == Some(true)
+        && src.get(12..14) == Some(&BGZF_SUBFIELD_ID[..]);
+
+    if is_bgzf {
+        Some(Compression::Bgzf)
+    } else {
+        Some(Compression::Gzip)
+    }
+}
+
+fn detect_format(src: &[u8]) -> Format {
+    const BCF_MAGIC_NUMBER: [u8; 3] = [b'B', b'C', b'F'];
+
+    if src.starts_with(&BCF_MAGIC_NUMBER) {
+        Format::Bcf
+    } else {
+        Format::Vcf
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_compression() -> io::Result<()> {
+        use std::io::Write;
+
+        use flate2::write::GzEncoder;
+
+        assert_eq!(detect_compression(b"##fileformat=VCFv4.3\n"), None);
+
+        let mut writer = bgzf::Writer::new(Vec::new());
+        writer.write_all(b"BCF")?;
+        let src = writer.finish()?;
+        assert_eq!(detect_compression(&src), Some(Compression::Bgzf));
+
+        let mut writer = GzEncoder::new(Vec::new(), Default::default());
+        writer.write_all(b"BCF")?;
+        let src = writer.finish()?;
+        assert_eq!(detect_compression(&src), Some(Compression::Gzip));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_detect_format() {
+        assert_eq!(detect_format(b"BCF\x02\x02"), Format::Bcf);
+        assert_eq!(detect_format(b"##fileformat=VCFv4.3\n"), Format::Vcf);
+        assert_eq!(detect_format(b""), Format::Vcf);
+    }
+}
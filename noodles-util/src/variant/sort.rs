@@ -0,0 +1,274 @@
+//! Variant record sorting.
+//!
+//! Records are sorted by reference sequence, in the order of the contigs (`##contig`) in the
+//! header, and then by position. Like [`crate::alignment::sort`], this uses an external merge sort.
+//! Buffered records that exceed the memory limit are spilled to temporary BCF files, which are
+//! then k-way merged.
+//!
+//! # Examples
+//!
+//! ```
+//! # use std::io;
+//! use noodles_vcf::{
+//!     self as vcf,
+//!     header::record::value::{map::Contig, Map},
+//!     record::Position,
+//! };
+//! use noodles_util::variant::sort;
+//!
+//! let header = vcf::Header::builder()
+//!     .add_contig(Map::<Contig>::new("sq0".parse()?))
+//!     .add_contig(Map::<Contig>::new("sq1".parse()?))
+//!     .build();
+//!
+//! let mut sorter = sort::Builder::default().build(header);
+//!
+//! for (chromosome, position) in [("sq1", 5), ("sq0", 13), ("sq0", 8)] {
+//!     let record = vcf::Record::builder()
+//!         .set_chromosome(chromosome.parse()?)
+//!         .set_position(Position::from(position))
+//!         .set_reference_bases("A".parse()?)
+//!         .build()?;
+//!
+//!     sorter.push(record)?;
+//! }
+//!
+//! let positions: Vec<_> = sorter
+//!     .finish()?
+//!     .map(|result| result.map(|record| (record.chromosome().to_string(), usize::from(record.position()))))
+//!     .collect::<io::Result<_>>()?;
+//!
+//! assert_eq!(positions, [
+//!     (String::from("sq0"), 8),
+//!     (String::from("sq0"), 13),
+//!     (String::from("sq1"), 5),
+//! ]);
+//! # Ok::<_, Box<dyn std::error::Error>>(())
+//! ```
+
+mod builder;
+
+pub use self::builder::Builder;
+
+use std::{
+    fs::File,
+    io::{self, BufWriter},
+    iter, mem,
+};
+
+use noodles_bcf::{self as bcf, header::StringMaps};
+use noodles_bgzf::{self as bgzf, writer::CompressionLevel};
+use noodles_vcf::{self as vcf, record::Chromosome};
+
+use crate::sort::{self, Chunk, SortKey, Spill};
+
+/// A variant record sorter.
+///
+/// This is created using a [`Builder`].
+pub struct Sorter {
+    inner: sort::Sorter<BcfSpill>,
+}
+
+impl Sorter {
+    /// Adds a record to be sorted.
+    ///
+    /// The record's reference sequence must be defined as a contig in the header. When the
+    /// buffered records exceed the memory limit, they are sorted and written to a temporary file.
+    pub fn push(&mut self, record: vcf::Record) -> io::Result<()> {
+        self.inner.push(record)
+    }
+
+    /// Finishes sorting and returns an iterator over the sorted records.
+    ///
+    /// Records that compare equal are returned in the order they were pushed. Temporary files are
+    /// removed when the iterator is dropped.
+    pub fn finish(self) -> io::Result<Records> {
+        self.inner.finish().map(|inner| Records { inner })
+    }
+}
+
+/// An iterator over sorted variant records.
+pub struct Records {
+    inner: sort::Merge<'static, BcfSpill>,
+}
+
+impl Iterator for Records {
+    type Item = io::Result<vcf::Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+struct BcfSpill {
+    header: vcf::Header,
+    string_maps: StringMaps,
+}
+
+impl SortKey for BcfSpill {
+    type Record = vcf::Record;
+    type Key = (usize, usize);
+
+    fn key(&self, record: &Self::Record) -> io::Result<Self::Key> {
+        let name = match record.chromosome() {
+            Chromosome::Name(name) | Chromosome::Symbol(name) => name,
+        };
+
+        let reference_sequence_id = self
+            .header
+            .contigs()
+            .get_index_of(name.as_str())
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("missing contig in header: {}", name),
+                )
+            })?;
+
+        Ok((reference_sequence_id, usize::from(record.position())))
+    }
+}
+
+impl Spill for BcfSpill {
+    const EXTENSION: &'static str = "bcf";
+
+    fn estimate_size(&self, record: &Self::Record) -> usize {
+        estimate_record_size(record)
+    }
+
+    fn write_chunk(&self, file: File, records: Chunk<'_, Self::Record>) -> io::Result<()> {
+        let inner = bgzf::Writer::builder(BufWriter::new(file))
+            .set_compression_level(CompressionLevel::fast())
+            .build();
+
+        let mut writer = bcf::Writer::from(inner);
+
+        writer.write_file_format()?;
+        writer.write_header(&self.header)?;
+
+        for result in records {
+            let record = result?;
+            writer.write_vcf_record(&self.header, &self.string_maps, &record)?;
+        }
+
+        writer.try_finish()
+    }
+
+    fn read_chunk(&self, file: File) -> io::Result<Chunk<'static, Self::Record>> {
+        let mut reader = bcf::Reader::new(file);
+        reader.read_file_format()?;
+
+        let raw_header = reader.read_header()?;
+
+        let header: vcf::Header = raw_header
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        let string_maps: StringMaps = raw_header
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        let mut record = bcf::Record::default();
+
+        Ok(Box::new(iter::from_fn(move || {
+            match reader.read_record(&mut record) {
+                Ok(0) => None,
+                Ok(_) => Some(record.try_into_vcf_record(&header, &string_maps)),
+                Err(e) => Some(Err(e)),
+            }
+        })))
+    }
+}
+
+fn estimate_record_size(record: &vcf::Record) -> usize {
+    // This is a rough estimate. Each INFO and genotype field is assumed to use a fixed amount of
+    // memory, regardless of its value.
+    const FIELD_SIZE: usize = 64;
+
+    let genotypes = record.genotypes();
+    let genotype_field_count = genotypes.len() * genotypes.keys().len();
+
+    mem::size_of::<vcf::Record>()
+        + record.reference_bases().len()
+        + record.alternate_bases().len() * FIELD_SIZE
+        + record.ids().len() * FIELD_SIZE
+        + record.info().len() * FIELD_SIZE
+        + genotype_field_count * FIELD_SIZE
+}
+
+#[cfg(test)]
+mod tests {
+    use vcf::{
+        header::record::value::{
+            map::{Contig, Format},
+            Map,
+        },
+        record::Position,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_sort_with_spills() -> Result<(), Box<dyn std::error::Error>> {
+        use vcf::header::format::Key;
+
+        let header = vcf::Header::builder()
+            .add_contig(Map::<Contig>::new("sq0".parse()?))
+            .add_contig(Map::<Contig>::new("sq1".parse()?))
+            .add_format(Map::<Format>::from(Key::Genotype))
+            .add_sample_name("sample0")
+            .build();
+
+        let mut sorter = Builder::default().set_memory_limit(1).build(header);
+
+        for (chromosome, position, genotype) in [
+            ("sq1", 5, "0/1"),
+            ("sq0", 13, "1|1"),
+            ("sq0", 8, "./."),
+            ("sq0", 8, "0/0"),
+        ] {
+            let record = vcf::Record::builder()
+                .set_chromosome(chromosome.parse()?)
+                .set_position(Position::from(position))
+                .set_reference_bases("A".parse()?)
+                .set_genotypes(format!("GT\t{}", genotype).parse()?)
+                .build()?;
+
+            sorter.push(record)?;
+        }
+
+        let actual: Vec<_> = sorter
+            .finish()?
+            .map(|result| result.map(|record| record.to_string()))
+            .collect::<io::Result<_>>()?;
+
+        let expected = [
+            "sq0\t8\t.\tA\t.\t.\t.\t.\tGT\t./.",
+            "sq0\t8\t.\tA\t.\t.\t.\t.\tGT\t0/0",
+            "sq0\t13\t.\tA\t.\t.\t.\t.\tGT\t1|1",
+            "sq1\t5\t.\tA\t.\t.\t.\t.\tGT\t0/1",
+        ];
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_push_with_missing_contig() -> Result<(), Box<dyn std::error::Error>> {
+        let mut sorter = Builder::default().build(vcf::Header::default());
+
+        let record = vcf::Record::builder()
+            .set_chromosome("sq0".parse()?)
+            .set_position(Position::from(8))
+            .set_reference_bases("A".parse()?)
+            .build()?;
+
+        assert!(matches!(
+            sorter.push(record),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,87 @@
+use std::{env, path::PathBuf};
+
+use noodles_bcf::header::StringMaps;
+use noodles_vcf as vcf;
+
+use super::{BcfSpill, Sorter};
+use crate::sort;
+
+// 768 MiB
+const DEFAULT_MEMORY_LIMIT: usize = 768 << 20;
+
+/// A variant record sorter builder.
+#[derive(Debug)]
+pub struct Builder {
+    memory_limit: usize,
+    temp_dir: Option<PathBuf>,
+}
+
+impl Builder {
+    /// Sets the approximate maximum amount of memory, in bytes, used to buffer records.
+    ///
+    /// When the limit is reached, buffered records are sorted and spilled to a temporary file. By
+    /// default, this is 768 MiB.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::variant::sort;
+    /// let builder = sort::Builder::default().set_memory_limit(1 << 30);
+    /// ```
+    pub fn set_memory_limit(mut self, memory_limit: usize) -> Self {
+        self.memory_limit = memory_limit;
+        self
+    }
+
+    /// Sets the directory where temporary files are written.
+    ///
+    /// By default, this is the system temporary directory ([`std::env::temp_dir`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::variant::sort;
+    /// let builder = sort::Builder::default().set_temp_dir("/tmp");
+    /// ```
+    pub fn set_temp_dir<P>(mut self, temp_dir: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        self.temp_dir = Some(temp_dir.into());
+        self
+    }
+
+    /// Builds a variant record sorter.
+    ///
+    /// The given header is the header of the input records.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    /// use noodles_util::variant::sort;
+    /// let sorter = sort::Builder::default().build(vcf::Header::default());
+    /// ```
+    pub fn build(self, header: vcf::Header) -> Sorter {
+        let string_maps = StringMaps::from(&header);
+        let spill = BcfSpill {
+            header,
+            string_maps,
+        };
+
+        let temp_dir = self.temp_dir.unwrap_or_else(env::temp_dir);
+
+        Sorter {
+            inner: sort::Sorter::new(spill, self.memory_limit, temp_dir),
+        }
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            memory_limit: DEFAULT_MEMORY_LIMIT,
+            temp_dir: None,
+        }
+    }
+}
@@ -0,0 +1,159 @@
+//! Variant writer.
+
+mod builder;
+
+pub use self::builder::Builder;
+
+use std::io::{self, Write};
+
+use flate2::write::GzEncoder;
+use noodles_bcf::{self as bcf, header::StringMaps};
+use noodles_bgzf as bgzf;
+use noodles_vcf as vcf;
+
+enum Inner {
+    Vcf(vcf::Writer<CompressedWriter>),
+    Bcf(bcf::Writer<CompressedWriter>, StringMaps),
+}
+
+/// A variant writer.
+///
+/// This writes VCF records to either a VCF or BCF.
+pub struct Writer {
+    inner: Inner,
+}
+
+impl Writer {
+    /// Writes a VCF header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_util::variant::{self, Format};
+    /// use noodles_vcf as vcf;
+    ///
+    /// let mut writer = variant::writer::Builder::default()
+    ///     .set_format(Format::Bcf)
+    ///     .build_from_writer(io::sink());
+    ///
+    /// let header = vcf::Header::default();
+    /// writer.write_header(&header)?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn write_header(&mut self, header: &vcf::Header) -> io::Result<()> {
+        match &mut self.inner {
+            Inner::Vcf(writer) => writer.write_header(header),
+            Inner::Bcf(writer, string_maps) => {
+                writer.write_file_format()?;
+                writer.write_header(header)?;
+                *string_maps = StringMaps::from(header);
+                Ok(())
+            }
+        }
+    }
+
+    /// Writes a VCF record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_util::variant::{self, Format};
+    /// use noodles_vcf::{
+    ///     self as vcf,
+    ///     header::record::value::{map::Contig, Map},
+    ///     record::Position,
+    /// };
+    ///
+    /// let mut writer = variant::writer::Builder::default()
+    ///     .set_format(Format::Vcf)
+    ///     .build_from_writer(io::sink());
+    ///
+    /// let header = vcf::Header::builder()
+    ///     .add_contig(Map::<Contig>::new("sq0".parse()?))
+    ///     .build();
+    ///
+    /// writer.write_header(&header)?;
+    ///
+    /// let record = vcf::Record::builder()
+    ///     .set_chromosome("sq0".parse()?)
+    ///     .set_position(Position::from(1))
+    ///     .set_reference_bases("A".parse()?)
+    ///     .build()?;
+    ///
+    /// writer.write_record(&header, &record)?;
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn write_record(&mut self, header: &vcf::Header, record: &vcf::Record) -> io::Result<()> {
+        match &mut self.inner {
+            Inner::Vcf(writer) => writer.write_record(record),
+            Inner::Bcf(writer, string_maps) => writer.write_vcf_record(header, string_maps, record),
+        }
+    }
+
+    /// Shuts down the variant format writer.
+    ///
+    /// This finishes the compressed stream, if any, and flushes the output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_util::variant::{self, Format};
+    ///
+    /// let mut writer = variant::writer::Builder::default()
+    ///     .set_format(Format::Vcf)
+    ///     .build_from_writer(io::sink());
+    ///
+    /// writer.finish()?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn finish(&mut self) -> io::Result<()> {
+        match &mut self.inner {
+            Inner::Vcf(writer) => writer.get_mut().finish(),
+            Inner::Bcf(writer, _) => writer.get_mut().finish(),
+        }
+    }
+}
+
+enum CompressedWriter {
+    Raw(Box<dyn Write>),
+    Bgzf(bgzf::Writer<Box<dyn Write>>),
+    Gzip(GzEncoder<Box<dyn Write>>),
+}
+
+impl CompressedWriter {
+    // Finishing a compressed stream unwraps it, leaving the inner writer. This prevents a second
+    // finish, e.g., on drop, from writing another trailer.
+    fn finish(&mut self) -> io::Result<()> {
+        let mut inner = match std::mem::replace(self, Self::Raw(Box::new(io::sink()))) {
+            Self::Raw(writer) => writer,
+            Self::Bgzf(writer) => writer.finish()?,
+            Self::Gzip(writer) => writer.finish()?,
+        };
+
+        inner.flush()?;
+        *self = Self::Raw(inner);
+
+        Ok(())
+    }
+}
+
+impl Write for CompressedWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Self::Raw(writer) => writer.write(buf),
+            Self::Bgzf(writer) => writer.write(buf),
+            Self::Gzip(writer) => writer.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Self::Raw(writer) => writer.flush(),
+            Self::Bgzf(writer) => writer.flush(),
+            Self::Gzip(writer) => writer.flush(),
+        }
+    }
+}
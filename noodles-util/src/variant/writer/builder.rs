@@ -0,0 +1,163 @@
+//! Variant writer builder.
+
+use std::{
+    fs::File,
+    io::{self, Write},
+    path::Path,
+};
+
+use flate2::write::GzEncoder;
+use noodles_bcf as bcf;
+use noodles_bgzf as bgzf;
+use noodles_vcf as vcf;
+
+use super::{CompressedWriter, Inner, Writer};
+use crate::variant::{Compression, Format};
+
+/// A variant writer builder.
+#[derive(Default)]
+pub struct Builder {
+    compression: Option<Option<Compression>>,
+    format: Option<Format>,
+}
+
+impl Builder {
+    /// Sets the compression of the output.
+    ///
+    /// By default, VCF output is uncompressed, and BCF output is BGZF-compressed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::variant::{self, Compression};
+    /// let builder = variant::writer::Builder::default().set_compression(Some(Compression::Bgzf));
+    /// ```
+    pub fn set_compression(mut self, compression: Option<Compression>) -> Self {
+        self.compression = Some(compression);
+        self
+    }
+
+    /// Sets the format of the output.
+    ///
+    /// By default, the output format is VCF.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::variant::{self, Format};
+    /// let builder = variant::writer::Builder::default().set_format(Format::Vcf);
+    /// ```
+    pub fn set_format(mut self, format: Format) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    /// Builds a variant writer from a path.
+    ///
+    /// If the format or compression is not set, it is detected from the path extension.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io;
+    /// use noodles_util::variant;
+    /// let writer = variant::writer::Builder::default().build_from_path("out.vcf.gz")?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn build_from_path<P>(mut self, dst: P) -> io::Result<Writer>
+    where
+        P: AsRef<Path>,
+    {
+        let dst = dst.as_ref();
+
+        if let Some((format, compression)) = detect_format_from_path_extension(dst) {
+            self.format = self.format.or(Some(format));
+            self.compression = self.compression.or(Some(compression));
+        }
+
+        let file = File::create(dst)?;
+        Ok(self.build_from_writer(file))
+    }
+
+    /// Builds a variant writer from a writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_util::variant::{self, Format};
+    ///
+    /// let writer = variant::writer::Builder::default()
+    ///     .set_format(Format::Vcf)
+    ///     .build_from_writer(io::sink());
+    /// ```
+    pub fn build_from_writer<W>(self, writer: W) -> Writer
+    where
+        W: Write + 'static,
+    {
+        let format = self.format.unwrap_or(Format::Vcf);
+
+        let compression = self.compression.unwrap_or(match format {
+            Format::Vcf => None,
+            Format::Bcf => Some(Compression::Bgzf),
+        });
+
+        let writer: Box<dyn Write> = Box::new(writer);
+
+        let writer = match compression {
+            None => CompressedWriter::Raw(writer),
+            Some(Compression::Bgzf) => CompressedWriter::Bgzf(bgzf::Writer::new(writer)),
+            Some(Compression::Gzip) => {
+                CompressedWriter::Gzip(GzEncoder::new(writer, Default::default()))
+            }
+        };
+
+        let inner = match format {
+            Format::Vcf => Inner::Vcf(vcf::Writer::new(writer)),
+            Format::Bcf => Inner::Bcf(bcf::Writer::from(writer), Default::default()),
+        };
+
+        Writer { inner }
+    }
+}
+
+fn detect_format_from_path_extension<P>(path: P) -> Option<(Format, Option<Compression>)>
+where
+    P: AsRef<Path>,
+{
+    let path = path.as_ref();
+
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("vcf") => Some((Format::Vcf, None)),
+        Some("bcf") => Some((Format::Bcf, Some(Compression::Bgzf))),
+        Some("gz" | "bgz") => match path.file_stem().map(Path::new).and_then(|p| p.extension()) {
+            Some(ext) if ext == "vcf" => Some((Format::Vcf, Some(Compression::Bgzf))),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_format_from_path_extension() {
+        assert_eq!(
+            detect_format_from_path_extension("out.vcf"),
+            Some((Format::Vcf, None))
+        );
+        assert_eq!(
+            detect_format_from_path_extension("out.vcf.gz"),
+            Some((Format::Vcf, Some(Compression::Bgzf)))
+        );
+        assert_eq!(
+            detect_format_from_path_extension("out.bcf"),
+            Some((Format::Bcf, Some(Compression::Bgzf)))
+        );
+
+        assert!(detect_format_from_path_extension("out.gz").is_none());
+        assert!(detect_format_from_path_extension("out.fa").is_none());
+    }
+}
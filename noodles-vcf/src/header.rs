@@ -5,12 +5,14 @@ pub mod file_format;
 mod fmt;
 pub mod format;
 pub mod info;
+mod merge;
 mod number;
 mod parser;
 pub mod record;
 
 pub use self::{
-    builder::Builder, file_format::FileFormat, number::Number, parser::ParseError, record::Record,
+    builder::Builder, file_format::FileFormat, merge::MergeError, number::Number,
+    parser::ParseError, record::Record,
 };
 
 use std::str::FromStr;
@@ -662,6 +664,38 @@ impl Header {
         let records = self.other_records.entry(key).or_default();
         records.push(value);
     }
+
+    /// Merges another header into this header.
+    ///
+    /// Info (`INFO`), filter (`FILTER`), format (`FORMAT`), alternative allele (`ALT`), contig
+    /// (`contig`), and meta (`META`) records are unioned, keeping the existing record when an ID
+    /// is in both headers. Sample names are appended. Unset assembly and pedigree database
+    /// records are taken from the other header, and other records are unioned.
+    ///
+    /// This returns an error if an info or format definition with the same ID has a different
+    /// number or type, a contig with the same name has a different length, or a sample name is in
+    /// both headers. The header is unchanged on error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    ///
+    /// let mut header = vcf::Header::builder().add_sample_name("sample0").build();
+    /// let other = vcf::Header::builder().add_sample_name("sample1").build();
+    ///
+    /// header.merge(&other)?;
+    ///
+    /// let sample_names: Vec<_> = header.sample_names().iter().collect();
+    /// assert_eq!(sample_names, ["sample0", "sample1"]);
+    /// # Ok::<_, vcf::header::MergeError>(())
+    /// ```
+    pub fn merge(&mut self, other: &Self) -> Result<(), MergeError> {
+        let mut header = self.clone();
+        merge::merge(&mut header, other)?;
+        *self = header;
+        Ok(())
+    }
 }
 
 impl Default for Header {
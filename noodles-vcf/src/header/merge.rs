@@ -0,0 +1,213 @@
+use std::{error, fmt};
+
+use super::{format, info, record::value::map::contig, Header};
+
+/// An error returned when VCF headers fail to merge.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MergeError {
+    /// An info definition has a different number or type.
+    InfoDefinitionMismatch(info::Key),
+    /// A format definition has a different number or type.
+    FormatDefinitionMismatch(format::Key),
+    /// A contig has a different length.
+    ContigLengthMismatch(contig::Name),
+    /// A sample name is duplicated.
+    DuplicateSampleName(String),
+}
+
+impl error::Error for MergeError {}
+
+impl fmt::Display for MergeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InfoDefinitionMismatch(key) => write!(f, "INFO definition mismatch: {}", key),
+            Self::FormatDefinitionMismatch(key) => {
+                write!(f, "FORMAT definition mismatch: {}", key)
+            }
+            Self::ContigLengthMismatch(name) => write!(f, "contig length mismatch: {}", name),
+            Self::DuplicateSampleName(name) => write!(f, "duplicate sample name: {}", name),
+        }
+    }
+}
+
+pub(super) fn merge(dst: &mut Header, src: &Header) -> Result<(), MergeError> {
+    for (key, info) in &src.infos {
+        if let Some(dst_info) = dst.infos.get(key) {
+            if dst_info.number() != info.number() || dst_info.ty() != info.ty() {
+                return Err(MergeError::InfoDefinitionMismatch(key.clone()));
+            }
+        } else {
+            dst.infos.insert(key.clone(), info.clone());
+        }
+    }
+
+    for (id, filter) in &src.filters {
+        dst.filters
+            .entry(id.clone())
+            .or_insert_with(|| filter.clone());
+    }
+
+    for (key, format) in &src.formats {
+        if let Some(dst_format) = dst.formats.get(key) {
+            if dst_format.number() != format.number() || dst_format.ty() != format.ty() {
+                return Err(MergeError::FormatDefinitionMismatch(key.clone()));
+            }
+        } else {
+            dst.formats.insert(key.clone(), format.clone());
+        }
+    }
+
+    for (symbol, alternative_allele) in &src.alternative_alleles {
+        dst.alternative_alleles
+            .entry(symbol.clone())
+            .or_insert_with(|| alternative_allele.clone());
+    }
+
+    if dst.assembly.is_none() {
+        dst.assembly = src.assembly.clone();
+    }
+
+    for (name, contig) in &src.contigs {
+        if let Some(dst_contig) = dst.contigs.get_mut(name) {
+            match (dst_contig.length(), contig.length()) {
+                (Some(a), Some(b)) if a != b => {
+                    return Err(MergeError::ContigLengthMismatch(name.clone()))
+                }
+                (None, Some(b)) => *dst_contig.length_mut() = Some(b),
+                _ => {}
+            }
+        } else {
+            dst.contigs.insert(name.clone(), contig.clone());
+        }
+    }
+
+    for (id, meta) in &src.meta {
+        dst.meta.entry(id.clone()).or_insert_with(|| meta.clone());
+    }
+
+    if dst.pedigree_db.is_none() {
+        dst.pedigree_db = src.pedigree_db.clone();
+    }
+
+    for sample_name in &src.sample_names {
+        if !dst.sample_names.insert(sample_name.clone()) {
+            return Err(MergeError::DuplicateSampleName(sample_name.clone()));
+        }
+    }
+
+    for (key, values) in &src.other_records {
+        let dst_values = dst.other_records.entry(key.clone()).or_default();
+
+        for value in values {
+            if !dst_values.contains(value) {
+                dst_values.push(value.clone());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::header::{
+        format::Key as FormatKey,
+        info::{Key as InfoKey, Type as InfoType},
+        record::value::{
+            map::{Contig, Filter, Format, Info},
+            Map,
+        },
+        Number,
+    };
+
+    #[test]
+    fn test_merge() -> Result<(), Box<dyn std::error::Error>> {
+        let mut header = Header::builder()
+            .add_info(Map::<Info>::from(InfoKey::TotalDepth))
+            .add_format(Map::<Format>::from(FormatKey::Genotype))
+            .add_contig(Map::<Contig>::new("sq0".parse()?))
+            .add_sample_name("sample0")
+            .build();
+
+        let other = Header::builder()
+            .add_info(Map::<Info>::from(InfoKey::TotalDepth))
+            .add_info(Map::<Info>::from(InfoKey::AlleleCount))
+            .add_filter(Map::<Filter>::new("q10", "Quality below 10"))
+            .add_format(Map::<Format>::from(FormatKey::Genotype))
+            .add_format(Map::<Format>::from(FormatKey::ReadDepth))
+            .add_contig({
+                let mut contig = Map::<Contig>::new("sq0".parse()?);
+                *contig.length_mut() = Some(8);
+                contig
+            })
+            .add_contig(Map::<Contig>::new("sq1".parse()?))
+            .add_sample_name("sample1")
+            .build();
+
+        merge(&mut header, &other)?;
+
+        let info_keys: Vec<_> = header.infos().keys().cloned().collect();
+        assert_eq!(info_keys, [InfoKey::TotalDepth, InfoKey::AlleleCount]);
+
+        assert!(header.filters().contains_key("q10"));
+
+        let format_keys: Vec<_> = header.formats().keys().cloned().collect();
+        assert_eq!(format_keys, [FormatKey::Genotype, FormatKey::ReadDepth]);
+
+        let contig_names: Vec<_> = header
+            .contigs()
+            .keys()
+            .map(|name| name.to_string())
+            .collect();
+        assert_eq!(contig_names, ["sq0", "sq1"]);
+        assert_eq!(header.contigs()[0].length(), Some(8));
+
+        let sample_names: Vec<_> = header.sample_names().iter().collect();
+        assert_eq!(sample_names, ["sample0", "sample1"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_with_conflicts() -> Result<(), Box<dyn std::error::Error>> {
+        let mut header = Header::builder()
+            .add_info(Map::<Info>::from(InfoKey::TotalDepth))
+            .build();
+
+        let other = Header::builder()
+            .add_info(Map::<Info>::new(
+                InfoKey::TotalDepth,
+                Number::Count(1),
+                InfoType::Float,
+                "Combined depth across samples",
+            ))
+            .build();
+
+        assert_eq!(
+            merge(&mut header, &other),
+            Err(MergeError::InfoDefinitionMismatch(InfoKey::TotalDepth))
+        );
+
+        let build_header = |length| -> Result<Header, Box<dyn std::error::Error>> {
+            let mut contig = Map::<Contig>::new("sq0".parse()?);
+            *contig.length_mut() = Some(length);
+            Ok(Header::builder().add_contig(contig).build())
+        };
+
+        let mut header = build_header(8)?;
+        assert_eq!(
+            merge(&mut header, &build_header(13)?),
+            Err(MergeError::ContigLengthMismatch("sq0".parse()?))
+        );
+
+        let mut header = Header::builder().add_sample_name("sample0").build();
+        let other = header.clone();
+        assert_eq!(
+            merge(&mut header, &other),
+            Err(MergeError::DuplicateSampleName(String::from("sample0")))
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,222 @@
+//! Indexed VCF reader.
+
+mod builder;
+
+pub use self::builder::Builder;
+
+use std::io::{self, BufRead, Read, Seek};
+
+use noodles_bgzf as bgzf;
+use noodles_core::Region;
+use noodles_csi::{self as csi, BinningIndex};
+use noodles_tabix as tabix;
+
+use super::{
+    lazy,
+    reader::{resolve_region, Query, Records},
+    Header, Reader,
+};
+
+/// An index associated with a VCF.
+#[derive(Debug)]
+pub enum Index {
+    /// A tabix index (TBI).
+    Tabix(tabix::Index),
+    /// A coordinate-sorted index (CSI).
+    Csi(csi::Index),
+}
+
+impl From<tabix::Index> for Index {
+    fn from(index: tabix::Index) -> Self {
+        Self::Tabix(index)
+    }
+}
+
+impl From<csi::Index> for Index {
+    fn from(index: csi::Index) -> Self {
+        Self::Csi(index)
+    }
+}
+
+/// An indexed VCF reader.
+pub struct IndexedReader<R> {
+    inner: Reader<R>,
+    index: Index,
+}
+
+impl<R> IndexedReader<R>
+where
+    R: BufRead,
+{
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        self.inner.get_ref()
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        self.inner.get_mut()
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner.into_inner()
+    }
+
+    /// Returns the associated index.
+    pub fn index(&self) -> &Index {
+        &self.index
+    }
+
+    /// Reads the raw VCF header.
+    pub fn read_header(&mut self) -> io::Result<String> {
+        self.inner.read_header()
+    }
+
+    /// Reads a single raw VCF record.
+    pub fn read_record(&mut self, buf: &mut String) -> io::Result<usize> {
+        self.inner.read_record(buf)
+    }
+
+    /// Reads a single record without eagerly parsing its fields.
+    pub fn read_lazy_record(&mut self, record: &mut lazy::Record) -> io::Result<usize> {
+        self.inner.read_lazy_record(record)
+    }
+
+    /// Returns an iterator over records starting from the current stream position.
+    pub fn records<'r, 'h>(&'r mut self, header: &'h Header) -> Records<'r, 'h, R> {
+        self.inner.records(header)
+    }
+}
+
+impl<R> IndexedReader<bgzf::Reader<R>>
+where
+    R: Read,
+{
+    /// Creates an indexed VCF reader.
+    pub fn new<I>(inner: R, index: I) -> Self
+    where
+        I: Into<Index>,
+    {
+        Self {
+            inner: Reader::new(bgzf::Reader::new(inner)),
+            index: index.into(),
+        }
+    }
+}
+
+impl<R> IndexedReader<bgzf::Reader<R>>
+where
+    R: Read + Seek,
+{
+    /// Returns an iterator over records that intersect the given region.
+    pub fn query<'r, 'h>(
+        &'r mut self,
+        header: &'h Header,
+        region: &Region,
+    ) -> io::Result<Query<'r, 'h, R>> {
+        let chunks = match &self.index {
+            Index::Tabix(index) => {
+                let (reference_sequence_id, _) = resolve_region(index, region)?;
+                index.query(reference_sequence_id, region.interval())?
+            }
+            Index::Csi(index) => {
+                let reference_sequence_id = resolve_csi_region(index, region)?;
+                index.query(reference_sequence_id, region.interval())?
+            }
+        };
+
+        Ok(Query::new(
+            &mut self.inner,
+            chunks,
+            region.name().into(),
+            region.interval(),
+            header,
+        ))
+    }
+}
+
+// A CSI for a VCF stores the tabix header in its auxiliary data. The reference sequence names are
+// the last field of this header.
+fn resolve_csi_region(index: &csi::Index, region: &Region) -> io::Result<usize> {
+    let names = parse_aux_reference_sequence_names(index.aux())?;
+
+    names
+        .iter()
+        .position(|name| name == region.name())
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "region reference sequence does not exist in reference sequences: {:?}",
+                    region
+                ),
+            )
+        })
+}
+
+fn parse_aux_reference_sequence_names(aux: &[u8]) -> io::Result<Vec<String>> {
+    use std::mem;
+
+    const NUL: u8 = 0x00;
+
+    // format, col_seq, col_beg, col_end, meta, skip
+    const FIELDS_SIZE: usize = 6 * mem::size_of::<i32>();
+
+    let invalid_aux = || io::Error::new(io::ErrorKind::InvalidData, "invalid CSI aux data");
+
+    let buf = aux.get(FIELDS_SIZE..).ok_or_else(invalid_aux)?;
+
+    if buf.len() < mem::size_of::<i32>() {
+        return Err(invalid_aux());
+    }
+
+    let (l_nm_buf, rest) = buf.split_at(mem::size_of::<i32>());
+
+    let l_nm = l_nm_buf
+        .try_into()
+        .map(i32::from_le_bytes)
+        .ok()
+        .and_then(|n| usize::try_from(n).ok())
+        .ok_or_else(invalid_aux)?;
+
+    let names_buf = rest.get(..l_nm).ok_or_else(invalid_aux)?;
+
+    names_buf
+        .split(|&b| b == NUL)
+        .filter(|buf| !buf.is_empty())
+        .map(|buf| {
+            String::from_utf8(buf.to_vec())
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_aux_reference_sequence_names() -> io::Result<()> {
+        let aux = [
+            0x02, 0x00, 0x00, 0x00, // format = 2 (VCF)
+            0x01, 0x00, 0x00, 0x00, // col_seq = 1
+            0x02, 0x00, 0x00, 0x00, // col_beg = 2
+            0x00, 0x00, 0x00, 0x00, // col_end = 0
+            b'#', 0x00, 0x00, 0x00, // meta = '#'
+            0x00, 0x00, 0x00, 0x00, // skip = 0
+            0x08, 0x00, 0x00, 0x00, // l_nm = 8
+            b's', b'q', b'0', 0x00, b's', b'q', b'1', 0x00, // names = ["sq0", "sq1"]
+        ];
+
+        let actual = parse_aux_reference_sequence_names(&aux)?;
+        assert_eq!(actual, [String::from("sq0"), String::from("sq1")]);
+
+        assert!(matches!(
+            parse_aux_reference_sequence_names(&[]),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+}
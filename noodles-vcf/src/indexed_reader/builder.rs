@@ -0,0 +1,98 @@
+use std::{
+    ffi::{OsStr, OsString},
+    fs::File,
+    io,
+    path::{Path, PathBuf},
+};
+
+use noodles_bgzf as bgzf;
+use noodles_csi as csi;
+use noodles_tabix as tabix;
+
+use super::{Index, IndexedReader};
+
+/// An indexed VCF reader builder.
+#[derive(Default)]
+pub struct Builder {
+    index: Option<Index>,
+}
+
+impl Builder {
+    /// Sets an index.
+    ///
+    /// This can either be a tabix index (TBI) or a coordinate-sorted index (CSI).
+    pub fn set_index<I>(mut self, index: I) -> Self
+    where
+        I: Into<Index>,
+    {
+        self.index = Some(index.into());
+        self
+    }
+
+    /// Builds an indexed VCF reader from a path.
+    ///
+    /// If no index is set, this attempts to read an associated index, first checking for a tabix
+    /// index (`<src>.tbi`) and then a coordinate-sorted index (`<src>.csi`).
+    pub fn build_from_path<P>(self, src: P) -> io::Result<IndexedReader<bgzf::Reader<File>>>
+    where
+        P: AsRef<Path>,
+    {
+        let src = src.as_ref();
+
+        let index = match self.index {
+            Some(index) => index,
+            None => read_associated_index(src)?,
+        };
+
+        let file = File::open(src)?;
+
+        Ok(IndexedReader::new(file, index))
+    }
+}
+
+fn read_associated_index<P>(src: P) -> io::Result<Index>
+where
+    P: AsRef<Path>,
+{
+    let src = src.as_ref();
+
+    let tbi_src = push_ext(src.into(), "tbi");
+
+    if tbi_src.exists() {
+        return tabix::read(tbi_src).map(Index::from);
+    }
+
+    let csi_src = push_ext(src.into(), "csi");
+
+    if csi_src.exists() {
+        return csi::read(csi_src).map(Index::from);
+    }
+
+    Err(io::Error::new(
+        io::ErrorKind::NotFound,
+        "could not find associated index (.tbi or .csi)",
+    ))
+}
+
+fn push_ext<S>(path: PathBuf, ext: S) -> PathBuf
+where
+    S: AsRef<OsStr>,
+{
+    let mut s = OsString::from(path);
+    s.push(".");
+    s.push(ext);
+    PathBuf::from(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_ext() {
+        assert_eq!(
+            push_ext(PathBuf::from("sample.vcf.gz"), "tbi"),
+            PathBuf::from("sample.vcf.gz.tbi")
+        );
+    }
+}
@@ -0,0 +1,389 @@
+//! Indexed VCF writer.
+
+mod builder;
+
+pub use self::builder::{Builder, IndexFormat};
+
+use std::{
+    collections::VecDeque,
+    io::{self, Write},
+    path::PathBuf,
+};
+
+use indexmap::IndexSet;
+use noodles_bgzf as bgzf;
+use noodles_core::Position;
+use noodles_csi::{self as csi, index::reference_sequence::bin::Chunk};
+use noodles_tabix as tabix;
+
+use super::{indexed_reader::Index, Header, Record, Writer};
+
+// A record that is written but not yet indexed, as the compressed positions of its chunk are not
+// yet known.
+struct PendingRecord {
+    reference_sequence_name: String,
+    start: Position,
+    end: Position,
+    start_position: bgzf::writer::BlockPosition,
+    resolved_start_position: Option<bgzf::VirtualPosition>,
+    end_position: bgzf::writer::BlockPosition,
+}
+
+enum Indexer {
+    Tabix(tabix::index::Indexer),
+    Csi {
+        indexer: csi::index::Indexer,
+        reference_sequence_names: IndexSet<String>,
+    },
+}
+
+impl Indexer {
+    fn add_record(
+        &mut self,
+        reference_sequence_name: &str,
+        start: Position,
+        end: Position,
+        chunk: Chunk,
+    ) -> io::Result<()> {
+        match self {
+            Self::Tabix(indexer) => {
+                indexer.add_record(reference_sequence_name, start, end, chunk);
+                Ok(())
+            }
+            Self::Csi {
+                indexer,
+                reference_sequence_names,
+            } => {
+                let (reference_sequence_id, _) =
+                    reference_sequence_names.insert_full(reference_sequence_name.into());
+
+                indexer.add_record(Some((reference_sequence_id, start, end, true)), chunk)
+            }
+        }
+    }
+
+    fn build(self) -> Index {
+        match self {
+            Self::Tabix(indexer) => Index::Tabix(indexer.build()),
+            Self::Csi {
+                indexer,
+                reference_sequence_names,
+            } => {
+                let aux = build_aux(&reference_sequence_names);
+                let index = indexer.set_aux(aux).build(reference_sequence_names.len());
+
+                Index::Csi(index)
+            }
+        }
+    }
+}
+
+/// An indexed VCF writer.
+///
+/// This writes a bgzipped VCF and builds a tabix index (TBI) or coordinate-sorted index (CSI) of
+/// the records as they are written. Records must be coordinate-sorted.
+///
+/// When the BGZF stream is compressed by multiple workers, a record is indexed once the blocks
+/// before its end are written, so writing never waits on pending blocks to be compressed.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_vcf::{
+///     self as vcf,
+///     header::record::value::{map::Contig, Map},
+///     indexed_reader::Index,
+///     record::Position,
+/// };
+///
+/// let mut writer = vcf::indexed_writer::Builder::default().build_from_writer(Vec::new());
+///
+/// let header = vcf::Header::builder()
+///     .add_contig(Map::<Contig>::new("sq0".parse()?))
+///     .build();
+///
+/// writer.write_header(&header)?;
+///
+/// let record = vcf::Record::builder()
+///     .set_chromosome("sq0".parse()?)
+///     .set_position(Position::from(8))
+///     .set_reference_bases("A".parse()?)
+///     .build()?;
+///
+/// writer.write_record(&record)?;
+///
+/// let index = writer.finish()?;
+/// assert!(matches!(index, Index::Tabix(_)));
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub struct IndexedWriter<W>
+where
+    W: Write,
+{
+    inner: Writer<bgzf::Writer<W>>,
+    indexer: Indexer,
+    pending_records: VecDeque<PendingRecord>,
+    index_dst: Option<PathBuf>,
+}
+
+impl<W> IndexedWriter<W>
+where
+    W: Write,
+{
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &bgzf::Writer<W> {
+        self.inner.get_ref()
+    }
+
+    /// Writes a VCF header.
+    pub fn write_header(&mut self, header: &Header) -> io::Result<()> {
+        self.inner.write_header(header)
+    }
+
+    /// Writes a VCF record and adds it to the index.
+    ///
+    /// When building a CSI, this returns an error if the record is out of coordinate order. When
+    /// the BGZF stream is compressed by multiple workers, the error may instead be returned by a
+    /// subsequent write or [`Self::finish`].
+    pub fn write_record(&mut self, record: &Record) -> io::Result<()> {
+        let start_position = self.inner.get_mut().block_position();
+        self.inner.write_record(record)?;
+        let end_position = self.inner.get_mut().block_position();
+
+        let end = record
+            .end()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        // Telomeric positions (0) are indexed at the first position.
+        let start = Position::new(usize::from(record.position())).unwrap_or(Position::MIN);
+        let end = Position::new(usize::from(end)).unwrap_or(Position::MIN);
+
+        self.pending_records.push_back(PendingRecord {
+            reference_sequence_name: record.chromosome().to_string(),
+            start,
+            end,
+            start_position,
+            resolved_start_position: None,
+            end_position,
+        });
+
+        self.index_pending_records()
+    }
+
+    // Adds pending records to the index, in order, whose chunks can be resolved.
+    fn index_pending_records(&mut self) -> io::Result<()> {
+        let writer = self.inner.get_mut();
+
+        while let Some(record) = self.pending_records.front_mut() {
+            if record.resolved_start_position.is_none() {
+                record.resolved_start_position =
+                    writer.resolve_block_position(record.start_position);
+            }
+
+            let start_position = match record.resolved_start_position {
+                Some(position) => position,
+                None => break,
+            };
+
+            let end_position = match writer.resolve_block_position(record.end_position) {
+                Some(position) => position,
+                None => break,
+            };
+
+            // SAFETY: `pending_records` is not empty.
+            let record = self.pending_records.pop_front().unwrap();
+            let chunk = Chunk::new(start_position, end_position);
+
+            self.indexer.add_record(
+                &record.reference_sequence_name,
+                record.start,
+                record.end,
+                chunk,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Finishes the output stream and returns the index.
+    ///
+    /// If the writer was built from a path, the index is also written next to it, i.e., to
+    /// `<dst>.tbi` or `<dst>.csi`.
+    pub fn finish(mut self) -> io::Result<Index> {
+        self.inner.get_mut().try_finish()?;
+        self.index_pending_records()?;
+
+        let index = self.indexer.build();
+
+        if let Some(dst) = self.index_dst {
+            match &index {
+                Index::Tabix(index) => tabix::write(dst, index)?,
+                Index::Csi(index) => csi::write(dst, index)?,
+            }
+        }
+
+        Ok(index)
+    }
+}
+
+// The auxiliary data is the same as the tabix header for VCF.
+fn build_aux(reference_sequence_names: &IndexSet<String>) -> Vec<u8> {
+    const FORMAT: i32 = 2; // VCF
+    const COL_SEQ: i32 = 1;
+    const COL_BEG: i32 = 2;
+    const COL_END: i32 = 0;
+    const META: i32 = b'#' as i32;
+    const SKIP: i32 = 0;
+    const NUL: u8 = 0x00;
+
+    let mut names = Vec::new();
+
+    for name in reference_sequence_names {
+        names.extend_from_slice(name.as_bytes());
+        names.push(NUL);
+    }
+
+    let mut aux = Vec::new();
+
+    for n in [FORMAT, COL_SEQ, COL_BEG, COL_END, META, SKIP] {
+        aux.extend_from_slice(&n.to_le_bytes());
+    }
+
+    // SAFETY: The total length of reference sequence names is assumed to fit in an i32.
+    let l_nm = names.len() as i32;
+    aux.extend_from_slice(&l_nm.to_le_bytes());
+    aux.extend_from_slice(&names);
+
+    aux
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::{indexed_reader, record::Position as VcfPosition};
+
+    #[test]
+    fn test_finish() -> Result<(), Box<dyn std::error::Error>> {
+        let header = Header::default();
+
+        for index_format in [
+            IndexFormat::Tabix,
+            IndexFormat::Csi {
+                min_shift: 14,
+                depth: 5,
+            },
+        ] {
+            let mut buf = Vec::new();
+
+            let mut writer = Builder::default()
+                .set_index_format(index_format)
+                .build_from_writer(&mut buf);
+
+            writer.write_header(&header)?;
+
+            for (chromosome, position) in [("sq0", 8), ("sq1", 13), ("sq1", 21)] {
+                let record = Record::builder()
+                    .set_chromosome(chromosome.parse()?)
+                    .set_position(VcfPosition::from(position))
+                    .set_reference_bases("A".parse()?)
+                    .build()?;
+
+                writer.write_record(&record)?;
+            }
+
+            let index = writer.finish()?;
+
+            let mut reader = indexed_reader::IndexedReader::new(Cursor::new(&buf), index);
+            reader.read_header()?;
+
+            let region = "sq1:14-21".parse()?;
+            let records: Vec<_> = reader.query(&header, &region)?.collect::<io::Result<_>>()?;
+
+            assert_eq!(records.len(), 1);
+            assert_eq!(usize::from(records[0].position()), 21);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_finish_with_multiple_workers() -> Result<(), Box<dyn std::error::Error>> {
+        use std::num::NonZeroUsize;
+
+        let header = Header::default();
+
+        let records = (1..=1 << 15)
+            .map(|position| {
+                Record::builder()
+                    .set_chromosome("sq0".parse()?)
+                    .set_position(VcfPosition::from(position))
+                    .set_reference_bases("A".parse()?)
+                    .build()
+                    .map_err(Box::<dyn std::error::Error>::from)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut indexes = Vec::new();
+
+        for worker_count in [1, 4] {
+            let mut writer = Builder::default()
+                .set_index_format(IndexFormat::Csi {
+                    min_shift: 14,
+                    depth: 5,
+                })
+                .set_worker_count(NonZeroUsize::try_from(worker_count)?)
+                .build_from_writer(Vec::new());
+
+            writer.write_header(&header)?;
+
+            for record in &records {
+                writer.write_record(record)?;
+            }
+
+            match writer.finish()? {
+                Index::Csi(index) => indexes.push(index),
+                Index::Tabix(_) => panic!("expected CSI"),
+            }
+        }
+
+        assert_eq!(sorted_bins(&indexes[1]), sorted_bins(&indexes[0]));
+
+        Ok(())
+    }
+
+    // Bins are built in an unspecified order.
+    fn sorted_bins(index: &csi::Index) -> Vec<Vec<csi::index::reference_sequence::Bin>> {
+        use csi::BinningIndex;
+
+        index
+            .reference_sequences()
+            .iter()
+            .map(|reference_sequence| {
+                let mut bins = reference_sequence.bins().to_vec();
+                bins.sort_by_key(|bin| bin.id());
+                bins
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_build_aux() {
+        let reference_sequence_names = [String::from("sq0")].into_iter().collect();
+
+        let expected = [
+            0x02, 0x00, 0x00, 0x00, // format = 2 (VCF)
+            0x01, 0x00, 0x00, 0x00, // col_seq = 1
+            0x02, 0x00, 0x00, 0x00, // col_beg = 2
+            0x00, 0x00, 0x00, 0x00, // col_end = 0
+            b'#', 0x00, 0x00, 0x00, // meta = '#'
+            0x00, 0x00, 0x00, 0x00, // skip = 0
+            0x04, 0x00, 0x00, 0x00, // l_nm = 4
+            b's', b'q', b'0', 0x00, // names = ["sq0"]
+        ];
+
+        assert_eq!(build_aux(&reference_sequence_names), expected);
+    }
+}
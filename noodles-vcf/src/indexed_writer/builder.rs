@@ -0,0 +1,196 @@
+use std::{
+    collections::VecDeque,
+    ffi::{OsStr, OsString},
+    fs::File,
+    io::{self, Write},
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+};
+
+use noodles_bgzf::{self as bgzf, writer::CompressionLevel};
+use noodles_csi as csi;
+use noodles_tabix as tabix;
+
+use super::{IndexedWriter, Indexer};
+use crate::Writer;
+
+/// The format of an index built by an [`IndexedWriter`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum IndexFormat {
+    /// A tabix index (TBI).
+    #[default]
+    Tabix,
+    /// A coordinate-sorted index (CSI) with the given min shift and depth.
+    Csi {
+        /// The number of bits for the minimum interval.
+        min_shift: u8,
+        /// The max depth of the binning index.
+        depth: u8,
+    },
+}
+
+impl IndexFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            Self::Tabix => "tbi",
+            Self::Csi { .. } => "csi",
+        }
+    }
+}
+
+/// An indexed VCF writer builder.
+#[derive(Debug, Default)]
+pub struct Builder {
+    index_format: IndexFormat,
+    compression_level: Option<CompressionLevel>,
+    worker_count: Option<NonZeroUsize>,
+}
+
+impl Builder {
+    /// Sets the format of the index to build.
+    ///
+    /// By default, this builds a tabix index (TBI).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::indexed_writer::{self, IndexFormat};
+    ///
+    /// let builder = indexed_writer::Builder::default().set_index_format(IndexFormat::Csi {
+    ///     min_shift: 14,
+    ///     depth: 5,
+    /// });
+    /// ```
+    pub fn set_index_format(mut self, index_format: IndexFormat) -> Self {
+        self.index_format = index_format;
+        self
+    }
+
+    /// Sets the compression level of the BGZF stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::writer::CompressionLevel;
+    /// use noodles_vcf::indexed_writer;
+    ///
+    /// let builder = indexed_writer::Builder::default()
+    ///     .set_compression_level(CompressionLevel::best());
+    /// ```
+    pub fn set_compression_level(mut self, compression_level: CompressionLevel) -> Self {
+        self.compression_level = Some(compression_level);
+        self
+    }
+
+    /// Sets the worker count of the BGZF stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_vcf::indexed_writer;
+    ///
+    /// let worker_count = NonZeroUsize::try_from(4)?;
+    /// let builder = indexed_writer::Builder::default().set_worker_count(worker_count);
+    /// # Ok::<_, std::num::TryFromIntError>(())
+    /// ```
+    pub fn set_worker_count(mut self, worker_count: NonZeroUsize) -> Self {
+        self.worker_count = Some(worker_count);
+        self
+    }
+
+    /// Builds an indexed VCF writer from a path.
+    ///
+    /// When the writer is finished, the index is written to `<dst>.tbi` or `<dst>.csi`,
+    /// depending on the index format.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use noodles_vcf::indexed_writer;
+    /// let writer = indexed_writer::Builder::default().build_from_path("out.vcf.gz")?;
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn build_from_path<P>(self, dst: P) -> io::Result<IndexedWriter<File>>
+    where
+        P: AsRef<Path>,
+    {
+        let dst = dst.as_ref();
+        let index_dst = push_ext(dst.into(), self.index_format.extension());
+
+        let file = File::create(dst)?;
+        let mut writer = self.build_from_writer(file);
+        writer.index_dst = Some(index_dst);
+
+        Ok(writer)
+    }
+
+    /// Builds an indexed VCF writer from a writer.
+    ///
+    /// The index is only returned by [`IndexedWriter::finish`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::indexed_writer;
+    /// let writer = indexed_writer::Builder::default().build_from_writer(Vec::new());
+    /// ```
+    pub fn build_from_writer<W>(self, writer: W) -> IndexedWriter<W>
+    where
+        W: Write,
+    {
+        let mut builder = bgzf::Writer::builder(writer);
+
+        if let Some(compression_level) = self.compression_level {
+            builder = builder.set_compression_level(compression_level);
+        }
+
+        if let Some(worker_count) = self.worker_count {
+            builder = builder.set_worker_count(worker_count);
+        }
+
+        let indexer = match self.index_format {
+            IndexFormat::Tabix => {
+                let mut indexer = tabix::Index::indexer();
+                indexer.set_header(tabix::index::header::Builder::vcf().build());
+                Indexer::Tabix(indexer)
+            }
+            IndexFormat::Csi { min_shift, depth } => Indexer::Csi {
+                indexer: csi::Index::indexer()
+                    .set_min_shift(min_shift)
+                    .set_depth(depth),
+                reference_sequence_names: Default::default(),
+            },
+        };
+
+        IndexedWriter {
+            inner: Writer::new(builder.build()),
+            indexer,
+            pending_records: VecDeque::new(),
+            index_dst: None,
+        }
+    }
+}
+
+fn push_ext<S>(path: PathBuf, ext: S) -> PathBuf
+where
+    S: AsRef<OsStr>,
+{
+    let mut s = OsString::from(path);
+    s.push(".");
+    s.push(ext);
+    PathBuf::from(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_ext() {
+        assert_eq!(
+            push_ext(PathBuf::from("out.vcf.gz"), "tbi"),
+            PathBuf::from("out.vcf.gz.tbi")
+        );
+    }
+}
@@ -0,0 +1,377 @@
+//! Lazily-evaluated VCF record.
+
+mod genotypes;
+mod info;
+
+pub use self::{genotypes::Genotypes, info::Info};
+
+use std::{
+    fmt, io,
+    ops::{Range, RangeFrom},
+    str::FromStr,
+};
+
+use crate::{
+    record::{
+        AlternateBases, Chromosome, Filters, Ids, Position, QualityScore, ReferenceBases,
+        MISSING_FIELD,
+    },
+    Header,
+};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct Bounds {
+    pub(crate) chromosome_end: usize,
+    pub(crate) position_end: usize,
+    pub(crate) ids_end: usize,
+    pub(crate) reference_bases_end: usize,
+    pub(crate) alternate_bases_end: usize,
+    pub(crate) quality_score_end: usize,
+    pub(crate) filters_end: usize,
+    pub(crate) info_end: usize,
+}
+
+impl Bounds {
+    fn chromosome_range(&self) -> Range<usize> {
+        0..self.chromosome_end
+    }
+
+    fn position_range(&self) -> Range<usize> {
+        self.chromosome_end + 1..self.position_end
+    }
+
+    fn ids_range(&self) -> Range<usize> {
+        self.position_end + 1..self.ids_end
+    }
+
+    fn reference_bases_range(&self) -> Range<usize> {
+        self.ids_end + 1..self.reference_bases_end
+    }
+
+    fn alternate_bases_range(&self) -> Range<usize> {
+        self.reference_bases_end + 1..self.alternate_bases_end
+    }
+
+    fn quality_score_range(&self) -> Range<usize> {
+        self.alternate_bases_end + 1..self.quality_score_end
+    }
+
+    fn filters_range(&self) -> Range<usize> {
+        self.quality_score_end + 1..self.filters_end
+    }
+
+    fn info_range(&self) -> Range<usize> {
+        self.filters_end + 1..self.info_end
+    }
+
+    fn genotypes_range(&self, len: usize) -> RangeFrom<usize> {
+        if self.info_end < len {
+            self.info_end + 1..
+        } else {
+            len..
+        }
+    }
+}
+
+/// An immutable, lazily-evalulated VCF record.
+///
+/// This holds the raw record line. The fields are parsed on access and are _not_ memoized.
+#[derive(Clone, Eq, PartialEq)]
+pub struct Record {
+    pub(crate) buf: String,
+    pub(crate) bounds: Bounds,
+}
+
+impl Record {
+    /// Returns the chromosome.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{self as vcf, record::Chromosome};
+    /// let record = vcf::lazy::Record::default();
+    /// assert_eq!(record.chromosome()?, Chromosome::Name(String::from("sq0")));
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn chromosome(&self) -> io::Result<Chromosome> {
+        parse(self.raw_chromosome())
+    }
+
+    pub(crate) fn raw_chromosome(&self) -> &str {
+        &self.buf[self.bounds.chromosome_range()]
+    }
+
+    /// Returns the position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{self as vcf, record::Position};
+    /// let record = vcf::lazy::Record::default();
+    /// assert_eq!(record.position()?, Position::from(1));
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn position(&self) -> io::Result<Position> {
+        parse(&self.buf[self.bounds.position_range()])
+    }
+
+    /// Returns the IDs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    /// let record = vcf::lazy::Record::default();
+    /// assert!(record.ids()?.is_empty());
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn ids(&self) -> io::Result<Ids> {
+        match &self.buf[self.bounds.ids_range()] {
+            MISSING_FIELD => Ok(Ids::default()),
+            s => parse(s),
+        }
+    }
+
+    /// Returns the reference bases.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{self as vcf, record::reference_bases::Base};
+    /// let record = vcf::lazy::Record::default();
+    /// assert_eq!(&record.reference_bases()?[..], [Base::N]);
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn reference_bases(&self) -> io::Result<ReferenceBases> {
+        parse(&self.buf[self.bounds.reference_bases_range()])
+    }
+
+    /// Returns the alternate bases.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    /// let record = vcf::lazy::Record::default();
+    /// assert!(record.alternate_bases()?.is_empty());
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn alternate_bases(&self) -> io::Result<AlternateBases> {
+        match &self.buf[self.bounds.alternate_bases_range()] {
+            MISSING_FIELD => Ok(AlternateBases::default()),
+            s => parse(s),
+        }
+    }
+
+    /// Returns the quality score.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    /// let record = vcf::lazy::Record::default();
+    /// assert!(record.quality_score()?.is_none());
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn quality_score(&self) -> io::Result<Option<QualityScore>> {
+        match &self.buf[self.bounds.quality_score_range()] {
+            MISSING_FIELD => Ok(None),
+            s => parse(s).map(Some),
+        }
+    }
+
+    /// Returns the filters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    /// let record = vcf::lazy::Record::default();
+    /// assert!(record.filters()?.is_none());
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn filters(&self) -> io::Result<Option<Filters>> {
+        match &self.buf[self.bounds.filters_range()] {
+            MISSING_FIELD => Ok(None),
+            s => parse(s).map(Some),
+        }
+    }
+
+    /// Returns the raw info fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    /// let record = vcf::lazy::Record::default();
+    /// assert!(record.info().is_empty());
+    /// ```
+    pub fn info(&self) -> Info<'_> {
+        Info::new(&self.buf[self.bounds.info_range()])
+    }
+
+    /// Returns the raw genotypes, i.e., the format and sample fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    /// let record = vcf::lazy::Record::default();
+    /// assert!(record.genotypes().is_empty());
+    /// ```
+    pub fn genotypes(&self) -> Genotypes<'_> {
+        Genotypes::new(&self.buf[self.bounds.genotypes_range(self.buf.len())])
+    }
+
+    /// Converts a lazy VCF record to a VCF record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_vcf as vcf;
+    ///
+    /// let header = vcf::Header::default();
+    /// let lazy_record = vcf::lazy::Record::default();
+    /// let record = lazy_record.try_into_vcf_record(&header)?;
+    ///
+    /// assert_eq!(usize::from(record.position()), 1);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn try_into_vcf_record(&self, header: &Header) -> io::Result<crate::Record> {
+        crate::Record::try_from_str(&self.buf, header)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+impl fmt::Debug for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Record")
+            .field("chromosome", &self.chromosome())
+            .field("position", &self.position())
+            .field("ids", &self.ids())
+            .field("reference_bases", &self.reference_bases())
+            .field("alternate_bases", &self.alternate_bases())
+            .field("quality_score", &self.quality_score())
+            .field("filters", &self.filters())
+            .field("info", &self.info())
+            .field("genotypes", &self.genotypes())
+            .finish()
+    }
+}
+
+impl AsRef<str> for Record {
+    fn as_ref(&self) -> &str {
+        &self.buf
+    }
+}
+
+impl Default for Record {
+    fn default() -> Self {
+        let buf = String::from("sq0\t1\t.\tN\t.\t.\t.\t.");
+
+        let bounds = Bounds {
+            chromosome_end: 3,
+            position_end: 5,
+            ids_end: 7,
+            reference_bases_end: 9,
+            alternate_bases_end: 11,
+            quality_score_end: 13,
+            filters_end: 15,
+            info_end: 17,
+        };
+
+        Self { buf, bounds }
+    }
+}
+
+impl TryFrom<String> for Record {
+    type Error = io::Error;
+
+    fn try_from(buf: String) -> Result<Self, Self::Error> {
+        let bounds = index(&buf)?;
+        Ok(Self { buf, bounds })
+    }
+}
+
+// Returns the bounds of the fixed fields of a raw record line.
+pub(crate) fn index(s: &str) -> io::Result<Bounds> {
+    const DELIMITER: u8 = b'\t';
+    const FIXED_FIELD_COUNT: usize = 8;
+
+    let mut ends = [0; FIXED_FIELD_COUNT];
+    let mut positions = memchr::memchr_iter(DELIMITER, s.as_bytes());
+
+    for (i, end) in ends.iter_mut().enumerate() {
+        *end = match positions.next() {
+            Some(j) => j,
+            None if i == FIXED_FIELD_COUNT - 1 => s.len(),
+            None => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "missing record field",
+                ))
+            }
+        };
+    }
+
+    Ok(Bounds {
+        chromosome_end: ends[0],
+        position_end: ends[1],
+        ids_end: ends[2],
+        reference_bases_end: ends[3],
+        alternate_bases_end: ends[4],
+        quality_score_end: ends[5],
+        filters_end: ends[6],
+        info_end: ends[7],
+    })
+}
+
+fn parse<T>(s: &str) -> io::Result<T>
+where
+    T: FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    s.parse()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default() {
+        let record = Record::default();
+        assert_eq!(index(&record.buf).ok(), Some(record.bounds));
+    }
+
+    #[test]
+    fn test_try_from_string() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::record::reference_bases::Base;
+
+        let record = Record::try_from(String::from("sq0\t8\tid0\tA\tC,G\t13\tPASS\tNS=2"))?;
+        assert_eq!(record.chromosome()?, Chromosome::Name(String::from("sq0")));
+        assert_eq!(record.position()?, Position::from(8));
+        assert_eq!(record.ids()?.len(), 1);
+        assert_eq!(&record.reference_bases()?[..], [Base::A]);
+        assert_eq!(record.alternate_bases()?.len(), 2);
+        assert_eq!(record.quality_score()?.map(f32::from), Some(13.0));
+        assert_eq!(record.filters()?, Some(Filters::Pass));
+        assert_eq!(record.info().as_ref(), "NS=2");
+        assert!(record.genotypes().is_empty());
+
+        let record = Record::try_from(String::from(
+            "sq0\t8\t.\tA\t.\t.\t.\t.\tGT:GQ\t0|0:13\t1/1:8",
+        ))?;
+        assert_eq!(record.info().as_ref(), ".");
+        assert_eq!(record.genotypes().as_ref(), "GT:GQ\t0|0:13\t1/1:8");
+
+        assert!(matches!(
+            Record::try_from(String::from("sq0\t8\t.\tA")),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,123 @@
+use std::io;
+
+use crate::{
+    record::{genotypes::Keys, Genotype},
+    Header,
+};
+
+const DELIMITER: char = '\t';
+
+/// Raw VCF record genotypes.
+///
+/// This is the format (`FORMAT`) field followed by the sample fields.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Genotypes<'a>(&'a str);
+
+impl<'a> Genotypes<'a> {
+    pub(super) fn new(buf: &'a str) -> Self {
+        Self(buf)
+    }
+
+    /// Returns whether there are any genotypes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    /// let record = vcf::lazy::Record::default();
+    /// assert!(record.genotypes().is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the number of samples.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_vcf as vcf;
+    ///
+    /// let record = vcf::lazy::Record::try_from(String::from(
+    ///     "sq0\t1\t.\tA\t.\t.\tPASS\t.\tGT\t0|0\t1/1",
+    /// ))?;
+    ///
+    /// assert_eq!(record.genotypes().len(), 2);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn len(&self) -> usize {
+        if self.is_empty() {
+            0
+        } else {
+            self.0.matches(DELIMITER).count()
+        }
+    }
+
+    /// Parses and returns the genotype field keys, i.e., the format (`FORMAT`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_vcf::{self as vcf, header::format::Key};
+    ///
+    /// let header = vcf::Header::default();
+    /// let record = vcf::lazy::Record::try_from(String::from(
+    ///     "sq0\t1\t.\tA\t.\t.\tPASS\t.\tGT:GQ\t0|0:13",
+    /// ))?;
+    ///
+    /// let keys = record.genotypes().keys(&header)?;
+    /// assert_eq!(keys.len(), 2);
+    /// assert!(keys.contains(&Key::ConditionalGenotypeQuality));
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn keys(&self, header: &Header) -> io::Result<Keys> {
+        let raw_keys = self.0.split(DELIMITER).next().unwrap_or_default();
+
+        Keys::try_from_str(raw_keys, header.formats())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Parses and returns the genotype of the sample at the given index.
+    ///
+    /// Only the requested sample is parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_vcf::{self as vcf, header::format::Key};
+    ///
+    /// let header = vcf::Header::default();
+    /// let record = vcf::lazy::Record::try_from(String::from(
+    ///     "sq0\t1\t.\tA\t.\t.\tPASS\t.\tGT:GQ\t0|0:13\t1/1:8",
+    /// ))?;
+    /// let genotypes = record.genotypes();
+    ///
+    /// let genotype = genotypes.get(&header, 1).transpose()?.expect("missing sample");
+    /// assert_eq!(
+    ///     genotype.get(&Key::ConditionalGenotypeQuality).and_then(|field| field.value()),
+    ///     Some(&vcf::record::genotypes::genotype::field::Value::Integer(8))
+    /// );
+    ///
+    /// assert!(genotypes.get(&header, 2).is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn get(&self, header: &Header, i: usize) -> Option<io::Result<Genotype>> {
+        let raw_genotype = self.0.split(DELIMITER).nth(i + 1)?;
+
+        let result = self.keys(header).and_then(|keys| {
+            Genotype::parse(raw_genotype, header.formats(), &keys)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        });
+
+        Some(result)
+    }
+}
+
+impl<'a> AsRef<str> for Genotypes<'a> {
+    fn as_ref(&self) -> &str {
+        self.0
+    }
+}
@@ -0,0 +1,110 @@
+use std::io;
+
+use crate::{
+    header::info::Key,
+    record::{info::Field, MISSING_FIELD},
+    Header,
+};
+
+const DELIMITER: char = ';';
+const SEPARATOR: char = '=';
+
+/// Raw VCF record info fields.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Info<'a>(&'a str);
+
+impl<'a> Info<'a> {
+    pub(super) fn new(buf: &'a str) -> Self {
+        Self(buf)
+    }
+
+    /// Returns whether there are any info fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    /// let record = vcf::lazy::Record::default();
+    /// assert!(record.info().is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty() || self.0 == MISSING_FIELD
+    }
+
+    /// Returns an iterator over the info field keys.
+    ///
+    /// The values of the fields are not parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_vcf::{self as vcf, header::info::Key};
+    ///
+    /// let record = vcf::lazy::Record::try_from(String::from("sq0\t1\t.\tA\t.\t.\tPASS\tNS=2;DB"))?;
+    /// let keys: Vec<_> = record.info().keys().collect::<io::Result<_>>()?;
+    ///
+    /// assert_eq!(keys, [Key::SamplesWithDataCount, Key::IsInDbSnp]);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn keys(&self) -> impl Iterator<Item = io::Result<Key>> + 'a {
+        self.raw_fields().map(|(raw_key, _)| {
+            raw_key
+                .parse()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        })
+    }
+
+    /// Returns the parsed field with the given key.
+    ///
+    /// Only the matching field is parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_vcf::{
+    ///     self as vcf,
+    ///     header::info::Key,
+    ///     record::info::{field::Value, Field},
+    /// };
+    ///
+    /// let header = vcf::Header::default();
+    /// let record = vcf::lazy::Record::try_from(String::from("sq0\t1\t.\tA\t.\t.\tPASS\tNS=2;DB"))?;
+    /// let info = record.info();
+    ///
+    /// assert_eq!(
+    ///     info.get(&header, &Key::SamplesWithDataCount).transpose()?,
+    ///     Some(Field::new(Key::SamplesWithDataCount, Some(Value::Integer(2))))
+    /// );
+    ///
+    /// assert!(info.get(&header, &Key::TotalDepth).is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn get(&self, header: &Header, key: &Key) -> Option<io::Result<Field>> {
+        self.raw_fields()
+            .find(|(raw_key, _)| *raw_key == key.as_ref())
+            .map(|(_, raw_field)| {
+                Field::try_from_str(raw_field, header.infos())
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            })
+    }
+
+    // Returns an iterator over (raw key, raw field) pairs.
+    fn raw_fields(&self) -> impl Iterator<Item = (&'a str, &'a str)> + 'a {
+        let s = if self.is_empty() { "" } else { self.0 };
+
+        s.split(DELIMITER)
+            .filter(|raw_field| !raw_field.is_empty())
+            .map(|raw_field| match raw_field.split_once(SEPARATOR) {
+                Some((raw_key, _)) => (raw_key, raw_field),
+                None => (raw_field, raw_field),
+            })
+    }
+}
+
+impl<'a> AsRef<str> for Info<'a> {
+    fn as_ref(&self) -> &str {
+        self.0
+    }
+}
@@ -24,11 +24,17 @@
 mod r#async;
 
 pub mod header;
+pub mod indexed_reader;
+pub mod indexed_writer;
+pub mod lazy;
 pub mod reader;
 pub mod record;
 mod writer;
 
-pub use self::{header::Header, reader::Reader, record::Record, writer::Writer};
+pub use self::{
+    header::Header, indexed_reader::IndexedReader, indexed_writer::IndexedWriter, reader::Reader,
+    record::Record, writer::Writer,
+};
 
 #[cfg(feature = "async")]
 pub use self::r#async::{Reader as AsyncReader, Writer as AsyncWriter};
@@ -13,7 +13,7 @@ use noodles_core::Region;
 use noodles_csi::BinningIndex;
 use noodles_tabix as tabix;
 
-use super::Header;
+use super::{lazy, Header};
 
 /// A VCF reader.
 ///
@@ -180,6 +180,41 @@ where
         read_line(&mut self.inner, buf)
     }
 
+    /// Reads a single record without eagerly parsing its fields.
+    ///
+    /// This reads a line from the underlying stream into the given record's buffer and indexes
+    /// the bounds of the fixed fields. No fields are parsed, meaning the record is not necessarily
+    /// valid. However, the record is guaranteed to have the eight fixed fields.
+    ///
+    /// The stream is expected to be directly after the header or at the start of another record.
+    ///
+    /// If successful, the number of bytes read is returned. If the number of bytes read is 0, the
+    /// stream reached EOF.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_vcf::{self as vcf, record::Position};
+    ///
+    /// let data = b"##fileformat=VCFv4.3
+    /// #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+    /// sq0\t8\t.\tA\t.\t.\tPASS\t.
+    /// ";
+    ///
+    /// let mut reader = vcf::Reader::new(&data[..]);
+    /// reader.read_header()?;
+    ///
+    /// let mut record = vcf::lazy::Record::default();
+    /// reader.read_lazy_record(&mut record)?;
+    ///
+    /// assert_eq!(record.position()?, Position::from(8));
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn read_lazy_record(&mut self, record: &mut lazy::Record) -> io::Result<usize> {
+        read_lazy_record(&mut self.inner, record)
+    }
+
     /// Returns an iterator over records starting from the current stream position.
     ///
     /// The stream is expected to be directly after the header or at the start of another record.
@@ -367,6 +402,33 @@ where
     }
 }
 
+fn read_lazy_record<R>(reader: &mut R, record: &mut lazy::Record) -> io::Result<usize>
+where
+    R: BufRead,
+{
+    use std::mem;
+
+    let mut buf = mem::take(&mut record.buf);
+    buf.clear();
+
+    let result = read_line(reader, &mut buf).and_then(|n| {
+        if n > 0 {
+            record.bounds = lazy::record::index(&buf)?;
+        }
+
+        Ok(n)
+    });
+
+    // On error, the record is reset so that its bounds never exceed its buffer.
+    if result.is_err() {
+        *record = lazy::Record::default();
+    } else {
+        record.buf = buf;
+    }
+
+    result
+}
+
 pub(crate) fn resolve_region(index: &tabix::Index, region: &Region) -> io::Result<(usize, String)> {
     let i = index
         .header()
@@ -501,4 +563,34 @@ sq0\t13
 
         Ok(())
     }
+
+    #[test]
+    fn test_read_lazy_record() -> io::Result<()> {
+        use crate::record::Position;
+
+        let data =
+            b"sq0\t8\t.\tA\t.\t.\tPASS\t.\tGT\t0|1\nsq0\t13\t.\tC\tG\t.\tPASS\tNS=1\r\nsq0\t21\n";
+        let mut reader = &data[..];
+        let mut record = lazy::Record::default();
+
+        assert_eq!(read_lazy_record(&mut reader, &mut record)?, 28);
+        assert_eq!(record.position()?, Position::from(8));
+        assert_eq!(record.info().as_ref(), ".");
+        assert_eq!(record.genotypes().as_ref(), "GT\t0|1");
+
+        assert_eq!(read_lazy_record(&mut reader, &mut record)?, 26);
+        assert_eq!(record.position()?, Position::from(13));
+        assert_eq!(record.info().as_ref(), "NS=1");
+        assert!(record.genotypes().is_empty());
+
+        assert!(matches!(
+            read_lazy_record(&mut reader, &mut record),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+        assert_eq!(record, lazy::Record::default());
+
+        assert_eq!(read_lazy_record(&mut reader, &mut record)?, 0);
+
+        Ok(())
+    }
 }
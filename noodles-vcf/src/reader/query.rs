@@ -8,7 +8,7 @@ use noodles_core::region::Interval;
 use noodles_csi::index::reference_sequence::bin::Chunk;
 
 use super::Reader;
-use crate::{Header, Record};
+use crate::{lazy, Header, Record};
 
 enum State {
     Seek,
@@ -32,14 +32,14 @@ where
 
     state: State,
     header: &'h Header,
-    line_buf: String,
+    record: lazy::Record,
 }
 
 impl<'r, 'h, R> Query<'r, 'h, R>
 where
     R: Read + Seek,
 {
-    pub(super) fn new(
+    pub(crate) fn new(
         reader: &'r mut Reader<bgzf::Reader<R>>,
         chunks: Vec<Chunk>,
         reference_sequence_name: String,
@@ -56,21 +56,30 @@ where
 
             state: State::Seek,
             header,
-            line_buf: String::new(),
+            record: lazy::Record::default(),
         }
     }
 
-    fn read_record(&mut self) -> io::Result<Option<Record>> {
-        self.line_buf.clear();
-
-        self.reader
-            .read_record(&mut self.line_buf)
-            .and_then(|n| match n {
-                0 => Ok(None),
-                _ => Record::try_from_str(&self.line_buf, self.header)
-                    .map(Some)
-                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
-            })
+    // Returns the next record that intersects the region.
+    //
+    // Records that cannot intersect the region are skipped without being fully parsed.
+    fn read_record(&mut self) -> io::Result<Option<Option<Record>>> {
+        match self.reader.read_lazy_record(&mut self.record)? {
+            0 => Ok(None),
+            _ => {
+                if !may_intersect(&self.record, &self.reference_sequence_name, self.interval)? {
+                    return Ok(Some(None));
+                }
+
+                let record = self.record.try_into_vcf_record(self.header)?;
+
+                if intersects(&record, &self.reference_sequence_name, self.interval)? {
+                    Ok(Some(Some(record)))
+                } else {
+                    Ok(Some(None))
+                }
+            }
+        }
     }
 }
 
@@ -101,10 +110,8 @@ where
                             self.state = State::Seek;
                         }
 
-                        match intersects(&record, &self.reference_sequence_name, self.interval) {
-                            Ok(true) => return Some(Ok(record)),
-                            Ok(false) => {}
-                            Err(e) => return Some(Err(e)),
+                        if let Some(record) = record {
+                            return Some(Ok(record));
                         }
                     }
                     Ok(None) => self.state = State::Seek,
@@ -116,6 +123,26 @@ where
     }
 }
 
+fn may_intersect(
+    record: &lazy::Record,
+    reference_sequence_name: &str,
+    region_interval: Interval,
+) -> io::Result<bool> {
+    use noodles_core::Position;
+
+    if record.raw_chromosome() != reference_sequence_name {
+        return Ok(false);
+    }
+
+    let start = Position::try_from(usize::from(record.position()?))
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    Ok(region_interval
+        .end()
+        .map(|end| start <= end)
+        .unwrap_or(true))
+}
+
 pub(crate) fn intersects(
     record: &Record,
     reference_sequence_name: &str,
@@ -140,3 +167,27 @@ pub(crate) fn intersects(
 
     Ok(name == reference_sequence_name && record_interval.intersects(region_interval))
 }
+
+#[cfg(test)]
+mod tests {
+    use noodles_core::Position;
+
+    use super::*;
+
+    #[test]
+    fn test_may_intersect() -> Result<(), Box<dyn std::error::Error>> {
+        let record = lazy::Record::try_from(String::from("sq0\t8\t.\tA\t.\t.\tPASS\t."))?;
+
+        let interval = Interval::from(Position::try_from(5)?..=Position::try_from(13)?);
+        assert!(may_intersect(&record, "sq0", interval)?);
+        assert!(!may_intersect(&record, "sq1", interval)?);
+
+        let interval = Interval::from(Position::try_from(1)?..=Position::try_from(5)?);
+        assert!(!may_intersect(&record, "sq0", interval)?);
+
+        let interval = Interval::from(Position::try_from(1)?..);
+        assert!(may_intersect(&record, "sq0", interval)?);
+
+        Ok(())
+    }
+}